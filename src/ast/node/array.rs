@@ -0,0 +1,14 @@
+use super::expression::Expr;
+
+/// An array literal, e.g. `[1, 2, 3]`
+#[derive(Debug, Clone)]
+pub struct Array<'a> {
+    pub items: Vec<Expr<'a>>,
+}
+
+/// An element of an array accessed by its index, e.g. `array[0]`
+#[derive(Debug, Clone)]
+pub struct Index<'a> {
+    pub array: Expr<'a>,
+    pub index: Expr<'a>,
+}
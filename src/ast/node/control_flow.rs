@@ -1,10 +1,13 @@
 use crate::parser::Span;
 
-use super::{expression::Expr, statement::Stmt};
+use super::{expression::Expr, identifier::Ident, pattern_match::EnumPattern, statement::Stmt};
 
 #[derive(Debug, Clone)]
 pub struct IfStmt<'a> {
     pub condition: Expr<'a>,
+    pub pattern: Option<EnumPattern<'a>>,
+    /// The name of the unwrapped value in the `if let name = optional` form
+    pub binding: Option<Ident<'a>>,
     pub span: Span<'a>,
     pub body: Box<Stmt<'a>>,
     pub else_body: Option<Box<Stmt<'a>>>,
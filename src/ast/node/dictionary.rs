@@ -0,0 +1,8 @@
+use super::expression::Expr;
+
+/// A dictionary literal of key-value pairs, e.g. `["one": 1, "two": 2]`,
+/// or `[:]` for an empty dictionary
+#[derive(Debug, Clone)]
+pub struct Dictionary<'a> {
+    pub entries: Vec<(Expr<'a>, Expr<'a>)>,
+}
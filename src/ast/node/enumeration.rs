@@ -1,8 +1,9 @@
-use super::{identifier::Ident, type_signature::TypeSignature};
+use super::{generics::TypeParam, identifier::Ident, type_signature::TypeSignature};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Enum<'a> {
     pub name: Ident<'a>,
+    pub type_params: Vec<TypeParam<'a>>,
     pub values: Vec<EnumValue<'a>>,
 }
 
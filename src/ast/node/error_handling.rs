@@ -0,0 +1,16 @@
+use crate::parser::Span;
+
+use super::{expression::Expr, identifier::Ident, statement::Stmt};
+
+#[derive(Debug, Clone)]
+pub struct DoCatch<'a> {
+    pub body: Box<Stmt<'a>>,
+    pub error_name: Ident<'a>,
+    pub catch_body: Box<Stmt<'a>>,
+    pub span: Span<'a>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Try<'a> {
+    pub expr: Expr<'a>,
+}
@@ -1,11 +1,17 @@
 use crate::parser::Span;
 
 use super::{
+    array::{Array, Index},
     assignment::Assignment,
+    dictionary::Dictionary,
+    error_handling::Try,
     escape_block::EscapeBlock,
-    function::{Function, FunctionCall},
+    function::{Await, Function, FunctionCall},
     identifier::Ident,
     member_access::MemberAccess,
+    operator::{BinaryOperation, UnaryOperation},
+    pattern_match::Match,
+    string_interpolation::StringInterpolation,
     structure::StructInit,
     tuple::{Tuple, TupleAccess},
 };
@@ -18,9 +24,11 @@ pub struct Expr<'a> {
 
 #[derive(Debug, Clone)]
 pub enum ExprValue<'a> {
-    StringLiteral(&'a str),
+    StringLiteral(String),
+    StringInterpolation(StringInterpolation<'a>),
     NumberLiteral(f64),
     BoolLiteral(bool),
+    NilLiteral,
     MemberAccess(Box<MemberAccess<'a>>),
     Function(Function<'a>),
     FunctionCall(Box<FunctionCall<'a>>),
@@ -30,4 +38,12 @@ pub enum ExprValue<'a> {
     EscapeBlock(EscapeBlock<'a>),
     Assignment(Box<Assignment<'a>>),
     Tuple(Tuple<'a>),
+    Array(Array<'a>),
+    Dictionary(Dictionary<'a>),
+    Index(Box<Index<'a>>),
+    BinaryOperation(Box<BinaryOperation<'a>>),
+    UnaryOperation(Box<UnaryOperation<'a>>),
+    Match(Box<Match<'a>>),
+    Await(Box<Await<'a>>),
+    Try(Box<Try<'a>>),
 }
@@ -0,0 +1,15 @@
+use crate::parser::Span;
+
+use super::{function::Function, identifier::Ident, structure::StructAttr};
+
+#[derive(Debug, Clone)]
+pub struct Extension<'a> {
+    pub name: Ident<'a>,
+    pub trait_name: Option<Ident<'a>>,
+    pub methods: Vec<Function<'a>>,
+    pub static_methods: Vec<Function<'a>>,
+    pub static_attrs: Vec<StructAttr<'a>>,
+    pub inits: Vec<Function<'a>>,
+    pub operators: Vec<Function<'a>>,
+    pub span: Span<'a>,
+}
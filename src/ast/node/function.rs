@@ -1,13 +1,19 @@
 use crate::parser::Span;
 
-use super::{expression::Expr, identifier::Ident, statement::Stmt, type_signature::TypeSignature};
+use super::{
+    expression::Expr, generics::TypeParam, identifier::Ident, statement::Stmt,
+    type_signature::TypeSignature,
+};
 
 #[derive(Debug, Clone)]
 pub struct Function<'a> {
     pub name: Option<Ident<'a>>,
+    pub type_params: Vec<TypeParam<'a>>,
     pub args: Vec<FunctionArg<'a>>,
     pub return_type: Option<TypeSignature<'a>>,
+    pub throws: Option<TypeSignature<'a>>,
     pub body: Box<Stmt<'a>>,
+    pub is_async: bool,
     pub span: Span<'a>,
 }
 
@@ -22,5 +28,11 @@ pub struct FunctionArg<'a> {
 pub struct FunctionCall<'a> {
     pub func: Expr<'a>,
     pub args: Vec<Expr<'a>>,
+    pub arg_labels: Vec<Option<Ident<'a>>>,
     pub args_span: Span<'a>,
 }
+
+#[derive(Debug, Clone)]
+pub struct Await<'a> {
+    pub expr: Expr<'a>,
+}
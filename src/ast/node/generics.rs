@@ -0,0 +1,8 @@
+use super::identifier::Ident;
+
+/// A type parameter of a generic declaration, optionally bound to a trait
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeParam<'a> {
+    pub name: Ident<'a>,
+    pub bound: Option<Ident<'a>>,
+}
@@ -0,0 +1,42 @@
+use crate::parser::Span;
+
+use super::{expression::Expr, identifier::Ident, statement::Stmt};
+
+#[derive(Debug, Clone)]
+pub struct WhileLoop<'a> {
+    pub label: Option<Ident<'a>>,
+    pub condition: Expr<'a>,
+    pub body: Box<Stmt<'a>>,
+    pub span: Span<'a>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ForLoop<'a> {
+    pub label: Option<Ident<'a>>,
+    pub item_name: Ident<'a>,
+    pub sequence: Expr<'a>,
+    pub body: Box<Stmt<'a>>,
+    pub span: Span<'a>,
+}
+
+#[derive(Debug, Clone)]
+pub struct LoopJump<'a> {
+    pub kind: LoopJumpKind,
+    pub label: Option<Ident<'a>>,
+    pub span: Span<'a>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoopJumpKind {
+    Break,
+    Continue,
+}
+
+impl LoopJumpKind {
+    pub const fn keyword(&self) -> &'static str {
+        match self {
+            LoopJumpKind::Break => "break",
+            LoopJumpKind::Continue => "continue",
+        }
+    }
+}
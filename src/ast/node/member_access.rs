@@ -7,5 +7,7 @@ pub struct MemberAccess<'a> {
     pub object: Option<Expr<'a>>,
     pub member_name: Ident<'a>,
     pub items: Option<(Span<'a>, Vec<Expr<'a>>)>,
+    /// Whether the member is accessed through optional chaining, `OBJECT?.MEMBER`
+    pub optional: bool,
     pub span: Span<'a>,
 }
@@ -1,16 +1,26 @@
+pub mod array;
 pub mod assignment;
 pub mod comment;
 pub mod control_flow;
+pub mod dictionary;
 pub mod enumeration;
+pub mod error_handling;
 pub mod escape_block;
 pub mod expression;
+pub mod extension;
 pub mod external;
 pub mod function;
+pub mod generics;
 pub mod identifier;
+pub mod loops;
 pub mod member_access;
 pub mod module;
+pub mod operator;
+pub mod pattern_match;
 pub mod statement;
+pub mod string_interpolation;
 pub mod structure;
 pub mod traits;
 pub mod tuple;
+pub mod type_alias;
 pub mod type_signature;
@@ -0,0 +1,147 @@
+use crate::parser::Span;
+
+use super::expression::Expr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BinaryOperator {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+    And,
+    Or,
+    Coalesce,
+    /// `a..<b`, the numbers from `a` up to but excluding `b`
+    HalfOpenRange,
+    /// `a...b`, the numbers from `a` up to and including `b`
+    ClosedRange,
+}
+
+impl BinaryOperator {
+    pub const fn symbol(&self) -> &'static str {
+        match self {
+            BinaryOperator::Add => "+",
+            BinaryOperator::Sub => "-",
+            BinaryOperator::Mul => "*",
+            BinaryOperator::Div => "/",
+            BinaryOperator::Mod => "%",
+            BinaryOperator::Eq => "==",
+            BinaryOperator::NotEq => "!=",
+            BinaryOperator::Lt => "<",
+            BinaryOperator::LtEq => "<=",
+            BinaryOperator::Gt => ">",
+            BinaryOperator::GtEq => ">=",
+            BinaryOperator::And => "&&",
+            BinaryOperator::Or => "||",
+            BinaryOperator::Coalesce => "??",
+            BinaryOperator::HalfOpenRange => "..<",
+            BinaryOperator::ClosedRange => "...",
+        }
+    }
+
+    /// Whether the operator can be declared for a struct or enum with `static func`,
+    /// the logical operators, `??` and the ranges are only defined for builtin types
+    pub const fn is_overloadable(&self) -> bool {
+        !matches!(
+            self,
+            BinaryOperator::And
+                | BinaryOperator::Or
+                | BinaryOperator::Coalesce
+                | BinaryOperator::HalfOpenRange
+                | BinaryOperator::ClosedRange
+        )
+    }
+
+    pub const fn is_comparison(&self) -> bool {
+        matches!(
+            self,
+            BinaryOperator::Eq
+                | BinaryOperator::NotEq
+                | BinaryOperator::Lt
+                | BinaryOperator::LtEq
+                | BinaryOperator::Gt
+                | BinaryOperator::GtEq
+        )
+    }
+
+    pub fn overloadable_from_symbol(symbol: &str) -> Option<BinaryOperator> {
+        [
+            BinaryOperator::Add,
+            BinaryOperator::Sub,
+            BinaryOperator::Mul,
+            BinaryOperator::Div,
+            BinaryOperator::Mod,
+            BinaryOperator::Eq,
+            BinaryOperator::NotEq,
+            BinaryOperator::Lt,
+            BinaryOperator::LtEq,
+            BinaryOperator::Gt,
+            BinaryOperator::GtEq,
+        ]
+        .into_iter()
+        .find(|operator| operator.symbol() == symbol)
+    }
+
+    pub const fn is_range(&self) -> bool {
+        matches!(
+            self,
+            BinaryOperator::HalfOpenRange | BinaryOperator::ClosedRange
+        )
+    }
+
+    /// Binding power of the operator, a higher value binds tighter.
+    /// All binary operators are left associative.
+    pub const fn precedence(&self) -> u8 {
+        match self {
+            BinaryOperator::Or => 1,
+            BinaryOperator::And => 2,
+            BinaryOperator::Eq
+            | BinaryOperator::NotEq
+            | BinaryOperator::Lt
+            | BinaryOperator::LtEq
+            | BinaryOperator::Gt
+            | BinaryOperator::GtEq => 3,
+            BinaryOperator::Coalesce => 4,
+            BinaryOperator::HalfOpenRange | BinaryOperator::ClosedRange => 5,
+            BinaryOperator::Add | BinaryOperator::Sub => 6,
+            BinaryOperator::Mul | BinaryOperator::Div | BinaryOperator::Mod => 7,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UnaryOperator {
+    Negate,
+    Not,
+}
+
+impl UnaryOperator {
+    pub const fn symbol(&self) -> &'static str {
+        match self {
+            UnaryOperator::Negate => "-",
+            UnaryOperator::Not => "!",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BinaryOperation<'a> {
+    pub lhs: Expr<'a>,
+    pub operator: BinaryOperator,
+    pub operator_span: Span<'a>,
+    pub rhs: Expr<'a>,
+}
+
+#[derive(Debug, Clone)]
+pub struct UnaryOperation<'a> {
+    pub operator: UnaryOperator,
+    pub operator_span: Span<'a>,
+    pub operand: Expr<'a>,
+}
@@ -0,0 +1,41 @@
+use crate::parser::Span;
+
+use super::{expression::Expr, identifier::Ident, statement::Stmt};
+
+#[derive(Debug, Clone)]
+pub struct Match<'a> {
+    pub scrutinee: Expr<'a>,
+    pub arms: Vec<MatchArm<'a>>,
+    pub span: Span<'a>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MatchArm<'a> {
+    pub pattern: Option<EnumPattern<'a>>,
+    pub body: MatchArmBody<'a>,
+    pub span: Span<'a>,
+}
+
+#[derive(Debug, Clone)]
+pub enum MatchArmBody<'a> {
+    Expression(Box<Expr<'a>>),
+    Block(Box<Stmt<'a>>),
+}
+
+#[derive(Debug, Clone)]
+pub struct EnumPattern<'a> {
+    pub enum_value: Ident<'a>,
+    /// Bindings of the enum value payload, `None` for an ignored item `_`
+    pub bindings: Vec<Option<Ident<'a>>>,
+    pub span: Span<'a>,
+}
+
+/// `let ENUM_PATTERN = EXPR else { ... }`, where the bindings of the pattern
+/// are available in the rest of the enclosing block
+#[derive(Debug, Clone)]
+pub struct LetElse<'a> {
+    pub pattern: EnumPattern<'a>,
+    pub value: Expr<'a>,
+    pub else_body: Box<Stmt<'a>>,
+    pub span: Span<'a>,
+}
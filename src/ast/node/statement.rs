@@ -4,12 +4,17 @@ use super::{
     comment::Comment,
     control_flow::IfStmt,
     enumeration::Enum,
+    error_handling::DoCatch,
     expression::Expr,
+    extension::Extension,
     external::ExternalObject,
     function::Function,
     identifier::Ident,
+    loops::{ForLoop, LoopJump, WhileLoop},
+    pattern_match::LetElse,
     structure::Struct,
     traits::Trait,
+    type_alias::TypeAlias,
     type_signature::{Mutability, TypeSignature},
 };
 
@@ -26,12 +31,20 @@ pub enum StmtValue<'a> {
     TraitDecl(Trait<'a>),
     StructDecl(Struct<'a>),
     EnumDecl(Enum<'a>),
+    Extension(Extension<'a>),
     Compound(Vec<Stmt<'a>>),
     Expression(Expr<'a>),
     Return(Expr<'a>),
+    Throw(Expr<'a>),
     Comment(Comment<'a>),
     ExternObj(ExternalObject<'a>),
+    TypeAlias(TypeAlias<'a>),
     IfBranch(IfStmt<'a>),
+    LetElse(LetElse<'a>),
+    DoCatch(DoCatch<'a>),
+    WhileLoop(WhileLoop<'a>),
+    ForLoop(ForLoop<'a>),
+    LoopJump(LoopJump<'a>),
 }
 
 #[derive(Debug, Clone)]
@@ -0,0 +1,13 @@
+use super::expression::Expr;
+
+/// A string literal with interpolated expressions, e.g. `"Hello \(name)"`
+#[derive(Debug, Clone)]
+pub struct StringInterpolation<'a> {
+    pub parts: Vec<StringPart<'a>>,
+}
+
+#[derive(Debug, Clone)]
+pub enum StringPart<'a> {
+    Literal(String),
+    Expr(Box<Expr<'a>>),
+}
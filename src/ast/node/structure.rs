@@ -2,6 +2,7 @@ use crate::parser::Span;
 
 use super::{
     expression::Expr,
+    generics::TypeParam,
     identifier::Ident,
     type_signature::{Mutability, TypeSignature},
 };
@@ -9,6 +10,7 @@ use super::{
 #[derive(Debug, Clone)]
 pub struct Struct<'a> {
     pub name: Ident<'a>,
+    pub type_params: Vec<TypeParam<'a>>,
     pub attrs: Vec<StructAttr<'a>>,
 }
 
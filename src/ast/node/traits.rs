@@ -1,11 +1,14 @@
 use crate::parser::Span;
 
-use super::{function::FunctionArg, identifier::Ident, type_signature::TypeSignature};
+use super::{
+    function::FunctionArg, identifier::Ident, statement::Stmt, type_signature::TypeSignature,
+};
 
 #[derive(Debug, Clone)]
 pub struct Trait<'a> {
     pub name: Ident<'a>,
     pub attrs: Vec<TraitFuncAttr<'a>>,
+    pub operators: Vec<TraitFuncAttr<'a>>,
     pub span: Span<'a>,
 }
 
@@ -14,5 +17,7 @@ pub struct TraitFuncAttr<'a> {
     pub name: Ident<'a>,
     pub args: Vec<FunctionArg<'a>>,
     pub return_type: Option<TypeSignature<'a>>,
+    pub throws: Option<TypeSignature<'a>>,
+    pub body: Option<Box<Stmt<'a>>>,
     pub span: Span<'a>,
 }
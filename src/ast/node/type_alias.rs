@@ -0,0 +1,11 @@
+use crate::parser::Span;
+
+use super::{identifier::Ident, type_signature::TypeSignature};
+
+/// A new name for an existing type, `type NAME = TYPE_SIG`
+#[derive(Debug, Clone)]
+pub struct TypeAlias<'a> {
+    pub name: Ident<'a>,
+    pub type_sig: TypeSignature<'a>,
+    pub span: Span<'a>,
+}
@@ -13,11 +13,26 @@ pub struct TypeSignature<'a> {
 #[derive(Debug, Clone, PartialEq)]
 pub enum TypeSignatureValue<'a> {
     Base(Ident<'a>),
+    /// A generic type together with its type arguments, `IDENT<TYPE_SIG, ...>`
+    Instance {
+        base: Ident<'a>,
+        args: Vec<TypeSignature<'a>>,
+    },
     Function {
         args: Vec<TypeSignature<'a>>,
         return_type: Box<TypeSignature<'a>>,
+        throws: Option<Box<TypeSignature<'a>>>,
     },
     Tuple(Vec<TypeSignature<'a>>),
+    /// A dictionary from keys to values, `[TYPE_SIG: TYPE_SIG]`
+    Dictionary {
+        key: Box<TypeSignature<'a>>,
+        value: Box<TypeSignature<'a>>,
+    },
+    /// A value that may be absent, `TYPE_SIG?`
+    Optional(Box<TypeSignature<'a>>),
+    /// An anonymous record of named fields, `{ IDENT: TYPE_SIG, ... }`
+    Record(Vec<(Ident<'a>, TypeSignature<'a>)>),
 }
 
 impl Eq for TypeSignatureValue<'_> {}
@@ -29,15 +44,41 @@ impl<'a> Hash for TypeSignatureValue<'a> {
                 state.write_u8(1);
                 base.hash(state);
             }
-            TypeSignatureValue::Function { args, return_type } => {
+            TypeSignatureValue::Function {
+                args,
+                return_type,
+                throws,
+            } => {
                 state.write_u8(2);
                 args.iter().for_each(|arg| arg.value.hash(state));
                 return_type.value.hash(state);
+                throws.iter().for_each(|t| t.value.hash(state));
             }
             TypeSignatureValue::Tuple(types) => {
                 state.write_u8(3);
                 types.iter().for_each(|t| t.value.hash(state));
             }
+            TypeSignatureValue::Instance { base, args } => {
+                state.write_u8(4);
+                base.hash(state);
+                args.iter().for_each(|arg| arg.value.hash(state));
+            }
+            TypeSignatureValue::Dictionary { key, value } => {
+                state.write_u8(5);
+                key.value.hash(state);
+                value.value.hash(state);
+            }
+            TypeSignatureValue::Optional(value) => {
+                state.write_u8(6);
+                value.value.hash(state);
+            }
+            TypeSignatureValue::Record(fields) => {
+                state.write_u8(7);
+                fields.iter().for_each(|(name, t)| {
+                    name.hash(state);
+                    t.value.hash(state);
+                });
+            }
         }
     }
 }
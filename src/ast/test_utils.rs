@@ -1,6 +1,7 @@
 use crate::parser::Span;
 
 use super::node::{
+    generics::TypeParam,
     identifier::Ident,
     type_signature::{TypeSignature, TypeSignatureValue},
 };
@@ -28,3 +29,10 @@ pub fn test_type_sig<'a>(name: &'a str) -> TypeSignature<'a> {
         value: TypeSignatureValue::Base(test_ident(name)),
     }
 }
+
+pub fn test_type_param<'a>(name: &'a str) -> TypeParam<'a> {
+    TypeParam {
+        name: test_ident(name),
+        bound: None,
+    }
+}
@@ -3,20 +3,33 @@ use std::io::{BufWriter, Write};
 use crate::{
     ir::{
         context::IrCtx,
+        ir_walker::{IrWalkable, IrWalker, ScopeValue},
         node::{
-            control_flow::IfStmt,
+            array::ArrayMemberAccess,
+            assignment::Assignment,
+            control_flow::{IfStmt, NarrowingReach},
+            dictionary::DictionaryMemberAccess,
+            enumeration::Enum,
+            error_handling::DoCatch,
             expression::Expr,
+            extension::Extension,
             function::{Function, FunctionArg},
             identifier::{Ident, IdentKey, IdentValue, ResolvedIdentValue},
+            loops::{ForLoop, Loop, LoopJump, LoopJumpKind, WhileLoop},
             module::Module,
+            operator::{BinaryOperation, BinaryOperator, OverloadedFunc, UnaryOperator},
+            pattern_match::{EnumPattern, LetElse, Match},
             statement::{Stmt, StmtBlock, VarDecl},
-            structure::Struct,
+            static_access::StaticMember,
+            string_interpolation::StringPart,
+            structure::{Struct, StructAccess},
+            traits::Trait,
             type_signature::Mutability,
             NodeRef,
         },
         IR,
     },
-    symbols::symbol_table::symbol_table_zipper::SymbolTableZipper,
+    symbols::symbol_table::{symbol_table_zipper::SymbolTableZipper, SymbolValueItem},
 };
 
 pub fn format_ir<'a, 'ctx, W: Write>(
@@ -26,19 +39,52 @@ pub fn format_ir<'a, 'ctx, W: Write>(
     ir: &mut IR<'a>,
 ) -> Result<SymbolTableZipper<'a>, std::io::Error> {
     symbols.reset(&ctx);
+    let temp_prefix = "$".repeat(longest_dollar_run(ctx) + 1);
     let mut ctx = CodeGenCtx {
         writer: BufWriter::new(writer),
         symbols,
         ctx,
+        in_async: false,
+        in_nested_func: false,
+        loop_labels: Vec::new(),
+        in_switch: false,
+        temp_prefix,
     };
     format_module(&mut ctx, &ir.0)?;
     Ok(ctx.symbols)
 }
 
+/// The length of the longest run of `$` in the identifiers of the source
+fn longest_dollar_run(ctx: &IrCtx<'_>) -> usize {
+    let names = ctx.idents.iter().filter_map(|(_, ident)| match ident {
+        IdentValue::Resolved(ResolvedIdentValue::Named { name, .. }) => Some(*name),
+        IdentValue::Unresolved(ident) => Some(ident.value),
+        _ => None,
+    });
+
+    names
+        .flat_map(|name| name.split(|c| c != '$'))
+        .map(str::len)
+        .max()
+        .unwrap_or(0)
+}
+
 pub struct CodeGenCtx<'a, 'ctx, W: Write> {
     pub writer: BufWriter<W>,
     pub symbols: SymbolTableZipper<'a>,
     pub ctx: &'ctx mut IrCtx<'a>,
+    /// Whether the code is generated directly inside of an async function
+    pub in_async: bool,
+    /// Whether the code is generated inside of a function declared in a method,
+    /// which has its own `this`, so `self` is the one captured by the method
+    pub in_nested_func: bool,
+    /// The labels of the loops enclosing the code, innermost last
+    pub loop_labels: Vec<Option<String>>,
+    /// Whether the code is generated inside of the `switch` of a match inside of the innermost loop
+    pub in_switch: bool,
+    /// The start of the names of the temporaries of the generated code, a run of `$` longer
+    /// than any in the identifiers of the source, so that they cannot collide
+    pub temp_prefix: String,
 }
 
 type CodeGenResult = std::io::Result<()>;
@@ -66,6 +112,26 @@ impl<'a, 'ctx, W: Write> CodeGenCtx<'a, 'ctx, W> {
             }
         }
     }
+
+    /// Writes the name of a temporary of the generated code
+    fn write_temp(&mut self, name: &str) -> CodeGenResult {
+        self.writer.write_all(self.temp_prefix.as_bytes())?;
+        self.write(name)
+    }
+
+    /// Writes the name of a temporary of the generated code named after an identifier
+    fn write_temp_ident(&mut self, ident: Ident<'a>) -> CodeGenResult {
+        self.writer.write_all(self.temp_prefix.as_bytes())?;
+        self.write_ident(ident)
+    }
+
+    fn write_self(&mut self) -> CodeGenResult {
+        if self.in_nested_func {
+            self.write_temp("self")
+        } else {
+            self.write("this")
+        }
+    }
 }
 
 impl<'a, 'ctx, W: Write> Write for CodeGenCtx<'a, 'ctx, W> {
@@ -97,6 +163,9 @@ fn format_struct<'a, 'ctx, W: Write>(
         .enter_scope(&gen.ctx, *gen.ctx[st].name)
         .unwrap();
 
+    // the default values are evaluated by the constructor
+    let enclosing_async = std::mem::replace(&mut gen.in_async, false);
+
     gen.write(" (")?;
 
     format_with_separator(
@@ -129,21 +198,339 @@ fn format_struct<'a, 'ctx, W: Write>(
 
     gen.write("\n}")?;
 
+    gen.in_async = enclosing_async;
     gen.symbols.exit_scope(&gen.ctx).unwrap();
 
     Ok(())
 }
 
-fn format_stmt_block<'a, 'ctx, W: Write>(
+fn format_extension<'a, 'ctx, W: Write>(
     gen: &mut CodeGenCtx<'a, 'ctx, W>,
-    stmt_block: NodeRef<'a, StmtBlock<'a>>,
+    ext: NodeRef<'a, Extension<'a>>,
+) -> CodeGenResult {
+    // the default methods of the trait come first, so they can be overridden
+    let default_impls = gen.ctx[ext]
+        .trait_name
+        .and_then(|trait_name| gen.symbols.lookup(gen.ctx, trait_name))
+        .and_then(|sym| match gen.ctx[sym] {
+            SymbolValueItem::TraitDecl(tr) if tr.has_default_impls(gen.ctx) => Some(tr),
+            _ => None,
+        });
+
+    gen.symbols
+        .enter_scope(gen.ctx, *gen.ctx[ext].scope_ident)
+        .expect("extension scope should exist");
+
+    let mut members_written = false;
+
+    if default_impls.is_some() || !gen.ctx[ext].methods.is_empty() {
+        gen.write("Object.assign(")?;
+        gen.write_ident(*gen.ctx[ext].name)?;
+        gen.write(".prototype, {\n")?;
+
+        if let Some(tr) = default_impls {
+            gen.write("...")?;
+            gen.write_ident(*gen.ctx[tr].name)?;
+            if !gen.ctx[ext].methods.is_empty() {
+                gen.write(",\n")?;
+            }
+        }
+
+        format_with_separator(
+            gen,
+            ",\n",
+            gen.ctx[ext].methods.clone().into_iter(),
+            format_method,
+        )?;
+
+        gen.write("\n});")?;
+        members_written = true;
+    }
+
+    for init in gen.ctx[ext].inits.clone() {
+        if members_written {
+            gen.write("\n")?;
+        }
+
+        format_init(gen, ext, init)?;
+        members_written = true;
+    }
+
+    let static_funcs = gen.ctx[ext]
+        .static_methods
+        .iter()
+        .chain(gen.ctx[ext].operators.iter())
+        .copied()
+        .collect::<Vec<_>>();
+
+    // the default operators of the trait that are not declared by the extension
+    let inherited_operators = ext
+        .conformed_trait(gen.ctx, &gen.symbols)
+        .map(|tr| {
+            gen.ctx[tr]
+                .operators
+                .iter()
+                .filter(|op| gen.ctx[**op].default_impl.is_some())
+                .filter_map(|op| op.overloaded_operator(gen.ctx))
+                .filter(|operator| ext.lookup_operator(*operator, gen.ctx).is_none())
+                .map(|operator| (tr, operator))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    if !static_funcs.is_empty() || !inherited_operators.is_empty() {
+        if members_written {
+            gen.write("\n")?;
+        }
+
+        gen.write("Object.assign(")?;
+        gen.write_ident(*gen.ctx[ext].name)?;
+        gen.write(", {\n")?;
+
+        format_with_separator(gen, ",\n", static_funcs.iter().copied(), |gen, func| {
+            if func.overloaded_operator(gen.ctx).is_some() {
+                return format_method(gen, func);
+            }
+
+            if gen.ctx[func].is_async {
+                gen.write("async ")?;
+            }
+            gen.write(STATIC_PREFIX)?;
+            format_named_function(gen, func, true)
+        })?;
+
+        for (i, (tr, operator)) in inherited_operators.into_iter().enumerate() {
+            if i > 0 || !static_funcs.is_empty() {
+                gen.write(",\n")?;
+            }
+
+            gen.write(operator_js_name(operator))?;
+            gen.write(": ")?;
+            gen.write_ident(*gen.ctx[tr].name)?;
+            gen.write(".")?;
+            gen.write(operator_js_name(operator))?;
+        }
+
+        gen.write("\n});")?;
+    }
+
+    gen.symbols.exit_scope(gen.ctx).unwrap();
+
+    Ok(())
+}
+
+/// Formats the static attributes of an extension where it is declared,
+/// as their values are evaluated in the order of the statements
+fn format_static_attrs<'a, 'ctx, W: Write>(
+    gen: &mut CodeGenCtx<'a, 'ctx, W>,
+    ext: NodeRef<'a, Extension<'a>>,
 ) -> CodeGenResult {
+    gen.symbols
+        .enter_scope(gen.ctx, *gen.ctx[ext].scope_ident)
+        .expect("extension scope should exist");
+
     format_with_separator(
         gen,
         "\n",
-        gen.ctx[stmt_block].0.clone().into_iter(),
-        format_stmt,
-    )
+        gen.ctx[ext].static_attrs.clone().into_iter(),
+        |gen, attr| {
+            gen.write_ident(*gen.ctx[ext].name)?;
+            gen.write(".")?;
+            gen.write(STATIC_PREFIX)?;
+            gen.write_ident(*gen.ctx[attr].name)?;
+            gen.write(" = ")?;
+            format_expr(gen, gen.ctx[attr].default_value.unwrap())?;
+            gen.write(";")
+        },
+    )?;
+
+    gen.symbols.exit_scope(gen.ctx).unwrap();
+
+    Ok(())
+}
+
+/// Formats an init as a constructor sharing the prototype of the struct,
+/// which first sets the attributes to their default values
+fn format_init<'a, 'ctx, W: Write>(
+    gen: &mut CodeGenCtx<'a, 'ctx, W>,
+    ext: NodeRef<'a, Extension<'a>>,
+    init: NodeRef<'a, Function<'a>>,
+) -> CodeGenResult {
+    let st_name = *gen.ctx[ext].name;
+    let init_name = init_js_name(gen.ctx, init);
+    let enclosing_async = std::mem::replace(&mut gen.in_async, false);
+
+    gen.write_ident(st_name)?;
+    gen.write(".")?;
+    gen.write(&init_name)?;
+    gen.write(" = function ")?;
+
+    gen.symbols
+        .enter_scope(gen.ctx, *gen.ctx[init].name)
+        .expect("init scope should exist");
+
+    format_func_args(gen, gen.ctx[init].args.clone())?;
+
+    gen.write(" {\n")?;
+    gen.write_ident(st_name)?;
+    gen.write(".call(this);\n")?;
+
+    let enclosing_nested = std::mem::replace(&mut gen.in_nested_func, false);
+    format_self_capture(gen, init)?;
+    format_stmt_block(gen, gen.ctx[init].body)?;
+
+    gen.in_nested_func = enclosing_nested;
+    gen.in_async = enclosing_async;
+    gen.symbols.exit_scope(gen.ctx).unwrap();
+
+    gen.write("};\n")?;
+    gen.write_ident(st_name)?;
+    gen.write(".")?;
+    gen.write(&init_name)?;
+    gen.write(".prototype = ")?;
+    gen.write_ident(st_name)?;
+    gen.write(".prototype;")
+}
+
+/// Prepended to the names of static methods and attributes, which are properties of the
+/// constructor, so they do not collide with the properties of functions like `name` or `call`
+const STATIC_PREFIX: &str = "static$";
+
+fn init_js_name<'a>(ctx: &IrCtx<'a>, init: NodeRef<'a, Function<'a>>) -> String {
+    format!("init${}", init.arg_names(ctx).join("$"))
+}
+
+/// The name of the function of an operator, as the symbol is not a valid JS identifier
+fn operator_js_name(operator: BinaryOperator) -> &'static str {
+    match operator {
+        BinaryOperator::Add => "op$add",
+        BinaryOperator::Sub => "op$sub",
+        BinaryOperator::Mul => "op$mul",
+        BinaryOperator::Div => "op$div",
+        BinaryOperator::Mod => "op$mod",
+        BinaryOperator::Eq => "op$eq",
+        BinaryOperator::NotEq => "op$notEq",
+        BinaryOperator::Lt => "op$lt",
+        BinaryOperator::LtEq => "op$ltEq",
+        BinaryOperator::Gt => "op$gt",
+        BinaryOperator::GtEq => "op$gtEq",
+        _ => unreachable!("operator `{}` can not be overloaded", operator.symbol()),
+    }
+}
+
+/// The values of an enum are arrays, so the enum itself is only written
+/// as an object holding the operators declared in its extensions, if there are any
+fn format_enum<'a, 'ctx, W: Write>(
+    gen: &mut CodeGenCtx<'a, 'ctx, W>,
+    enm: NodeRef<'a, Enum<'a>>,
+) -> CodeGenResult {
+    let name = *gen.ctx[enm].name;
+    gen.write("const ")?;
+    gen.write_ident(name)?;
+    gen.write(" = {};")
+}
+
+fn format_trait<'a, 'ctx, W: Write>(
+    gen: &mut CodeGenCtx<'a, 'ctx, W>,
+    tr: NodeRef<'a, Trait<'a>>,
+) -> CodeGenResult {
+    gen.write("let ")?;
+    gen.write_ident(*gen.ctx[tr].name)?;
+    gen.write(" = {\n")?;
+
+    gen.symbols
+        .enter_scope(gen.ctx, *gen.ctx[tr].name)
+        .expect("trait scope should exist");
+
+    let default_impls = gen.ctx[tr]
+        .attrs
+        .iter()
+        .chain(gen.ctx[tr].operators.iter())
+        .filter_map(|attr| gen.ctx[*attr].default_impl)
+        .collect::<Vec<_>>();
+
+    format_with_separator(gen, ",\n", default_impls.into_iter(), format_method)?;
+
+    gen.symbols.exit_scope(gen.ctx).unwrap();
+
+    gen.write("\n};")
+}
+
+fn format_stmt_block<'a, 'ctx, W: Write>(
+    gen: &mut CodeGenCtx<'a, 'ctx, W>,
+    stmt_block: NodeRef<'a, StmtBlock<'a>>,
+) -> CodeGenResult {
+    let stmts = gen.ctx[stmt_block].0.clone();
+
+    // JS only hoists function declarations, so the enums, traits and extensions, which are
+    // written as assignments, come first to be usable before they are declared like in Taro.
+    // The structs and enums go before the extensions assigning to them, and the traits
+    // before the extensions they are spread into
+    let hoisted_order = |stmt: &Stmt<'a>| match stmt {
+        Stmt::StructDecl(_) => Some(0),
+        Stmt::EnumDecl(_) => Some(1),
+        Stmt::TraitDecl(_) => Some(2),
+        Stmt::Extension(_) => Some(3),
+        _ => None,
+    };
+
+    let mut hoisted = stmts
+        .iter()
+        .filter(|stmt| hoisted_order(&gen.ctx[**stmt]).is_some() && is_written(gen, **stmt))
+        .copied()
+        .collect::<Vec<_>>();
+    hoisted.sort_by_key(|stmt| hoisted_order(&gen.ctx[*stmt]));
+
+    let in_place = stmts
+        .into_iter()
+        .filter(|stmt| match gen.ctx[*stmt] {
+            Stmt::Extension(ext) => !gen.ctx[ext].static_attrs.is_empty(),
+            Stmt::StructDecl(_) | Stmt::EnumDecl(_) | Stmt::TraitDecl(_) => false,
+            _ => true,
+        })
+        .collect::<Vec<_>>();
+
+    let hoisted_written = !hoisted.is_empty();
+    format_with_separator(gen, "\n", hoisted.into_iter(), format_stmt)?;
+
+    if hoisted_written && !in_place.is_empty() {
+        gen.write("\n")?;
+    }
+
+    format_with_separator(gen, "\n", in_place.into_iter(), |gen, stmt| {
+        match gen.ctx[stmt] {
+            Stmt::Extension(ext) => format_static_attrs(gen, ext),
+            _ => format_stmt(gen, stmt),
+        }
+    })
+}
+
+/// Whether anything is written for the declaration of an enum, trait or extension,
+/// as they are mostly used for type checking
+fn is_written<'a, 'ctx, W: Write>(
+    gen: &CodeGenCtx<'a, 'ctx, W>,
+    stmt: NodeRef<'a, Stmt<'a>>,
+) -> bool {
+    match gen.ctx[stmt] {
+        Stmt::EnumDecl(enm) => !gen
+            .symbols
+            .lookup_extensions(gen.ctx, *gen.ctx[enm].name)
+            .is_empty(),
+        Stmt::TraitDecl(tr) => tr.has_default_impls(gen.ctx) || tr.has_default_operators(gen.ctx),
+        Stmt::Extension(ext) => {
+            let ext_value = &gen.ctx[ext];
+            !ext_value.methods.is_empty()
+                || !ext_value.inits.is_empty()
+                || !ext_value.static_methods.is_empty()
+                || !ext_value.operators.is_empty()
+                || ext
+                    .conformed_trait(gen.ctx, &gen.symbols)
+                    .is_some_and(|tr| {
+                        tr.has_default_impls(gen.ctx) || tr.has_default_operators(gen.ctx)
+                    })
+        }
+        _ => true,
+    }
 }
 
 fn format_stmt<'a, 'ctx, W: Write>(
@@ -153,20 +540,44 @@ fn format_stmt<'a, 'ctx, W: Write>(
     match gen.ctx[stmt].clone() {
         Stmt::VariableDecl(var_decl) => format_var_decl(gen, var_decl),
         Stmt::FunctionDecl(func_decl) => format_func_decl(gen, func_decl),
-        Stmt::Expression(expr) => {
-            format_expr(gen, expr)?;
-            gen.write(";")
-        }
+        Stmt::Expression(expr) => match gen.ctx[expr] {
+            Expr::Match(mtch) => format_match(gen, mtch, MatchOutput::Discard),
+            Expr::Assignment(asg)
+                if matches!(gen.ctx[gen.ctx[asg].lhs], Expr::DictionaryIndex(_)) =>
+            {
+                let rhs = gen.ctx[asg].rhs;
+                format_dictionary_set(gen, asg, |gen| format_expr(gen, rhs))?;
+                gen.write(";")
+            }
+            _ => {
+                format_expr(gen, expr)?;
+                gen.write(";")
+            }
+        },
         Stmt::StructDecl(st) => format_struct(gen, st),
-        Stmt::EnumDecl(_) => Ok(()),
-        Stmt::Return(expr) => {
-            gen.write("return ")?;
+        Stmt::EnumDecl(enm) => format_enum(gen, enm),
+        Stmt::Return(expr) => match gen.ctx[expr] {
+            Expr::Match(mtch) => format_match(gen, mtch, MatchOutput::Return),
+            _ => {
+                gen.write("return ")?;
+                format_expr(gen, expr)?;
+                gen.write(";")
+            }
+        },
+        Stmt::Throw(expr) => {
+            gen.write("throw ")?;
             format_expr(gen, expr)?;
             gen.write(";")
         }
-        Stmt::ExternObj(_) => Ok(()),
+        Stmt::ExternObj(_) | Stmt::TypeAlias(_) => Ok(()),
         Stmt::IfBranch(ifb) => format_if_branch(gen, ifb),
-        Stmt::TraitDecl(_) => Ok(()),
+        Stmt::TraitDecl(tr) => format_trait(gen, tr),
+        Stmt::Extension(ext) => format_extension(gen, ext),
+        Stmt::LetElse(let_else) => format_let_else(gen, let_else),
+        Stmt::DoCatch(dc) => format_do_catch(gen, dc),
+        Stmt::WhileLoop(wl) => format_while_loop(gen, wl),
+        Stmt::ForLoop(fl) => format_for_loop(gen, fl),
+        Stmt::LoopJump(jump) => format_loop_jump(gen, jump),
     }
 }
 
@@ -176,6 +587,23 @@ fn format_var_decl<'a, 'ctx, W: Write>(
 ) -> CodeGenResult {
     gen.symbols.visit_next_symbol(&gen.ctx);
 
+    // the arms of the switch statement assign a temporary `$result`, as the variable
+    // could be shadowed by the bindings and declarations of the arms
+    if let Expr::Match(mtch) = gen.ctx[gen.ctx[var_decl].value] {
+        let name = *gen.ctx[var_decl].name;
+        gen.write("let ")?;
+        gen.write_ident(name)?;
+        gen.write(";\n{\nlet ")?;
+        gen.write_temp("result")?;
+        gen.write(";\n")?;
+        format_match(gen, mtch, MatchOutput::Assign)?;
+        gen.write("\n")?;
+        gen.write_ident(name)?;
+        gen.write(" = ")?;
+        gen.write_temp("result")?;
+        return gen.write(";\n}");
+    }
+
     if gen.ctx[var_decl].mutability == Mutability::Mutable {
         gen.write("let ")?;
     } else {
@@ -192,10 +620,121 @@ fn format_func_decl<'a, 'ctx, W: Write>(
     gen: &mut CodeGenCtx<'a, 'ctx, W>,
     func: NodeRef<'a, Function<'a>>,
 ) -> CodeGenResult {
-    let func_name = *gen.ctx[func].name;
+    if gen.ctx[func].is_async {
+        gen.write("async ")?;
+    }
 
     gen.write("function ")?;
-    gen.write_ident(func_name)?;
+    format_named_function(gen, func, false)
+}
+
+/// Formats a named function without the `function` keyword, as used in object literals
+fn format_method<'a, 'ctx, W: Write>(
+    gen: &mut CodeGenCtx<'a, 'ctx, W>,
+    func: NodeRef<'a, Function<'a>>,
+) -> CodeGenResult {
+    if gen.ctx[func].is_async {
+        gen.write("async ")?;
+    }
+
+    format_named_function(gen, func, true)
+}
+
+/// Captures the `this` of a method as `$self` if `self` is used inside of the functions
+/// declared in its body
+fn format_self_capture<'a, 'ctx, W: Write>(
+    gen: &mut CodeGenCtx<'a, 'ctx, W>,
+    method: NodeRef<'a, Function<'a>>,
+) -> CodeGenResult {
+    let body = gen.ctx[method].body;
+    let mut scope = NestedSelfScope::default();
+    let captured = body
+        .walk(&mut NestedSelfWalker, gen.ctx, &mut scope)
+        .is_err();
+
+    if captured {
+        gen.write("const ")?;
+        gen.write_temp("self")?;
+        gen.write(" = this;\n")?;
+    }
+
+    Ok(())
+}
+
+/// Searches the body of a method for uses of `self` inside of the functions declared in it
+struct NestedSelfWalker;
+
+#[derive(Debug, Default)]
+struct NestedSelfScope {
+    in_nested_func: bool,
+    /// Whether the functions declared directly inside of the scope are methods with their own `self`
+    declares_methods: bool,
+}
+
+impl<'a> IrWalker<'a> for NestedSelfWalker {
+    type Scope = NestedSelfScope;
+
+    fn visit_scope_begin(
+        &mut self,
+        ctx: &mut IrCtx<'a>,
+        parent: &mut Self::Scope,
+        value: ScopeValue<'a>,
+    ) -> Result<Self::Scope, Self::Error> {
+        Ok(match value {
+            ScopeValue::Extension(_) | ScopeValue::Trait(_) => NestedSelfScope {
+                in_nested_func: false,
+                declares_methods: true,
+            },
+            ScopeValue::Func(_) if parent.declares_methods => NestedSelfScope::default(),
+            // anonymous functions are arrow functions, which share the `this` of the method
+            ScopeValue::Func(func) => NestedSelfScope {
+                in_nested_func: parent.in_nested_func || ctx[func].name.value(ctx).is_some(),
+                declares_methods: false,
+            },
+            _ => NestedSelfScope {
+                in_nested_func: parent.in_nested_func,
+                declares_methods: false,
+            },
+        })
+    }
+
+    fn visit_expr(
+        &mut self,
+        ctx: &mut IrCtx<'a>,
+        scope: &mut Self::Scope,
+        expr: NodeRef<'a, Expr<'a>>,
+    ) -> Result<(), Self::Error> {
+        if !scope.in_nested_func {
+            return Ok(());
+        }
+
+        match &ctx[expr] {
+            Expr::Identifier(ident, _) if ident.value(ctx) == Some("self") => Err(()),
+            Expr::BinaryOperation(bin_op)
+                if matches!(
+                    ctx[*bin_op].overload.map(|overload| overload.func),
+                    Some(OverloadedFunc::Trait(_, _))
+                ) =>
+            {
+                Err(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+fn format_named_function<'a, 'ctx, W: Write>(
+    gen: &mut CodeGenCtx<'a, 'ctx, W>,
+    func: NodeRef<'a, Function<'a>>,
+    is_method: bool,
+) -> CodeGenResult {
+    let func_name = *gen.ctx[func].name;
+    let enclosing_async = std::mem::replace(&mut gen.in_async, gen.ctx[func].is_async);
+
+    match func.overloaded_operator(gen.ctx) {
+        Some(operator) => gen.write(operator_js_name(operator))?,
+        None => gen.write_ident(func_name)?,
+    }
 
     gen.symbols
         .enter_scope(&gen.ctx, func_name)
@@ -205,8 +744,14 @@ fn format_func_decl<'a, 'ctx, W: Write>(
 
     gen.write(" {\n")?;
 
+    let enclosing_nested = std::mem::replace(&mut gen.in_nested_func, !is_method);
+    if is_method {
+        format_self_capture(gen, func)?;
+    }
     format_stmt_block(gen, gen.ctx[func].body)?;
 
+    gen.in_nested_func = enclosing_nested;
+    gen.in_async = enclosing_async;
     gen.symbols.exit_scope(&gen.ctx).unwrap();
 
     gen.write("}")?;
@@ -218,23 +763,532 @@ fn format_if_branch<'a, 'ctx, W: Write>(
     gen: &mut CodeGenCtx<'a, 'ctx, W>,
     ifb: NodeRef<'a, IfStmt<'a>>,
 ) -> CodeGenResult {
+    let pattern = gen.ctx[ifb].pattern;
+    let unwrapped = match gen.ctx[ifb].unwraps_condition {
+        true => Some(gen.ctx[ifb].narrowed[0].name),
+        false => None,
+    };
+    let scrutinee = match (pattern, unwrapped) {
+        (Some(pattern), _) => Some(format_scrutinee_begin(
+            gen,
+            gen.ctx[ifb].condition,
+            &[pattern],
+        )?),
+        (None, Some(name)) => Some(format_unwrapped_begin(gen, gen.ctx[ifb].condition, name)?),
+        (None, None) => None,
+    };
+
     gen.write("if (")?;
-    format_expr(gen, gen.ctx[ifb].condition)?;
+    match (pattern, scrutinee) {
+        (Some(pattern), Some(scrutinee)) => {
+            write_scrutinee(gen, scrutinee)?;
+            let idx = enum_pattern_index(gen, pattern);
+            gen.write(format!("[0] === {idx}").as_str())?;
+        }
+        (None, Some(scrutinee)) => {
+            write_scrutinee(gen, scrutinee)?;
+            gen.write(" != undefined")?;
+        }
+        _ => format_expr(gen, gen.ctx[ifb].condition)?,
+    }
     gen.write(" ) {\n")?;
+
+    gen.symbols
+        .enter_scope(gen.ctx, *gen.ctx[ifb].body_scope_ident)
+        .expect("if branch scope should exist");
+    match (pattern, unwrapped, scrutinee) {
+        (Some(pattern), _, Some(scrutinee)) => format_pattern_bindings(gen, pattern, scrutinee)?,
+        (None, Some(name), Some(scrutinee)) => {
+            gen.write("const ")?;
+            gen.write_ident(name)?;
+            gen.write(" = ")?;
+            write_scrutinee(gen, scrutinee)?;
+            gen.write(";\n")?;
+        }
+        _ => {}
+    }
     format_stmt_block(gen, gen.ctx[ifb].body)?;
+    gen.symbols.exit_scope(gen.ctx).unwrap();
+
     gen.write("\n}")?;
 
     if let Some(else_body) = gen.ctx[ifb].else_body {
         gen.write(" else {\n")?;
+        gen.symbols
+            .enter_scope(gen.ctx, *gen.ctx[ifb].else_scope_ident)
+            .expect("else branch scope should exist");
         format_stmt_block(gen, else_body)?;
+        gen.symbols.exit_scope(gen.ctx).unwrap();
         gen.write("}\n")?;
     } else {
         gen.write("\n")?;
     }
 
+    if let Some(scrutinee) = scrutinee {
+        format_scrutinee_end(gen, scrutinee)?;
+    }
+
+    for binding in gen.ctx[ifb].narrowed.clone() {
+        if binding.reach == NarrowingReach::AfterIf {
+            gen.symbols.visit_next_symbol(gen.ctx);
+        }
+    }
+
+    Ok(())
+}
+
+fn format_do_catch<'a, 'ctx, W: Write>(
+    gen: &mut CodeGenCtx<'a, 'ctx, W>,
+    dc: NodeRef<'a, DoCatch<'a>>,
+) -> CodeGenResult {
+    gen.write("try {\n")?;
+    gen.symbols
+        .enter_scope(gen.ctx, *gen.ctx[dc].body_scope_ident)
+        .expect("do body scope should exist");
+    format_stmt_block(gen, gen.ctx[dc].body)?;
+    gen.symbols.exit_scope(gen.ctx).unwrap();
+
+    gen.write("\n} catch (")?;
+    gen.write_ident(*gen.ctx[dc].error_name)?;
+    gen.write(") {\n")?;
+
+    gen.symbols
+        .enter_scope(gen.ctx, *gen.ctx[dc].catch_scope_ident)
+        .expect("catch body scope should exist");
+    format_stmt_block(gen, gen.ctx[dc].catch_body)?;
+    gen.symbols.exit_scope(gen.ctx).unwrap();
+
+    gen.write("\n}\n")
+}
+
+fn format_while_loop<'a, 'ctx, W: Write>(
+    gen: &mut CodeGenCtx<'a, 'ctx, W>,
+    wl: NodeRef<'a, WhileLoop<'a>>,
+) -> CodeGenResult {
+    format_loop_label(gen, Loop::While(wl))?;
+    gen.write("while (")?;
+    format_expr(gen, gen.ctx[wl].condition)?;
+    gen.write(") {\n")?;
+
+    gen.symbols
+        .enter_scope(gen.ctx, *gen.ctx[wl].body_scope_ident)
+        .expect("while loop scope should exist");
+    let enclosing_switch = std::mem::replace(&mut gen.in_switch, false);
+    format_stmt_block(gen, gen.ctx[wl].body)?;
+    gen.in_switch = enclosing_switch;
+    gen.loop_labels.pop();
+    gen.symbols.exit_scope(gen.ctx).unwrap();
+
+    gen.write("\n}\n")
+}
+
+fn format_for_loop<'a, 'ctx, W: Write>(
+    gen: &mut CodeGenCtx<'a, 'ctx, W>,
+    fl: NodeRef<'a, ForLoop<'a>>,
+) -> CodeGenResult {
+    let item_name = *gen.ctx[fl].item_name;
+    let sequence = gen.ctx[fl].sequence;
+
+    format_loop_label(gen, Loop::For(fl))?;
+
+    if let Some(range) = literal_range(gen, sequence) {
+        // a range written in the loop header is counted without creating it, its end is
+        // only evaluated once, so unless it is a number it is kept in a temporary
+        let end = gen.ctx[range].rhs;
+        let constant_end = matches!(gen.ctx[end], Expr::NumberLiteral(_, _));
+
+        gen.write("for (let ")?;
+        gen.write_ident(item_name)?;
+        gen.write(" = ")?;
+        format_expr(gen, gen.ctx[range].lhs)?;
+        if !constant_end {
+            gen.write(", ")?;
+            gen.write_temp_ident(item_name)?;
+            gen.write(" = ")?;
+            format_expr(gen, end)?;
+        }
+        gen.write("; ")?;
+        gen.write_ident(item_name)?;
+        gen.write(match gen.ctx[range].operator {
+            BinaryOperator::ClosedRange => " <= ",
+            _ => " < ",
+        })?;
+        if constant_end {
+            format_expr(gen, end)?;
+        } else {
+            gen.write_temp_ident(item_name)?;
+        }
+        gen.write("; ")?;
+        gen.write_ident(item_name)?;
+        gen.write("++) {\n")?;
+    } else if gen.ctx[fl].counts_range {
+        // the end of the range and whether it is closed are kept in temporaries named after the item
+        gen.write("for (let [")?;
+        gen.write_ident(item_name)?;
+        gen.write(", ")?;
+        gen.write_temp_ident(item_name)?;
+        gen.write(", ")?;
+        gen.write_temp_ident(item_name)?;
+        gen.write("Closed] = ")?;
+        format_expr(gen, sequence)?;
+        gen.write("; ")?;
+        gen.write_temp_ident(item_name)?;
+        gen.write("Closed ? ")?;
+        gen.write_ident(item_name)?;
+        gen.write(" <= ")?;
+        gen.write_temp_ident(item_name)?;
+        gen.write(" : ")?;
+        gen.write_ident(item_name)?;
+        gen.write(" < ")?;
+        gen.write_temp_ident(item_name)?;
+        gen.write("; ")?;
+        gen.write_ident(item_name)?;
+        gen.write("++) {\n")?;
+    } else {
+        gen.write("for (const ")?;
+        gen.write_ident(item_name)?;
+        gen.write(" of ")?;
+        format_expr(gen, sequence)?;
+        gen.write(") {\n")?;
+    }
+
+    gen.symbols
+        .enter_scope(gen.ctx, *gen.ctx[fl].body_scope_ident)
+        .expect("for loop scope should exist");
+    let enclosing_switch = std::mem::replace(&mut gen.in_switch, false);
+    format_stmt_block(gen, gen.ctx[fl].body)?;
+    gen.in_switch = enclosing_switch;
+    gen.loop_labels.pop();
+    gen.symbols.exit_scope(gen.ctx).unwrap();
+
+    gen.write("\n}\n")
+}
+
+/// The range operation if the expression is a range written out in place
+fn literal_range<'a, 'ctx, W: Write>(
+    gen: &CodeGenCtx<'a, 'ctx, W>,
+    expr: NodeRef<'a, Expr<'a>>,
+) -> Option<NodeRef<'a, BinaryOperation<'a>>> {
+    match gen.ctx[expr] {
+        Expr::BinaryOperation(bin_op) if gen.ctx[bin_op].operator.is_range() => Some(bin_op),
+        _ => None,
+    }
+}
+
+/// Writes the exclusive end of the range as an index, closed ranges end one after their upper bound
+fn format_range_end<'a, 'ctx, W: Write>(
+    gen: &mut CodeGenCtx<'a, 'ctx, W>,
+    range: NodeRef<'a, BinaryOperation<'a>>,
+) -> CodeGenResult {
+    let end = gen.ctx[range].rhs;
+
+    match gen.ctx[range].operator {
+        BinaryOperator::ClosedRange => {
+            format_operand(gen, end, BinaryOperator::Add.precedence())?;
+            gen.write(" + 1")
+        }
+        _ => format_expr(gen, end),
+    }
+}
+
+/// Labels are not symbols, so they are written as they appear in the source. A loop without
+/// a label that is broken out of from a match is labelled after the number of enclosing loops
+fn format_loop_label<'a, 'ctx, W: Write>(
+    gen: &mut CodeGenCtx<'a, 'ctx, W>,
+    lp: Loop<'a>,
+) -> CodeGenResult {
+    let broken_from_match = match lp {
+        Loop::While(wl) => gen.ctx[wl].broken_from_match,
+        Loop::For(fl) => gen.ctx[fl].broken_from_match,
+    };
+
+    let label = match lp.label(gen.ctx) {
+        Some(label) => Some(label.value(gen.ctx).unwrap().to_owned()),
+        None if broken_from_match => {
+            Some(format!("loop{}{}", gen.temp_prefix, gen.loop_labels.len()))
+        }
+        None => None,
+    };
+
+    if let Some(label) = &label {
+        gen.write(label)?;
+        gen.write(": ")?;
+    }
+
+    gen.loop_labels.push(label);
+    Ok(())
+}
+
+fn format_loop_jump<'a, 'ctx, W: Write>(
+    gen: &mut CodeGenCtx<'a, 'ctx, W>,
+    jump: NodeRef<'a, LoopJump<'a>>,
+) -> CodeGenResult {
+    let kind = gen.ctx[jump].kind;
+    gen.write(kind.keyword())?;
+
+    if let Some(label) = gen.ctx[jump].label {
+        gen.write(" ")?;
+        gen.write(label.value(gen.ctx).unwrap())?;
+    } else if kind == LoopJumpKind::Break && gen.in_switch {
+        let label = gen.loop_labels.last().cloned().flatten();
+        gen.write(" ")?;
+        gen.write(&label.expect("loop broken out of from a match should be labelled"))?;
+    }
+
+    gen.write(";")
+}
+
+fn format_let_else<'a, 'ctx, W: Write>(
+    gen: &mut CodeGenCtx<'a, 'ctx, W>,
+    let_else: NodeRef<'a, LetElse<'a>>,
+) -> CodeGenResult {
+    let pattern = gen.ctx[let_else].pattern;
+    let value = gen.ctx[let_else].value;
+
+    let direct = match &gen.ctx[value] {
+        Expr::Identifier(ident, _) if !is_shadowed_by(gen, **ident, &[pattern]) => Some(**ident),
+        _ => None,
+    };
+
+    // the bindings outlive the check, so unlike the other patterns the value can't be
+    // stored in a block, and gets a temporary named after the first binding instead
+    let temp = match direct {
+        Some(_) => None,
+        None => gen.ctx[pattern]
+            .bindings
+            .iter()
+            .find_map(|binding| binding.name),
+    };
+
+    if let Some(temp) = temp {
+        gen.write("const ")?;
+        gen.write_temp_ident(temp)?;
+        gen.write(" = ")?;
+        format_expr(gen, value)?;
+        gen.write(";\n")?;
+    }
+
+    let write_value = |gen: &mut CodeGenCtx<'a, 'ctx, W>| match (direct, temp) {
+        (Some(ident), _) => gen.write_ident(ident),
+        (None, Some(temp)) => gen.write_temp_ident(temp),
+        // without bindings the value is only used once
+        (None, None) => format_expr(gen, value),
+    };
+
+    gen.write("if (")?;
+    write_value(gen)?;
+    let idx = enum_pattern_index(gen, pattern);
+    gen.write(format!("[0] !== {idx} ) {{\n").as_str())?;
+
+    gen.symbols
+        .enter_scope(gen.ctx, *gen.ctx[let_else].else_scope_ident)
+        .expect("let else scope should exist");
+    format_stmt_block(gen, gen.ctx[let_else].else_body)?;
+    gen.symbols.exit_scope(gen.ctx).unwrap();
+
+    gen.write("\n}\n")?;
+
+    for (i, binding) in gen.ctx[pattern].bindings.clone().into_iter().enumerate() {
+        if let Some(name) = binding.name {
+            gen.symbols.visit_next_symbol(gen.ctx);
+
+            gen.write("const ")?;
+            gen.write_ident(name)?;
+            gen.write(" = ")?;
+            write_value(gen)?;
+            gen.write(format!("[1][{i}];\n").as_str())?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Makes the value matched by enum patterns available to the generated checks,
+/// where a variable can be used directly, as long as no binding shadows it.
+/// Any other value is stored in a temporary `$match` inside a new block.
+fn format_scrutinee_begin<'a, 'ctx, W: Write>(
+    gen: &mut CodeGenCtx<'a, 'ctx, W>,
+    scrutinee: NodeRef<'a, Expr<'a>>,
+    patterns: &[NodeRef<'a, EnumPattern<'a>>],
+) -> std::io::Result<Option<Ident<'a>>> {
+    if let Expr::Identifier(ident, _) = &gen.ctx[scrutinee] {
+        if !is_shadowed_by(gen, **ident, patterns) {
+            return Ok(Some(**ident));
+        }
+    }
+
+    gen.write("{\nconst ")?;
+    gen.write_temp("match")?;
+    gen.write(" = ")?;
+    format_expr(gen, scrutinee)?;
+    gen.write(";\n")?;
+
+    Ok(None)
+}
+
+/// Like `format_scrutinee_begin`, for the optional value unwrapped into `name`
+fn format_unwrapped_begin<'a, 'ctx, W: Write>(
+    gen: &mut CodeGenCtx<'a, 'ctx, W>,
+    optional: NodeRef<'a, Expr<'a>>,
+    name: Ident<'a>,
+) -> std::io::Result<Option<Ident<'a>>> {
+    if let Expr::Identifier(ident, _) = &gen.ctx[optional] {
+        if !IdentKey::idents_eq(gen.ctx, **ident, name) {
+            return Ok(Some(**ident));
+        }
+    }
+
+    gen.write("{\nconst ")?;
+    gen.write_temp("match")?;
+    gen.write(" = ")?;
+    format_expr(gen, optional)?;
+    gen.write(";\n")?;
+
+    Ok(None)
+}
+
+fn is_shadowed_by<'a, 'ctx, W: Write>(
+    gen: &CodeGenCtx<'a, 'ctx, W>,
+    ident: Ident<'a>,
+    patterns: &[NodeRef<'a, EnumPattern<'a>>],
+) -> bool {
+    patterns
+        .iter()
+        .flat_map(|pattern| gen.ctx[*pattern].bindings.iter())
+        .filter_map(|binding| binding.name)
+        .any(|name| IdentKey::idents_eq(gen.ctx, name, ident))
+}
+
+fn format_scrutinee_end<'a, 'ctx, W: Write>(
+    gen: &mut CodeGenCtx<'a, 'ctx, W>,
+    scrutinee: Option<Ident<'a>>,
+) -> CodeGenResult {
+    match scrutinee {
+        Some(_) => Ok(()),
+        None => gen.write("}"),
+    }
+}
+
+fn write_scrutinee<'a, 'ctx, W: Write>(
+    gen: &mut CodeGenCtx<'a, 'ctx, W>,
+    scrutinee: Option<Ident<'a>>,
+) -> CodeGenResult {
+    match scrutinee {
+        Some(ident) => gen.write_ident(ident),
+        None => gen.write_temp("match"),
+    }
+}
+
+fn enum_pattern_index<'a, 'ctx, W: Write>(
+    gen: &mut CodeGenCtx<'a, 'ctx, W>,
+    pattern: NodeRef<'a, EnumPattern<'a>>,
+) -> usize {
+    let enm = pattern
+        .lookup_enum(gen.ctx, &gen.symbols)
+        .expect("pattern should be resolved");
+
+    enm.lookup_value(gen.ctx, *gen.ctx[pattern].enum_value)
+        .expect("Expected to find enum value")
+        .0
+}
+
+fn format_pattern_bindings<'a, 'ctx, W: Write>(
+    gen: &mut CodeGenCtx<'a, 'ctx, W>,
+    pattern: NodeRef<'a, EnumPattern<'a>>,
+    scrutinee: Option<Ident<'a>>,
+) -> CodeGenResult {
+    for (i, binding) in gen.ctx[pattern].bindings.clone().into_iter().enumerate() {
+        if let Some(name) = binding.name {
+            gen.write("const ")?;
+            gen.write_ident(name)?;
+            gen.write(" = ")?;
+            write_scrutinee(gen, scrutinee)?;
+            gen.write(format!("[1][{i}];\n").as_str())?;
+        }
+    }
+
     Ok(())
 }
 
+/// Where the value of the arms of a match expression ends up
+#[derive(Debug, Clone, Copy)]
+enum MatchOutput {
+    Discard,
+    Assign,
+    Return,
+}
+
+fn format_match<'a, 'ctx, W: Write>(
+    gen: &mut CodeGenCtx<'a, 'ctx, W>,
+    mtch: NodeRef<'a, Match<'a>>,
+    output: MatchOutput,
+) -> CodeGenResult {
+    let arms = gen.ctx[mtch].arms.clone();
+    let scrutinee = format_scrutinee_begin(gen, gen.ctx[mtch].scrutinee, &mtch.patterns(gen.ctx))?;
+
+    gen.write("switch (")?;
+    write_scrutinee(gen, scrutinee)?;
+    gen.write("[0]) {\n")?;
+
+    let enclosing_switch = std::mem::replace(&mut gen.in_switch, true);
+
+    for arm in arms {
+        gen.symbols
+            .enter_scope(gen.ctx, *gen.ctx[arm].scope_ident)
+            .expect("match arm scope should exist");
+
+        if let Some(pattern) = gen.ctx[arm].pattern {
+            let idx = enum_pattern_index(gen, pattern);
+            gen.write(format!("case {idx}: {{\n").as_str())?;
+            format_pattern_bindings(gen, pattern, scrutinee)?;
+        } else {
+            gen.write("default: {\n")?;
+        }
+
+        let body = gen.ctx[arm].body;
+        if !gen.ctx[body].0.is_empty() {
+            format_stmt_block(gen, body)?;
+            gen.write("\n")?;
+        }
+
+        let mut diverges = body.diverges(gen.ctx);
+
+        if let Some(result) = gen.ctx[arm].result {
+            match output {
+                MatchOutput::Discard => {}
+                MatchOutput::Assign => {
+                    gen.write_temp("result")?;
+                    gen.write(" = ")?;
+                }
+                MatchOutput::Return => {
+                    gen.write("return ")?;
+                    diverges = true;
+                }
+            }
+
+            format_expr(gen, result)?;
+            gen.write(";\n")?;
+        }
+
+        if !diverges {
+            gen.write("break;\n")?;
+        }
+
+        gen.write("}\n")?;
+
+        gen.symbols.exit_scope(gen.ctx).unwrap();
+    }
+
+    gen.in_switch = enclosing_switch;
+    gen.write("}")?;
+
+    if scrutinee.is_none() {
+        gen.write("\n")?;
+    }
+
+    format_scrutinee_end(gen, scrutinee)
+}
+
 fn format_expr<'a, 'ctx, W: Write>(
     gen: &mut CodeGenCtx<'a, 'ctx, W>,
     expr: NodeRef<'a, Expr<'a>>,
@@ -242,29 +1296,61 @@ fn format_expr<'a, 'ctx, W: Write>(
     match gen.ctx[expr].clone() {
         Expr::StringLiteral(str, _) => {
             gen.write("\"")?;
-            gen.write(str)?;
+            gen.write(&escape_js_string(&str, StringDelimiter::Quote))?;
             gen.write("\"")
         }
-        Expr::NumberLiteral(num, _) => gen.write_fmt(format_args!("{}", num)),
+        Expr::StringInterpolation(interp) => {
+            gen.write("`")?;
+
+            for part in gen.ctx[interp].parts.clone() {
+                match part {
+                    StringPart::Literal(str) => {
+                        gen.write(&escape_js_string(&str, StringDelimiter::Backtick))?
+                    }
+                    StringPart::Expr(expr) => {
+                        gen.write("${")?;
+                        format_expr(gen, expr)?;
+                        gen.write("}")?;
+                    }
+                }
+            }
+
+            gen.write("`")
+        }
+        Expr::NumberLiteral(num, _) => gen.write(&format_js_number(num)),
         Expr::BoolLiteral(val, _) => gen.write(if val == true { "true" } else { "false" }),
+        Expr::NilLiteral(_, _) => gen.write("undefined"),
         Expr::Function(func) => {
             gen.symbols
                 .enter_scope(&gen.ctx, *gen.ctx[func].name)
                 .expect("function scope should exist");
 
+            let enclosing_async = std::mem::replace(&mut gen.in_async, gen.ctx[func].is_async);
+
             gen.write("(")?;
+            if gen.in_async {
+                gen.write("async ")?;
+            }
 
             format_func_args(gen, gen.ctx[func].args.clone())?;
             gen.write(" => {")?;
 
             format_stmt_block(gen, gen.ctx[func].body)?;
 
+            gen.in_async = enclosing_async;
             gen.symbols.exit_scope(&gen.ctx).unwrap();
 
             gen.write("})")
         }
         Expr::FunctionCall(call) => {
-            format_expr(gen, gen.ctx[call].func)?;
+            match gen.ctx[gen.ctx[call].func] {
+                Expr::StructAccess(st_access) => format_struct_access(gen, st_access)?,
+                Expr::ArrayMemberAccess(arr_acc) => format_array_member_access(gen, arr_acc)?,
+                Expr::DictionaryMemberAccess(dict_acc) => {
+                    format_dictionary_member_access(gen, dict_acc)?
+                }
+                _ => format_expr(gen, gen.ctx[call].func)?,
+            }
             gen.write("(")?;
             format_with_separator(
                 gen,
@@ -274,7 +1360,39 @@ fn format_expr<'a, 'ctx, W: Write>(
             )?;
             gen.write(")")
         }
-        Expr::Identifier(ident, _) => gen.write_ident(*ident),
+        Expr::Identifier(ident, _) => match gen.symbols.lookup(gen.ctx, *ident) {
+            Some(sym)
+                if matches!(
+                    gen.ctx[sym],
+                    SymbolValueItem::SelfValue(_) | SymbolValueItem::TraitSelfValue(_)
+                ) =>
+            {
+                gen.write_self()
+            }
+            _ => gen.write_ident(*ident),
+        },
+        Expr::StructInit(st_init) if st_init.is_record(gen.ctx) => {
+            gen.symbols
+                .enter_scope(gen.ctx, *gen.ctx[st_init].scope_name)
+                .expect("struct init scope should exist");
+
+            // records are plain objects with the fields in the order they are written
+            gen.write("{")?;
+            format_with_separator(
+                gen,
+                ", ",
+                gen.ctx[st_init].values.clone().into_iter(),
+                |gen, val| {
+                    gen.write(gen.ctx[val].name.value(gen.ctx).unwrap())?;
+                    gen.write(": ")?;
+                    format_expr(gen, gen.ctx[val].value)
+                },
+            )?;
+            gen.write("}")?;
+
+            gen.symbols.exit_scope(gen.ctx).unwrap();
+            Ok(())
+        }
         Expr::StructInit(st_init) => {
             gen.symbols
                 .enter_scope(&gen.ctx, *gen.ctx[st_init].scope_name)
@@ -311,13 +1429,25 @@ fn format_expr<'a, 'ctx, W: Write>(
             gen.symbols.exit_scope(&gen.ctx).unwrap();
             Ok(())
         }
-        Expr::StructAccess(st_access) => {
-            format_expr(gen, gen.ctx[st_access].struct_expr)?;
-            gen.write(".")?;
-
-            gen.write_ident(gen.ctx[st_access].attr_name)
+        Expr::StructAccess(st_access) if st_access.is_method(gen.ctx, &mut gen.symbols) => {
+            format_bound_method(gen, gen.ctx[st_access].struct_expr, |gen| {
+                format_struct_member(gen, st_access)
+            })
         }
+        Expr::StructAccess(st_access) => format_struct_access(gen, st_access),
         Expr::EscapeBlock(block) => gen.write(gen.ctx[block].content),
+        // `set` results in the dictionary, so the assigned value is passed through
+        Expr::Assignment(asg) if matches!(gen.ctx[gen.ctx[asg].lhs], Expr::DictionaryIndex(_)) => {
+            gen.write("((")?;
+            gen.write_temp("value")?;
+            gen.write(") => (")?;
+            format_dictionary_set(gen, asg, |gen| gen.write_temp("value"))?;
+            gen.write(", ")?;
+            gen.write_temp("value")?;
+            gen.write("))(")?;
+            format_expr(gen, gen.ctx[asg].rhs)?;
+            gen.write(")")
+        }
         Expr::Assignment(asg) => {
             format_expr(gen, gen.ctx[asg].lhs)?;
             gen.write(" = ")?;
@@ -372,6 +1502,431 @@ fn format_expr<'a, 'ctx, W: Write>(
         Expr::UnresolvedMemberAccess(_) => {
             unreachable!("Unresolved member access should have been handled by now")
         }
+        // ranges are stored as their start and end, closed ranges are marked by a third item
+        Expr::BinaryOperation(bin_op) if gen.ctx[bin_op].operator.is_range() => {
+            gen.write("[")?;
+            format_expr(gen, gen.ctx[bin_op].lhs)?;
+            gen.write(", ")?;
+            format_expr(gen, gen.ctx[bin_op].rhs)?;
+            if gen.ctx[bin_op].operator == BinaryOperator::ClosedRange {
+                gen.write(", true")?;
+            }
+            gen.write("]")
+        }
+        Expr::BinaryOperation(bin_op) if gen.ctx[bin_op].overload.is_some() => {
+            format_overloaded_operation(gen, bin_op)
+        }
+        Expr::BinaryOperation(bin_op) => {
+            let operator = gen.ctx[bin_op].operator;
+            let (lhs, rhs) = (gen.ctx[bin_op].lhs, gen.ctx[bin_op].rhs);
+            // absent values may be either `undefined` or `null` in JS,
+            // which are only equal to each other when compared loosely
+            let compares_nil = [lhs, rhs]
+                .iter()
+                .any(|operand| matches!(gen.ctx[*operand], Expr::NilLiteral(_, _)));
+
+            format_operand(gen, lhs, operator.precedence())?;
+            gen.write(" ")?;
+            gen.write(match operator {
+                BinaryOperator::Eq if compares_nil => "==",
+                BinaryOperator::NotEq if compares_nil => "!=",
+                BinaryOperator::Eq => "===",
+                BinaryOperator::NotEq => "!==",
+                _ => operator.symbol(),
+            })?;
+            gen.write(" ")?;
+
+            let Some(scope_ident) = gen.ctx[bin_op].rhs_scope_ident else {
+                return format_operand(gen, rhs, operator.precedence() + 1);
+            };
+            gen.symbols
+                .enter_scope(gen.ctx, scope_ident)
+                .expect("right operand scope should exist");
+            format_operand(gen, rhs, operator.precedence() + 1)?;
+            gen.symbols.exit_scope(gen.ctx).unwrap();
+            Ok(())
+        }
+        Expr::UnaryOperation(unary_op) => {
+            gen.write(match gen.ctx[unary_op].operator {
+                UnaryOperator::Negate => "-",
+                UnaryOperator::Not => "!",
+            })?;
+            format_operand(gen, gen.ctx[unary_op].operand, u8::MAX)
+        }
+        Expr::Match(mtch) => {
+            // a switch statement is not an expression in JS,
+            // inside of an async function the arms may await as well
+            if gen.in_async {
+                gen.write("(await (async () => {\n")?;
+                format_match(gen, mtch, MatchOutput::Return)?;
+                gen.write("\n})())")
+            } else {
+                gen.write("(() => {\n")?;
+                format_match(gen, mtch, MatchOutput::Return)?;
+                gen.write("\n})()")
+            }
+        }
+        Expr::Await(aw) => {
+            gen.write("await ")?;
+            format_operand(gen, gen.ctx[aw].expr, u8::MAX)
+        }
+        // errors propagate through the JS exception of the call
+        Expr::Try(tr) => format_expr(gen, gen.ctx[tr].expr),
+        Expr::Array(arr) => {
+            gen.write("[")?;
+            format_with_separator(
+                gen,
+                ", ",
+                gen.ctx[arr].items.clone().into_iter(),
+                format_expr,
+            )?;
+            gen.write("]")
+        }
+        Expr::Index(idx) => {
+            format_operand(gen, gen.ctx[idx].array, u8::MAX)?;
+            gen.write("[")?;
+            format_expr(gen, gen.ctx[idx].index)?;
+            gen.write("]")
+        }
+        Expr::ArraySlice(slice) => {
+            let range = gen.ctx[slice].range;
+
+            match literal_range(gen, range) {
+                Some(range) => {
+                    format_operand(gen, gen.ctx[slice].array, u8::MAX)?;
+                    gen.write(".slice(")?;
+                    format_expr(gen, gen.ctx[range].lhs)?;
+                    gen.write(", ")?;
+                    format_range_end(gen, range)?;
+                    gen.write(")")
+                }
+                // whether a stored range is closed is only known at runtime
+                None => {
+                    let t = gen.temp_prefix.clone();
+                    gen.write(&format!("(({t}array, [{t}start, {t}end, {t}closed]) => "))?;
+                    gen.write(&format!(
+                        "{t}array.slice({t}start, {t}closed ? {t}end + 1 : {t}end))("
+                    ))?;
+                    format_expr(gen, gen.ctx[slice].array)?;
+                    gen.write(", ")?;
+                    format_expr(gen, range)?;
+                    gen.write(")")
+                }
+            }
+        }
+        Expr::ArrayMemberAccess(arr_acc) if gen.ctx[arr_acc].member.is_method() => {
+            let member = gen.ctx[arr_acc].member;
+            format_bound_method(gen, gen.ctx[arr_acc].array, |gen| {
+                gen.write(".")?;
+                gen.write(member.name())
+            })
+        }
+        Expr::ArrayMemberAccess(arr_acc) => format_array_member_access(gen, arr_acc),
+        Expr::Dictionary(dict) => {
+            gen.write("new Map(")?;
+
+            if !gen.ctx[dict].entries.is_empty() {
+                gen.write("[")?;
+                format_with_separator(
+                    gen,
+                    ", ",
+                    gen.ctx[dict].entries.clone().into_iter(),
+                    |gen, (key, value)| {
+                        gen.write("[")?;
+                        format_expr(gen, key)?;
+                        gen.write(", ")?;
+                        format_expr(gen, value)?;
+                        gen.write("]")
+                    },
+                )?;
+                gen.write("]")?;
+            }
+
+            gen.write(")")
+        }
+        Expr::DictionaryIndex(dict_idx) => {
+            format_operand(gen, gen.ctx[dict_idx].dictionary, u8::MAX)?;
+            gen.write(".get(")?;
+            format_expr(gen, gen.ctx[dict_idx].key)?;
+            gen.write(")")
+        }
+        Expr::DictionaryMemberAccess(dict_acc) if gen.ctx[dict_acc].member.is_method() => {
+            let member = gen.ctx[dict_acc].member;
+            format_bound_method(gen, gen.ctx[dict_acc].dictionary, |gen| {
+                gen.write(".")?;
+                gen.write(member.name())
+            })
+        }
+        Expr::DictionaryMemberAccess(dict_acc) => format_dictionary_member_access(gen, dict_acc),
+        Expr::RecordAccess(rec_acc) => {
+            format_operand(gen, gen.ctx[rec_acc].record, u8::MAX)?;
+            gen.write(if gen.ctx[rec_acc].optional { "?." } else { "." })?;
+
+            gen.write(gen.ctx[rec_acc].field_name.value(gen.ctx).unwrap())
+        }
+        Expr::StaticAccess(st_acc) => {
+            let member = gen.ctx[st_acc].member;
+            if let StaticMember::Init(_) = member {
+                gen.write("new ")?;
+            }
+
+            format_expr(gen, gen.ctx[st_acc].struct_expr)?;
+            gen.write(".")?;
+
+            match member {
+                StaticMember::Init(init) => gen.write(&init_js_name(gen.ctx, init)),
+                StaticMember::Method(method) => {
+                    gen.write(STATIC_PREFIX)?;
+                    gen.write_ident(*gen.ctx[method].name)
+                }
+                StaticMember::Attr(attr) => {
+                    gen.write(STATIC_PREFIX)?;
+                    gen.write_ident(*gen.ctx[attr].name)
+                }
+            }
+        }
+    }
+}
+
+fn format_struct_access<'a, 'ctx, W: Write>(
+    gen: &mut CodeGenCtx<'a, 'ctx, W>,
+    st_access: NodeRef<'a, StructAccess<'a>>,
+) -> CodeGenResult {
+    format_expr(gen, gen.ctx[st_access].struct_expr)?;
+    format_struct_member(gen, st_access)
+}
+
+fn format_struct_member<'a, 'ctx, W: Write>(
+    gen: &mut CodeGenCtx<'a, 'ctx, W>,
+    st_access: NodeRef<'a, StructAccess<'a>>,
+) -> CodeGenResult {
+    gen.write(if gen.ctx[st_access].optional {
+        "?."
+    } else {
+        "."
+    })?;
+
+    gen.write_ident(gen.ctx[st_access].attr_name)
+}
+
+fn format_array_member_access<'a, 'ctx, W: Write>(
+    gen: &mut CodeGenCtx<'a, 'ctx, W>,
+    arr_acc: NodeRef<'a, ArrayMemberAccess<'a>>,
+) -> CodeGenResult {
+    format_operand(gen, gen.ctx[arr_acc].array, u8::MAX)?;
+    gen.write(".")?;
+    gen.write(gen.ctx[arr_acc].member.name())
+}
+
+fn format_dictionary_member_access<'a, 'ctx, W: Write>(
+    gen: &mut CodeGenCtx<'a, 'ctx, W>,
+    dict_acc: NodeRef<'a, DictionaryMemberAccess<'a>>,
+) -> CodeGenResult {
+    let member = gen.ctx[dict_acc].member;
+
+    // the iterators of a map are collected, so that they can be used like arrays
+    if member.is_iterator() {
+        gen.write("Array.from(")?;
+    }
+
+    format_operand(gen, gen.ctx[dict_acc].dictionary, u8::MAX)?;
+    gen.write(".")?;
+    gen.write(member.name())?;
+
+    if member.is_iterator() {
+        gen.write("())")?;
+    }
+
+    Ok(())
+}
+
+/// Formats the assignment of an entry of a dictionary, as the entries of a map are not its properties
+fn format_dictionary_set<'a, 'ctx, W: Write>(
+    gen: &mut CodeGenCtx<'a, 'ctx, W>,
+    asg: NodeRef<'a, Assignment<'a>>,
+    format_value: impl FnOnce(&mut CodeGenCtx<'a, 'ctx, W>) -> CodeGenResult,
+) -> CodeGenResult {
+    let Expr::DictionaryIndex(dict_idx) = gen.ctx[gen.ctx[asg].lhs] else {
+        unreachable!("only the entries of dictionaries are set");
+    };
+
+    format_operand(gen, gen.ctx[dict_idx].dictionary, u8::MAX)?;
+    gen.write(".set(")?;
+    format_expr(gen, gen.ctx[dict_idx].key)?;
+    gen.write(", ")?;
+    format_value(gen)?;
+    gen.write(")")
+}
+
+/// Formats a method that is not called right away as a function bound to its receiver,
+/// which is kept in a parameter unless it is a plain name, to be evaluated once
+fn format_bound_method<'a, 'ctx, W: Write>(
+    gen: &mut CodeGenCtx<'a, 'ctx, W>,
+    receiver: NodeRef<'a, Expr<'a>>,
+    format_member: impl Fn(&mut CodeGenCtx<'a, 'ctx, W>) -> CodeGenResult,
+) -> CodeGenResult {
+    if !matches!(gen.ctx[receiver], Expr::Identifier(_, _)) {
+        gen.write("((")?;
+        gen.write_temp("receiver")?;
+        gen.write(") => ")?;
+        gen.write_temp("receiver")?;
+        format_member(gen)?;
+        gen.write(".bind(")?;
+        gen.write_temp("receiver")?;
+        gen.write("))(")?;
+        format_expr(gen, receiver)?;
+        return gen.write(")");
+    }
+
+    format_expr(gen, receiver)?;
+    format_member(gen)?;
+    gen.write(".bind(")?;
+    format_expr(gen, receiver)?;
+    gen.write(")")
+}
+
+fn format_overloaded_operation<'a, 'ctx, W: Write>(
+    gen: &mut CodeGenCtx<'a, 'ctx, W>,
+    bin_op: NodeRef<'a, BinaryOperation<'a>>,
+) -> CodeGenResult {
+    let overload = gen.ctx[bin_op].overload.unwrap();
+    let operator = if overload.negated {
+        gen.write("!")?;
+        BinaryOperator::Eq
+    } else {
+        gen.ctx[bin_op].operator
+    };
+
+    let lhs = gen.ctx[bin_op].lhs;
+    match overload.func {
+        OverloadedFunc::Static(ext, _) => gen.write_ident(*gen.ctx[ext].name)?,
+        // the default operators of a trait are called through the conforming type
+        OverloadedFunc::Trait(_, _) => gen.write_self()?,
+        // the structure is only known at runtime, so a left operand that is not a plain
+        // name is kept in a parameter, to be evaluated once
+        OverloadedFunc::TypeParam(_, _) if !matches!(gen.ctx[lhs], Expr::Identifier(_, _)) => {
+            let t = gen.temp_prefix.clone();
+            gen.write(&format!("(({t}lhs, {t}rhs) => {t}lhs.constructor."))?;
+            gen.write(operator_js_name(operator))?;
+            gen.write(&format!("({t}lhs, {t}rhs))("))?;
+            format_expr(gen, lhs)?;
+            gen.write(", ")?;
+            format_expr(gen, gen.ctx[bin_op].rhs)?;
+            return gen.write(")");
+        }
+        OverloadedFunc::TypeParam(_, _) => {
+            format_expr(gen, lhs)?;
+            gen.write(".constructor")?;
+        }
+    }
+
+    gen.write(".")?;
+    gen.write(operator_js_name(operator))?;
+    gen.write("(")?;
+    format_expr(gen, lhs)?;
+    gen.write(", ")?;
+    format_expr(gen, gen.ctx[bin_op].rhs)?;
+    gen.write(")")
+}
+
+/// Formats an operand of an operator expression,
+/// surrounded by parentheses if it binds looser than `min_precedence`.
+fn format_operand<'a, 'ctx, W: Write>(
+    gen: &mut CodeGenCtx<'a, 'ctx, W>,
+    operand: NodeRef<'a, Expr<'a>>,
+    min_precedence: u8,
+) -> CodeGenResult {
+    let precedence = match &gen.ctx[operand] {
+        // `??` binds looser than the comparisons in JS and cannot be mixed with `&&` and `||`
+        // without parentheses, so it is always parenthesized as an operand
+        Expr::BinaryOperation(bin_op) if gen.ctx[*bin_op].operator == BinaryOperator::Coalesce => 0,
+        // a declared operator is a call, negated for a `!=` derived from `==`
+        Expr::BinaryOperation(bin_op) => match gen.ctx[*bin_op].overload {
+            Some(overload) if overload.negated => u8::MAX - 1,
+            Some(_) => u8::MAX,
+            None => gen.ctx[*bin_op].operator.precedence(),
+        },
+        Expr::UnaryOperation(_) | Expr::Await(_) | Expr::Try(_) => u8::MAX - 1,
+        Expr::Assignment(_) => 0,
+        _ => u8::MAX,
+    };
+
+    if precedence < min_precedence {
+        gen.write("(")?;
+        format_expr(gen, operand)?;
+        gen.write(")")
+    } else {
+        format_expr(gen, operand)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum StringDelimiter {
+    Quote,
+    Backtick,
+}
+
+/// Escapes the decoded value of a string so that it can be written between the delimiters
+/// of a JS string literal
+fn escape_js_string(value: &str, delimiter: StringDelimiter) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' if delimiter == StringDelimiter::Quote => escaped.push_str("\\\""),
+            '`' if delimiter == StringDelimiter::Backtick => escaped.push_str("\\`"),
+            '$' if delimiter == StringDelimiter::Backtick && chars.peek() == Some(&'{') => {
+                escaped.push_str("\\$")
+            }
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            '\u{2028}' | '\u{2029}' => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c if c.is_ascii_control() => escaped.push_str(&format!("\\x{:02x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// Formats a number the way javascript's `Number.prototype.toString` does,
+/// using the shortest digits which round-trip and an exponent for very large or small values
+fn format_js_number(num: f64) -> String {
+    if num == 0.0 {
+        return "0".to_string();
+    }
+
+    // the shortest round-tripping digits, e.g. `1.2345e3`
+    let scientific = format!("{:e}", num.abs());
+    let (mantissa, exponent) = scientific.split_once('e').unwrap();
+    let digits = mantissa.replace('.', "");
+    let exponent: i32 = exponent.parse().unwrap();
+
+    let k = digits.len() as i32;
+    let n = exponent + 1;
+
+    let formatted = if k <= n && n <= 21 {
+        format!("{}{}", digits, "0".repeat((n - k) as usize))
+    } else if 0 < n && n <= 21 {
+        format!("{}.{}", &digits[..n as usize], &digits[n as usize..])
+    } else if -6 < n && n <= 0 {
+        format!("0.{}{}", "0".repeat(-n as usize), digits)
+    } else {
+        let sign = if n > 0 { "+" } else { "-" };
+        let fraction = match k {
+            1 => String::new(),
+            _ => format!(".{}", &digits[1..]),
+        };
+
+        format!("{}{}e{}{}", &digits[..1], fraction, sign, (n - 1).abs())
+    };
+
+    match num.is_sign_negative() {
+        true => format!("-{}", formatted),
+        false => formatted,
     }
 }
 
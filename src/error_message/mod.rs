@@ -1,5 +1,6 @@
 use crate::{
-    parser::{ParserError, Span},
+    error_message::error_formatter::{format_span_items, ErrMsgType, ErrRemark, SpanItem},
+    parser::{number_literal::NumberLiteralError, ParserError, Span},
     symbols::{symbol_resolver::SymbolResolutionError, symbol_table::SymbolCollectionError},
     type_checker::TypeCheckerError,
     TranspilerError,
@@ -46,16 +47,79 @@ where
 
 impl<'a: 'ret, 'ret, W: Write> ErrorMessage<'a, 'ret, (), W> for ParserError<'a> {
     fn err_msg(&self, _ctx: ()) -> ErrMsg<'a, 'ret, W> {
-        let err_msg = self.to_string();
+        match self {
+            ParserError::InvalidEscape(span) => parser_span_err_msg(
+                span.clone(),
+                format!("invalid escape sequence `{}`", span.fragment),
+                "valid escape sequences are `\\n`, `\\r`, `\\t`, `\\0`, `\\\"`, `\\'`, `\\\\` \
+                 and `\\u{...}` with 1 to 6 hex digits",
+            ),
+            ParserError::InsufficientIndentation(span) => parser_span_err_msg(
+                span.clone(),
+                "insufficient indentation of a line in a multi-line string".to_string(),
+                "every line of a multi-line string has to start with the indentation \
+                 of the closing quotes",
+            ),
+            ParserError::InvalidNumber(span, err) => {
+                let (title, hint) = match err {
+                    NumberLiteralError::Malformed => (
+                        format!("malformed number literal `{}`", span.fragment),
+                        "`0x`, `0b` and `0o` literals only contain digits of their radix, \
+                         and `_` can only separate two digits",
+                    ),
+                    NumberLiteralError::OutOfRange => (
+                        format!("number literal `{}` is out of range", span.fragment),
+                        "numbers are 64-bit floating point values",
+                    ),
+                    NumberLiteralError::PrecisionLoss => (
+                        format!(
+                            "number literal `{}` cannot be represented exactly",
+                            span.fragment
+                        ),
+                        "integers above 2^53 lose precision as 64-bit floating point values",
+                    ),
+                };
+
+                parser_span_err_msg(span.clone(), title, hint)
+            }
+            ParserError::NomErr(_) | ParserError::EarlyTermination() => {
+                let err_msg = self.to_string();
 
-        ErrMsg {
-            span: None,
-            title: Box::new(|w| write!(w, "Parser error")),
-            msg: Box::new(move |w| writeln!(w, "\n{}", err_msg)),
+                ErrMsg {
+                    span: None,
+                    title: Box::new(|w| write!(w, "Parser error")),
+                    msg: Box::new(move |w| writeln!(w, "\n{}", err_msg)),
+                }
+            }
         }
     }
 }
 
+fn parser_span_err_msg<'a: 'ret, 'ret, W: Write>(
+    span: Span<'a>,
+    title: String,
+    hint: &'static str,
+) -> ErrMsg<'a, 'ret, W> {
+    ErrMsg {
+        span: Some(span.clone()),
+        title: Box::new(move |w| write!(w, "{}", title)),
+        msg: Box::new(move |w| {
+            format_span_items(
+                w,
+                &mut [SpanItem {
+                    span: span.clone(),
+                    msg: None,
+                    err_type: ErrMsgType::Err,
+                }],
+                &[ErrRemark {
+                    msg: hint.to_string(),
+                    err_type: ErrMsgType::Hint,
+                }],
+            )
+        }),
+    }
+}
+
 impl<'a: 'ret, 'ret, W: Write> ErrorMessage<'a, 'ret, (), W> for TranspilerError<'a> {
     fn err_msg(&'ret self, _ctx: ()) -> ErrMsg<'a, 'ret, W> {
         match self {
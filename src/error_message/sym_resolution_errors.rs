@@ -1,4 +1,7 @@
-use crate::{ir::context::IrCtx, symbols::symbol_resolver::SymbolResolutionError};
+use crate::{
+    ir::context::IrCtx,
+    symbols::symbol_resolver::{Declaration, SymbolResolutionError},
+};
 use std::io::Write;
 
 use super::{
@@ -104,37 +107,142 @@ impl<'a: 'ret, 'ret, W: Write> ErrorMessage<'a, 'ret, &'ret IrCtx<'a>, W>
                     )
                 }),
             },
-            SymbolResolutionError::RecursiveDeclaration {
-                var_decl,
-                ident_span,
-            } => ErrMsg {
-                span: ctx[*var_decl].name.get_span(ctx),
-                title: Box::new(|w| {
-                    write!(
-                        w,
-                        "variable used before initialized `{}`",
-                        ctx[*var_decl].name.value(ctx).unwrap()
-                    )
-                }),
-                msg: Box::new(|w| {
-                    format_span_items(
-                        w,
-                        &mut [
-                            SpanItem {
-                                span: ctx[*var_decl].name.get_span(ctx).unwrap(),
-                                msg: Some(format!("variable declared here")),
+            SymbolResolutionError::RecursiveDeclaration { decl, ident_span } => {
+                let (name, title, declared) = match decl {
+                    Declaration::Var(var_decl) => (
+                        *ctx[*var_decl].name,
+                        "variable used before initialized",
+                        "variable declared here",
+                    ),
+                    Declaration::TypeAlias(alias) => (
+                        *ctx[*alias].name,
+                        "type alias refers to itself",
+                        "type alias declared here",
+                    ),
+                    Declaration::StaticAttr(attr) => (
+                        *ctx[*attr].name,
+                        "static attribute used before initialized",
+                        "static attribute declared here",
+                    ),
+                };
+
+                ErrMsg {
+                    span: name.get_span(ctx),
+                    title: Box::new(move |w| write!(w, "{title} `{}`", name.value(ctx).unwrap())),
+                    msg: Box::new(move |w| {
+                        format_span_items(
+                            w,
+                            &mut [
+                                SpanItem {
+                                    span: name.get_span(ctx).unwrap(),
+                                    msg: Some(declared.to_string()),
+                                    err_type: ErrMsgType::Err,
+                                },
+                                SpanItem {
+                                    span: ident_span.clone(),
+                                    msg: Some("used in declaration here".to_string()),
+                                    err_type: ErrMsgType::Err,
+                                },
+                            ],
+                            &[],
+                        )
+                    }),
+                }
+            }
+            SymbolResolutionError::TypeArgCountMismatch { type_sig, expected } => {
+                let actual = type_sig.split_instance(ctx).1.len();
+
+                ErrMsg {
+                    span: type_sig.get_span(ctx),
+                    title: Box::new(move |w| {
+                        write!(
+                            w,
+                            "wrong number of type arguments for `{}`",
+                            type_sig.split_instance(ctx).0.format(ctx)
+                        )
+                    }),
+                    msg: Box::new(move |w| {
+                        format_span_items(
+                            w,
+                            &mut [SpanItem {
+                                span: type_sig.get_span(ctx).unwrap(),
+                                msg: Some(format!(
+                                    "expected {} type {}, found {}",
+                                    expected,
+                                    if *expected == 1 {
+                                        "argument"
+                                    } else {
+                                        "arguments"
+                                    },
+                                    actual
+                                )),
                                 err_type: ErrMsgType::Err,
-                            },
-                            SpanItem {
-                                span: ident_span.clone(),
-                                msg: Some(format!("used in declaration here")),
+                            }],
+                            &[],
+                        )
+                    }),
+                }
+            }
+            SymbolResolutionError::UnknownInit { call, st } => {
+                let st_name = ctx[*st].name.value(ctx).unwrap();
+                let labels = ctx[*call]
+                    .arg_labels
+                    .iter()
+                    .map(|label| format!("{}:", label.unwrap_or("_")))
+                    .collect::<String>();
+
+                ErrMsg {
+                    span: call.get_span(ctx),
+                    title: Box::new(move |w| {
+                        write!(w, "no init of `{st_name}` matches the labels `({labels})`")
+                    }),
+                    msg: Box::new(move |w| {
+                        format_span_items(
+                            w,
+                            &mut [SpanItem {
+                                span: call.get_span(ctx).unwrap(),
+                                msg: Some(format!(
+                                    "the arguments need to be labelled with the argument names of an init of `{st_name}`"
+                                )),
                                 err_type: ErrMsgType::Err,
-                            },
-                        ],
-                        &[],
-                    )
-                }),
-            },
+                            }],
+                            &[],
+                        )
+                    }),
+                }
+            }
+            SymbolResolutionError::StaticAttrUsedBeforeDeclaration { st_acc, attr } => {
+                let attr_name = ctx[*attr].name;
+
+                ErrMsg {
+                    span: st_acc.get_span(ctx),
+                    title: Box::new(move |w| {
+                        write!(
+                            w,
+                            "static attribute `{}` used before it is declared",
+                            attr_name.value(ctx).unwrap()
+                        )
+                    }),
+                    msg: Box::new(move |w| {
+                        format_span_items(
+                            w,
+                            &mut [
+                                SpanItem {
+                                    span: st_acc.get_span(ctx).unwrap(),
+                                    msg: Some("used here".to_owned()),
+                                    err_type: ErrMsgType::Err,
+                                },
+                                SpanItem {
+                                    span: attr.get_span(ctx).unwrap(),
+                                    msg: Some("declared here".to_owned()),
+                                    err_type: ErrMsgType::Note,
+                                },
+                            ],
+                            &[],
+                        )
+                    }),
+                }
+            }
         }
     }
 }
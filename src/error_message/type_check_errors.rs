@@ -3,11 +3,17 @@ use std::io::Write;
 use crate::{
     ir::{
         context::IrCtx,
-        node::type_signature::{TypeSignature, TypeSignatureValue},
+        node::{
+            function::FunctionArg,
+            operator::BinaryOperator,
+            type_signature::{TypeSignature, TypeSignatureValue},
+            NodeRef,
+        },
     },
     type_checker::{
-        check_assignment::AssignmentError, check_struct::StructTypeError, FunctionError,
-        TypeChecker, TypeCheckerError,
+        check_assignment::AssignmentError, check_extension::ExtensionError, check_init::InitError,
+        check_struct::StructTypeError, check_throw::ThrowError, FunctionError, TypeChecker,
+        TypeCheckerError,
     },
 };
 
@@ -64,27 +70,21 @@ impl<'a: 'ret, 'ret, W: Write> ErrorMessage<'a, 'ret, (&'ret TypeChecker<'a>, &'
                 }),
             },
             TypeCheckerError::UnresolvableTypeConstraints(_) => ErrMsg {
-                span: type_checker
-                    .constraints
-                    .front()
-                    .unwrap()
-                    .0
-                    .clone()
-                    .get_span(ctx),
+                span: type_checker.constraints.front().unwrap().type_sigs()[0].get_span(ctx),
                 title: Box::new(|w| write!(w, "undeterminable types")),
                 msg: Box::new(|w| {
                     let mut eq_groups: Vec<Vec<&TypeSignature<'a>>> = Vec::new();
 
                     for constraint in &type_checker.constraints {
-                        let (a, b) = (&constraint.0, &constraint.1);
+                        let mut type_sigs = constraint.type_sigs();
                         let found_group = eq_groups
                             .iter_mut()
-                            .find(|types| types.iter().any(|t| **t == *a || **t == *b));
+                            .find(|types| types.iter().any(|t| type_sigs.contains(t)));
 
                         if let Some(group) = found_group {
-                            group.append(&mut vec![a, b]);
+                            group.append(&mut type_sigs);
                         } else {
-                            eq_groups.push(vec![a, b]);
+                            eq_groups.push(type_sigs);
                         }
                     }
 
@@ -235,10 +235,7 @@ impl<'a: 'ret, 'ret, W: Write> ErrorMessage<'a, 'ret, (&'ret TypeChecker<'a>, &'
 
                         for t in [a, b] {
                             let args = match &ctx[t] {
-                                TypeSignatureValue::Function {
-                                    args,
-                                    return_type: _,
-                                } => args.len(),
+                                TypeSignatureValue::Function { args, .. } => args.len(),
                                 _ => unreachable!(),
                             };
 
@@ -260,10 +257,7 @@ impl<'a: 'ret, 'ret, W: Write> ErrorMessage<'a, 'ret, (&'ret TypeChecker<'a>, &'
                     let args_span = ctx[*call].args_span.clone();
 
                     let expected = match &ctx[func_type] {
-                        TypeSignatureValue::Function {
-                            args,
-                            return_type: _,
-                        } => args.len(),
+                        TypeSignatureValue::Function { args, .. } => args.len(),
                         _ => unreachable!(),
                     };
                     let actual = ctx[*call].args.len();
@@ -289,6 +283,28 @@ impl<'a: 'ret, 'ret, W: Write> ErrorMessage<'a, 'ret, (&'ret TypeChecker<'a>, &'
                         }),
                     }
                 }
+                FunctionError::UnexpectedArgLabels(call) => {
+                    let args_span = ctx[*call].args_span.clone();
+
+                    ErrMsg {
+                        span: Some(args_span.clone()),
+                        title: Box::new(|w| write!(w, "unexpected argument labels")),
+                        msg: Box::new(move |w| {
+                            format_span_items(
+                                w,
+                                &mut [SpanItem {
+                                    span: args_span.clone(),
+                                    msg: Some(
+                                        "only the init of a struct takes labelled arguments"
+                                            .to_owned(),
+                                    ),
+                                    err_type: ErrMsgType::Err,
+                                }],
+                                &[],
+                            )
+                        }),
+                    }
+                }
             },
             TypeCheckerError::EnumInitArgCountMismatch(enm_init, enm_val) => {
                 let init_arg_count = ctx[*enm_init].items.len();
@@ -415,6 +431,1317 @@ impl<'a: 'ret, 'ret, W: Write> ErrorMessage<'a, 'ret, (&'ret TypeChecker<'a>, &'
                     }),
                 }
             }
-        }
-    }
+            TypeCheckerError::InvalidOperandType(bin_op, type_sig) => {
+                let operator = ctx[*bin_op].operator.symbol();
+                let operator_span = ctx[*bin_op].operator_span.clone();
+                let type_fmt = type_sig.format(ctx);
+
+                let defined_for = match ctx[*bin_op].operator {
+                    BinaryOperator::Eq | BinaryOperator::NotEq => {
+                        "`Number`, `String`, `Boolean` and their optionals"
+                    }
+                    BinaryOperator::Coalesce => "optionals",
+                    BinaryOperator::Add
+                    | BinaryOperator::Lt
+                    | BinaryOperator::LtEq
+                    | BinaryOperator::Gt
+                    | BinaryOperator::GtEq => "`Number` and `String`",
+                    BinaryOperator::And | BinaryOperator::Or => "`Boolean`",
+                    _ => "`Number`",
+                };
+
+                let mut remarks = vec![ErrRemark {
+                    msg: format!(
+                        "operator `{}` is only defined for {}",
+                        operator, defined_for
+                    ),
+                    err_type: ErrMsgType::Note,
+                }];
+
+                // `??` cannot be declared, so there is nothing to suggest for it
+                let overloadable = ctx[*bin_op].operator.is_overloadable();
+                if let (
+                    true,
+                    TypeSignatureValue::Struct { name } | TypeSignatureValue::Enum { name },
+                ) = (overloadable, &ctx[&type_sig.split_instance(ctx).0])
+                {
+                    let return_type = if ctx[*bin_op].operator.is_comparison() {
+                        "Boolean"
+                    } else {
+                        "Self"
+                    };
+
+                    remarks.push(ErrRemark {
+                        msg: format!(
+                            "consider declaring `static func {} (lhs: Self, rhs: Self) -> {}` in an extension of `{}`",
+                            operator,
+                            return_type,
+                            name.value(ctx).unwrap()
+                        ),
+                        err_type: ErrMsgType::Hint,
+                    });
+                }
+
+                ErrMsg {
+                    span: Some(operator_span.clone()),
+                    title: Box::new(move |w| {
+                        write!(
+                            w,
+                            "binary operator `{}` cannot be applied to type `{}`",
+                            operator,
+                            type_sig.format(ctx)
+                        )
+                    }),
+                    msg: Box::new(move |w| {
+                        format_span_items(
+                            w,
+                            &mut [
+                                SpanItem {
+                                    span: operator_span.clone(),
+                                    msg: Some(format!("operator `{}`", operator)),
+                                    err_type: ErrMsgType::Err,
+                                },
+                                SpanItem {
+                                    span: ctx[*bin_op].lhs.get_span(ctx).unwrap(),
+                                    msg: Some(format!("of type `{}`", type_fmt)),
+                                    err_type: ErrMsgType::Note,
+                                },
+                            ],
+                            &remarks,
+                        )
+                    }),
+                }
+            }
+            TypeCheckerError::TraitOperatorCall(bin_op, tr) => {
+                let operator = ctx[*bin_op].operator.symbol();
+                let operator_span = ctx[*bin_op].operator_span.clone();
+                let trait_name = ctx[*tr].name.value(ctx).unwrap();
+
+                ErrMsg {
+                    span: Some(operator_span.clone()),
+                    title: Box::new(move |w| {
+                        write!(
+                            w,
+                            "operator `{}` of trait `{}` cannot be applied to a value of unknown type",
+                            operator, trait_name
+                        )
+                    }),
+                    msg: Box::new(move |w| {
+                        format_span_items(
+                            w,
+                            &mut [
+                                SpanItem {
+                                    span: operator_span.clone(),
+                                    msg: Some(format!("operator `{}`", operator)),
+                                    err_type: ErrMsgType::Err,
+                                },
+                                SpanItem {
+                                    span: ctx[*bin_op].lhs.get_span(ctx).unwrap(),
+                                    msg: Some(format!("only known to conform to `{}`", trait_name)),
+                                    err_type: ErrMsgType::Note,
+                                },
+                            ],
+                            &[
+                                ErrRemark {
+                                    msg: format!(
+                                        "the operators of a trait can only be applied to `Self` inside of the default operators of `{}`",
+                                        trait_name
+                                    ),
+                                    err_type: ErrMsgType::Note,
+                                },
+                            ],
+                        )
+                    }),
+                }
+            }
+            TypeCheckerError::MatchNonEnum(scrutinee, type_sig) => {
+                let type_fmt = type_sig.format(ctx);
+
+                ErrMsg {
+                    span: scrutinee.get_span(ctx),
+                    title: Box::new(move |w| {
+                        write!(w, "cannot match on non-enumeration type `{}`", type_fmt)
+                    }),
+                    msg: Box::new(move |w| {
+                        format_span_items(
+                            w,
+                            &mut [SpanItem {
+                                span: scrutinee.get_span(ctx).unwrap(),
+                                msg: Some(format!(
+                                    "expected enumeration type here, found {}",
+                                    type_sig.format(ctx)
+                                )),
+                                err_type: ErrMsgType::Err,
+                            }],
+                            &[],
+                        )
+                    }),
+                }
+            }
+            TypeCheckerError::EnumPatternArgCountMismatch(pattern, enm_val) => {
+                let pattern_arg_count = ctx[*pattern].bindings.len();
+                let enm_arg_count = ctx[*enm_val].items.len();
+                let pattern_span = ctx[*pattern].span.clone();
+
+                ErrMsg {
+                    span: Some(pattern_span.clone()),
+                    title: Box::new(move |w| {
+                        write!(
+                            w,
+                            "expected {} {} when matching enum, got {}",
+                            enm_arg_count,
+                            if enm_arg_count == 1 {
+                                "binding"
+                            } else {
+                                "bindings"
+                            },
+                            pattern_arg_count
+                        )
+                    }),
+                    msg: Box::new(move |w| {
+                        format_span_items(
+                            w,
+                            &mut [SpanItem {
+                                span: pattern_span.clone(),
+                                msg: Some(format!(
+                                    "expected {} {} here",
+                                    enm_arg_count,
+                                    if enm_arg_count == 1 {
+                                        "binding"
+                                    } else {
+                                        "bindings"
+                                    }
+                                )),
+                                err_type: ErrMsgType::Err,
+                            }],
+                            &[],
+                        )
+                    }),
+                }
+            }
+            TypeCheckerError::NonExhaustiveMatch(mtch, missing) => {
+                let missing_fmt = missing
+                    .iter()
+                    .map(|val| format!("`.{}`", ctx[*val].name.value(ctx).unwrap()))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let scrutinee = ctx[*mtch].scrutinee;
+
+                ErrMsg {
+                    span: mtch.get_span(ctx),
+                    title: Box::new(move |w| {
+                        write!(w, "non-exhaustive match, missing {}", missing_fmt)
+                    }),
+                    msg: Box::new(move |w| {
+                        format_span_items(
+                            w,
+                            &mut [SpanItem {
+                                span: scrutinee.get_span(ctx).unwrap(),
+                                msg: Some(format!(
+                                    "{} not covered",
+                                    if missing.len() == 1 {
+                                        "value is"
+                                    } else {
+                                        "values are"
+                                    }
+                                )),
+                                err_type: ErrMsgType::Err,
+                            }],
+                            &[ErrRemark {
+                                msg: "consider adding an arm for each missing value, or a wildcard arm `_`"
+                                    .to_string(),
+                                err_type: ErrMsgType::Hint,
+                            }],
+                        )
+                    }),
+                }
+            }
+            TypeCheckerError::UnreachableMatchArm(arm, prev_arm) => ErrMsg {
+                span: arm.get_span(ctx),
+                title: Box::new(move |w| write!(w, "unreachable match arm")),
+                msg: Box::new(move |w| {
+                    let mut items = vec![SpanItem {
+                        span: arm.get_span(ctx).unwrap(),
+                        msg: Some("this arm can never be reached".to_string()),
+                        err_type: ErrMsgType::Err,
+                    }];
+
+                    let mut remarks = Vec::new();
+
+                    if let Some(prev_arm) = prev_arm {
+                        items.push(SpanItem {
+                            span: prev_arm.get_span(ctx).unwrap(),
+                            msg: Some("already matched here".to_string()),
+                            err_type: ErrMsgType::Note,
+                        });
+                    } else {
+                        remarks.push(ErrRemark {
+                            msg: "all values are already matched by the previous arms".to_string(),
+                            err_type: ErrMsgType::Note,
+                        });
+                    }
+
+                    format_span_items(w, &mut items, &remarks)
+                }),
+            },
+            TypeCheckerError::LetElseNotDiverging(let_else) => ErrMsg {
+                span: let_else.get_span(ctx),
+                title: Box::new(move |w| write!(w, "`else` body of `let` must not fall through")),
+                msg: Box::new(move |w| {
+                    format_span_items(
+                        w,
+                        &mut [SpanItem {
+                            span: let_else.get_span(ctx).unwrap(),
+                            msg: Some(
+                                "the pattern bindings are unassigned if the else body completes"
+                                    .to_string(),
+                            ),
+                            err_type: ErrMsgType::Err,
+                        }],
+                        &[ErrRemark {
+                            msg: "consider ending the else body with a `return`".to_string(),
+                            err_type: ErrMsgType::Hint,
+                        }],
+                    )
+                }),
+            },
+            TypeCheckerError::ExtensionError(ext, ext_err) => {
+                let ext_name = *ctx[*ext].name;
+                let ext_span = ctx[*ext].span.clone();
+
+                match ext_err {
+                    ExtensionError::NonStructExtension(sym) => ErrMsg {
+                        span: Some(ext_span.clone()),
+                        title: Box::new(move |w| {
+                            write!(
+                                w,
+                                "cannot extend {} `{}`",
+                                sym.describe_type(ctx),
+                                ext_name.value(ctx).unwrap()
+                            )
+                        }),
+                        msg: Box::new(move |w| {
+                            format_span_items(
+                                w,
+                                &mut [SpanItem {
+                                    span: ext_span.clone(),
+                                    msg: Some("only structs and enums can be extended".to_owned()),
+                                    err_type: ErrMsgType::Err,
+                                }],
+                                &[],
+                            )
+                        }),
+                    },
+                    ExtensionError::EnumMethods(sym) => ErrMsg {
+                        span: Some(ext_span.clone()),
+                        title: Box::new(move |w| {
+                            write!(
+                                w,
+                                "{} `{}` can only be extended with operators",
+                                sym.describe_type(ctx),
+                                ext_name.value(ctx).unwrap()
+                            )
+                        }),
+                        msg: Box::new(move |w| {
+                            format_span_items(
+                                w,
+                                &mut [SpanItem {
+                                    span: ext_span.clone(),
+                                    msg: Some(
+                                        "the values of an enum cannot hold methods".to_owned(),
+                                    ),
+                                    err_type: ErrMsgType::Err,
+                                }],
+                                &[ErrRemark {
+                                    msg: "consider declaring a function taking the enum as an argument"
+                                        .to_owned(),
+                                    err_type: ErrMsgType::Hint,
+                                }],
+                            )
+                        }),
+                    },
+                    ExtensionError::NonTraitConformance(sym) => {
+                        let trait_name = ctx[*ext].trait_name.unwrap();
+
+                        ErrMsg {
+                            span: Some(ext_span.clone()),
+                            title: Box::new(move |w| {
+                                write!(
+                                    w,
+                                    "expected trait, found {} `{}`",
+                                    sym.describe_type(ctx),
+                                    trait_name.value(ctx).unwrap()
+                                )
+                            }),
+                            msg: Box::new(move |w| {
+                                let mut items = vec![SpanItem {
+                                    span: ext_span.clone(),
+                                    msg: Some("can only conform to a trait".to_owned()),
+                                    err_type: ErrMsgType::Err,
+                                }];
+
+                                if let Some(span) = trait_name.get_span(ctx) {
+                                    items.push(SpanItem {
+                                        span,
+                                        msg: Some(format!(
+                                            "`{}` declared here",
+                                            trait_name.value(ctx).unwrap()
+                                        )),
+                                        err_type: ErrMsgType::Note,
+                                    });
+                                }
+
+                                format_span_items(w, &mut items, &[])
+                            }),
+                        }
+                    }
+                    ExtensionError::MissingTraitMethod(attr) => {
+                        let trait_name = ctx[*ext].trait_name.unwrap();
+                        let method_name = ctx[*attr].name.value(ctx).unwrap();
+                        let kind = match attr.overloaded_operator(ctx) {
+                            Some(_) => "operator",
+                            None => "method",
+                        };
+
+                        ErrMsg {
+                            span: Some(ext_span.clone()),
+                            title: Box::new(move |w| {
+                                write!(
+                                    w,
+                                    "`{}` does not conform to trait `{}`",
+                                    ext_name.value(ctx).unwrap(),
+                                    trait_name.value(ctx).unwrap()
+                                )
+                            }),
+                            msg: Box::new(move |w| {
+                                format_span_items(
+                                    w,
+                                    &mut [
+                                        SpanItem {
+                                            span: ext_span.clone(),
+                                            msg: Some(format!(
+                                                "missing {} `{}`",
+                                                kind, method_name
+                                            )),
+                                            err_type: ErrMsgType::Err,
+                                        },
+                                        SpanItem {
+                                            span: attr.get_span(ctx).unwrap(),
+                                            msg: Some(format!("`{}` declared here", method_name)),
+                                            err_type: ErrMsgType::Note,
+                                        },
+                                    ],
+                                    &[ErrRemark {
+                                        msg: format!(
+                                            "consider implementing `{}` in the extension",
+                                            method_name
+                                        ),
+                                        err_type: ErrMsgType::Hint,
+                                    }],
+                                )
+                            }),
+                        }
+                    }
+                    ExtensionError::TraitMethodMismatch(attr, method) => {
+                        let trait_name = ctx[*ext].trait_name.unwrap();
+                        let method_name = ctx[*attr].name.value(ctx).unwrap();
+
+                        let expected =
+                            format_signature(ctx, &ctx[*attr].args, &attr.return_type(ctx));
+                        let found =
+                            format_signature(ctx, &ctx[*method].args, &ctx[*method].return_type);
+
+                        ErrMsg {
+                            span: method.get_span(ctx),
+                            title: Box::new(move |w| {
+                                write!(
+                                    w,
+                                    "method `{}` does not match its declaration in trait `{}`",
+                                    method_name,
+                                    trait_name.value(ctx).unwrap()
+                                )
+                            }),
+                            msg: Box::new(move |w| {
+                                format_span_items(
+                                    w,
+                                    &mut [
+                                        SpanItem {
+                                            span: method.get_span(ctx).unwrap(),
+                                            msg: Some(format!("found `{}`", found)),
+                                            err_type: ErrMsgType::Err,
+                                        },
+                                        SpanItem {
+                                            span: attr.get_span(ctx).unwrap(),
+                                            msg: Some(format!("expected `{}`", expected)),
+                                            err_type: ErrMsgType::Note,
+                                        },
+                                    ],
+                                    &[],
+                                )
+                            }),
+                        }
+                    }
+                    ExtensionError::DuplicateInit(first, second) => {
+                        let labels = first
+                            .arg_names(ctx)
+                            .iter()
+                            .map(|name| format!("{name}:"))
+                            .collect::<String>();
+
+                        ErrMsg {
+                            span: second.get_span(ctx),
+                            title: Box::new(move |w| {
+                                write!(
+                                    w,
+                                    "init `{}({})` is declared more than once",
+                                    ext_name.value(ctx).unwrap(),
+                                    labels
+                                )
+                            }),
+                            msg: Box::new(move |w| {
+                                format_span_items(
+                                    w,
+                                    &mut [
+                                        SpanItem {
+                                            span: second.get_span(ctx).unwrap(),
+                                            msg: Some("declared again here".to_owned()),
+                                            err_type: ErrMsgType::Err,
+                                        },
+                                        SpanItem {
+                                            span: first.get_span(ctx).unwrap(),
+                                            msg: Some("first declared here".to_owned()),
+                                            err_type: ErrMsgType::Note,
+                                        },
+                                    ],
+                                    &[ErrRemark {
+                                        msg: "inits are told apart by the names of their arguments"
+                                            .to_owned(),
+                                        err_type: ErrMsgType::Hint,
+                                    }],
+                                )
+                            }),
+                        }
+                    }
+                    ExtensionError::DuplicateMember(first, second) => ErrMsg {
+                        span: second.get_span(ctx),
+                        title: Box::new(move |w| {
+                            write!(
+                                w,
+                                "`{}` is declared in more than one extension of `{}`",
+                                second.value(ctx).unwrap(),
+                                ext_name.value(ctx).unwrap()
+                            )
+                        }),
+                        msg: Box::new(move |w| {
+                            format_span_items(
+                                w,
+                                &mut [
+                                    SpanItem {
+                                        span: second.get_span(ctx).unwrap(),
+                                        msg: Some("declared again here".to_owned()),
+                                        err_type: ErrMsgType::Err,
+                                    },
+                                    SpanItem {
+                                        span: first.get_span(ctx).unwrap(),
+                                        msg: Some("first declared here".to_owned()),
+                                        err_type: ErrMsgType::Note,
+                                    },
+                                ],
+                                &[],
+                            )
+                        }),
+                    },
+                    ExtensionError::MethodShadowedByAttr(attr, method) => {
+                        let method_name = ctx[*method].name.value(ctx).unwrap();
+
+                        ErrMsg {
+                            span: method.get_span(ctx),
+                            title: Box::new(move |w| {
+                                write!(
+                                    w,
+                                    "method `{}` has the name of an attribute of `{}`",
+                                    method_name,
+                                    ext_name.value(ctx).unwrap()
+                                )
+                            }),
+                            msg: Box::new(move |w| {
+                                format_span_items(
+                                    w,
+                                    &mut [
+                                        SpanItem {
+                                            span: method.get_span(ctx).unwrap(),
+                                            msg: Some("method declared here".to_owned()),
+                                            err_type: ErrMsgType::Err,
+                                        },
+                                        SpanItem {
+                                            span: attr.get_span(ctx).unwrap(),
+                                            msg: Some("attribute declared here".to_owned()),
+                                            err_type: ErrMsgType::Note,
+                                        },
+                                    ],
+                                    &[ErrRemark {
+                                        msg: "the attribute of a value would shadow the method"
+                                            .to_owned(),
+                                        err_type: ErrMsgType::Hint,
+                                    }],
+                                )
+                            }),
+                        }
+                    }
+                    ExtensionError::DuplicateConformance(other) => {
+                        let trait_name = ctx[*ext].trait_name.unwrap();
+                        let other_span = ctx[*other].span.clone();
+
+                        ErrMsg {
+                            span: Some(ext_span.clone()),
+                            title: Box::new(move |w| {
+                                write!(
+                                    w,
+                                    "`{}` already conforms to trait `{}`",
+                                    ext_name.value(ctx).unwrap(),
+                                    trait_name.value(ctx).unwrap()
+                                )
+                            }),
+                            msg: Box::new(move |w| {
+                                format_span_items(
+                                    w,
+                                    &mut [
+                                        SpanItem {
+                                            span: ext_span.clone(),
+                                            msg: Some("conformance declared again here".to_owned()),
+                                            err_type: ErrMsgType::Err,
+                                        },
+                                        SpanItem {
+                                            span: other_span.clone(),
+                                            msg: Some("first declared here".to_owned()),
+                                            err_type: ErrMsgType::Note,
+                                        },
+                                    ],
+                                    &[],
+                                )
+                            }),
+                        }
+                    }
+                    ExtensionError::InvalidOperatorArgs(op) => {
+                        let op_name = ctx[*op].name.value(ctx).unwrap();
+                        let ext_name = ext_name.value(ctx).unwrap();
+
+                        ErrMsg {
+                            span: op.get_span(ctx),
+                            title: Box::new(move |w| {
+                                write!(w, "invalid arguments of operator `{}`", op_name)
+                            }),
+                            msg: Box::new(move |w| {
+                                format_span_items(
+                                    w,
+                                    &mut [SpanItem {
+                                        span: op.get_span(ctx).unwrap(),
+                                        msg: Some(format!(
+                                            "expected two arguments, the first of type `{}`",
+                                            ext_name
+                                        )),
+                                        err_type: ErrMsgType::Err,
+                                    }],
+                                    &[ErrRemark {
+                                        msg: "the arguments are the left and right operands"
+                                            .to_owned(),
+                                        err_type: ErrMsgType::Note,
+                                    }],
+                                )
+                            }),
+                        }
+                    }
+                    ExtensionError::NonBooleanComparison(op) => {
+                        let op_name = ctx[*op].name.value(ctx).unwrap();
+                        let return_fmt = ctx[*op].return_type.format(ctx);
+
+                        ErrMsg {
+                            span: op.get_span(ctx),
+                            title: Box::new(move |w| {
+                                write!(w, "operator `{}` must return `Boolean`", op_name)
+                            }),
+                            msg: Box::new(move |w| {
+                                format_span_items(
+                                    w,
+                                    &mut [SpanItem {
+                                        span: op.get_span(ctx).unwrap(),
+                                        msg: Some(format!("returns `{}`", return_fmt)),
+                                        err_type: ErrMsgType::Err,
+                                    }],
+                                    &[],
+                                )
+                            }),
+                        }
+                    }
+                    ExtensionError::StaticAttrWithoutValue(attr) => {
+                        let attr_name = ctx[*attr].name.value(ctx).unwrap();
+
+                        ErrMsg {
+                            span: attr.get_span(ctx),
+                            title: Box::new(move |w| {
+                                write!(w, "static attribute `{}` has no value", attr_name)
+                            }),
+                            msg: Box::new(move |w| {
+                                format_span_items(
+                                    w,
+                                    &mut [SpanItem {
+                                        span: attr.get_span(ctx).unwrap(),
+                                        msg: Some("needs a value, e.g. `= 0`".to_owned()),
+                                        err_type: ErrMsgType::Err,
+                                    }],
+                                    &[],
+                                )
+                            }),
+                        }
+                    }
+                }
+            }
+            TypeCheckerError::MissingConformance(type_sig, param) => {
+                let type_fmt = type_sig.format(ctx);
+                let param_name = ctx[*param].name.value(ctx).unwrap();
+                let bound_name = ctx[*param].bound.unwrap().value(ctx).unwrap();
+
+                ErrMsg {
+                    span: type_sig.get_span(ctx),
+                    title: Box::new(move |w| {
+                        write!(
+                            w,
+                            "type `{}` does not conform to trait `{}`",
+                            type_fmt, bound_name
+                        )
+                    }),
+                    msg: Box::new(move |w| {
+                        let mut items = vec![];
+
+                        if let Some(span) = type_sig.get_span(ctx) {
+                            items.push(SpanItem {
+                                span,
+                                msg: Some(format!("of type `{}`", type_sig.format(ctx))),
+                                err_type: ErrMsgType::Err,
+                            });
+                        }
+
+                        if let Some(span) = param.get_span(ctx) {
+                            items.push(SpanItem {
+                                span,
+                                msg: Some(format!(
+                                    "required by the bound `{}: {}`",
+                                    param_name, bound_name
+                                )),
+                                err_type: ErrMsgType::Note,
+                            });
+                        }
+
+                        // only structures and enums can conform to a trait through an extension
+                        let remarks = match &ctx[&type_sig.split_instance(ctx).0] {
+                            TypeSignatureValue::Struct { name }
+                            | TypeSignatureValue::Enum { name } => vec![ErrRemark {
+                                msg: format!(
+                                    "consider conforming to the trait with `extend {}: {}`",
+                                    name.value(ctx).unwrap(),
+                                    bound_name
+                                ),
+                                err_type: ErrMsgType::Hint,
+                            }],
+                            _ => vec![],
+                        };
+
+                        format_span_items(w, &mut items, &remarks)
+                    }),
+                }
+            }
+            TypeCheckerError::OperatorBoundNonStruct(type_sig, param) => {
+                let type_fmt = type_sig.format(ctx);
+                let param_name = ctx[*param].name.value(ctx).unwrap();
+                let bound_name = ctx[*param].bound.unwrap().value(ctx).unwrap();
+
+                ErrMsg {
+                    span: type_sig.get_span(ctx),
+                    title: Box::new(move |w| {
+                        write!(
+                            w,
+                            "type `{}` cannot be used for type parameter `{}`",
+                            type_fmt, param_name
+                        )
+                    }),
+                    msg: Box::new(move |w| {
+                        let mut items = vec![];
+
+                        if let Some(span) = type_sig.get_span(ctx) {
+                            items.push(SpanItem {
+                                span,
+                                msg: Some(format!("of type `{}`", type_sig.format(ctx))),
+                                err_type: ErrMsgType::Err,
+                            });
+                        }
+
+                        if let Some(span) = param.get_span(ctx) {
+                            items.push(SpanItem {
+                                span,
+                                msg: Some(format!(
+                                    "bounded by `{}`, which declares operators",
+                                    bound_name
+                                )),
+                                err_type: ErrMsgType::Note,
+                            });
+                        }
+
+                        format_span_items(
+                            w,
+                            &mut items,
+                            &[ErrRemark {
+                                msg: format!(
+                                    "the operators of `{}` can only be applied to values of a type parameter that are structures",
+                                    bound_name
+                                ),
+                                err_type: ErrMsgType::Note,
+                            }],
+                        )
+                    }),
+                }
+            }
+            TypeCheckerError::AwaitOutsideAsync(aw, func) => ErrMsg {
+                span: aw.get_span(ctx),
+                title: Box::new(move |w| {
+                    write!(w, "`await` is only allowed inside of async functions")
+                }),
+                msg: Box::new(move |w| {
+                    let mut items = vec![SpanItem {
+                        span: aw.get_span(ctx).unwrap(),
+                        msg: Some("awaited outside of an async function".to_string()),
+                        err_type: ErrMsgType::Err,
+                    }];
+
+                    let remarks = match func {
+                        Some(func) => {
+                            items.push(SpanItem {
+                                span: func.get_span(ctx).unwrap(),
+                                msg: Some("the enclosing function is not async".to_string()),
+                                err_type: ErrMsgType::Note,
+                            });
+
+                            vec![ErrRemark {
+                                msg: "consider marking the function with `async`".to_string(),
+                                err_type: ErrMsgType::Hint,
+                            }]
+                        }
+                        None => vec![],
+                    };
+
+                    format_span_items(w, &mut items, &remarks)
+                }),
+            },
+            TypeCheckerError::NonStringExpressible(expr, type_sig) => ErrMsg {
+                span: expr.get_span(ctx),
+                title: Box::new(move |w| {
+                    write!(
+                        w,
+                        "cannot interpolate value of type `{}`",
+                        type_sig.format(ctx)
+                    )
+                }),
+                msg: Box::new(move |w| {
+                    format_span_items(
+                        w,
+                        &mut [SpanItem {
+                            span: expr.get_span(ctx).unwrap(),
+                            msg: Some(format!("of type `{}`", type_sig.format(ctx))),
+                            err_type: ErrMsgType::Err,
+                        }],
+                        &[ErrRemark {
+                            msg: "only strings, numbers, booleans and types conforming to \
+                                  `StringExpressible` can be interpolated"
+                                .to_string(),
+                            err_type: ErrMsgType::Hint,
+                        }],
+                    )
+                }),
+            },
+            TypeCheckerError::InvalidExpressibleTrait(tr, expr) => ErrMsg {
+                span: tr.get_span(ctx),
+                title: Box::new(move |w| {
+                    write!(
+                        w,
+                        "trait `StringExpressible` does not declare `func toString() -> String`"
+                    )
+                }),
+                msg: Box::new(move |w| {
+                    format_span_items(
+                        w,
+                        &mut [
+                            SpanItem {
+                                span: tr.get_span(ctx).unwrap(),
+                                msg: Some("declared without `toString`".to_string()),
+                                err_type: ErrMsgType::Err,
+                            },
+                            SpanItem {
+                                span: expr.get_span(ctx).unwrap(),
+                                msg: Some("interpolated through the trait".to_string()),
+                                err_type: ErrMsgType::Note,
+                            },
+                        ],
+                        &[ErrRemark {
+                            msg: "javascript calls `toString` when converting a value to a string"
+                                .to_string(),
+                            err_type: ErrMsgType::Hint,
+                        }],
+                    )
+                }),
+            },
+            TypeCheckerError::InvalidDictionaryKey(dict, key_type) => ErrMsg {
+                span: dict.get_span(ctx),
+                title: Box::new(move |w| {
+                    write!(
+                        w,
+                        "dictionary keys cannot be of type `{}`",
+                        key_type.format(ctx)
+                    )
+                }),
+                msg: Box::new(move |w| {
+                    format_span_items(
+                        w,
+                        &mut [SpanItem {
+                            span: dict.get_span(ctx).unwrap(),
+                            msg: Some(format!("with keys of type `{}`", key_type.format(ctx))),
+                            err_type: ErrMsgType::Err,
+                        }],
+                        &[ErrRemark {
+                            msg: "only numbers, strings and booleans can be keys, \
+                                  as a `Map` compares any other key by reference"
+                                .to_string(),
+                            err_type: ErrMsgType::Hint,
+                        }],
+                    )
+                }),
+            },
+            TypeCheckerError::ThrowError(throw_err) => match throw_err {
+                ThrowError::MissingTry(call) => ErrMsg {
+                    span: call.get_span(ctx),
+                    title: Box::new(move |w| {
+                        write!(w, "call to a throwing function without `try`")
+                    }),
+                    msg: Box::new(move |w| {
+                        format_span_items(
+                            w,
+                            &mut [SpanItem {
+                                span: call.get_span(ctx).unwrap(),
+                                msg: Some("this call can throw".to_string()),
+                                err_type: ErrMsgType::Err,
+                            }],
+                            &[ErrRemark {
+                                msg: "consider propagating the error with `try`".to_string(),
+                                err_type: ErrMsgType::Hint,
+                            }],
+                        )
+                    }),
+                },
+                ThrowError::TryNonThrowing(tr) => ErrMsg {
+                    span: tr.get_span(ctx),
+                    title: Box::new(move |w| {
+                        write!(w, "`try` on an expression that can not throw")
+                    }),
+                    msg: Box::new(move |w| {
+                        format_span_items(
+                            w,
+                            &mut [SpanItem {
+                                span: tr.get_span(ctx).unwrap(),
+                                msg: Some(
+                                    "only calls to throwing functions can be tried".to_string(),
+                                ),
+                                err_type: ErrMsgType::Err,
+                            }],
+                            &[],
+                        )
+                    }),
+                },
+                ThrowError::Unhandled(expr, func) => ErrMsg {
+                    span: expr.get_span(ctx),
+                    title: Box::new(move |w| write!(w, "unhandled error")),
+                    msg: Box::new(move |w| {
+                        let mut items = vec![SpanItem {
+                            span: expr.get_span(ctx).unwrap(),
+                            msg: Some(
+                                "thrown outside of a `do` body or throwing function".to_string(),
+                            ),
+                            err_type: ErrMsgType::Err,
+                        }];
+
+                        let remarks = match func {
+                            Some(func) => {
+                                items.push(SpanItem {
+                                    span: func.get_span(ctx).unwrap(),
+                                    msg: Some("the enclosing function does not throw".to_string()),
+                                    err_type: ErrMsgType::Note,
+                                });
+
+                                vec![ErrRemark {
+                                    msg: "consider declaring the thrown type with `throws`"
+                                        .to_string(),
+                                    err_type: ErrMsgType::Hint,
+                                }]
+                            }
+                            None => vec![ErrRemark {
+                                msg: "consider catching the error with `do { } catch e { }`"
+                                    .to_string(),
+                                err_type: ErrMsgType::Hint,
+                            }],
+                        };
+
+                        format_span_items(w, &mut items, &remarks)
+                    }),
+                },
+                ThrowError::NothingThrown(dc) => ErrMsg {
+                    span: dc.get_span(ctx),
+                    title: Box::new(move |w| {
+                        write!(w, "no error is thrown inside of the `do` body")
+                    }),
+                    msg: Box::new(move |w| {
+                        format_span_items(
+                            w,
+                            &mut [SpanItem {
+                                span: dc.get_span(ctx).unwrap(),
+                                msg: Some("the catch body is never reached".to_string()),
+                                err_type: ErrMsgType::Err,
+                            }],
+                            &[],
+                        )
+                    }),
+                },
+            },
+            TypeCheckerError::LoopJumpOutsideLoop(jump) => ErrMsg {
+                span: jump.get_span(ctx),
+                title: Box::new(move |w| {
+                    write!(
+                        w,
+                        "`{}` is only allowed inside of loops",
+                        ctx[*jump].kind.keyword()
+                    )
+                }),
+                msg: Box::new(move |w| {
+                    format_span_items(
+                        w,
+                        &mut [SpanItem {
+                            span: jump.get_span(ctx).unwrap(),
+                            msg: Some("not inside of a loop".to_string()),
+                            err_type: ErrMsgType::Err,
+                        }],
+                        &[ErrRemark {
+                            msg: "loops can not be left from inside of a function".to_string(),
+                            err_type: ErrMsgType::Note,
+                        }],
+                    )
+                }),
+            },
+            TypeCheckerError::UnknownLoopLabel(jump) => ErrMsg {
+                span: jump.get_span(ctx),
+                title: Box::new(move |w| {
+                    write!(
+                        w,
+                        "no enclosing loop is labelled `{}`",
+                        ctx[*jump].label.unwrap().value(ctx).unwrap()
+                    )
+                }),
+                msg: Box::new(move |w| {
+                    format_span_items(
+                        w,
+                        &mut [SpanItem {
+                            span: jump.get_span(ctx).unwrap(),
+                            msg: Some(format!(
+                                "`{}` of an unknown loop",
+                                ctx[*jump].kind.keyword()
+                            )),
+                            err_type: ErrMsgType::Err,
+                        }],
+                        &[],
+                    )
+                }),
+            },
+            TypeCheckerError::DuplicateLoopLabel(lp, outer) => {
+                let label = lp.label(ctx).unwrap();
+                let outer_label = outer.label(ctx).unwrap();
+
+                ErrMsg {
+                    span: label.get_span(ctx),
+                    title: Box::new(move |w| {
+                        write!(
+                            w,
+                            "loop label `{}` is already used by an enclosing loop",
+                            label.value(ctx).unwrap()
+                        )
+                    }),
+                    msg: Box::new(move |w| {
+                        format_span_items(
+                            w,
+                            &mut [
+                                SpanItem {
+                                    span: label.get_span(ctx).unwrap(),
+                                    msg: Some("labelled again here".to_string()),
+                                    err_type: ErrMsgType::Err,
+                                },
+                                SpanItem {
+                                    span: outer_label.get_span(ctx).unwrap(),
+                                    msg: Some("enclosing loop labelled here".to_string()),
+                                    err_type: ErrMsgType::Note,
+                                },
+                            ],
+                            &[],
+                        )
+                    }),
+                }
+            }
+            TypeCheckerError::ReturnInMatchExpr(value, _) => ErrMsg {
+                span: value.get_span(ctx),
+                title: Box::new(move |w| {
+                    write!(w, "cannot `return` from a match inside of an expression")
+                }),
+                msg: Box::new(move |w| {
+                    format_span_items(
+                        w,
+                        &mut [SpanItem {
+                            span: value.get_span(ctx).unwrap(),
+                            msg: Some("returned from the arm of the match".to_string()),
+                            err_type: ErrMsgType::Err,
+                        }],
+                        &[
+                            ErrRemark {
+                                msg: "an arm can only return when the match is the whole value of a statement"
+                                    .to_string(),
+                                err_type: ErrMsgType::Note,
+                            },
+                            ErrRemark {
+                                msg: "consider assigning the match to a variable first".to_string(),
+                                err_type: ErrMsgType::Hint,
+                            },
+                        ],
+                    )
+                }),
+            },
+            TypeCheckerError::LoopJumpInMatchExpr(jump, _) => ErrMsg {
+                span: jump.get_span(ctx),
+                title: Box::new(move |w| {
+                    write!(
+                        w,
+                        "cannot `{}` from a match inside of an expression",
+                        ctx[*jump].kind.keyword()
+                    )
+                }),
+                msg: Box::new(move |w| {
+                    format_span_items(
+                        w,
+                        &mut [SpanItem {
+                            span: jump.get_span(ctx).unwrap(),
+                            msg: Some("leaves the arm of the match".to_string()),
+                            err_type: ErrMsgType::Err,
+                        }],
+                        &[
+                            ErrRemark {
+                                msg: "an arm can only leave a loop when the match is the whole value of a statement"
+                                    .to_string(),
+                                err_type: ErrMsgType::Note,
+                            },
+                            ErrRemark {
+                                msg: "consider assigning the match to a variable first".to_string(),
+                                err_type: ErrMsgType::Hint,
+                            },
+                        ],
+                    )
+                }),
+            },
+            TypeCheckerError::InitError(_, InitError::ReadBeforeAssignment(attr, expr)) => {
+                let attr_name = ctx[*attr].name.value(ctx).unwrap();
+
+                ErrMsg {
+                    span: expr.get_span(ctx),
+                    title: Box::new(move |w| {
+                        write!(w, "attribute `{}` is read before it is assigned", attr_name)
+                    }),
+                    msg: Box::new(move |w| {
+                        let mut items = vec![SpanItem {
+                            span: expr.get_span(ctx).unwrap(),
+                            msg: Some(format!("`self.{attr_name}` is not assigned on every path")),
+                            err_type: ErrMsgType::Err,
+                        }];
+
+                        if let Some(span) = attr.get_span(ctx) {
+                            items.push(SpanItem {
+                                span,
+                                msg: Some("attribute without a default value".to_owned()),
+                                err_type: ErrMsgType::Note,
+                            });
+                        }
+
+                        format_span_items(w, &mut items, &[])
+                    }),
+                }
+            }
+            TypeCheckerError::InitError(_, InitError::SelfUsedBeforeAssignment(attr, expr)) => {
+                let attr_name = ctx[*attr].name.value(ctx).unwrap();
+
+                ErrMsg {
+                    span: expr.get_span(ctx),
+                    title: Box::new(move |w| {
+                        write!(
+                            w,
+                            "`self` is used before all of its attributes are assigned"
+                        )
+                    }),
+                    msg: Box::new(move |w| {
+                        let mut items = vec![SpanItem {
+                            span: expr.get_span(ctx).unwrap(),
+                            msg: Some(format!("`self.{attr_name}` is not assigned on every path")),
+                            err_type: ErrMsgType::Err,
+                        }];
+
+                        if let Some(span) = attr.get_span(ctx) {
+                            items.push(SpanItem {
+                                span,
+                                msg: Some("attribute without a default value".to_owned()),
+                                err_type: ErrMsgType::Note,
+                            });
+                        }
+
+                        format_span_items(
+                            w,
+                            &mut items,
+                            &[ErrRemark {
+                                msg: "only the attributes of `self` can be assigned and read until every attribute is assigned".to_owned(),
+                                err_type: ErrMsgType::Note,
+                            }],
+                        )
+                    }),
+                }
+            }
+            TypeCheckerError::InitError(_, InitError::AssignedTwice(attr, asg)) => {
+                let attr_name = ctx[*attr].name.value(ctx).unwrap();
+
+                ErrMsg {
+                    span: asg.get_span(ctx),
+                    title: Box::new(move |w| {
+                        write!(w, "`let` attribute `{}` may be assigned twice", attr_name)
+                    }),
+                    msg: Box::new(move |w| {
+                        let mut items = vec![SpanItem {
+                            span: asg.get_span(ctx).unwrap(),
+                            msg: Some(format!("`self.{attr_name}` may already be assigned here")),
+                            err_type: ErrMsgType::Err,
+                        }];
+
+                        if let Some(span) = attr.get_span(ctx) {
+                            items.push(SpanItem {
+                                span,
+                                msg: Some(match ctx[*attr].default_value {
+                                    Some(_) => "attribute with a default value".to_owned(),
+                                    None => "attribute declared with `let`".to_owned(),
+                                }),
+                                err_type: ErrMsgType::Note,
+                            });
+                        }
+
+                        format_span_items(w, &mut items, &[])
+                    }),
+                }
+            }
+            TypeCheckerError::InitError(init, init_err) => {
+                let (attr, return_span) = match init_err {
+                    InitError::UnassignedAttribute(attr) => (*attr, None),
+                    InitError::UnassignedBeforeReturn(attr, expr) => (*attr, expr.get_span(ctx)),
+                    InitError::ReadBeforeAssignment(..)
+                    | InitError::SelfUsedBeforeAssignment(..)
+                    | InitError::AssignedTwice(..) => unreachable!(),
+                };
+                let attr_name = ctx[attr].name.value(ctx).unwrap();
+
+                ErrMsg {
+                    span: return_span.clone().or(init.get_span(ctx)),
+                    title: Box::new(move |w| {
+                        write!(w, "attribute `{}` is not assigned by the init", attr_name)
+                    }),
+                    msg: Box::new(move |w| {
+                        let mut items = vec![SpanItem {
+                            span: return_span.clone().or(init.get_span(ctx)).unwrap(),
+                            msg: Some(match return_span {
+                                Some(_) => format!("returns before assigning `self.{attr_name}`"),
+                                None => format!("ends before assigning `self.{attr_name}`"),
+                            }),
+                            err_type: ErrMsgType::Err,
+                        }];
+
+                        if let Some(span) = attr.get_span(ctx) {
+                            items.push(SpanItem {
+                                span,
+                                msg: Some("attribute without a default value".to_owned()),
+                                err_type: ErrMsgType::Note,
+                            });
+                        }
+
+                        format_span_items(w, &mut items, &[])
+                    }),
+                }
+            }
+            TypeCheckerError::SelfInStaticMember(expr) => ErrMsg {
+                span: expr.get_span(ctx),
+                title: Box::new(|w| write!(w, "`self` is not available in a static member")),
+                msg: Box::new(move |w| {
+                    format_span_items(
+                        w,
+                        &mut [SpanItem {
+                            span: expr.get_span(ctx).unwrap(),
+                            msg: Some(
+                                "static members belong to the struct, not its values".to_owned(),
+                            ),
+                            err_type: ErrMsgType::Err,
+                        }],
+                        &[],
+                    )
+                }),
+            },
+            TypeCheckerError::DuplicateField(first, second) => {
+                let second_span = ctx[*second].value.get_span(ctx).unwrap();
+
+                ErrMsg {
+                    span: Some(second_span.clone()),
+                    title: Box::new(move |w| {
+                        write!(
+                            w,
+                            "field `{}` is given more than once",
+                            ctx[*second].name.value(ctx).unwrap()
+                        )
+                    }),
+                    msg: Box::new(move |w| {
+                        format_span_items(
+                            w,
+                            &mut [
+                                SpanItem {
+                                    span: second_span.clone(),
+                                    msg: Some("given again here".to_owned()),
+                                    err_type: ErrMsgType::Err,
+                                },
+                                SpanItem {
+                                    span: ctx[*first].value.get_span(ctx).unwrap(),
+                                    msg: Some("first given here".to_owned()),
+                                    err_type: ErrMsgType::Note,
+                                },
+                            ],
+                            &[],
+                        )
+                    }),
+                }
+            }
+        }
+    }
+}
+
+fn format_signature<'a>(
+    ctx: &IrCtx<'a>,
+    args: &[NodeRef<'a, FunctionArg<'a>>],
+    return_type: &TypeSignature<'a>,
+) -> String {
+    format!(
+        "({}) -> {}",
+        args.iter()
+            .map(|arg| ctx[*arg].type_sig.format(ctx))
+            .intersperse(", ".to_owned())
+            .collect::<String>(),
+        return_type.format(ctx)
+    )
 }
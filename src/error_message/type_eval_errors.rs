@@ -75,6 +75,42 @@ impl<'a: 'ret, 'ret, W: Write> ErrorMessage<'a, 'ret, &'ret IrCtx<'a>, W> for Ty
                 }),
             },
             TypeEvalError::AccessNonEnum(_) => todo!(),
+            TypeEvalError::AccessNonArray(type_sig) => ErrMsg {
+                span: type_sig.get_span(ctx),
+                title: Box::new(|w| write!(w, "access non-array")),
+                msg: Box::new(|w| {
+                    format_span_items(
+                        w,
+                        &mut [SpanItem {
+                            span: type_sig.get_span(ctx).unwrap(),
+                            msg: Some(format!(
+                                "type of accessed object is `{}`, expected array",
+                                type_sig.format(ctx)
+                            )),
+                            err_type: ErrMsgType::Err,
+                        }],
+                        &[],
+                    )
+                }),
+            },
+            TypeEvalError::AccessNonDictionary(type_sig) => ErrMsg {
+                span: type_sig.get_span(ctx),
+                title: Box::new(|w| write!(w, "access non-dictionary")),
+                msg: Box::new(|w| {
+                    format_span_items(
+                        w,
+                        &mut [SpanItem {
+                            span: type_sig.get_span(ctx).unwrap(),
+                            msg: Some(format!(
+                                "type of accessed object is `{}`, expected dictionary",
+                                type_sig.format(ctx)
+                            )),
+                            err_type: ErrMsgType::Err,
+                        }],
+                        &[],
+                    )
+                }),
+            },
             TypeEvalError::TupleAccessOutOfBounds(tup_acc, tuple_type) => ErrMsg {
                 span: tup_acc.get_span(ctx),
                 title: Box::new(|w| write!(w, "tuple access index out of bounds")),
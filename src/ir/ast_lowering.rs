@@ -84,16 +84,40 @@ impl<'a> IrCtx<'a> {
                 crate::ast::node::statement::StmtValue::Return(expr) => {
                     acc.push(Stmt::Return(expr.ir_lower(ctx)).allocate(ctx));
                 }
+                crate::ast::node::statement::StmtValue::Throw(expr) => {
+                    acc.push(Stmt::Throw(expr.ir_lower(ctx)).allocate(ctx));
+                }
                 crate::ast::node::statement::StmtValue::Comment(_) => {}
                 crate::ast::node::statement::StmtValue::ExternObj(ast_obj) => {
                     acc.push(Stmt::ExternObj(ast_obj.ir_lower(ctx)).allocate(ctx));
                 }
+                crate::ast::node::statement::StmtValue::TypeAlias(alias) => {
+                    acc.push(Stmt::TypeAlias(alias.ir_lower(ctx)).allocate(ctx));
+                }
                 crate::ast::node::statement::StmtValue::IfBranch(ifb) => {
                     acc.push(Stmt::IfBranch(ifb.ir_lower(ctx)).allocate(ctx))
                 }
                 crate::ast::node::statement::StmtValue::TraitDecl(tr) => {
                     acc.push(Stmt::TraitDecl(tr.ir_lower(ctx)).allocate(ctx));
                 }
+                crate::ast::node::statement::StmtValue::Extension(ext) => {
+                    acc.push(Stmt::Extension(ext.ir_lower(ctx)).allocate(ctx));
+                }
+                crate::ast::node::statement::StmtValue::LetElse(let_else) => {
+                    acc.push(Stmt::LetElse(let_else.ir_lower(ctx)).allocate(ctx));
+                }
+                crate::ast::node::statement::StmtValue::DoCatch(dc) => {
+                    acc.push(Stmt::DoCatch(dc.ir_lower(ctx)).allocate(ctx));
+                }
+                crate::ast::node::statement::StmtValue::WhileLoop(wl) => {
+                    acc.push(Stmt::WhileLoop(wl.ir_lower(ctx)).allocate(ctx));
+                }
+                crate::ast::node::statement::StmtValue::ForLoop(fl) => {
+                    acc.push(Stmt::ForLoop(fl.ir_lower(ctx)).allocate(ctx));
+                }
+                crate::ast::node::statement::StmtValue::LoopJump(jump) => {
+                    acc.push(Stmt::LoopJump(jump.ir_lower(ctx)).allocate(ctx));
+                }
             };
         }
 
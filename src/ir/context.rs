@@ -194,6 +194,46 @@ impl<'a> IrCtx<'a> {
         }
     }
 
+    /// The type `Promise<T>`, where `T` is the given value type
+    pub fn get_promise_type_sig(
+        &mut self,
+        value_type: TypeSignature<'a>,
+        type_ctx: Rc<TypeSignatureContext<'a>>,
+    ) -> TypeSignature<'a> {
+        self.get_type_sig(
+            TypeSignatureValue::Instance {
+                base: self.get_builtin_type_sig(BuiltinType::Promise),
+                args: vec![value_type],
+            },
+            type_ctx,
+        )
+    }
+
+    /// The type `Array<T>`, where `T` is the given element type
+    pub fn get_array_type_sig(
+        &mut self,
+        elem_type: TypeSignature<'a>,
+        type_ctx: Rc<TypeSignatureContext<'a>>,
+    ) -> TypeSignature<'a> {
+        self.get_type_sig(
+            TypeSignatureValue::Instance {
+                base: self.get_builtin_type_sig(BuiltinType::Array),
+                args: vec![elem_type],
+            },
+            type_ctx,
+        )
+    }
+
+    /// The record type with the given fields, which are sorted by name
+    pub fn get_record_type_sig(
+        &mut self,
+        mut fields: Vec<(&'a str, TypeSignature<'a>)>,
+        type_ctx: Rc<TypeSignatureContext<'a>>,
+    ) -> TypeSignature<'a> {
+        fields.sort_by_key(|(name, _)| *name);
+        self.get_type_sig(TypeSignatureValue::Record(fields), type_ctx)
+    }
+
     pub fn make_ident(
         &mut self,
         ident: ast::node::identifier::Ident<'a>,
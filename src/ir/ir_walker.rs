@@ -6,16 +6,23 @@ use super::{
     ast_lowering::LowerAstResult,
     context::IrCtx,
     node::{
-        control_flow::{IfBranchBody, IfStmt},
+        control_flow::{IfBranchBody, IfStmt, NarrowingReach},
         enumeration::{Enum, EnumValue},
+        error_handling::{DoCatch, DoCatchBody},
         expression::Expr,
+        extension::Extension,
         external::ExternalObject,
         function::{Function, FunctionArg},
+        generics::TypeParam,
         identifier::Ident,
+        loops::{ForLoop, WhileLoop},
         module::Module,
+        operator::BinaryOperation,
+        pattern_match::{EnumPattern, LetElse, Match, MatchArm},
         statement::{Stmt, StmtBlock, VarDecl},
         structure::{Struct, StructInit},
         traits::{Trait, TraitFuncAttr},
+        type_alias::TypeAlias,
         type_signature::{TypeSignature, TypeSignatureValue},
         NodeRef,
     },
@@ -29,6 +36,14 @@ pub enum ScopeValue<'a> {
     Enum(NodeRef<'a, Enum<'a>>),
     IfBranch(NodeRef<'a, IfStmt<'a>>, IfBranchBody),
     Trait(NodeRef<'a, Trait<'a>>),
+    MatchArm(NodeRef<'a, MatchArm<'a>>),
+    LetElse(NodeRef<'a, LetElse<'a>>),
+    Extension(NodeRef<'a, Extension<'a>>),
+    DoCatch(NodeRef<'a, DoCatch<'a>>, DoCatchBody),
+    WhileLoop(NodeRef<'a, WhileLoop<'a>>),
+    ForLoop(NodeRef<'a, ForLoop<'a>>),
+    /// The right operand of a `&&` or `||` operation, with the values narrowed by the left one
+    RightOperand(NodeRef<'a, BinaryOperation<'a>>),
 }
 
 impl<'a> ScopeValue<'a> {
@@ -42,6 +57,15 @@ impl<'a> ScopeValue<'a> {
 
             ScopeValue::IfBranch(ifb, branch) => ctx[*ifb].branch_ident(*branch),
             ScopeValue::Trait(tr) => *ctx[*tr].name,
+            ScopeValue::MatchArm(arm) => *ctx[*arm].scope_ident,
+            ScopeValue::LetElse(let_else) => *ctx[*let_else].else_scope_ident,
+            ScopeValue::Extension(ext) => *ctx[*ext].scope_ident,
+            ScopeValue::DoCatch(dc, body) => ctx[*dc].body_ident(*body),
+            ScopeValue::WhileLoop(wl) => *ctx[*wl].body_scope_ident,
+            ScopeValue::ForLoop(fl) => *ctx[*fl].body_scope_ident,
+            ScopeValue::RightOperand(bin_op) => ctx[*bin_op]
+                .rhs_scope_ident
+                .expect("right operand without narrowed values has no scope"),
         };
 
         symbols
@@ -109,6 +133,36 @@ pub trait IrWalker<'a> {
         Ok(())
     }
 
+    fn visit_match_arm(
+        &mut self,
+        ctx: &mut IrCtx<'a>,
+        scope: &mut Self::Scope,
+        mtch: NodeRef<'a, Match<'a>>,
+        arm: NodeRef<'a, MatchArm<'a>>,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Visited before the values narrowed by an early exit become available after the if statement
+    fn visit_if_stmt(
+        &mut self,
+        ctx: &mut IrCtx<'a>,
+        scope: &mut Self::Scope,
+        ifb: NodeRef<'a, IfStmt<'a>>,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Visited before the bindings of the pattern become available
+    fn visit_let_else(
+        &mut self,
+        ctx: &mut IrCtx<'a>,
+        scope: &mut Self::Scope,
+        let_else: NodeRef<'a, LetElse<'a>>,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
     fn visit_scope_begin(
         &mut self,
         ctx: &mut IrCtx<'a>,
@@ -195,6 +249,10 @@ impl<'a> IrWalkable<'a> for NodeRef<'a, Struct<'a>> {
     ) -> Result<Self::Output, W::Error> {
         let mut st_scope = walker.visit_scope_begin(ctx, scope, ScopeValue::Struct(self))?;
 
+        for param in ctx[self].type_params.clone() {
+            param.walk(walker, ctx, &mut st_scope)?;
+        }
+
         for attr_id in ctx[self].attrs.clone() {
             let attr_name = *ctx[attr_id].name;
             walker.visit_ident(ctx, scope, attr_name)?;
@@ -234,6 +292,10 @@ impl<'a> IrWalkable<'a> for NodeRef<'a, Enum<'a>> {
         let mut enm_scope = walker.visit_scope_begin(ctx, scope, ScopeValue::Enum(self))?;
         walker.visit_ident(ctx, scope, *ctx[self].name)?;
 
+        for param in ctx[self].type_params.clone() {
+            param.walk(walker, ctx, &mut enm_scope)?;
+        }
+
         for val in ctx[self].values.clone() {
             val.walk(walker, ctx, &mut enm_scope)?;
         }
@@ -297,10 +359,17 @@ impl<'a> IrWalkable<'a> for NodeRef<'a, Stmt<'a>> {
             Stmt::FunctionDecl(func) => func.walk(walker, ctx, scope),
             Stmt::StructDecl(st) => st.walk(walker, ctx, scope),
             Stmt::EnumDecl(enm) => enm.walk(walker, ctx, scope),
-            Stmt::Return(expr) => expr.walk(walker, ctx, scope),
+            Stmt::Return(expr) | Stmt::Throw(expr) => expr.walk(walker, ctx, scope),
             Stmt::ExternObj(obj) => obj.walk(walker, ctx, scope),
+            Stmt::TypeAlias(alias) => alias.walk(walker, ctx, scope),
             Stmt::IfBranch(ifb) => ifb.walk(walker, ctx, scope),
             Stmt::TraitDecl(tr_decl) => tr_decl.walk(walker, ctx, scope),
+            Stmt::Extension(ext) => ext.walk(walker, ctx, scope),
+            Stmt::LetElse(let_else) => let_else.walk(walker, ctx, scope),
+            Stmt::DoCatch(dc) => dc.walk(walker, ctx, scope),
+            Stmt::WhileLoop(wl) => wl.walk(walker, ctx, scope),
+            Stmt::ForLoop(fl) => fl.walk(walker, ctx, scope),
+            Stmt::LoopJump(_) => Ok(()),
         }?;
 
         walker.visit_stmt(ctx, scope, self)?;
@@ -344,6 +413,21 @@ impl<'a> IrWalkable<'a> for NodeRef<'a, ExternalObject<'a>> {
     }
 }
 
+impl<'a> IrWalkable<'a> for NodeRef<'a, TypeAlias<'a>> {
+    type Output = ();
+
+    fn walk<W: IrWalker<'a>>(
+        self,
+        walker: &mut W,
+        ctx: &mut IrCtx<'a>,
+        scope: &mut W::Scope,
+    ) -> Result<Self::Output, W::Error> {
+        walker.visit_ident(ctx, scope, *ctx[self].name)?;
+        ctx[self].type_sig = ctx[self].type_sig.cloned().walk(walker, ctx, scope)?.into();
+        Ok(())
+    }
+}
+
 impl<'a> IrWalkable<'a> for NodeRef<'a, Function<'a>> {
     type Output = ();
 
@@ -355,6 +439,10 @@ impl<'a> IrWalkable<'a> for NodeRef<'a, Function<'a>> {
     ) -> Result<Self::Output, W::Error> {
         let mut func_scope = walker.visit_scope_begin(ctx, scope, ScopeValue::Func(self))?;
 
+        for param in ctx[self].type_params.clone() {
+            param.walk(walker, ctx, &mut func_scope)?;
+        }
+
         for arg in ctx[self].args.clone() {
             arg.walk(walker, ctx, &mut func_scope)?;
         }
@@ -368,6 +456,10 @@ impl<'a> IrWalkable<'a> for NodeRef<'a, Function<'a>> {
             .walk(walker, ctx, scope)?
             .into();
 
+        if let Some(throws) = ctx[self].throws.clone() {
+            ctx[self].throws = Some(throws.walk(walker, ctx, scope)?);
+        }
+
         ctx[self].body.walk(walker, ctx, &mut func_scope)?;
 
         walker.visit_func_decl(ctx, &mut func_scope, self)?;
@@ -378,6 +470,25 @@ impl<'a> IrWalkable<'a> for NodeRef<'a, Function<'a>> {
     }
 }
 
+impl<'a> IrWalkable<'a> for NodeRef<'a, TypeParam<'a>> {
+    type Output = ();
+
+    fn walk<W: IrWalker<'a>>(
+        self,
+        walker: &mut W,
+        ctx: &mut IrCtx<'a>,
+        scope: &mut W::Scope,
+    ) -> Result<Self::Output, W::Error> {
+        walker.visit_ident(ctx, scope, *ctx[self].name)?;
+
+        if let Some(bound) = ctx[self].bound {
+            walker.visit_ident(ctx, scope, bound)?;
+        }
+
+        Ok(())
+    }
+}
+
 impl<'a> IrWalkable<'a> for NodeRef<'a, FunctionArg<'a>> {
     type Output = ();
 
@@ -410,6 +521,18 @@ impl<'a> IrWalkable<'a> for NodeRef<'a, IfStmt<'a>> {
             ScopeValue::IfBranch(self, IfBranchBody::MainBody),
         )?;
 
+        if let Some(pattern) = ctx[self].pattern {
+            pattern.walk(walker, ctx, &mut if_main_scope)?;
+        }
+
+        walk_narrowed_bindings(
+            self,
+            NarrowingReach::Branch(IfBranchBody::MainBody),
+            walker,
+            ctx,
+            &mut if_main_scope,
+        )?;
+
         ctx[self].body.walk(walker, ctx, &mut if_main_scope)?;
 
         walker.visit_scope_end(
@@ -426,6 +549,14 @@ impl<'a> IrWalkable<'a> for NodeRef<'a, IfStmt<'a>> {
                 ScopeValue::IfBranch(self, IfBranchBody::ElseBody),
             )?;
 
+            walk_narrowed_bindings(
+                self,
+                NarrowingReach::Branch(IfBranchBody::ElseBody),
+                walker,
+                ctx,
+                &mut if_else_scope,
+            )?;
+
             else_body.walk(walker, ctx, &mut if_else_scope)?;
 
             walker.visit_scope_end(
@@ -436,6 +567,128 @@ impl<'a> IrWalkable<'a> for NodeRef<'a, IfStmt<'a>> {
             )?;
         }
 
+        walker.visit_if_stmt(ctx, scope, self)?;
+
+        // values narrowed by an early exit are only available after the if statement
+        for binding in ctx[self].narrowed.clone() {
+            if binding.reach == NarrowingReach::AfterIf {
+                walker.visit_ordered_symbol(ctx, scope)?;
+            }
+        }
+        walk_narrowed_bindings(self, NarrowingReach::AfterIf, walker, ctx, scope)?;
+
+        Ok(())
+    }
+}
+
+fn walk_narrowed_bindings<'a, W: IrWalker<'a>>(
+    ifb: NodeRef<'a, IfStmt<'a>>,
+    reach: NarrowingReach,
+    walker: &mut W,
+    ctx: &mut IrCtx<'a>,
+    scope: &mut W::Scope,
+) -> Result<(), W::Error> {
+    for (i, binding) in ctx[ifb].narrowed.clone().into_iter().enumerate() {
+        if binding.reach == reach {
+            walker.visit_ident(ctx, scope, binding.name)?;
+            ctx[ifb].narrowed[i].type_sig =
+                binding.type_sig.cloned().walk(walker, ctx, scope)?.into();
+        }
+    }
+
+    Ok(())
+}
+
+impl<'a> IrWalkable<'a> for NodeRef<'a, DoCatch<'a>> {
+    type Output = ();
+
+    fn walk<W: IrWalker<'a>>(
+        self,
+        walker: &mut W,
+        ctx: &mut IrCtx<'a>,
+        scope: &mut W::Scope,
+    ) -> Result<Self::Output, W::Error> {
+        let mut do_scope =
+            walker.visit_scope_begin(ctx, scope, ScopeValue::DoCatch(self, DoCatchBody::DoBody))?;
+
+        ctx[self].body.walk(walker, ctx, &mut do_scope)?;
+
+        walker.visit_scope_end(
+            ctx,
+            scope,
+            do_scope,
+            ScopeValue::DoCatch(self, DoCatchBody::DoBody),
+        )?;
+
+        let mut catch_scope = walker.visit_scope_begin(
+            ctx,
+            scope,
+            ScopeValue::DoCatch(self, DoCatchBody::CatchBody),
+        )?;
+
+        walker.visit_ident(ctx, &mut catch_scope, *ctx[self].error_name)?;
+        ctx[self].error_type = ctx[self]
+            .error_type
+            .cloned()
+            .walk(walker, ctx, &mut catch_scope)?
+            .into();
+
+        ctx[self].catch_body.walk(walker, ctx, &mut catch_scope)?;
+
+        walker.visit_scope_end(
+            ctx,
+            scope,
+            catch_scope,
+            ScopeValue::DoCatch(self, DoCatchBody::CatchBody),
+        )?;
+
+        Ok(())
+    }
+}
+
+impl<'a> IrWalkable<'a> for NodeRef<'a, WhileLoop<'a>> {
+    type Output = ();
+
+    fn walk<W: IrWalker<'a>>(
+        self,
+        walker: &mut W,
+        ctx: &mut IrCtx<'a>,
+        scope: &mut W::Scope,
+    ) -> Result<Self::Output, W::Error> {
+        ctx[self].condition.walk(walker, ctx, scope)?;
+
+        let mut body_scope = walker.visit_scope_begin(ctx, scope, ScopeValue::WhileLoop(self))?;
+        ctx[self].body.walk(walker, ctx, &mut body_scope)?;
+        walker.visit_scope_end(ctx, scope, body_scope, ScopeValue::WhileLoop(self))?;
+
+        Ok(())
+    }
+}
+
+impl<'a> IrWalkable<'a> for NodeRef<'a, ForLoop<'a>> {
+    type Output = ();
+
+    fn walk<W: IrWalker<'a>>(
+        self,
+        walker: &mut W,
+        ctx: &mut IrCtx<'a>,
+        scope: &mut W::Scope,
+    ) -> Result<Self::Output, W::Error> {
+        ctx[self].sequence.walk(walker, ctx, scope)?;
+
+        let mut body_scope = walker.visit_scope_begin(ctx, scope, ScopeValue::ForLoop(self))?;
+
+        walker.visit_ident(ctx, &mut body_scope, *ctx[self].item_name)?;
+        ctx[self].item_type = ctx[self]
+            .item_type
+            .cloned()
+            .walk(walker, ctx, &mut body_scope)?
+            .into();
+
+        ctx[self].body.walk(walker, ctx, &mut body_scope)?;
+
+        walker.visit_scope_end(ctx, scope, body_scope, ScopeValue::ForLoop(self))?;
+
         Ok(())
     }
 }
@@ -464,14 +717,32 @@ impl<'a> IrWalkable<'a> for NodeRef<'a, Expr<'a>> {
             Expr::StructInit(st_init) => st_init.walk(walker, ctx, scope),
             Expr::Identifier(ident, _) => walker.visit_ident(ctx, scope, *ident),
             Expr::StringLiteral(_, _) => Ok(()),
+            Expr::StringInterpolation(interp) => {
+                for expr in interp.exprs(ctx) {
+                    expr.walk(walker, ctx, scope)?;
+                }
+
+                Ok(())
+            }
             Expr::NumberLiteral(_, _) => Ok(()),
             Expr::BoolLiteral(_, _) => Ok(()),
+            Expr::NilLiteral(value_type, span) => {
+                let value_type = value_type.cloned().walk(walker, ctx, scope)?;
+                ctx[self] = Expr::NilLiteral(value_type.into(), span);
+                Ok(())
+            }
             Expr::FunctionCall(func_call) => {
                 for param in ctx[func_call].args.clone() {
                     param.walk(walker, ctx, scope)?;
                 }
 
-                ctx[func_call].func.walk(walker, ctx, scope)
+                ctx[func_call].func.walk(walker, ctx, scope)?;
+
+                if let Some(instance) = ctx[func_call].instance.clone() {
+                    ctx[func_call].instance = Some(instance.walk(walker, ctx, scope)?);
+                }
+
+                Ok(())
             }
             Expr::TupleAccess(tup_acc) => ctx[tup_acc].tuple_expr.walk(walker, ctx, scope),
             Expr::EscapeBlock(esc_blk) => {
@@ -499,6 +770,10 @@ impl<'a> IrWalkable<'a> for NodeRef<'a, Expr<'a>> {
                 walker.visit_ident(ctx, scope, ctx[enm_init].enum_value)?;
                 walker.visit_ident(ctx, scope, ctx[enm_init].enum_name)?;
 
+                for (i, type_arg) in ctx[enm_init].type_args.clone().into_iter().enumerate() {
+                    ctx[enm_init].type_args[i] = type_arg.walk(walker, ctx, scope)?;
+                }
+
                 Ok(())
             }
             Expr::UnresolvedMemberAccess(mem_acc) => {
@@ -516,12 +791,178 @@ impl<'a> IrWalkable<'a> for NodeRef<'a, Expr<'a>> {
 
                 Ok(())
             }
+            Expr::BinaryOperation(bin_op) => {
+                ctx[bin_op].lhs.walk(walker, ctx, scope)?;
+
+                if ctx[bin_op].rhs_scope_ident.is_none() {
+                    ctx[bin_op].rhs.walk(walker, ctx, scope)
+                } else {
+                    let mut rhs_scope =
+                        walker.visit_scope_begin(ctx, scope, ScopeValue::RightOperand(bin_op))?;
+                    ctx[bin_op].rhs.walk(walker, ctx, &mut rhs_scope)?;
+                    walker.visit_scope_end(ctx, scope, rhs_scope, ScopeValue::RightOperand(bin_op))
+                }
+            }
+            Expr::UnaryOperation(unary_op) => ctx[unary_op].operand.walk(walker, ctx, scope),
+            Expr::Match(mtch) => mtch.walk(walker, ctx, scope),
+            Expr::Await(aw) => {
+                ctx[aw].expr.walk(walker, ctx, scope)?;
+
+                ctx[aw].type_sig = ctx[aw].type_sig.cloned().walk(walker, ctx, scope)?.into();
+                Ok(())
+            }
+            Expr::Try(tr) => ctx[tr].expr.walk(walker, ctx, scope),
+            Expr::Array(arr) => {
+                for item in ctx[arr].items.clone() {
+                    item.walk(walker, ctx, scope)?;
+                }
+
+                ctx[arr].elem_type = ctx[arr].elem_type.cloned().walk(walker, ctx, scope)?.into();
+                Ok(())
+            }
+            Expr::Index(idx) => {
+                ctx[idx].array.walk(walker, ctx, scope)?;
+                ctx[idx].index.walk(walker, ctx, scope)?;
+
+                ctx[idx].elem_type = ctx[idx].elem_type.cloned().walk(walker, ctx, scope)?.into();
+                Ok(())
+            }
+            Expr::ArrayMemberAccess(arr_acc) => {
+                ctx[arr_acc].array.walk(walker, ctx, scope)?;
+
+                if let Some(mapped_type) = ctx[arr_acc].mapped_type.clone() {
+                    ctx[arr_acc].mapped_type = Some(mapped_type.walk(walker, ctx, scope)?);
+                }
+
+                Ok(())
+            }
+            Expr::Dictionary(dict) => {
+                for (key, value) in ctx[dict].entries.clone() {
+                    key.walk(walker, ctx, scope)?;
+                    value.walk(walker, ctx, scope)?;
+                }
+
+                ctx[dict].key_type = ctx[dict].key_type.cloned().walk(walker, ctx, scope)?.into();
+                ctx[dict].value_type = ctx[dict]
+                    .value_type
+                    .cloned()
+                    .walk(walker, ctx, scope)?
+                    .into();
+                Ok(())
+            }
+            Expr::ArraySlice(slice) => {
+                ctx[slice].array.walk(walker, ctx, scope)?;
+                ctx[slice].range.walk(walker, ctx, scope)
+            }
+            Expr::DictionaryIndex(dict_idx) => {
+                ctx[dict_idx].dictionary.walk(walker, ctx, scope)?;
+                ctx[dict_idx].key.walk(walker, ctx, scope)
+            }
+            Expr::DictionaryMemberAccess(dict_acc) => {
+                ctx[dict_acc].dictionary.walk(walker, ctx, scope)
+            }
+            Expr::RecordAccess(rec_acc) => ctx[rec_acc].record.walk(walker, ctx, scope),
+            // the struct name is not an expression on its own, so only its identifier is visited
+            Expr::StaticAccess(st_acc) => {
+                let st_name = ctx[st_acc].struct_expr.unwrap_ident(ctx);
+                walker.visit_ident(ctx, scope, st_name)
+            }
         }?;
 
         walker.visit_expr(ctx, scope, self)
     }
 }
 
+impl<'a> IrWalkable<'a> for NodeRef<'a, Match<'a>> {
+    type Output = ();
+
+    fn walk<W: IrWalker<'a>>(
+        self,
+        walker: &mut W,
+        ctx: &mut IrCtx<'a>,
+        scope: &mut W::Scope,
+    ) -> Result<Self::Output, W::Error> {
+        ctx[self].scrutinee.walk(walker, ctx, scope)?;
+
+        for arm in ctx[self].arms.clone() {
+            let mut arm_scope = walker.visit_scope_begin(ctx, scope, ScopeValue::MatchArm(arm))?;
+
+            if let Some(pattern) = ctx[arm].pattern {
+                pattern.walk(walker, ctx, &mut arm_scope)?;
+            }
+
+            ctx[arm].body.walk(walker, ctx, &mut arm_scope)?;
+
+            if let Some(result) = ctx[arm].result {
+                result.walk(walker, ctx, &mut arm_scope)?;
+            }
+
+            walker.visit_match_arm(ctx, &mut arm_scope, self, arm)?;
+
+            walker.visit_scope_end(ctx, scope, arm_scope, ScopeValue::MatchArm(arm))?;
+        }
+
+        ctx[self].type_sig = ctx[self].type_sig.cloned().walk(walker, ctx, scope)?.into();
+
+        Ok(())
+    }
+}
+
+impl<'a> IrWalkable<'a> for NodeRef<'a, EnumPattern<'a>> {
+    type Output = ();
+
+    fn walk<W: IrWalker<'a>>(
+        self,
+        walker: &mut W,
+        ctx: &mut IrCtx<'a>,
+        scope: &mut W::Scope,
+    ) -> Result<Self::Output, W::Error> {
+        walker.visit_ident(ctx, scope, *ctx[self].enum_value)?;
+
+        for (i, binding) in ctx[self].bindings.clone().into_iter().enumerate() {
+            if let Some(name) = binding.name {
+                walker.visit_ident(ctx, scope, name)?;
+            }
+
+            ctx[self].bindings[i].type_sig =
+                binding.type_sig.cloned().walk(walker, ctx, scope)?.into();
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> IrWalkable<'a> for NodeRef<'a, LetElse<'a>> {
+    type Output = ();
+
+    fn walk<W: IrWalker<'a>>(
+        self,
+        walker: &mut W,
+        ctx: &mut IrCtx<'a>,
+        scope: &mut W::Scope,
+    ) -> Result<Self::Output, W::Error> {
+        ctx[self].value.walk(walker, ctx, scope)?;
+
+        let mut else_scope = walker.visit_scope_begin(ctx, scope, ScopeValue::LetElse(self))?;
+        ctx[self].else_body.walk(walker, ctx, &mut else_scope)?;
+        walker.visit_scope_end(ctx, scope, else_scope, ScopeValue::LetElse(self))?;
+
+        walker.visit_let_else(ctx, scope, self)?;
+
+        // the bindings are only available after the else body
+        let pattern = ctx[self].pattern;
+        for binding in ctx[pattern].bindings.clone() {
+            if binding.name.is_some() {
+                walker.visit_ordered_symbol(ctx, scope)?;
+            }
+        }
+
+        pattern.walk(walker, ctx, scope)?;
+
+        Ok(())
+    }
+}
+
 impl<'a> IrWalkable<'a> for NodeRef<'a, StructInit<'a>> {
     type Output = ();
 
@@ -569,7 +1010,11 @@ impl<'a> IrWalkable<'a> for TypeSignature<'a> {
                 walker.visit_ident(ctx, scope, ident)?;
                 self
             }
-            TypeSignatureValue::Function { args, return_type } => {
+            TypeSignatureValue::Function {
+                args,
+                return_type,
+                throws,
+            } => {
                 let mut new_args = Vec::with_capacity(args.len());
                 for arg in &*args {
                     new_args.push(arg.clone().walk(walker, ctx, scope)?);
@@ -577,10 +1022,16 @@ impl<'a> IrWalkable<'a> for TypeSignature<'a> {
 
                 let new_return_type = return_type.cloned().walk(walker, ctx, scope)?;
 
+                let new_throws = match throws {
+                    Some(throws) => Some(throws.walk(walker, ctx, scope)?),
+                    None => None,
+                };
+
                 ctx.get_type_sig(
                     TypeSignatureValue::Function {
                         args: new_args.into(),
                         return_type: new_return_type.into(),
+                        throws: new_throws,
                     },
                     self.context,
                 )
@@ -606,6 +1057,47 @@ impl<'a> IrWalkable<'a> for TypeSignature<'a> {
                 ctx.get_type_sig(TypeSignatureValue::Tuple(new_items.into()), self.context)
             }
             TypeSignatureValue::TypeVariable(_) => self,
+            TypeSignatureValue::TypeParameter { name: _ } => self,
+            TypeSignatureValue::Instance { base, args } => {
+                let new_base = base.walk(walker, ctx, scope)?;
+
+                let mut new_args = Vec::with_capacity(args.len());
+                for arg in args {
+                    new_args.push(arg.walk(walker, ctx, scope)?);
+                }
+
+                ctx.get_type_sig(
+                    TypeSignatureValue::Instance {
+                        base: new_base,
+                        args: new_args,
+                    },
+                    self.context,
+                )
+            }
+            TypeSignatureValue::Dictionary { key, value } => {
+                let new_key = key.walk(walker, ctx, scope)?;
+                let new_value = value.walk(walker, ctx, scope)?;
+
+                ctx.get_type_sig(
+                    TypeSignatureValue::Dictionary {
+                        key: new_key,
+                        value: new_value,
+                    },
+                    self.context,
+                )
+            }
+            TypeSignatureValue::Optional(value) => {
+                let new_value = value.walk(walker, ctx, scope)?;
+                ctx.get_type_sig(TypeSignatureValue::Optional(new_value), self.context)
+            }
+            TypeSignatureValue::Record(fields) => {
+                let mut new_fields = Vec::new();
+                for (name, t) in fields {
+                    new_fields.push((name, t.walk(walker, ctx, scope)?));
+                }
+
+                ctx.get_type_sig(TypeSignatureValue::Record(new_fields), self.context)
+            }
         };
 
         walker.visit_type_sig(ctx, scope, new_type_sig)
@@ -629,6 +1121,10 @@ impl<'a> IrWalkable<'a> for NodeRef<'a, Trait<'a>> {
             attr.walk(walker, ctx, &mut child_scope)?;
         }
 
+        for op in ctx[self].operators.clone() {
+            op.walk(walker, ctx, &mut child_scope)?;
+        }
+
         walker.visit_scope_end(ctx, scope, child_scope, ScopeValue::Trait(self))?;
 
         Ok(())
@@ -652,10 +1148,74 @@ impl<'a> IrWalkable<'a> for NodeRef<'a, TraitFuncAttr<'a>> {
             .map(|ret_type| ret_type.walk(walker, ctx, scope))
             .map_or(Ok(None), |val| val.map(Some))?;
 
+        ctx[self].throws = ctx[self]
+            .throws
+            .clone()
+            .map(|throws| throws.walk(walker, ctx, scope))
+            .map_or(Ok(None), |val| val.map(Some))?;
+
         for arg in ctx[self].args.clone() {
             arg.walk(walker, ctx, scope)?;
         }
 
+        if let Some(default_impl) = ctx[self].default_impl {
+            default_impl.walk(walker, ctx, scope)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> IrWalkable<'a> for NodeRef<'a, Extension<'a>> {
+    type Output = ();
+
+    fn walk<W: IrWalker<'a>>(
+        self,
+        walker: &mut W,
+        ctx: &mut IrCtx<'a>,
+        scope: &mut W::Scope,
+    ) -> Result<Self::Output, W::Error> {
+        walker.visit_ident(ctx, scope, *ctx[self].name)?;
+
+        if let Some(trait_name) = ctx[self].trait_name {
+            walker.visit_ident(ctx, scope, trait_name)?;
+        }
+
+        let mut child_scope = walker.visit_scope_begin(ctx, scope, ScopeValue::Extension(self))?;
+
+        for method in ctx[self].methods.clone() {
+            method.walk(walker, ctx, &mut child_scope)?;
+        }
+
+        for init in ctx[self].inits.clone() {
+            init.walk(walker, ctx, &mut child_scope)?;
+        }
+
+        for method in ctx[self].static_methods.clone() {
+            method.walk(walker, ctx, &mut child_scope)?;
+        }
+
+        for op in ctx[self].operators.clone() {
+            op.walk(walker, ctx, &mut child_scope)?;
+        }
+
+        for attr_id in ctx[self].static_attrs.clone() {
+            let attr_name = *ctx[attr_id].name;
+            walker.visit_ident(ctx, scope, attr_name)?;
+
+            if let Some(value) = ctx[attr_id].default_value {
+                value.walk(walker, ctx, &mut child_scope)?;
+            }
+
+            ctx[attr_id].type_sig = ctx[attr_id]
+                .type_sig
+                .cloned()
+                .walk(walker, ctx, scope)?
+                .into();
+        }
+
+        walker.visit_scope_end(ctx, scope, child_scope, ScopeValue::Extension(self))?;
+
         Ok(())
     }
 }
@@ -0,0 +1,494 @@
+use crate::{
+    error_message::error_formatter::Spanned,
+    ir::{context::IrCtx, late_init::LateInit},
+    parser::Span,
+    symbols::symbol_table::symbol_table_zipper::SymbolTableZipper,
+};
+
+use super::{
+    expression::Expr,
+    type_signature::{
+        BuiltinType, TypeEvalError, TypeSignature, TypeSignatureContext, TypeSignatureParent,
+        TypeSignatureValue, Typed,
+    },
+    NodeRef,
+};
+
+#[derive(Debug)]
+pub struct Array<'a> {
+    pub items: Vec<NodeRef<'a, Expr<'a>>>,
+    /// The type of the elements, all items have to be of this type
+    pub elem_type: LateInit<TypeSignature<'a>>,
+    pub span: Span<'a>,
+}
+
+#[derive(Debug)]
+pub struct Index<'a> {
+    pub array: NodeRef<'a, Expr<'a>>,
+    pub index: NodeRef<'a, Expr<'a>>,
+    /// The type of the accessed element
+    pub elem_type: LateInit<TypeSignature<'a>>,
+    pub span: Span<'a>,
+}
+
+/// The elements of an array within a range, e.g. `array[1..<3]`
+#[derive(Debug)]
+pub struct ArraySlice<'a> {
+    pub array: NodeRef<'a, Expr<'a>>,
+    pub range: NodeRef<'a, Expr<'a>>,
+    pub span: Span<'a>,
+}
+
+/// The builtin members of an array, which map directly to the JavaScript array
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArrayMember {
+    Length,
+    Push,
+    Map,
+    Filter,
+}
+
+impl ArrayMember {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "length" => Some(ArrayMember::Length),
+            "push" => Some(ArrayMember::Push),
+            "map" => Some(ArrayMember::Map),
+            "filter" => Some(ArrayMember::Filter),
+            _ => None,
+        }
+    }
+
+    pub const fn name(&self) -> &'static str {
+        match self {
+            ArrayMember::Length => "length",
+            ArrayMember::Push => "push",
+            ArrayMember::Map => "map",
+            ArrayMember::Filter => "filter",
+        }
+    }
+
+    pub const fn is_method(&self) -> bool {
+        !matches!(self, ArrayMember::Length)
+    }
+}
+
+#[derive(Debug)]
+pub struct ArrayMemberAccess<'a> {
+    pub array: NodeRef<'a, Expr<'a>>,
+    pub member: ArrayMember,
+    /// The element type of the array returned by `map`
+    pub mapped_type: Option<TypeSignature<'a>>,
+    pub span: Span<'a>,
+}
+
+impl<'a> Spanned<'a> for NodeRef<'a, Array<'a>> {
+    fn get_span(&self, ctx: &IrCtx<'a>) -> Option<Span<'a>> {
+        Some(ctx[*self].span.clone())
+    }
+}
+
+impl<'a> Spanned<'a> for NodeRef<'a, Index<'a>> {
+    fn get_span(&self, ctx: &IrCtx<'a>) -> Option<Span<'a>> {
+        Some(ctx[*self].span.clone())
+    }
+}
+
+impl<'a> Spanned<'a> for NodeRef<'a, ArraySlice<'a>> {
+    fn get_span(&self, ctx: &IrCtx<'a>) -> Option<Span<'a>> {
+        Some(ctx[*self].span.clone())
+    }
+}
+
+impl<'a> Spanned<'a> for NodeRef<'a, ArrayMemberAccess<'a>> {
+    fn get_span(&self, ctx: &IrCtx<'a>) -> Option<Span<'a>> {
+        Some(ctx[*self].span.clone())
+    }
+}
+
+impl<'a> NodeRef<'a, Array<'a>> {
+    /// Types the elements of the literal, and of the array literals nested in it, as the
+    /// elements of the array type it is assigned to, so that its items are coerced to them
+    pub fn expect_type(&self, ctx: &mut IrCtx<'a>, expected: &TypeSignature<'a>) {
+        let Some(elem_type) = expected.array_element_type(ctx) else {
+            return;
+        };
+
+        ctx[*self].elem_type = elem_type.clone().into();
+
+        for item in ctx[*self].items.clone() {
+            if let Expr::Array(arr) = ctx[item] {
+                arr.expect_type(ctx, &elem_type);
+            }
+        }
+    }
+}
+
+impl<'a> Typed<'a> for NodeRef<'a, Array<'a>> {
+    fn eval_type(
+        &self,
+        symbols: &mut SymbolTableZipper<'a>,
+        ctx: &mut IrCtx<'a>,
+    ) -> Result<TypeSignature<'a>, TypeEvalError<'a>> {
+        // the element type is already known before inference when the array has items,
+        // which is needed to resolve member accesses on the elements, unless the items
+        // are coerced to the element type of the array they are assigned to
+        let elem_type = ctx[*self].elem_type.cloned();
+        let elem_type = match ctx[*self].items.first().copied() {
+            Some(item) if matches!(ctx[&elem_type], TypeSignatureValue::TypeVariable(_)) => {
+                item.eval_type(symbols, ctx)?
+            }
+            _ => elem_type,
+        };
+
+        Ok(ctx.get_array_type_sig(
+            elem_type,
+            TypeSignatureContext {
+                parent: TypeSignatureParent::Array(*self),
+                type_span: None,
+            }
+            .alloc(),
+        ))
+    }
+
+    fn specified_type(&self, ctx: &IrCtx<'a>) -> Option<TypeSignature<'a>> {
+        Some(ctx[*self].elem_type.cloned())
+    }
+
+    fn specify_type(
+        &self,
+        ctx: &mut IrCtx<'a>,
+        new_type: TypeSignature<'a>,
+    ) -> Result<(), TypeEvalError<'a>> {
+        ctx[*self].elem_type = new_type.into();
+        Ok(())
+    }
+}
+
+impl<'a> Typed<'a> for NodeRef<'a, Index<'a>> {
+    fn eval_type(
+        &self,
+        symbols: &mut SymbolTableZipper<'a>,
+        ctx: &mut IrCtx<'a>,
+    ) -> Result<TypeSignature<'a>, TypeEvalError<'a>> {
+        let array = ctx[*self].array;
+        let array_type = array.eval_type(symbols, ctx)?;
+
+        Ok(array_type
+            .array_element_type(ctx)
+            .unwrap_or_else(|| ctx[*self].elem_type.cloned()))
+    }
+
+    fn specified_type(&self, ctx: &IrCtx<'a>) -> Option<TypeSignature<'a>> {
+        Some(ctx[*self].elem_type.cloned())
+    }
+
+    fn specify_type(
+        &self,
+        ctx: &mut IrCtx<'a>,
+        new_type: TypeSignature<'a>,
+    ) -> Result<(), TypeEvalError<'a>> {
+        ctx[*self].elem_type = new_type.into();
+        Ok(())
+    }
+}
+
+impl<'a> Typed<'a> for NodeRef<'a, ArraySlice<'a>> {
+    fn eval_type(
+        &self,
+        symbols: &mut SymbolTableZipper<'a>,
+        ctx: &mut IrCtx<'a>,
+    ) -> Result<TypeSignature<'a>, TypeEvalError<'a>> {
+        let array = ctx[*self].array;
+        let array_type = array.eval_type(symbols, ctx)?;
+
+        match array_type.array_element_type(ctx) {
+            Some(_) => Ok(array_type),
+            None => Err(TypeEvalError::AccessNonArray(array_type)),
+        }
+    }
+}
+
+impl<'a> Typed<'a> for NodeRef<'a, ArrayMemberAccess<'a>> {
+    fn eval_type(
+        &self,
+        symbols: &mut SymbolTableZipper<'a>,
+        ctx: &mut IrCtx<'a>,
+    ) -> Result<TypeSignature<'a>, TypeEvalError<'a>> {
+        let array = ctx[*self].array;
+        let array_type = array.eval_type(symbols, ctx)?;
+        let elem_type = array_type
+            .array_element_type(ctx)
+            .ok_or_else(|| TypeEvalError::AccessNonArray(array_type.clone()))?;
+
+        let type_ctx = TypeSignatureContext {
+            parent: TypeSignatureParent::ArrayMemberAccess(*self),
+            type_span: None,
+        }
+        .alloc();
+        let builtin = |ctx: &mut IrCtx<'a>, builtin| {
+            ctx.get_type_sig(TypeSignatureValue::Builtin(builtin), type_ctx.clone())
+        };
+        let func =
+            |ctx: &mut IrCtx<'a>, args: Vec<TypeSignature<'a>>, return_type: TypeSignature<'a>| {
+                ctx.get_type_sig(
+                    TypeSignatureValue::Function {
+                        args: args.into(),
+                        return_type: return_type.into(),
+                        throws: None,
+                    },
+                    type_ctx.clone(),
+                )
+            };
+
+        Ok(match ctx[*self].member {
+            // `length` -> Number
+            ArrayMember::Length => builtin(ctx, BuiltinType::Number),
+            // `push` -> (T) -> Void
+            ArrayMember::Push => {
+                let void = builtin(ctx, BuiltinType::Void);
+                func(ctx, vec![elem_type], void)
+            }
+            // `map` -> ((T) -> U) -> Array<U>
+            ArrayMember::Map => {
+                let mapped_type = ctx[*self]
+                    .mapped_type
+                    .clone()
+                    .expect("map has a mapped type");
+                let transform = func(ctx, vec![elem_type], mapped_type.clone());
+                let mapped_array = ctx.get_array_type_sig(mapped_type, type_ctx.clone());
+                func(ctx, vec![transform], mapped_array)
+            }
+            // `filter` -> ((T) -> Boolean) -> Array<T>
+            ArrayMember::Filter => {
+                let boolean = builtin(ctx, BuiltinType::Boolean);
+                let predicate = func(ctx, vec![elem_type], boolean);
+                func(ctx, vec![predicate], array_type)
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::assert_matches;
+
+    use crate::{
+        ir::{
+            node::type_signature::TypeEvalError,
+            test_utils::utils::{lowered_ir, resolve_symbols, type_check},
+        },
+        symbols::symbol_resolver::SymbolResolutionError,
+        type_checker::TypeCheckerError,
+    };
+
+    #[test]
+    fn test_array_element_type() {
+        let mut ir = lowered_ir(
+            "
+        let numbers = [1, 2, 3]
+        let first: Number = numbers[0]
+        let empty: Array<String> = []
+        let name: String = empty[0]
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_array_mixed_elements() {
+        let mut ir = lowered_ir("let values = [1, \"two\"]").unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ConflictingTypes(_, _))
+        );
+    }
+
+    #[test]
+    fn test_index_element_mismatch() {
+        let mut ir = lowered_ir(
+            "
+        let numbers = [1, 2, 3]
+        let first: String = numbers[0]
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ConflictingTypes(_, _))
+        );
+    }
+
+    #[test]
+    fn test_index_assignment() {
+        let mut ir = lowered_ir(
+            "
+        let numbers = [1, 2, 3]
+        numbers[0] = 5
+        let grid = [[1, 2], [3, 4]]
+        grid[1][0] = numbers[2] = 7
+        let scores: Array<Number?> = [1, nil]
+        scores[0] = nil
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+
+        for code in [
+            "let numbers = [1, 2, 3]; numbers[0] = \"5\"",
+            "let numbers = [1, 2, 3]; numbers[0] = nil",
+            "let numbers = [1, 2, 3]; numbers[\"0\"] = 5",
+        ] {
+            let mut ir = lowered_ir(code).unwrap();
+
+            assert_matches!(
+                type_check(&mut ir).1,
+                Err(TypeCheckerError::ConflictingTypes(_, _)),
+                "{code}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_index_non_number() {
+        let mut ir = lowered_ir(
+            "
+        let numbers = [1, 2, 3]
+        let first = numbers[\"0\"]
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ConflictingTypes(_, _))
+        );
+    }
+
+    #[test]
+    fn test_index_non_array() {
+        let mut ir = lowered_ir(
+            "
+        let number = 1
+        let first = number[0]
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ConflictingTypes(_, _))
+        );
+    }
+
+    #[test]
+    fn test_array_slice() {
+        let mut ir = lowered_ir(
+            "
+        let names = [\"a\", \"b\", \"c\"]
+        let window: Range = 0..<2
+        let first: Array<String> = names[window]
+        let rest: Array<String> = names[1...2]
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_array_slice_element_mismatch() {
+        let mut ir = lowered_ir(
+            "
+        let names = [\"a\", \"b\", \"c\"]
+        let first: Array<Number> = names[0..<2]
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ConflictingTypes(_, _))
+        );
+    }
+
+    #[test]
+    fn test_array_members() {
+        let mut ir = lowered_ir(
+            "
+        func total(values: Array<Number>) -> Number {
+            values.push(values.length)
+            return values[0]
+        }
+        let numbers = [1, 2, 3]
+        let labels: Array<String> = numbers.map((n) { return \"\\(n)\" })
+        let even: Array<Number> = numbers.filter((n) { return n % 2 == 0 })
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_array_member_mismatch() {
+        let mut ir = lowered_ir(
+            "
+        let numbers = [1, 2, 3]
+        numbers.push(\"four\")
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ConflictingTypes(_, _))
+        );
+
+        let mut ir = lowered_ir(
+            "
+        let numbers = [1, 2, 3]
+        let labels: Array<String> = numbers.map((n) { return n * 2 })
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ConflictingTypes(_, _))
+        );
+    }
+
+    #[test]
+    fn test_unknown_array_member() {
+        let mut ir = lowered_ir(
+            "
+        let numbers = [1, 2, 3]
+        let size = numbers.size
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            resolve_symbols(&mut ir),
+            Err(SymbolResolutionError::TypeEval(
+                TypeEvalError::UnknownIdent(_)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_array_type_arg_count() {
+        let mut ir = lowered_ir("let values: Array<Number, String> = []").unwrap();
+
+        assert_matches!(
+            resolve_symbols(&mut ir),
+            Err(SymbolResolutionError::TypeArgCountMismatch { .. })
+        );
+    }
+}
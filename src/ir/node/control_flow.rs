@@ -1,23 +1,51 @@
 use crate::{
-    ir::{ast_lowering::IrLowerable, late_init::LateInit},
+    ir::{ast_lowering::IrLowerable, context::IrCtx, late_init::LateInit},
     parser::Span,
 };
 
 use super::{
     expression::Expr,
-    identifier::{Ident, IdentParent},
+    identifier::{Ident, IdentKey, IdentParent, IdentValue},
+    operator::{BinaryOperator, UnaryOperator},
+    pattern_match::EnumPattern,
     statement::StmtBlock,
+    type_signature::{TypeSignature, TypeSignatureParent},
     IrAlloc, NodeRef,
 };
 
 #[derive(Debug, Clone)]
 pub struct IfStmt<'a> {
+    /// The value matched against `pattern` in the `if let` form
     pub condition: NodeRef<'a, Expr<'a>>,
+    pub pattern: Option<NodeRef<'a, EnumPattern<'a>>>,
     pub span: Span<'a>,
     pub body: NodeRef<'a, StmtBlock<'a>>,
     pub else_body: Option<NodeRef<'a, StmtBlock<'a>>>,
     pub body_scope_ident: LateInit<Ident<'a>>,
     pub else_scope_ident: LateInit<Ident<'a>>,
+    /// Whether the condition is unwrapped into the first narrowed binding
+    pub unwraps_condition: bool,
+    pub narrowed: Vec<NarrowedBinding<'a>>,
+}
+
+/// An optional value that is available as its unwrapped type under the given name
+#[derive(Debug, Clone)]
+pub struct NarrowedBinding<'a> {
+    pub name: Ident<'a>,
+    pub value: NodeRef<'a, Expr<'a>>,
+    pub type_sig: LateInit<TypeSignature<'a>>,
+    pub reach: NarrowingReach,
+    /// Whether the value is a `var`, which could be reassigned while it is narrowed,
+    /// such that its name keeps referring to the optional value
+    pub value_is_mutable: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NarrowingReach {
+    Branch(IfBranchBody),
+    /// The statements following the if statement, when one of the branches exits early
+    AfterIf,
+    RightOperand,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -40,16 +68,20 @@ impl<'a> IrLowerable<'a> for crate::ast::node::control_flow::IfStmt<'a> {
 
     fn ir_lower(self, ctx: &mut crate::ir::context::IrCtx<'a>) -> NodeRef<'a, Self::IrType> {
         let condition = self.condition.ir_lower(ctx);
+        let pattern = self.pattern.map(|pattern| pattern.ir_lower(ctx));
         let body = self.body.ir_lower(ctx);
         let else_body = self.else_body.map(|bdy| bdy.ir_lower(ctx));
 
         let if_branch = IfStmt {
             condition,
+            pattern,
             body,
             else_body,
             span: self.span,
             body_scope_ident: LateInit::empty(),
             else_scope_ident: LateInit::empty(),
+            unwraps_condition: self.binding.is_some(),
+            narrowed: Vec::new(),
         }
         .allocate(ctx);
 
@@ -61,6 +93,247 @@ impl<'a> IrLowerable<'a> for crate::ast::node::control_flow::IfStmt<'a> {
             .make_anon_ident(IdentParent::IfBranchScope(if_branch))
             .into();
 
+        let mut narrowed = Vec::new();
+        if let Some(binding) = self.binding {
+            narrowed.push((
+                binding,
+                condition,
+                NarrowingReach::Branch(IfBranchBody::MainBody),
+            ));
+        } else if pattern.is_none() {
+            let mut narrow = |values: Vec<NodeRef<'a, Expr<'a>>>, reach| {
+                for value in values {
+                    if let IdentValue::Unresolved(name) = &ctx[value.unwrap_ident(ctx)] {
+                        narrowed.push((name.clone(), value, reach));
+                    }
+                }
+            };
+
+            narrow(
+                nil_checked_values(ctx, condition, true),
+                NarrowingReach::Branch(IfBranchBody::MainBody),
+            );
+            narrow(
+                nil_checked_values(ctx, condition, false),
+                NarrowingReach::Branch(IfBranchBody::ElseBody),
+            );
+
+            let body_diverges = body.diverges(ctx);
+            let else_diverges = else_body.is_some_and(|else_body| else_body.diverges(ctx));
+            if body_diverges && !else_diverges {
+                narrow(
+                    nil_checked_values(ctx, condition, false),
+                    NarrowingReach::AfterIf,
+                );
+            } else if else_diverges && !body_diverges {
+                narrow(
+                    nil_checked_values(ctx, condition, true),
+                    NarrowingReach::AfterIf,
+                );
+            }
+        }
+
+        for (i, (name, value, reach)) in narrowed.into_iter().enumerate() {
+            let binding = NarrowedBinding {
+                name: ctx.make_ident(name, IdentParent::NarrowedBindingName(if_branch, i)),
+                value,
+                type_sig: ctx
+                    .make_type_var(TypeSignatureParent::NarrowedBinding(if_branch, i))
+                    .into(),
+                reach,
+                value_is_mutable: false,
+            };
+            ctx[if_branch].narrowed.push(binding);
+        }
+
         if_branch
     }
 }
+
+/// The identifiers that are known not to be nil when `condition` evaluates to `outcome`
+pub fn nil_checked_values<'a>(
+    ctx: &IrCtx<'a>,
+    condition: NodeRef<'a, Expr<'a>>,
+    outcome: bool,
+) -> Vec<NodeRef<'a, Expr<'a>>> {
+    match &ctx[condition] {
+        Expr::BinaryOperation(bin_op) => {
+            let (lhs, rhs) = (ctx[*bin_op].lhs, ctx[*bin_op].rhs);
+
+            match (ctx[*bin_op].operator, outcome) {
+                (BinaryOperator::NotEq, true) | (BinaryOperator::Eq, false) => {
+                    match (&ctx[lhs], &ctx[rhs]) {
+                        (Expr::Identifier(_, _), Expr::NilLiteral(_, _)) => vec![lhs],
+                        (Expr::NilLiteral(_, _), Expr::Identifier(_, _)) => vec![rhs],
+                        _ => Vec::new(),
+                    }
+                }
+                (BinaryOperator::And, true) | (BinaryOperator::Or, false) => {
+                    let mut values = nil_checked_values(ctx, lhs, outcome);
+                    for value in nil_checked_values(ctx, rhs, outcome) {
+                        if !values.iter().any(|existing| {
+                            IdentKey::idents_eq(
+                                ctx,
+                                existing.unwrap_ident(ctx),
+                                value.unwrap_ident(ctx),
+                            )
+                        }) {
+                            values.push(value);
+                        }
+                    }
+                    values
+                }
+                _ => Vec::new(),
+            }
+        }
+        Expr::UnaryOperation(unary_op) if ctx[*unary_op].operator == UnaryOperator::Not => {
+            nil_checked_values(ctx, ctx[*unary_op].operand, !outcome)
+        }
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::assert_matches;
+
+    use crate::{
+        ir::test_utils::utils::{lowered_ir, type_check},
+        type_checker::TypeCheckerError,
+    };
+
+    #[test]
+    fn test_narrow_not_nil() {
+        let mut ir = lowered_ir(
+            "
+        struct Car { let model: String }
+        func describe(car: Car?, owner: String?) -> String {
+            if car != nil && owner != nil {
+                return owner + car.model
+            }
+            if car == nil {
+                return \"no car\"
+            } else {
+                return car.model
+            }
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_narrow_if_let() {
+        let mut ir = lowered_ir(
+            "
+        struct Car { let model: String }
+        func describe(car: Car?, spare: Car?) -> String {
+            if let car = car ?? spare {
+                return car.model
+            }
+            return \"no car\"
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_narrow_early_return() {
+        let mut ir = lowered_ir(
+            "
+        struct Car { let model: String }
+        func describe(car: Car?) -> String {
+            if car == nil {
+                return \"no car\"
+            }
+            return car.model
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_narrow_right_operand() {
+        let mut ir = lowered_ir(
+            "
+        struct Car { let model: String }
+        func isNamed(name: String) -> Boolean { return name != \"\" }
+        func describe(car: Car?, owner: String?) -> Boolean {
+            let named = car != nil && isNamed(car.model)
+            return owner == nil || isNamed(owner)
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_not_narrowed() {
+        for code in [
+            "func f(x: Number?) -> Number { if x == nil { return x }; return 0 }",
+            "func f(x: Number?) -> Number { if x != nil { return 0 }; return x }",
+            "func f(x: Number?) -> Number { if x != nil || true { return x }; return 0 }",
+            "func f(x: Number?) -> Number { if x == nil { let y = 1 }; return x }",
+            "func f(x: Number) -> Number { if let y = x { return y }; return 0 }",
+            "func g(x: Number) -> Boolean { return true }
+            func f(x: Number?) -> Boolean { return x == nil && g(x) }",
+            "func g(x: Number) -> Boolean { return true }
+            func f(x: Number?) -> Boolean { return x != nil || g(x) }",
+        ] {
+            let mut ir = lowered_ir(code).unwrap();
+            assert_matches!(
+                type_check(&mut ir).1,
+                Err(TypeCheckerError::ConflictingTypes(_, _)),
+                "{code}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_var_not_narrowed() {
+        for code in [
+            "var x: Number? = 1
+            func f() -> Number { if x != nil { return x }; return 0 }",
+            "func f(y: Number?) -> Number { var x = y; if x == nil { return 0 }; return x }",
+            "func g(x: Number) -> Boolean { return true }
+            func f(y: Number?) -> Boolean { var x = y; return x != nil && g(x) }",
+        ] {
+            let mut ir = lowered_ir(code).unwrap();
+            assert_matches!(
+                type_check(&mut ir).1,
+                Err(TypeCheckerError::ConflictingTypes(_, _)),
+                "{code}"
+            );
+        }
+
+        let mut ir = lowered_ir(
+            "
+        struct Car { let model: String }
+        func describe(car: Car?) -> String {
+            var current = car
+            if current != nil {
+                current = nil
+            }
+            if current == nil {
+                return \"no car\"
+            }
+            current = nil
+            return \"car\"
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+}
@@ -0,0 +1,420 @@
+use crate::{
+    error_message::error_formatter::Spanned,
+    ir::{context::IrCtx, late_init::LateInit},
+    parser::Span,
+    symbols::symbol_table::symbol_table_zipper::SymbolTableZipper,
+};
+
+use super::{
+    expression::Expr,
+    type_signature::{
+        BuiltinType, TypeEvalError, TypeSignature, TypeSignatureContext, TypeSignatureParent,
+        TypeSignatureValue, Typed,
+    },
+    NodeRef,
+};
+
+#[derive(Debug)]
+pub struct Dictionary<'a> {
+    pub entries: Vec<(NodeRef<'a, Expr<'a>>, NodeRef<'a, Expr<'a>>)>,
+    pub key_type: LateInit<TypeSignature<'a>>,
+    pub value_type: LateInit<TypeSignature<'a>>,
+    pub span: Span<'a>,
+}
+
+/// The value of a dictionary looked up by its key, e.g. `dictionary["key"]`,
+/// which is absent if the key is not in the dictionary
+#[derive(Debug)]
+pub struct DictionaryIndex<'a> {
+    pub dictionary: NodeRef<'a, Expr<'a>>,
+    pub key: NodeRef<'a, Expr<'a>>,
+    pub span: Span<'a>,
+}
+
+/// The builtin members of a dictionary, which map directly to the JavaScript `Map`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DictionaryMember {
+    Size,
+    Has,
+    Set,
+    Delete,
+    Keys,
+    Values,
+    Entries,
+}
+
+impl DictionaryMember {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "size" => Some(DictionaryMember::Size),
+            "has" => Some(DictionaryMember::Has),
+            "set" => Some(DictionaryMember::Set),
+            "delete" => Some(DictionaryMember::Delete),
+            "keys" => Some(DictionaryMember::Keys),
+            "values" => Some(DictionaryMember::Values),
+            "entries" => Some(DictionaryMember::Entries),
+            _ => None,
+        }
+    }
+
+    pub const fn name(&self) -> &'static str {
+        match self {
+            DictionaryMember::Size => "size",
+            DictionaryMember::Has => "has",
+            DictionaryMember::Set => "set",
+            DictionaryMember::Delete => "delete",
+            DictionaryMember::Keys => "keys",
+            DictionaryMember::Values => "values",
+            DictionaryMember::Entries => "entries",
+        }
+    }
+
+    pub const fn is_method(&self) -> bool {
+        matches!(
+            self,
+            DictionaryMember::Has | DictionaryMember::Set | DictionaryMember::Delete
+        )
+    }
+
+    /// Whether the member is an iterator in JavaScript, which is collected into an array
+    pub const fn is_iterator(&self) -> bool {
+        matches!(
+            self,
+            DictionaryMember::Keys | DictionaryMember::Values | DictionaryMember::Entries
+        )
+    }
+}
+
+#[derive(Debug)]
+pub struct DictionaryMemberAccess<'a> {
+    pub dictionary: NodeRef<'a, Expr<'a>>,
+    pub member: DictionaryMember,
+    pub span: Span<'a>,
+}
+
+impl<'a> Spanned<'a> for NodeRef<'a, Dictionary<'a>> {
+    fn get_span(&self, ctx: &IrCtx<'a>) -> Option<Span<'a>> {
+        Some(ctx[*self].span.clone())
+    }
+}
+
+impl<'a> Spanned<'a> for NodeRef<'a, DictionaryIndex<'a>> {
+    fn get_span(&self, ctx: &IrCtx<'a>) -> Option<Span<'a>> {
+        Some(ctx[*self].span.clone())
+    }
+}
+
+impl<'a> Spanned<'a> for NodeRef<'a, DictionaryMemberAccess<'a>> {
+    fn get_span(&self, ctx: &IrCtx<'a>) -> Option<Span<'a>> {
+        Some(ctx[*self].span.clone())
+    }
+}
+
+impl<'a> Typed<'a> for NodeRef<'a, Dictionary<'a>> {
+    fn eval_type(
+        &self,
+        symbols: &mut SymbolTableZipper<'a>,
+        ctx: &mut IrCtx<'a>,
+    ) -> Result<TypeSignature<'a>, TypeEvalError<'a>> {
+        // like arrays, the types are already known before inference when there are entries
+        let (key, value) = match ctx[*self].entries.first().copied() {
+            Some((key, value)) => (key.eval_type(symbols, ctx)?, value.eval_type(symbols, ctx)?),
+            None => (ctx[*self].key_type.cloned(), ctx[*self].value_type.cloned()),
+        };
+
+        Ok(ctx.get_type_sig(
+            TypeSignatureValue::Dictionary { key, value },
+            TypeSignatureContext {
+                parent: TypeSignatureParent::Dictionary(*self),
+                type_span: None,
+            }
+            .alloc(),
+        ))
+    }
+}
+
+impl<'a> NodeRef<'a, DictionaryIndex<'a>> {
+    /// The type of the values of the dictionary, which an entry is assigned
+    pub fn value_type(
+        &self,
+        symbols: &mut SymbolTableZipper<'a>,
+        ctx: &mut IrCtx<'a>,
+    ) -> Result<TypeSignature<'a>, TypeEvalError<'a>> {
+        let dictionary = ctx[*self].dictionary;
+        let dictionary_type = dictionary.eval_type(symbols, ctx)?;
+        let (_, value) = dictionary_type
+            .dictionary_types(ctx)
+            .ok_or_else(|| TypeEvalError::AccessNonDictionary(dictionary_type.clone()))?;
+
+        Ok(value)
+    }
+}
+
+impl<'a> Typed<'a> for NodeRef<'a, DictionaryIndex<'a>> {
+    fn eval_type(
+        &self,
+        symbols: &mut SymbolTableZipper<'a>,
+        ctx: &mut IrCtx<'a>,
+    ) -> Result<TypeSignature<'a>, TypeEvalError<'a>> {
+        let value = self.value_type(symbols, ctx)?;
+
+        Ok(ctx.get_type_sig(
+            TypeSignatureValue::Optional(value),
+            TypeSignatureContext {
+                parent: TypeSignatureParent::DictionaryIndex(*self),
+                type_span: None,
+            }
+            .alloc(),
+        ))
+    }
+}
+
+impl<'a> Typed<'a> for NodeRef<'a, DictionaryMemberAccess<'a>> {
+    fn eval_type(
+        &self,
+        symbols: &mut SymbolTableZipper<'a>,
+        ctx: &mut IrCtx<'a>,
+    ) -> Result<TypeSignature<'a>, TypeEvalError<'a>> {
+        let dictionary = ctx[*self].dictionary;
+        let dictionary_type = dictionary.eval_type(symbols, ctx)?;
+        let (key, value) = dictionary_type
+            .dictionary_types(ctx)
+            .ok_or_else(|| TypeEvalError::AccessNonDictionary(dictionary_type.clone()))?;
+
+        let type_ctx = TypeSignatureContext {
+            parent: TypeSignatureParent::DictionaryMemberAccess(*self),
+            type_span: None,
+        }
+        .alloc();
+        let builtin = |ctx: &mut IrCtx<'a>, builtin| {
+            ctx.get_type_sig(TypeSignatureValue::Builtin(builtin), type_ctx.clone())
+        };
+        let func =
+            |ctx: &mut IrCtx<'a>, args: Vec<TypeSignature<'a>>, return_type: TypeSignature<'a>| {
+                ctx.get_type_sig(
+                    TypeSignatureValue::Function {
+                        args: args.into(),
+                        return_type: return_type.into(),
+                        throws: None,
+                    },
+                    type_ctx.clone(),
+                )
+            };
+
+        Ok(match ctx[*self].member {
+            // `size` -> Number
+            DictionaryMember::Size => builtin(ctx, BuiltinType::Number),
+            // `has` and `delete` -> (K) -> Boolean
+            DictionaryMember::Has | DictionaryMember::Delete => {
+                let boolean = builtin(ctx, BuiltinType::Boolean);
+                func(ctx, vec![key], boolean)
+            }
+            // `set` -> (K, V) -> Void
+            DictionaryMember::Set => {
+                let void = builtin(ctx, BuiltinType::Void);
+                func(ctx, vec![key, value], void)
+            }
+            // `keys` -> Array<K>
+            DictionaryMember::Keys => ctx.get_array_type_sig(key, type_ctx.clone()),
+            // `values` -> Array<V>
+            DictionaryMember::Values => ctx.get_array_type_sig(value, type_ctx.clone()),
+            // `entries` -> Array<(K, V)>
+            DictionaryMember::Entries => {
+                let entry = ctx.get_type_sig(
+                    TypeSignatureValue::Tuple(vec![key, value].into()),
+                    type_ctx.clone(),
+                );
+                ctx.get_array_type_sig(entry, type_ctx.clone())
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::assert_matches;
+
+    use crate::{
+        ir::{
+            node::type_signature::TypeEvalError,
+            test_utils::utils::{lowered_ir, resolve_symbols, type_check},
+        },
+        symbols::symbol_resolver::SymbolResolutionError,
+        type_checker::TypeCheckerError,
+    };
+
+    #[test]
+    fn test_dictionary_types() {
+        let mut ir = lowered_ir(
+            "
+        let ages = [\"alice\": 31, \"bob\": 27]
+        let empty: [String: Number] = [:]
+        let copy: [String: Number] = ages
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_dictionary_mixed_entries() {
+        let mut ir = lowered_ir("let ages = [\"alice\": 31, \"bob\": \"27\"]").unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ConflictingTypes(_, _))
+        );
+
+        let mut ir = lowered_ir("let ages: [String: Number] = [1: 31]").unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ConflictingTypes(_, _))
+        );
+    }
+
+    #[test]
+    fn test_dictionary_index_is_optional() {
+        let mut ir = lowered_ir(
+            "
+        let ages = [\"alice\": 31]
+        let alice = ages[\"alice\"]
+        let age: Number = alice
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ConflictingTypes(_, _))
+        );
+    }
+
+    #[test]
+    fn test_dictionary_index_key_mismatch() {
+        let mut ir = lowered_ir(
+            "
+        let ages = [\"alice\": 31]
+        let alice = ages[0]
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ConflictingTypes(_, _))
+        );
+    }
+
+    #[test]
+    fn test_dictionary_members() {
+        let mut ir = lowered_ir(
+            "
+        func describe(ages: [String: Number]) -> Number {
+            ages.set(\"carol\", 40)
+            let known: Boolean = ages.has(\"bob\") && ages.delete(\"bob\")
+            let names: Array<String> = ages.keys
+            let values: Array<Number> = ages.values
+            let first: (String, Number) = ages.entries[0]
+            return ages.size
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_dictionary_member_mismatch() {
+        let mut ir = lowered_ir(
+            "
+        let ages = [\"alice\": 31]
+        ages.set(\"bob\", \"27\")
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ConflictingTypes(_, _))
+        );
+    }
+
+    #[test]
+    fn test_dictionary_entry_assignment() {
+        let mut ir = lowered_ir(
+            "
+        let ages = [\"alice\": 31]
+        ages[\"bob\"] = 27
+        var age = 0
+        age = ages[\"carol\"] = 40
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+
+        for code in [
+            "let ages = [\"alice\": 31]; ages[\"bob\"] = \"27\"",
+            "let ages = [\"alice\": 31]; ages[\"bob\"] = nil",
+        ] {
+            let mut ir = lowered_ir(code).unwrap();
+
+            assert_matches!(
+                type_check(&mut ir).1,
+                Err(TypeCheckerError::ConflictingTypes(_, _)),
+                "{code}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_dictionary_iteration() {
+        let mut ir = lowered_ir(
+            "
+        let ages = [\"alice\": 31, \"bob\": 27]
+        var total = 0
+        for entry in ages {
+            let name: String = entry.0
+            total = total + entry.1
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+
+        let mut ir = lowered_ir(
+            "
+        let ages = [\"alice\": 31]
+        for entry in ages { let age: String = entry.1 }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ConflictingTypes(_, _))
+        );
+    }
+
+    #[test]
+    fn test_unknown_dictionary_member() {
+        let mut ir = lowered_ir(
+            "
+        let ages = [\"alice\": 31]
+        let count = ages.length
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            resolve_symbols(&mut ir),
+            Err(SymbolResolutionError::TypeEval(
+                TypeEvalError::UnknownIdent(_)
+            ))
+        );
+    }
+}
@@ -7,6 +7,7 @@ use crate::{
 
 use super::{
     expression::Expr,
+    generics::{lower_type_params, TypeParam},
     identifier::{Ident, IdentKey, IdentParent, Identifiable},
     type_signature::{
         TypeEvalError, TypeSignature, TypeSignatureContext, TypeSignatureParent,
@@ -18,6 +19,7 @@ use super::{
 #[derive(Debug, Clone)]
 pub struct Enum<'a> {
     pub name: LateInit<Ident<'a>>,
+    pub type_params: Vec<NodeRef<'a, TypeParam<'a>>>,
     pub values: Vec<NodeRef<'a, EnumValue<'a>>>,
     pub type_sig: LateInit<TypeSignature<'a>>,
 }
@@ -49,6 +51,8 @@ pub struct EnumInit<'a> {
     pub enum_value: Ident<'a>,
     pub items: Vec<NodeRef<'a, Expr<'a>>>,
     pub items_span: Option<Span<'a>>,
+    /// The type arguments of a generic enum, empty otherwise
+    pub type_args: Vec<TypeSignature<'a>>,
     pub span: Span<'a>,
 }
 
@@ -62,6 +66,23 @@ impl<'a> NodeRef<'a, EnumInit<'a>> {
             .lookup(ctx, ctx[*self].enum_name)
             .map(|sym| sym.unwrap_enum(ctx))
     }
+
+    /// Sets the type arguments of the initialized enum if it is generic,
+    /// the arguments are inferred if none are given
+    pub fn instantiate(
+        &self,
+        ctx: &mut IrCtx<'a>,
+        enm: NodeRef<'a, Enum<'a>>,
+        type_args: Vec<TypeSignature<'a>>,
+    ) {
+        ctx[*self].type_args = if type_args.is_empty() {
+            (0..ctx[enm].type_params.len())
+                .map(|_| ctx.make_type_var(TypeSignatureParent::EnumInit(*self)))
+                .collect()
+        } else {
+            type_args
+        };
+    }
 }
 
 impl<'a> Spanned<'a> for NodeRef<'a, EnumInit<'a>> {
@@ -142,14 +163,28 @@ impl<'a> Typed<'a> for NodeRef<'a, EnumInit<'a>> {
         ctx: &mut IrCtx<'a>,
     ) -> Result<TypeSignature<'a>, TypeEvalError<'a>> {
         let enm_name = ctx[*self].enum_name;
+        let type_ctx = TypeSignatureContext {
+            parent: TypeSignatureParent::EnumInit(*self),
+            type_span: None,
+        }
+        .alloc();
 
-        Ok(ctx.get_type_sig(
+        let enm_type = ctx.get_type_sig(
             TypeSignatureValue::Enum { name: enm_name },
-            TypeSignatureContext {
-                parent: TypeSignatureParent::EnumInit(*self),
-                type_span: None,
-            }
-            .alloc(),
+            type_ctx.clone(),
+        );
+
+        let type_args = ctx[*self].type_args.clone();
+        if type_args.is_empty() {
+            return Ok(enm_type);
+        }
+
+        Ok(ctx.get_type_sig(
+            TypeSignatureValue::Instance {
+                base: enm_type,
+                args: type_args,
+            },
+            type_ctx,
         ))
     }
 }
@@ -164,8 +199,11 @@ impl<'a> IrLowerable<'a> for crate::ast::node::enumeration::Enum<'a> {
             .map(|val| val.ir_lower(ctx))
             .collect();
 
+        let type_params = lower_type_params(ctx, self.type_params);
+
         let enm_decl = Enum {
             name: LateInit::empty(),
+            type_params,
             values,
             type_sig: LateInit::empty(),
         }
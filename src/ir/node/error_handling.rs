@@ -0,0 +1,148 @@
+use crate::{
+    error_message::error_formatter::Spanned,
+    ir::{ast_lowering::IrLowerable, context::IrCtx, late_init::LateInit},
+    parser::Span,
+    symbols::symbol_table::symbol_table_zipper::SymbolTableZipper,
+};
+
+use super::{
+    expression::Expr,
+    function::FunctionCall,
+    identifier::{Ident, IdentParent},
+    statement::StmtBlock,
+    type_signature::{TypeEvalError, TypeSignature, TypeSignatureParent, Typed},
+    IrAlloc, NodeRef,
+};
+
+/// A `do` body whose thrown errors are handled by the `catch` body
+#[derive(Debug, Clone)]
+pub struct DoCatch<'a> {
+    pub body: NodeRef<'a, StmtBlock<'a>>,
+    pub error_name: LateInit<Ident<'a>>,
+    pub error_type: LateInit<TypeSignature<'a>>,
+    pub catch_body: NodeRef<'a, StmtBlock<'a>>,
+    pub body_scope_ident: LateInit<Ident<'a>>,
+    pub catch_scope_ident: LateInit<Ident<'a>>,
+    pub span: Span<'a>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DoCatchBody {
+    DoBody,
+    CatchBody,
+}
+
+impl<'a> DoCatch<'a> {
+    pub fn body_ident(&self, body: DoCatchBody) -> Ident<'a> {
+        match body {
+            DoCatchBody::DoBody => *self.body_scope_ident,
+            DoCatchBody::CatchBody => *self.catch_scope_ident,
+        }
+    }
+}
+
+/// A call to a throwing function, propagating its errors to the enclosing throw target
+#[derive(Debug)]
+pub struct Try<'a> {
+    pub expr: NodeRef<'a, Expr<'a>>,
+    pub span: Span<'a>,
+}
+
+impl<'a> NodeRef<'a, Try<'a>> {
+    /// The tried call, which may be awaited
+    pub fn call(&self, ctx: &IrCtx<'a>) -> Option<NodeRef<'a, FunctionCall<'a>>> {
+        let expr = match ctx[ctx[*self].expr] {
+            Expr::Await(aw) => ctx[aw].expr,
+            _ => ctx[*self].expr,
+        };
+
+        match ctx[expr] {
+            Expr::FunctionCall(call) => Some(call),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> Spanned<'a> for NodeRef<'a, DoCatch<'a>> {
+    fn get_span(&self, ctx: &IrCtx<'a>) -> Option<Span<'a>> {
+        Some(ctx[*self].span.clone())
+    }
+}
+
+impl<'a> Spanned<'a> for NodeRef<'a, Try<'a>> {
+    fn get_span(&self, ctx: &IrCtx<'a>) -> Option<Span<'a>> {
+        Some(ctx[*self].span.clone())
+    }
+}
+
+impl<'a> Typed<'a> for NodeRef<'a, DoCatch<'a>> {
+    fn eval_type(
+        &self,
+        _symbols: &mut SymbolTableZipper<'a>,
+        ctx: &mut IrCtx<'a>,
+    ) -> Result<TypeSignature<'a>, TypeEvalError<'a>> {
+        Ok(ctx[*self].error_type.cloned())
+    }
+
+    fn specified_type(&self, ctx: &IrCtx<'a>) -> Option<TypeSignature<'a>> {
+        Some(ctx[*self].error_type.cloned())
+    }
+
+    fn specify_type(
+        &self,
+        ctx: &mut IrCtx<'a>,
+        new_type: TypeSignature<'a>,
+    ) -> Result<(), TypeEvalError<'a>> {
+        ctx[*self].error_type = new_type.into();
+        Ok(())
+    }
+}
+
+impl<'a> Typed<'a> for NodeRef<'a, Try<'a>> {
+    fn eval_type(
+        &self,
+        symbols: &mut SymbolTableZipper<'a>,
+        ctx: &mut IrCtx<'a>,
+    ) -> Result<TypeSignature<'a>, TypeEvalError<'a>> {
+        let expr = ctx[*self].expr;
+        expr.eval_type(symbols, ctx)
+    }
+}
+
+impl<'a> IrLowerable<'a> for crate::ast::node::error_handling::DoCatch<'a> {
+    type IrType = DoCatch<'a>;
+
+    fn ir_lower(self, ctx: &mut IrCtx<'a>) -> NodeRef<'a, Self::IrType> {
+        let body = (*self.body).ir_lower(ctx);
+        let catch_body = (*self.catch_body).ir_lower(ctx);
+
+        let do_catch = DoCatch {
+            body,
+            error_name: LateInit::empty(),
+            error_type: LateInit::empty(),
+            catch_body,
+            body_scope_ident: LateInit::empty(),
+            catch_scope_ident: LateInit::empty(),
+            span: self.span,
+        }
+        .allocate(ctx);
+
+        ctx[do_catch].error_name = ctx
+            .make_ident(self.error_name, IdentParent::CatchErrorName(do_catch))
+            .into();
+
+        ctx[do_catch].error_type = ctx
+            .make_type_var(TypeSignatureParent::DoCatch(do_catch))
+            .into();
+
+        ctx[do_catch].body_scope_ident = ctx
+            .make_anon_ident(IdentParent::DoCatchScope(do_catch))
+            .into();
+
+        ctx[do_catch].catch_scope_ident = ctx
+            .make_anon_ident(IdentParent::DoCatchScope(do_catch))
+            .into();
+
+        do_catch
+    }
+}
@@ -6,12 +6,20 @@ use crate::{
 };
 
 use super::{
+    array::{Array, ArrayMemberAccess, ArraySlice, Index},
     assignment::Assignment,
+    dictionary::{Dictionary, DictionaryIndex, DictionaryMemberAccess},
     enumeration::EnumInit,
+    error_handling::Try,
     escape_block::EscapeBlock,
-    function::{Function, FunctionCall},
+    function::{Await, Function, FunctionCall},
     identifier::{Ident, IdentParent},
     member_access::UnresolvedMemberAccess,
+    operator::{BinaryOperation, UnaryOperation},
+    pattern_match::Match,
+    record::RecordAccess,
+    static_access::StaticAccess,
+    string_interpolation::{StringInterpolation, StringPart, EXPRESSIBLE_TRAIT_NAME},
     structure::{StructAccess, StructInit},
     tuple::{Tuple, TupleAccess},
     type_signature::{
@@ -23,9 +31,12 @@ use super::{
 
 #[derive(Debug, Clone)]
 pub enum Expr<'a> {
-    StringLiteral(&'a str, Span<'a>),
+    StringLiteral(String, Span<'a>),
+    StringInterpolation(NodeRef<'a, StringInterpolation<'a>>),
     NumberLiteral(f64, Span<'a>),
     BoolLiteral(bool, Span<'a>),
+    /// The absence of a value, typed by the type of the value that is absent
+    NilLiteral(LateInit<TypeSignature<'a>>, Span<'a>),
     Function(NodeRef<'a, Function<'a>>),
     FunctionCall(NodeRef<'a, FunctionCall<'a>>),
     Identifier(LateInit<Ident<'a>>, Span<'a>),
@@ -37,6 +48,20 @@ pub enum Expr<'a> {
     Tuple(NodeRef<'a, Tuple<'a>>),
     EnumInit(NodeRef<'a, EnumInit<'a>>),
     UnresolvedMemberAccess(NodeRef<'a, UnresolvedMemberAccess<'a>>),
+    BinaryOperation(NodeRef<'a, BinaryOperation<'a>>),
+    UnaryOperation(NodeRef<'a, UnaryOperation<'a>>),
+    Match(NodeRef<'a, Match<'a>>),
+    Await(NodeRef<'a, Await<'a>>),
+    Try(NodeRef<'a, Try<'a>>),
+    Array(NodeRef<'a, Array<'a>>),
+    Index(NodeRef<'a, Index<'a>>),
+    ArrayMemberAccess(NodeRef<'a, ArrayMemberAccess<'a>>),
+    ArraySlice(NodeRef<'a, ArraySlice<'a>>),
+    Dictionary(NodeRef<'a, Dictionary<'a>>),
+    DictionaryIndex(NodeRef<'a, DictionaryIndex<'a>>),
+    DictionaryMemberAccess(NodeRef<'a, DictionaryMemberAccess<'a>>),
+    RecordAccess(NodeRef<'a, RecordAccess<'a>>),
+    StaticAccess(NodeRef<'a, StaticAccess<'a>>),
 }
 
 impl<'a> NodeRef<'a, Expr<'a>> {
@@ -62,7 +87,7 @@ impl<'a> Typed<'a> for NodeRef<'a, Expr<'a>> {
         ctx: &mut IrCtx<'a>,
     ) -> Result<TypeSignature<'a>, TypeEvalError<'a>> {
         match ctx[*self].clone() {
-            Expr::StringLiteral(_, _) => Ok(ctx.get_type_sig(
+            Expr::StringLiteral(_, _) | Expr::StringInterpolation(_) => Ok(ctx.get_type_sig(
                 TypeSignatureValue::Builtin(BuiltinType::String),
                 TypeSignatureContext {
                     parent: TypeSignatureParent::Expr(*self),
@@ -86,6 +111,14 @@ impl<'a> Typed<'a> for NodeRef<'a, Expr<'a>> {
                 }
                 .alloc(),
             )),
+            Expr::NilLiteral(value_type, _) => Ok(ctx.get_type_sig(
+                TypeSignatureValue::Optional(value_type.cloned()),
+                TypeSignatureContext {
+                    parent: TypeSignatureParent::Expr(*self),
+                    type_span: None,
+                }
+                .alloc(),
+            )),
             Expr::Function(func) => func.eval_type(symbols, ctx),
             Expr::FunctionCall(call) => call.eval_type(symbols, ctx),
             Expr::Identifier(ident, _) => {
@@ -106,14 +139,30 @@ impl<'a> Typed<'a> for NodeRef<'a, Expr<'a>> {
             Expr::TupleAccess(tup_acc) => tup_acc.eval_type(symbols, ctx),
             Expr::EnumInit(enm_init) => enm_init.eval_type(symbols, ctx),
             Expr::UnresolvedMemberAccess(mem_acc) => mem_acc.eval_type(symbols, ctx),
+            Expr::BinaryOperation(bin_op) => bin_op.eval_type(symbols, ctx),
+            Expr::UnaryOperation(unary_op) => unary_op.eval_type(symbols, ctx),
+            Expr::Match(mtch) => mtch.eval_type(symbols, ctx),
+            Expr::Await(aw) => aw.eval_type(symbols, ctx),
+            Expr::Try(tr) => tr.eval_type(symbols, ctx),
+            Expr::Array(arr) => arr.eval_type(symbols, ctx),
+            Expr::Index(idx) => idx.eval_type(symbols, ctx),
+            Expr::ArrayMemberAccess(arr_acc) => arr_acc.eval_type(symbols, ctx),
+            Expr::ArraySlice(slice) => slice.eval_type(symbols, ctx),
+            Expr::Dictionary(dict) => dict.eval_type(symbols, ctx),
+            Expr::DictionaryIndex(dict_idx) => dict_idx.eval_type(symbols, ctx),
+            Expr::DictionaryMemberAccess(dict_acc) => dict_acc.eval_type(symbols, ctx),
+            Expr::RecordAccess(rec_acc) => rec_acc.eval_type(symbols, ctx),
+            Expr::StaticAccess(st_acc) => st_acc.eval_type(symbols, ctx),
         }
     }
 
     fn specified_type(&self, ctx: &IrCtx<'a>) -> Option<TypeSignature<'a>> {
         match ctx[*self].clone() {
             Expr::StringLiteral(_, _) => None,
+            Expr::StringInterpolation(_) => None,
             Expr::NumberLiteral(_, _) => None,
             Expr::BoolLiteral(_, _) => None,
+            Expr::NilLiteral(_, _) => None,
             Expr::Function(func) => func.specified_type(ctx),
             Expr::FunctionCall(call) => call.specified_type(ctx),
             Expr::Identifier(_, _) => None,
@@ -125,6 +174,20 @@ impl<'a> Typed<'a> for NodeRef<'a, Expr<'a>> {
             Expr::TupleAccess(tup_acc) => tup_acc.specified_type(ctx),
             Expr::EnumInit(enm_init) => enm_init.specified_type(ctx),
             Expr::UnresolvedMemberAccess(mem_acc) => mem_acc.specified_type(ctx),
+            Expr::BinaryOperation(_) => None,
+            Expr::UnaryOperation(_) => None,
+            Expr::Match(mtch) => mtch.specified_type(ctx),
+            Expr::Await(aw) => aw.specified_type(ctx),
+            Expr::Try(_) => None,
+            Expr::Array(arr) => arr.specified_type(ctx),
+            Expr::Index(idx) => idx.specified_type(ctx),
+            Expr::ArrayMemberAccess(_) => None,
+            Expr::ArraySlice(_) => None,
+            Expr::Dictionary(_) => None,
+            Expr::DictionaryIndex(_) => None,
+            Expr::DictionaryMemberAccess(_) => None,
+            Expr::RecordAccess(_) => None,
+            Expr::StaticAccess(_) => None,
         }
     }
 
@@ -135,8 +198,10 @@ impl<'a> Typed<'a> for NodeRef<'a, Expr<'a>> {
     ) -> Result<(), TypeEvalError<'a>> {
         match ctx[*self].clone() {
             Expr::StringLiteral(_, _) => Ok(()),
+            Expr::StringInterpolation(_) => Ok(()),
             Expr::NumberLiteral(_, _) => Ok(()),
             Expr::BoolLiteral(_, _) => Ok(()),
+            Expr::NilLiteral(_, _) => Ok(()),
             Expr::Function(func) => func.specify_type(ctx, new_type),
             Expr::FunctionCall(call) => call.specify_type(ctx, new_type),
             Expr::Identifier(_, _) => Ok(()),
@@ -148,6 +213,20 @@ impl<'a> Typed<'a> for NodeRef<'a, Expr<'a>> {
             Expr::TupleAccess(tup_acc) => tup_acc.specify_type(ctx, new_type),
             Expr::EnumInit(enm_init) => enm_init.specify_type(ctx, new_type),
             Expr::UnresolvedMemberAccess(mem_acc) => mem_acc.specify_type(ctx, new_type),
+            Expr::BinaryOperation(_) => Ok(()),
+            Expr::UnaryOperation(_) => Ok(()),
+            Expr::Match(mtch) => mtch.specify_type(ctx, new_type),
+            Expr::Await(aw) => aw.specify_type(ctx, new_type),
+            Expr::Try(_) => Ok(()),
+            Expr::Array(arr) => arr.specify_type(ctx, new_type),
+            Expr::Index(idx) => idx.specify_type(ctx, new_type),
+            Expr::ArrayMemberAccess(_) => Ok(()),
+            Expr::ArraySlice(_) => Ok(()),
+            Expr::Dictionary(_) => Ok(()),
+            Expr::DictionaryIndex(_) => Ok(()),
+            Expr::DictionaryMemberAccess(_) => Ok(()),
+            Expr::RecordAccess(_) => Ok(()),
+            Expr::StaticAccess(_) => Ok(()),
         }
     }
 }
@@ -156,8 +235,10 @@ impl<'a> Spanned<'a> for NodeRef<'a, Expr<'a>> {
     fn get_span(&self, ctx: &IrCtx<'a>) -> Option<Span<'a>> {
         match ctx[*self].clone() {
             Expr::StringLiteral(_, span) => Some(span),
+            Expr::StringInterpolation(interp) => interp.get_span(ctx),
             Expr::NumberLiteral(_, span) => Some(span),
             Expr::BoolLiteral(_, span) => Some(span),
+            Expr::NilLiteral(_, span) => Some(span),
             Expr::Function(func) => func.get_span(ctx),
             Expr::FunctionCall(call) => call.get_span(ctx),
             Expr::Identifier(_, span) => Some(span),
@@ -169,6 +250,20 @@ impl<'a> Spanned<'a> for NodeRef<'a, Expr<'a>> {
             Expr::Tuple(tup) => tup.get_span(ctx),
             Expr::EnumInit(enm_init) => enm_init.get_span(ctx),
             Expr::UnresolvedMemberAccess(mem_acc) => Some(ctx[mem_acc].span.clone()),
+            Expr::BinaryOperation(bin_op) => bin_op.get_span(ctx),
+            Expr::UnaryOperation(unary_op) => unary_op.get_span(ctx),
+            Expr::Match(mtch) => mtch.get_span(ctx),
+            Expr::Await(aw) => aw.get_span(ctx),
+            Expr::Try(tr) => tr.get_span(ctx),
+            Expr::Array(arr) => arr.get_span(ctx),
+            Expr::Index(idx) => idx.get_span(ctx),
+            Expr::ArrayMemberAccess(arr_acc) => arr_acc.get_span(ctx),
+            Expr::ArraySlice(slice) => slice.get_span(ctx),
+            Expr::Dictionary(dict) => dict.get_span(ctx),
+            Expr::DictionaryIndex(dict_idx) => dict_idx.get_span(ctx),
+            Expr::DictionaryMemberAccess(dict_acc) => dict_acc.get_span(ctx),
+            Expr::RecordAccess(rec_acc) => rec_acc.get_span(ctx),
+            Expr::StaticAccess(st_acc) => st_acc.get_span(ctx),
         }
     }
 }
@@ -181,12 +276,54 @@ impl<'a> IrLowerable<'a> for crate::ast::node::expression::Expr<'a> {
             crate::ast::node::expression::ExprValue::StringLiteral(str) => {
                 Expr::StringLiteral(str, self.span).allocate(ctx)
             }
+            crate::ast::node::expression::ExprValue::StringInterpolation(interp) => {
+                let parts = interp
+                    .parts
+                    .into_iter()
+                    .map(|part| match part {
+                        crate::ast::node::string_interpolation::StringPart::Literal(str) => {
+                            StringPart::Literal(str)
+                        }
+                        crate::ast::node::string_interpolation::StringPart::Expr(expr) => {
+                            StringPart::Expr((*expr).ir_lower(ctx))
+                        }
+                    })
+                    .collect();
+
+                let interp = StringInterpolation {
+                    parts,
+                    expressible_trait: LateInit::empty(),
+                    span: self.span.clone(),
+                }
+                .allocate(ctx);
+
+                let expressible_trait = crate::ast::node::identifier::Ident {
+                    span: self.span,
+                    value: EXPRESSIBLE_TRAIT_NAME,
+                };
+                ctx[interp].expressible_trait = ctx
+                    .make_unresolved_ident(
+                        expressible_trait,
+                        IdentParent::StringInterpolationTrait(interp).into(),
+                    )
+                    .into();
+
+                Expr::StringInterpolation(interp).allocate(ctx)
+            }
             crate::ast::node::expression::ExprValue::NumberLiteral(num) => {
                 Expr::NumberLiteral(num, self.span).allocate(ctx)
             }
             crate::ast::node::expression::ExprValue::BoolLiteral(bool) => {
                 Expr::BoolLiteral(bool, self.span).allocate(ctx)
             }
+            crate::ast::node::expression::ExprValue::NilLiteral => {
+                let nil = Expr::NilLiteral(LateInit::empty(), self.span.clone()).allocate(ctx);
+
+                let value_type = ctx.make_type_var(TypeSignatureParent::Expr(nil));
+                ctx[nil] = Expr::NilLiteral(value_type.into(), self.span);
+
+                nil
+            }
             crate::ast::node::expression::ExprValue::Function(func) => {
                 Expr::Function(func.ir_lower(ctx)).allocate(ctx)
             }
@@ -230,6 +367,108 @@ impl<'a> IrLowerable<'a> for crate::ast::node::expression::Expr<'a> {
             crate::ast::node::expression::ExprValue::MemberAccess(mem_acc) => {
                 Expr::UnresolvedMemberAccess(mem_acc.ir_lower(ctx)).allocate(ctx)
             }
+            crate::ast::node::expression::ExprValue::BinaryOperation(bin_op) => {
+                let bin_op = BinaryOperation {
+                    lhs: bin_op.lhs.ir_lower(ctx),
+                    operator: bin_op.operator,
+                    operator_span: bin_op.operator_span,
+                    rhs: bin_op.rhs.ir_lower(ctx),
+                    overload: None,
+                    narrowed: Vec::new(),
+                    rhs_scope_ident: None,
+                    span: self.span,
+                }
+                .allocate(ctx);
+                bin_op.narrow_rhs(ctx);
+
+                Expr::BinaryOperation(bin_op).allocate(ctx)
+            }
+            crate::ast::node::expression::ExprValue::UnaryOperation(unary_op) => {
+                Expr::UnaryOperation(
+                    UnaryOperation {
+                        operator: unary_op.operator,
+                        operator_span: unary_op.operator_span,
+                        operand: unary_op.operand.ir_lower(ctx),
+                        span: self.span,
+                    }
+                    .allocate(ctx),
+                )
+                .allocate(ctx)
+            }
+            crate::ast::node::expression::ExprValue::Match(mtch) => {
+                Expr::Match(mtch.ir_lower(ctx)).allocate(ctx)
+            }
+            crate::ast::node::expression::ExprValue::Await(aw) => {
+                let aw = Await {
+                    expr: aw.expr.ir_lower(ctx),
+                    type_sig: LateInit::empty(),
+                    span: self.span,
+                }
+                .allocate(ctx);
+
+                ctx[aw].type_sig = ctx.make_type_var(TypeSignatureParent::Await(aw)).into();
+
+                Expr::Await(aw).allocate(ctx)
+            }
+            crate::ast::node::expression::ExprValue::Try(tr) => Expr::Try(
+                Try {
+                    expr: tr.expr.ir_lower(ctx),
+                    span: self.span,
+                }
+                .allocate(ctx),
+            )
+            .allocate(ctx),
+            crate::ast::node::expression::ExprValue::Array(arr) => {
+                let arr = Array {
+                    items: arr
+                        .items
+                        .into_iter()
+                        .map(|item| item.ir_lower(ctx))
+                        .collect(),
+                    elem_type: LateInit::empty(),
+                    span: self.span,
+                }
+                .allocate(ctx);
+
+                ctx[arr].elem_type = ctx.make_type_var(TypeSignatureParent::Array(arr)).into();
+
+                Expr::Array(arr).allocate(ctx)
+            }
+            crate::ast::node::expression::ExprValue::Index(idx) => {
+                let idx = Index {
+                    array: idx.array.ir_lower(ctx),
+                    index: idx.index.ir_lower(ctx),
+                    elem_type: LateInit::empty(),
+                    span: self.span,
+                }
+                .allocate(ctx);
+
+                ctx[idx].elem_type = ctx.make_type_var(TypeSignatureParent::Index(idx)).into();
+
+                Expr::Index(idx).allocate(ctx)
+            }
+            crate::ast::node::expression::ExprValue::Dictionary(dict) => {
+                let dict = Dictionary {
+                    entries: dict
+                        .entries
+                        .into_iter()
+                        .map(|(key, value)| (key.ir_lower(ctx), value.ir_lower(ctx)))
+                        .collect(),
+                    key_type: LateInit::empty(),
+                    value_type: LateInit::empty(),
+                    span: self.span,
+                }
+                .allocate(ctx);
+
+                ctx[dict].key_type = ctx
+                    .make_type_var(TypeSignatureParent::Dictionary(dict))
+                    .into();
+                ctx[dict].value_type = ctx
+                    .make_type_var(TypeSignatureParent::Dictionary(dict))
+                    .into();
+
+                Expr::Dictionary(dict).allocate(ctx)
+            }
         }
     }
 }
@@ -0,0 +1,313 @@
+use crate::{
+    error_message::error_formatter::Spanned,
+    ir::{ast_lowering::IrLowerable, context::IrCtx, late_init::LateInit},
+    parser::Span,
+    symbols::symbol_table::{symbol_table_zipper::SymbolTableZipper, SymbolValueItem},
+};
+
+use super::{
+    function::Function,
+    identifier::{Ident, IdentKey, IdentParent},
+    operator::BinaryOperator,
+    static_access::StaticMember,
+    structure::StructAttr,
+    traits::Trait,
+    type_signature::{BuiltinType, TypeEvalError, TypeSignature, Typed},
+    IrAlloc, NodeRef,
+};
+
+/// An `extend` block adding methods to a type, optionally declaring conformance to a trait.
+/// The span only covers the head of the block, up to the opening bracket
+#[derive(Debug)]
+pub struct Extension<'a> {
+    pub name: LateInit<Ident<'a>>,
+    pub trait_name: Option<Ident<'a>>,
+    pub methods: Vec<NodeRef<'a, Function<'a>>>,
+    pub static_methods: Vec<NodeRef<'a, Function<'a>>>,
+    /// The `static let` and `static var` declarations, which always have a value
+    pub static_attrs: Vec<NodeRef<'a, StructAttr<'a>>>,
+    /// The `init` declarations, which are anonymous functions told apart by their argument names
+    pub inits: Vec<NodeRef<'a, Function<'a>>>,
+    /// The `static func` declarations of operators, named by the symbol of the operator
+    pub operators: Vec<NodeRef<'a, Function<'a>>>,
+    /// The implicit `self` argument of the methods, referring to the extended value
+    pub self_name: LateInit<Ident<'a>>,
+    pub self_type_name: LateInit<Ident<'a>>,
+    pub scope_ident: LateInit<Ident<'a>>,
+    pub span: Span<'a>,
+}
+
+impl<'a> NodeRef<'a, Extension<'a>> {
+    pub fn lookup_method(
+        &self,
+        ident: Ident<'a>,
+        ctx: &IrCtx<'a>,
+    ) -> Option<NodeRef<'a, Function<'a>>> {
+        ctx[*self]
+            .methods
+            .iter()
+            .find(|method| IdentKey::idents_eq(ctx, *ctx[**method].name, ident))
+            .copied()
+    }
+
+    pub fn lookup_static_member(
+        &self,
+        ident: Ident<'a>,
+        ctx: &IrCtx<'a>,
+    ) -> Option<StaticMember<'a>> {
+        let method = ctx[*self]
+            .static_methods
+            .iter()
+            .find(|method| IdentKey::idents_eq(ctx, *ctx[**method].name, ident))
+            .map(|method| StaticMember::Method(*method));
+
+        method.or_else(|| {
+            ctx[*self]
+                .static_attrs
+                .iter()
+                .find(|attr| IdentKey::idents_eq(ctx, *ctx[**attr].name, ident))
+                .map(|attr| StaticMember::Attr(*attr))
+        })
+    }
+
+    pub fn lookup_init(
+        &self,
+        labels: &[Option<&'a str>],
+        ctx: &IrCtx<'a>,
+    ) -> Option<NodeRef<'a, Function<'a>>> {
+        ctx[*self]
+            .inits
+            .iter()
+            .find(|init| {
+                let arg_names = init.arg_names(ctx);
+                arg_names.len() == labels.len()
+                    && arg_names
+                        .iter()
+                        .zip(labels)
+                        .all(|(name, label)| *label == Some(*name))
+            })
+            .copied()
+    }
+
+    pub fn lookup_operator(
+        &self,
+        operator: BinaryOperator,
+        ctx: &IrCtx<'a>,
+    ) -> Option<NodeRef<'a, Function<'a>>> {
+        ctx[*self]
+            .operators
+            .iter()
+            .find(|op| op.overloaded_operator(ctx) == Some(operator))
+            .copied()
+    }
+
+    pub fn conformed_trait(
+        &self,
+        ctx: &IrCtx<'a>,
+        symbols: &SymbolTableZipper<'a>,
+    ) -> Option<NodeRef<'a, Trait<'a>>> {
+        match ctx[symbols.lookup(ctx, ctx[*self].trait_name?)?] {
+            SymbolValueItem::TraitDecl(tr) => Some(tr),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> Typed<'a> for NodeRef<'a, Extension<'a>> {
+    /// The type of `self` inside of the methods
+    fn eval_type(
+        &self,
+        symbols: &mut SymbolTableZipper<'a>,
+        ctx: &mut IrCtx<'a>,
+    ) -> Result<TypeSignature<'a>, TypeEvalError<'a>> {
+        let name = *ctx[*self].name;
+        symbols
+            .lookup(ctx, name)
+            .ok_or(TypeEvalError::UnknownIdent(name))?
+            .eval_type(symbols, ctx)
+    }
+}
+
+impl<'a> Spanned<'a> for NodeRef<'a, Extension<'a>> {
+    fn get_span(&self, ctx: &IrCtx<'a>) -> Option<Span<'a>> {
+        Some(ctx[*self].span.clone())
+    }
+}
+
+impl<'a> IrLowerable<'a> for crate::ast::node::extension::Extension<'a> {
+    type IrType = Extension<'a>;
+
+    fn ir_lower(self, ctx: &mut IrCtx<'a>) -> NodeRef<'a, Self::IrType> {
+        let ext = Extension {
+            name: LateInit::empty(),
+            trait_name: None,
+            methods: self
+                .methods
+                .into_iter()
+                .map(|method| method.ir_lower(ctx))
+                .collect(),
+            static_methods: self
+                .static_methods
+                .into_iter()
+                .map(|method| method.ir_lower(ctx))
+                .collect(),
+            static_attrs: self
+                .static_attrs
+                .into_iter()
+                .map(|attr| attr.ir_lower(ctx))
+                .collect(),
+            inits: self
+                .inits
+                .into_iter()
+                .map(|init| init.ir_lower(ctx))
+                .collect(),
+            operators: self
+                .operators
+                .into_iter()
+                .map(|op| op.ir_lower(ctx))
+                .collect(),
+            self_name: LateInit::empty(),
+            self_type_name: LateInit::empty(),
+            scope_ident: LateInit::empty(),
+            span: self.span.clone(),
+        }
+        .allocate(ctx);
+
+        ctx[ext].name = ctx
+            .make_unresolved_ident(self.name, IdentParent::ExtensionName(ext).into())
+            .into();
+
+        ctx[ext].trait_name = self.trait_name.map(|trait_name| {
+            ctx.make_unresolved_ident(trait_name, IdentParent::ExtensionTraitName(ext).into())
+        });
+
+        let self_name = crate::ast::node::identifier::Ident {
+            span: self.span.clone(),
+            value: "self",
+        };
+        ctx[ext].self_name = ctx
+            .make_ident(self_name, IdentParent::ExtensionSelfName(ext))
+            .into();
+
+        let self_type_name = crate::ast::node::identifier::Ident {
+            span: self.span,
+            value: "Self",
+        };
+        ctx[ext].self_type_name = ctx
+            .make_ident(self_type_name, IdentParent::ExtensionSelfTypeName(ext))
+            .into();
+
+        ctx[ext].scope_ident = ctx.make_anon_ident(IdentParent::ExtensionScope(ext)).into();
+
+        // an init does not return a value, the instance is returned to the caller instead
+        for init in ctx[ext].inits.clone() {
+            ctx[init].return_type = ctx.get_builtin_type_sig(BuiltinType::Void).into();
+        }
+
+        ext
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::assert_matches;
+
+    use crate::{
+        ir::{
+            node::type_signature::TypeEvalError,
+            test_utils::utils::{lowered_ir, resolve_symbols, type_check},
+        },
+        symbols::symbol_resolver::SymbolResolutionError,
+        type_checker::{check_assignment::AssignmentError, TypeCheckerError},
+    };
+
+    #[test]
+    fn test_method_call() {
+        let mut ir = lowered_ir(
+            "
+        struct Car { let brand: String; var speed: Number }
+        extend Car {
+            func describe() -> String { return self.brand }
+            func accelerate(by: Number) { self.speed = self.speed + by }
+        }
+        let car = Car { brand: \"Volvo\", speed: 10 }
+        car.accelerate(5)
+        let description: String = car.describe()
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_method_declared_after_use() {
+        let mut ir = lowered_ir(
+            "
+        struct Car { let speed: Number }
+        func isFast(car: Car) -> Boolean { return car.isFast() }
+        extend Car {
+            func isFast() -> Boolean { return self.speed > 100 }
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_method_return_mismatch() {
+        let mut ir = lowered_ir(
+            "
+        struct Car { let speed: Number }
+        extend Car { func double() -> Number { return self.speed * 2 } }
+        let doubled: String = Car { speed: 1 }.double()
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ConflictingTypes(_, _))
+        );
+    }
+
+    #[test]
+    fn test_unknown_method() {
+        let mut ir = lowered_ir(
+            "
+        struct Car { let speed: Number }
+        let car = Car { speed: 1 }
+        car.fly()
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            resolve_symbols(&mut ir),
+            Err(SymbolResolutionError::TypeEval(
+                TypeEvalError::UnknownIdent(_)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_assign_self() {
+        let mut ir = lowered_ir(
+            "
+        struct Car { let speed: Number }
+        extend Car { func reset() { self = Car { speed: 0 } } }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::AssignmentError(
+                _,
+                AssignmentError::NotLValue(_)
+            ))
+        );
+    }
+}
@@ -10,6 +10,7 @@ use crate::{
 
 use super::{
     expression::Expr,
+    generics::{lower_type_params, TypeParam},
     identifier::{Ident, IdentParent, Identifiable},
     statement::StmtBlock,
     type_signature::{
@@ -21,13 +22,43 @@ use super::{
 #[derive(Debug)]
 pub struct Function<'a> {
     pub name: LateInit<Ident<'a>>,
+    pub type_params: Vec<NodeRef<'a, TypeParam<'a>>>,
     pub args: Vec<NodeRef<'a, FunctionArg<'a>>>,
     pub return_type: LateInit<TypeSignature<'a>>,
+    pub throws: Option<TypeSignature<'a>>,
     pub body: NodeRef<'a, StmtBlock<'a>>,
+    pub is_async: bool,
     pub span: Span<'a>,
 }
 
 impl<'a> NodeRef<'a, Function<'a>> {
+    /// The type of the value returned to the caller,
+    /// for an async function this is a promise of the declared return type
+    pub fn result_type(&self, ctx: &mut IrCtx<'a>) -> TypeSignature<'a> {
+        let return_type = ctx[*self].return_type.cloned();
+        if !ctx[*self].is_async {
+            return return_type;
+        }
+
+        let type_span = return_type.context.type_span.clone();
+        ctx.get_promise_type_sig(
+            return_type,
+            TypeSignatureContext {
+                parent: TypeSignatureParent::FunctionDefReturn(*self),
+                type_span,
+            }
+            .alloc(),
+        )
+    }
+
+    pub fn arg_names(&self, ctx: &IrCtx<'a>) -> Vec<&'a str> {
+        ctx[*self]
+            .args
+            .iter()
+            .map(|arg| ctx[*arg].name.value(ctx).unwrap())
+            .collect()
+    }
+
     pub fn calculate_type_sig(
         &self,
         ctx: &mut IrCtx<'a>,
@@ -41,11 +72,13 @@ impl<'a> NodeRef<'a, Function<'a>> {
             .map(|arg| &ctx[arg])
             .map(|arg| (*arg.type_sig).clone())
             .collect::<Vec<_>>();
+        let return_type = self.result_type(ctx);
 
         ctx.get_type_sig(
             TypeSignatureValue::Function {
                 args: arg_types.into(),
-                return_type: (*ctx[*self].return_type).clone().into(),
+                return_type: return_type.into(),
+                throws: ctx[*self].throws.clone(),
             },
             TypeSignatureContext {
                 parent: TypeSignatureParent::Function(*self),
@@ -73,7 +106,13 @@ pub struct FunctionArg<'a> {
 pub struct FunctionCall<'a> {
     pub func: NodeRef<'a, Expr<'a>>,
     pub args: Vec<NodeRef<'a, Expr<'a>>>,
+    /// The labels written before the arguments, which are only allowed for the init of a struct
+    pub arg_labels: Vec<Option<&'a str>>,
     pub args_span: Span<'a>,
+    /// The type of a generic callee with its type parameters replaced by type variables
+    pub instance: Option<TypeSignature<'a>>,
+    /// Whether the call is the operand of a `try`, propagating the errors of the callee
+    pub tried: bool,
 }
 
 impl<'a> Spanned<'a> for NodeRef<'a, FunctionCall<'a>> {
@@ -82,6 +121,21 @@ impl<'a> Spanned<'a> for NodeRef<'a, FunctionCall<'a>> {
     }
 }
 
+/// Only allowed inside of async functions
+#[derive(Debug)]
+pub struct Await<'a> {
+    pub expr: NodeRef<'a, Expr<'a>>,
+    /// The type of the value the awaited promise resolves to
+    pub type_sig: LateInit<TypeSignature<'a>>,
+    pub span: Span<'a>,
+}
+
+impl<'a> Spanned<'a> for NodeRef<'a, Await<'a>> {
+    fn get_span(&self, ctx: &IrCtx<'a>) -> Option<Span<'a>> {
+        Some(ctx[*self].span.clone())
+    }
+}
+
 impl<'a> Identifiable<'a> for Function<'a> {
     fn name(&self, _ctx: &IrCtx<'a>) -> Ident<'a> {
         *self.name
@@ -113,11 +167,13 @@ impl<'a> Typed<'a> for NodeRef<'a, Function<'a>> {
             .map(|arg| arg.eval_type(symbols, ctx))
             .collect::<Result<Vec<_>, _>>()?
             .into();
+        let return_type = self.result_type(ctx);
 
         Ok(ctx.get_type_sig(
             TypeSignatureValue::Function {
                 args,
-                return_type: (*ctx[*self].return_type).clone().into(),
+                return_type: return_type.into(),
+                throws: ctx[*self].throws.clone(),
             },
             TypeSignatureContext {
                 parent: TypeSignatureParent::Function(*self),
@@ -137,10 +193,12 @@ impl<'a> Typed<'a> for NodeRef<'a, Function<'a>> {
         ctx: &mut IrCtx<'a>,
         new_type: TypeSignature<'a>,
     ) -> Result<(), TypeEvalError<'a>> {
-        let (new_args, new_return_type) = match &ctx[&new_type] {
-            TypeSignatureValue::Function { args, return_type } => {
-                (args.clone(), (*return_type).clone())
-            }
+        let (new_args, new_return_type, new_throws) = match &ctx[&new_type] {
+            TypeSignatureValue::Function {
+                args,
+                return_type,
+                throws,
+            } => (args.clone(), (*return_type).clone(), throws.clone()),
             _ => unreachable!("specified type expected to be function"),
         };
 
@@ -157,7 +215,17 @@ impl<'a> Typed<'a> for NodeRef<'a, Function<'a>> {
             ctx[arg].type_sig = (*arg_type).clone().into();
         }
 
+        let mut new_return_type = new_return_type.cloned();
+
+        // the return statements of an async function return the promised value
+        if ctx[*self].is_async {
+            if let Some(promised) = new_return_type.promised_type(ctx) {
+                new_return_type = promised;
+            }
+        }
+
         ctx[*self].return_type = new_return_type.into();
+        ctx[*self].throws = new_throws;
 
         Ok(())
     }
@@ -186,20 +254,96 @@ impl<'a> Typed<'a> for NodeRef<'a, FunctionArg<'a>> {
     }
 }
 
-impl<'a> Typed<'a> for NodeRef<'a, FunctionCall<'a>> {
-    fn eval_type(
+impl<'a> NodeRef<'a, FunctionCall<'a>> {
+    /// The type of the called function, instantiated if the callee is generic
+    pub fn callee_type(
+        &self,
+        symbols: &mut SymbolTableZipper<'a>,
+        ctx: &mut IrCtx<'a>,
+    ) -> Result<TypeSignature<'a>, TypeEvalError<'a>> {
+        match ctx[*self].instance.clone() {
+            Some(instance) => Ok(instance),
+            None => self.func_type(symbols, ctx),
+        }
+    }
+
+    /// The type of the called expression, where a method accessed through optional chaining
+    /// is unwrapped, as it is only called when the struct is present
+    pub fn func_type(
         &self,
         symbols: &mut SymbolTableZipper<'a>,
         ctx: &mut IrCtx<'a>,
     ) -> Result<TypeSignature<'a>, TypeEvalError<'a>> {
         let type_sig = ctx[*self].func.clone().eval_type(symbols, ctx)?;
+
         match &ctx[&type_sig] {
-            TypeSignatureValue::Function {
-                args: _,
-                return_type,
-            } => Ok((**return_type).clone()),
-            _wrong_type => Err(TypeEvalError::CallNonFunction(*self, type_sig)),
+            TypeSignatureValue::Optional(func) if self.is_optional_chain(ctx) => Ok(func.clone()),
+            _ => Ok(type_sig),
+        }
+    }
+
+    fn is_optional_chain(&self, ctx: &IrCtx<'a>) -> bool {
+        matches!(ctx[ctx[*self].func], Expr::StructAccess(access) if ctx[access].optional)
+    }
+}
+
+impl<'a> Typed<'a> for NodeRef<'a, FunctionCall<'a>> {
+    fn eval_type(
+        &self,
+        symbols: &mut SymbolTableZipper<'a>,
+        ctx: &mut IrCtx<'a>,
+    ) -> Result<TypeSignature<'a>, TypeEvalError<'a>> {
+        let type_sig = self.callee_type(symbols, ctx)?;
+        let return_type = match &ctx[&type_sig] {
+            TypeSignatureValue::Function { return_type, .. } => (**return_type).clone(),
+            _wrong_type => return Err(TypeEvalError::CallNonFunction(*self, type_sig)),
+        };
+
+        // a method called through optional chaining results in `nil` when the struct is absent
+        if !self.is_optional_chain(ctx)
+            || matches!(ctx[&return_type], TypeSignatureValue::Optional(_))
+        {
+            return Ok(return_type);
         }
+
+        Ok(ctx.get_type_sig(
+            TypeSignatureValue::Optional(return_type),
+            TypeSignatureContext {
+                parent: TypeSignatureParent::Expr(ctx[*self].func),
+                type_span: None,
+            }
+            .alloc(),
+        ))
+    }
+}
+
+impl<'a> Typed<'a> for NodeRef<'a, Await<'a>> {
+    fn eval_type(
+        &self,
+        symbols: &mut SymbolTableZipper<'a>,
+        ctx: &mut IrCtx<'a>,
+    ) -> Result<TypeSignature<'a>, TypeEvalError<'a>> {
+        // the promised type is already known before inference when awaiting a typed promise,
+        // which is needed to resolve member accesses on the awaited value
+        let expr = ctx[*self].expr;
+        let expr_type = expr.eval_type(symbols, ctx)?;
+
+        Ok(expr_type
+            .promised_type(ctx)
+            .unwrap_or_else(|| ctx[*self].type_sig.cloned()))
+    }
+
+    fn specified_type(&self, ctx: &IrCtx<'a>) -> Option<TypeSignature<'a>> {
+        Some(ctx[*self].type_sig.cloned())
+    }
+
+    fn specify_type(
+        &self,
+        ctx: &mut IrCtx<'a>,
+        new_type: TypeSignature<'a>,
+    ) -> Result<(), TypeEvalError<'a>> {
+        ctx[*self].type_sig = new_type.into();
+        Ok(())
     }
 }
 
@@ -210,11 +354,16 @@ impl<'a> IrLowerable<'a> for crate::ast::node::function::Function<'a> {
         let ir_args: Vec<NodeRef<'a, FunctionArg<'a>>> =
             self.args.into_iter().map(|arg| arg.ir_lower(ctx)).collect();
 
+        let type_params = lower_type_params(ctx, self.type_params);
+
         let func = Function {
             name: LateInit::empty(),
+            type_params,
             args: ir_args,
             return_type: LateInit::empty(),
+            throws: None,
             body: self.body.ir_lower(ctx),
+            is_async: self.is_async,
             span: self.span,
         }
         .allocate(ctx);
@@ -232,6 +381,10 @@ impl<'a> IrLowerable<'a> for crate::ast::node::function::Function<'a> {
             .unwrap_or_else(|| ctx.make_type_var(TypeSignatureParent::FunctionDefReturn(func)))
             .into();
 
+        ctx[func].throws = self
+            .throws
+            .map(|t| t.into_ir_type(ctx, TypeSignatureParent::FunctionDefThrows(func)));
+
         func
     }
 }
@@ -272,7 +425,14 @@ impl<'a> IrLowerable<'a> for crate::ast::node::function::FunctionCall<'a> {
                 .into_iter()
                 .map(|param| param.ir_lower(ctx))
                 .collect(),
+            arg_labels: self
+                .arg_labels
+                .into_iter()
+                .map(|label| label.map(|label| label.value))
+                .collect(),
             args_span: self.args_span,
+            instance: None,
+            tried: false,
         }
         .allocate(ctx)
     }
@@ -0,0 +1,395 @@
+use crate::{
+    error_message::error_formatter::Spanned,
+    ir::{context::IrCtx, late_init::LateInit},
+    parser::Span,
+    symbols::symbol_table::{symbol_table_zipper::SymbolTableZipper, SymbolValueItem},
+};
+
+use super::{
+    identifier::{Ident, IdentParent, Identifiable},
+    traits::Trait,
+    type_signature::{
+        TypeEvalError, TypeSignature, TypeSignatureContext, TypeSignatureParent,
+        TypeSignatureValue, Typed,
+    },
+    IrAlloc, NodeRef,
+};
+
+#[derive(Debug)]
+pub struct TypeParam<'a> {
+    pub name: LateInit<Ident<'a>>,
+    /// The trait that the type arguments have to conform to
+    pub bound: Option<Ident<'a>>,
+}
+
+impl<'a> Identifiable<'a> for TypeParam<'a> {
+    fn name(&self, _ctx: &IrCtx<'a>) -> Ident<'a> {
+        *self.name
+    }
+}
+
+impl<'a> Spanned<'a> for NodeRef<'a, TypeParam<'a>> {
+    fn get_span(&self, ctx: &IrCtx<'a>) -> Option<Span<'a>> {
+        ctx[*self].name.get_span(ctx)
+    }
+}
+
+impl<'a> Typed<'a> for NodeRef<'a, TypeParam<'a>> {
+    fn eval_type(
+        &self,
+        _symbols: &mut SymbolTableZipper<'a>,
+        ctx: &mut IrCtx<'a>,
+    ) -> Result<TypeSignature<'a>, TypeEvalError<'a>> {
+        let name = *ctx[*self].name;
+        Ok(ctx.get_type_sig(
+            TypeSignatureValue::TypeParameter { name },
+            TypeSignatureContext {
+                parent: TypeSignatureParent::TypeParam(*self),
+                type_span: None,
+            }
+            .alloc(),
+        ))
+    }
+}
+
+impl<'a> NodeRef<'a, TypeParam<'a>> {
+    pub fn bound_trait(
+        &self,
+        ctx: &IrCtx<'a>,
+        symbols: &SymbolTableZipper<'a>,
+    ) -> Option<NodeRef<'a, Trait<'a>>> {
+        match ctx[symbols.lookup(ctx, ctx[*self].bound?)?] {
+            SymbolValueItem::TraitDecl(tr) => Some(tr),
+            _ => None,
+        }
+    }
+}
+
+pub fn lower_type_params<'a>(
+    ctx: &mut IrCtx<'a>,
+    type_params: Vec<crate::ast::node::generics::TypeParam<'a>>,
+) -> Vec<NodeRef<'a, TypeParam<'a>>> {
+    type_params
+        .into_iter()
+        .map(|type_param| {
+            let param = TypeParam {
+                name: LateInit::empty(),
+                bound: None,
+            }
+            .allocate(ctx);
+
+            ctx[param].name = ctx
+                .make_ident(type_param.name, IdentParent::TypeParamName(param))
+                .into();
+
+            ctx[param].bound = type_param.bound.map(|bound| {
+                ctx.make_unresolved_ident(bound, IdentParent::TypeParamBound(param).into())
+            });
+
+            param
+        })
+        .collect()
+}
+
+/// The type parameter declaring the given name of a `TypeSignatureValue::TypeParameter`
+pub fn declared_type_param<'a>(name: Ident<'a>) -> Option<NodeRef<'a, TypeParam<'a>>> {
+    match *name.parent {
+        IdentParent::TypeParamName(param) => Some(param),
+        _ => None,
+    }
+}
+
+/// The names of the given type parameters, as used by `TypeSignatureValue::TypeParameter`
+pub fn type_param_names<'a>(
+    ctx: &IrCtx<'a>,
+    type_params: &[NodeRef<'a, TypeParam<'a>>],
+) -> Vec<Ident<'a>> {
+    type_params.iter().map(|param| *ctx[*param].name).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::assert_matches;
+
+    use crate::{
+        ir::{
+            node::{
+                statement::Stmt,
+                type_signature::{BuiltinType, TypeEvalError, TypeSignatureValue},
+            },
+            test_utils::utils::{lowered_ir, resolve_symbols, type_check},
+        },
+        symbols::symbol_resolver::SymbolResolutionError,
+        type_checker::TypeCheckerError,
+    };
+
+    #[test]
+    fn test_generic_function_call() {
+        let mut ir = lowered_ir(
+            "
+        func identity<T>(value: T) -> T { return value }
+        let n: Number = identity(1)
+        let s: String = identity(\"hello\")
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_generic_function_call_mismatch() {
+        let mut ir = lowered_ir(
+            "
+        func identity<T>(value: T) -> T { return value }
+        let s: String = identity(1)
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ConflictingTypes(_, _))
+        );
+    }
+
+    #[test]
+    fn test_type_param_is_rigid() {
+        let mut ir = lowered_ir("func f<T>(value: T) -> Number { return value }").unwrap();
+
+        match type_check(&mut ir).1 {
+            Err(TypeCheckerError::ConflictingTypes(a, b)) => {
+                let number = ir.ctx.get_builtin_type_sig(BuiltinType::Number);
+                assert!(a == number || b == number);
+            }
+            res => panic!("expected conflicting types, got {res:?}"),
+        }
+    }
+
+    #[test]
+    fn test_type_eq_compares_lengths() {
+        let mut ir = lowered_ir("func f<T>(value: T) -> String { return \"\" }").unwrap();
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+
+        let ctx = &ir.ctx;
+        let func = match ctx[ctx[ir.ir.0.stmt_block].0[0]] {
+            Stmt::FunctionDecl(func) => func,
+            _ => unreachable!(),
+        };
+        let param = (*ctx[ctx[func].args[0]].type_sig).clone();
+        let string = ctx.get_builtin_type_sig(BuiltinType::String);
+        let number = ctx.get_builtin_type_sig(BuiltinType::Number);
+
+        let func_type = |args: Vec<_>| TypeSignatureValue::Function {
+            args: args.into(),
+            return_type: string.clone().into(),
+            throws: None,
+        };
+        assert_ne!(func_type(vec![param.clone()]), func_type(vec![]));
+        assert_ne!(
+            func_type(vec![param.clone()]),
+            func_type(vec![param.clone(), param.clone()])
+        );
+
+        let tuple = |items: Vec<_>| TypeSignatureValue::Tuple(items.into());
+        assert_ne!(
+            tuple(vec![number.clone(), string.clone()]),
+            tuple(vec![number.clone()])
+        );
+        assert_eq!(tuple(vec![number.clone()]), tuple(vec![number]));
+    }
+
+    #[test]
+    fn test_generic_struct() {
+        let mut ir = lowered_ir(
+            "
+        struct Pair<A, B> { let first: A; let second: B }
+        func first<A, B>(pair: Pair<A, B>) -> A { return pair.first }
+        let pair = Pair { first: 1, second: \"two\" }
+        let second: String = pair.second
+        let num: Number = first(pair)
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_generic_struct_attr_mismatch() {
+        let mut ir = lowered_ir(
+            "
+        struct Box<T> { let value: T }
+        let num: Box<Number> = Box { value: true }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ConflictingTypes(_, _))
+        );
+    }
+
+    #[test]
+    fn test_generic_struct_extension() {
+        let mut ir = lowered_ir(
+            "
+        let pair = Pair(first: 1, second: \"two\")
+        let second: String = pair.swapped().first
+        let num: Number = pair.firstValue()
+        struct Pair<A, B> { let first: A; let second: B }
+        extend Pair {
+            init(first: A, second: B) { self.first = first; self.second = second }
+            func swapped() -> Pair<B, A> { return Pair(first: self.second, second: self.first) }
+        }
+        extend Pair { func firstValue() -> A { return self.first } }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_generic_struct_extension_mismatch() {
+        let mut ir = lowered_ir(
+            "
+        struct Box<T> { let value: T }
+        extend Box {
+            init(value: T) { self.value = value }
+            func get() -> T { return self.value }
+        }
+        let text: String = Box(value: 1).get()
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ConflictingTypes(_, _))
+        );
+    }
+
+    #[test]
+    fn test_generic_enum_match() {
+        let mut ir = lowered_ir(
+            "
+        enum Option<T> { some(T); none }
+        let maybe: Option<String> = .some(\"value\")
+        let value: String = match maybe {
+            .some(v) => v,
+            .none => \"nothing\"
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_generic_enum_binding_mismatch() {
+        let mut ir = lowered_ir(
+            "
+        enum Option<T> { some(T); none }
+        let maybe = Option.some(1)
+        if let .some(v) = maybe {
+            let s: String = v
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ConflictingTypes(_, _))
+        );
+    }
+
+    #[test]
+    fn test_trait_bound() {
+        let mut ir = lowered_ir(
+            "
+        trait Describable { func describe() -> String }
+        struct Car { let brand: String }
+        extend Car: Describable { func describe() -> String { return self.brand } }
+        func describeTwice<T: Describable>(value: T) -> String {
+            return value.describe() + value.describe()
+        }
+        let twice: String = describeTwice(Car { brand: \"Volvo\" })
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_trait_bound_self_type() {
+        let mut ir = lowered_ir(
+            "
+        trait Combinable { func combine(other: Self) -> Self }
+        struct Money { let cents: Number }
+        extend Money: Combinable {
+            func combine(other: Money) -> Money { return Money { cents: self.cents + other.cents } }
+        }
+        func sum<T: Combinable>(a: T, b: T) -> T { return a.combine(b) }
+        let total: Money = sum(Money { cents: 1 }, Money { cents: 2 })
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_trait_bound_missing_conformance() {
+        let mut ir = lowered_ir(
+            "
+        trait Describable { func describe() -> String }
+        struct Car { let brand: String }
+        func describe<T: Describable>(value: T) -> String { return value.describe() }
+        let description = describe(Car { brand: \"Volvo\" })
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::MissingConformance(_, _))
+        );
+    }
+
+    #[test]
+    fn test_trait_bound_forwarded() {
+        let mut ir = lowered_ir(
+            "
+        trait Describable { func describe() -> String }
+        func describe<T: Describable>(value: T) -> String { return value.describe() }
+        func forward<U: Describable>(value: U) -> String { return describe(value) }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_unbounded_method_call() {
+        let mut ir = lowered_ir(
+            "
+        trait Describable { func describe() -> String }
+        func describe<T>(value: T) -> String { return value.describe() }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            resolve_symbols(&mut ir),
+            Err(SymbolResolutionError::TypeEval(
+                TypeEvalError::UnknownIdent(_)
+            ))
+        );
+    }
+}
@@ -10,13 +10,22 @@ use std::{fmt::Debug, hash::Hash};
 use super::{
     control_flow::IfStmt,
     enumeration::{Enum, EnumInit, EnumValue},
+    error_handling::DoCatch,
     expression::Expr,
+    extension::Extension,
     external::ExternalObject,
     function::{Function, FunctionArg},
+    generics::TypeParam,
+    loops::{ForLoop, Loop, LoopJump, WhileLoop},
     member_access::UnresolvedMemberAccess,
+    operator::BinaryOperation,
+    pattern_match::{EnumPattern, LetElse, MatchArm},
+    record::RecordAccess,
     statement::VarDecl,
+    string_interpolation::StringInterpolation,
     structure::{Struct, StructAccess, StructAttr, StructInit, StructInitValue},
     traits::{Trait, TraitFuncAttr},
+    type_alias::TypeAlias,
     type_signature::{BuiltinType, TypeSignatureValue},
     NodeRef,
 };
@@ -124,6 +133,7 @@ pub enum IdentParent<'a> {
     StructInitStructName(NodeRef<'a, StructInit<'a>>),
     StructInitScopeName(NodeRef<'a, StructInit<'a>>),
     StructAccessAttrName(NodeRef<'a, StructAccess<'a>>),
+    RecordAccessFieldName(NodeRef<'a, RecordAccess<'a>>),
     EnumDeclName(NodeRef<'a, Enum<'a>>),
     EnumDeclValueName(NodeRef<'a, EnumValue<'a>>),
     EnumInitValueName(NodeRef<'a, EnumInit<'a>>),
@@ -135,10 +145,36 @@ pub enum IdentParent<'a> {
     TypeSigName(Id<TypeSignatureValue<'a>>),
     MemberAccessMemberName(NodeRef<'a, UnresolvedMemberAccess<'a>>),
     ExternObjName(NodeRef<'a, ExternalObject<'a>>),
+    TypeAliasName(NodeRef<'a, TypeAlias<'a>>),
     BuiltinIdent,
     IfBranchScope(NodeRef<'a, IfStmt<'a>>),
+    NarrowedBindingName(NodeRef<'a, IfStmt<'a>>, usize),
+    RightOperandScope(NodeRef<'a, BinaryOperation<'a>>),
+    OperandNarrowedBindingName(NodeRef<'a, BinaryOperation<'a>>, usize),
+    LoopLabel(Loop<'a>),
+    LoopJumpLabel(NodeRef<'a, LoopJump<'a>>),
+    WhileLoopScope(NodeRef<'a, WhileLoop<'a>>),
+    ForLoopScope(NodeRef<'a, ForLoop<'a>>),
+    ForLoopItemName(NodeRef<'a, ForLoop<'a>>),
     TraitName(NodeRef<'a, Trait<'a>>),
     TraitFuncAttrName(NodeRef<'a, TraitFuncAttr<'a>>),
+    TraitSelfName(NodeRef<'a, Trait<'a>>),
+    TraitSelfTypeName(NodeRef<'a, Trait<'a>>),
+    MatchArmScope(NodeRef<'a, MatchArm<'a>>),
+    EnumPatternEnumName(NodeRef<'a, EnumPattern<'a>>),
+    EnumPatternValueName(NodeRef<'a, EnumPattern<'a>>),
+    PatternBindingName(NodeRef<'a, EnumPattern<'a>>, usize),
+    LetElseScope(NodeRef<'a, LetElse<'a>>),
+    TypeParamName(NodeRef<'a, TypeParam<'a>>),
+    TypeParamBound(NodeRef<'a, TypeParam<'a>>),
+    ExtensionName(NodeRef<'a, Extension<'a>>),
+    ExtensionTraitName(NodeRef<'a, Extension<'a>>),
+    ExtensionSelfName(NodeRef<'a, Extension<'a>>),
+    ExtensionSelfTypeName(NodeRef<'a, Extension<'a>>),
+    ExtensionScope(NodeRef<'a, Extension<'a>>),
+    DoCatchScope(NodeRef<'a, DoCatch<'a>>),
+    CatchErrorName(NodeRef<'a, DoCatch<'a>>),
+    StringInterpolationTrait(NodeRef<'a, StringInterpolation<'a>>),
 }
 
 impl<'a> IdentParent<'a> {
@@ -182,11 +218,60 @@ impl<'a> IdentParent<'a> {
             IdentParent::MemberAccessMemberName(mem_acc) => {
                 ctx[*mem_acc].member_name.id = new_ident.id
             }
+            IdentParent::RecordAccessFieldName(rec_acc) => {
+                ctx[*rec_acc].field_name.id = new_ident.id
+            }
             IdentParent::BuiltinIdent => panic!("builtin ident cannot be changed"),
             IdentParent::ExternObjName(obj) => ctx[*obj].ident.id = new_ident.id,
+            IdentParent::TypeAliasName(alias) => ctx[*alias].name.id = new_ident.id,
             IdentParent::IfBranchScope(_) => unreachable!(),
+            IdentParent::NarrowedBindingName(ifb, idx) => {
+                ctx[*ifb].narrowed[*idx].name.id = new_ident.id
+            }
+            IdentParent::RightOperandScope(_) => unreachable!(),
+            IdentParent::OperandNarrowedBindingName(bin_op, idx) => {
+                ctx[*bin_op].narrowed[*idx].name.id = new_ident.id
+            }
+            IdentParent::LoopLabel(_) | IdentParent::LoopJumpLabel(_) => {
+                unreachable!("loop labels are not symbols")
+            }
+            IdentParent::WhileLoopScope(_) | IdentParent::ForLoopScope(_) => unreachable!(),
+            IdentParent::ForLoopItemName(fl) => ctx[*fl].item_name.id = new_ident.id,
             IdentParent::TraitName(tr) => ctx[*tr].name.id = new_ident.id,
             IdentParent::TraitFuncAttrName(attr) => ctx[*attr].name.id = new_ident.id,
+            IdentParent::TraitSelfName(tr) => ctx[*tr].self_name.id = new_ident.id,
+            IdentParent::TraitSelfTypeName(tr) => ctx[*tr].self_type_name.id = new_ident.id,
+            IdentParent::MatchArmScope(_) => unreachable!(),
+            IdentParent::EnumPatternEnumName(pattern) => ctx[*pattern].enum_name.id = new_ident.id,
+            IdentParent::EnumPatternValueName(pattern) => {
+                ctx[*pattern].enum_value.id = new_ident.id
+            }
+            IdentParent::PatternBindingName(pattern, idx) => {
+                if let Some(name) = &mut ctx[*pattern].bindings[*idx].name {
+                    name.id = new_ident.id
+                }
+            }
+            IdentParent::LetElseScope(_) => unreachable!(),
+            IdentParent::TypeParamName(param) => ctx[*param].name.id = new_ident.id,
+            IdentParent::TypeParamBound(param) => {
+                if let Some(bound) = &mut ctx[*param].bound {
+                    bound.id = new_ident.id
+                }
+            }
+            IdentParent::ExtensionName(ext) => ctx[*ext].name.id = new_ident.id,
+            IdentParent::ExtensionTraitName(ext) => {
+                if let Some(trait_name) = &mut ctx[*ext].trait_name {
+                    trait_name.id = new_ident.id
+                }
+            }
+            IdentParent::ExtensionSelfName(ext) => ctx[*ext].self_name.id = new_ident.id,
+            IdentParent::ExtensionSelfTypeName(ext) => ctx[*ext].self_type_name.id = new_ident.id,
+            IdentParent::ExtensionScope(_) => unreachable!(),
+            IdentParent::DoCatchScope(_) => unreachable!(),
+            IdentParent::CatchErrorName(dc) => ctx[*dc].error_name.id = new_ident.id,
+            IdentParent::StringInterpolationTrait(interp) => {
+                ctx[*interp].expressible_trait.id = new_ident.id
+            }
         }
     }
 }
@@ -0,0 +1,315 @@
+use crate::{
+    error_message::error_formatter::Spanned,
+    ir::{ast_lowering::IrLowerable, context::IrCtx, late_init::LateInit},
+    parser::Span,
+    symbols::symbol_table::symbol_table_zipper::SymbolTableZipper,
+};
+
+use super::{
+    expression::Expr,
+    identifier::{Ident, IdentParent},
+    statement::StmtBlock,
+    type_signature::{TypeEvalError, TypeSignature, TypeSignatureParent, Typed},
+    IrAlloc, NodeRef,
+};
+
+pub type LoopJumpKind = crate::ast::node::loops::LoopJumpKind;
+
+/// A loop running its body for as long as the condition holds
+#[derive(Debug, Clone)]
+pub struct WhileLoop<'a> {
+    pub label: Option<Ident<'a>>,
+    pub condition: NodeRef<'a, Expr<'a>>,
+    pub body: NodeRef<'a, StmtBlock<'a>>,
+    pub body_scope_ident: LateInit<Ident<'a>>,
+    /// Whether a `break` without a label leaves the loop from the arm of a match, which is
+    /// generated as a `switch`, so the loop is given a label to break out of
+    pub broken_from_match: bool,
+    pub span: Span<'a>,
+}
+
+/// A loop running its body once for every item of an array, number of a range
+/// or entry of a dictionary
+#[derive(Debug, Clone)]
+pub struct ForLoop<'a> {
+    pub label: Option<Ident<'a>>,
+    pub item_name: LateInit<Ident<'a>>,
+    pub item_type: LateInit<TypeSignature<'a>>,
+    pub sequence: NodeRef<'a, Expr<'a>>,
+    /// Whether the sequence is a `Range` whose numbers are counted,
+    /// instead of an array whose items are iterated
+    pub counts_range: bool,
+    pub body: NodeRef<'a, StmtBlock<'a>>,
+    pub body_scope_ident: LateInit<Ident<'a>>,
+    pub broken_from_match: bool,
+    pub span: Span<'a>,
+}
+
+/// A `break` or `continue` of the innermost loop, or of the loop with the label
+#[derive(Debug, Clone)]
+pub struct LoopJump<'a> {
+    pub kind: LoopJumpKind,
+    pub label: Option<Ident<'a>>,
+    pub span: Span<'a>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Loop<'a> {
+    While(NodeRef<'a, WhileLoop<'a>>),
+    For(NodeRef<'a, ForLoop<'a>>),
+}
+
+impl<'a> Loop<'a> {
+    pub fn label(&self, ctx: &IrCtx<'a>) -> Option<Ident<'a>> {
+        match self {
+            Loop::While(wl) => ctx[*wl].label,
+            Loop::For(fl) => ctx[*fl].label,
+        }
+    }
+
+    pub fn set_broken_from_match(&self, ctx: &mut IrCtx<'a>) {
+        match self {
+            Loop::While(wl) => ctx[*wl].broken_from_match = true,
+            Loop::For(fl) => ctx[*fl].broken_from_match = true,
+        }
+    }
+}
+
+impl<'a> Spanned<'a> for NodeRef<'a, LoopJump<'a>> {
+    fn get_span(&self, ctx: &IrCtx<'a>) -> Option<Span<'a>> {
+        Some(ctx[*self].span.clone())
+    }
+}
+
+impl<'a> Typed<'a> for NodeRef<'a, ForLoop<'a>> {
+    /// The type of the items of the sequence
+    fn eval_type(
+        &self,
+        _symbols: &mut SymbolTableZipper<'a>,
+        ctx: &mut IrCtx<'a>,
+    ) -> Result<TypeSignature<'a>, TypeEvalError<'a>> {
+        Ok(ctx[*self].item_type.cloned())
+    }
+
+    fn specified_type(&self, ctx: &IrCtx<'a>) -> Option<TypeSignature<'a>> {
+        Some(ctx[*self].item_type.cloned())
+    }
+
+    fn specify_type(
+        &self,
+        ctx: &mut IrCtx<'a>,
+        new_type: TypeSignature<'a>,
+    ) -> Result<(), TypeEvalError<'a>> {
+        ctx[*self].item_type = new_type.into();
+        Ok(())
+    }
+}
+
+impl<'a> IrLowerable<'a> for crate::ast::node::loops::WhileLoop<'a> {
+    type IrType = WhileLoop<'a>;
+
+    fn ir_lower(self, ctx: &mut IrCtx<'a>) -> NodeRef<'a, Self::IrType> {
+        let condition = self.condition.ir_lower(ctx);
+        let body = (*self.body).ir_lower(ctx);
+
+        let while_loop = WhileLoop {
+            label: None,
+            condition,
+            body,
+            body_scope_ident: LateInit::empty(),
+            broken_from_match: false,
+            span: self.span,
+        }
+        .allocate(ctx);
+
+        ctx[while_loop].label = self
+            .label
+            .map(|label| ctx.make_ident(label, IdentParent::LoopLabel(Loop::While(while_loop))));
+
+        ctx[while_loop].body_scope_ident = ctx
+            .make_anon_ident(IdentParent::WhileLoopScope(while_loop))
+            .into();
+
+        while_loop
+    }
+}
+
+impl<'a> IrLowerable<'a> for crate::ast::node::loops::ForLoop<'a> {
+    type IrType = ForLoop<'a>;
+
+    fn ir_lower(self, ctx: &mut IrCtx<'a>) -> NodeRef<'a, Self::IrType> {
+        let sequence = self.sequence.ir_lower(ctx);
+        let body = (*self.body).ir_lower(ctx);
+
+        let for_loop = ForLoop {
+            label: None,
+            item_name: LateInit::empty(),
+            item_type: LateInit::empty(),
+            sequence,
+            counts_range: false,
+            body,
+            body_scope_ident: LateInit::empty(),
+            broken_from_match: false,
+            span: self.span,
+        }
+        .allocate(ctx);
+
+        ctx[for_loop].label = self
+            .label
+            .map(|label| ctx.make_ident(label, IdentParent::LoopLabel(Loop::For(for_loop))));
+
+        ctx[for_loop].item_name = ctx
+            .make_ident(self.item_name, IdentParent::ForLoopItemName(for_loop))
+            .into();
+
+        ctx[for_loop].item_type = ctx
+            .make_type_var(TypeSignatureParent::ForLoop(for_loop))
+            .into();
+
+        ctx[for_loop].body_scope_ident = ctx
+            .make_anon_ident(IdentParent::ForLoopScope(for_loop))
+            .into();
+
+        for_loop
+    }
+}
+
+impl<'a> IrLowerable<'a> for crate::ast::node::loops::LoopJump<'a> {
+    type IrType = LoopJump<'a>;
+
+    fn ir_lower(self, ctx: &mut IrCtx<'a>) -> NodeRef<'a, Self::IrType> {
+        let jump = LoopJump {
+            kind: self.kind,
+            label: None,
+            span: self.span,
+        }
+        .allocate(ctx);
+
+        ctx[jump].label = self
+            .label
+            .map(|label| ctx.make_ident(label, IdentParent::LoopJumpLabel(jump)));
+
+        jump
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::assert_matches;
+
+    use crate::{
+        ir::{
+            node::type_signature::TypeEvalError,
+            test_utils::utils::{lowered_ir, resolve_symbols, type_check},
+        },
+        symbols::symbol_resolver::SymbolResolutionError,
+        type_checker::TypeCheckerError,
+    };
+
+    #[test]
+    fn test_while_loop() {
+        let mut ir = lowered_ir(
+            "
+        var i = 0
+        while i < 10 { i = i + 1 }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_while_loop_non_boolean() {
+        let mut ir = lowered_ir("var i = 10; while i { i = i - 1 }").unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ConflictingTypes(_, _))
+        );
+    }
+
+    #[test]
+    fn test_for_loop_item_member() {
+        let mut ir = lowered_ir(
+            "
+        struct Car { let speed: Number }
+        func fastest(cars: Array<Car>) -> Number {
+            var max = 0
+            for car in cars {
+                if car.speed > max { max = car.speed }
+            }
+            return max
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_for_loop_item_type() {
+        let mut ir = lowered_ir("for name in [\"a\", \"b\"] { let n: Number = name }").unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ConflictingTypes(_, _))
+        );
+    }
+
+    #[test]
+    fn test_for_loop_non_array() {
+        let mut ir = lowered_ir("for digit in 123 {}").unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ConflictingTypes(_, _))
+        );
+    }
+
+    #[test]
+    fn test_for_loop_range() {
+        let mut ir = lowered_ir(
+            "
+        func sum(numbers: Range) -> Number {
+            var total = 0
+            for n in numbers { total = total + n }
+            for i in 0...3 { total = total + i }
+            return total
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_for_loop_range_item_type() {
+        let mut ir = lowered_ir("for i in 0..<3 { let s: String = i }").unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ConflictingTypes(_, _))
+        );
+    }
+
+    #[test]
+    fn test_loop_item_scope() {
+        let mut ir = lowered_ir(
+            "
+        for item in [1, 2] {}
+        let last = item
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            resolve_symbols(&mut ir),
+            Err(SymbolResolutionError::TypeEval(
+                TypeEvalError::UnknownIdent(_)
+            ))
+        );
+    }
+}
@@ -17,6 +17,7 @@ pub struct UnresolvedMemberAccess<'a> {
     pub object: Option<NodeRef<'a, Expr<'a>>>,
     pub member_name: LateInit<Ident<'a>>,
     pub items: Option<(Vec<NodeRef<'a, Expr<'a>>>, Span<'a>)>,
+    pub optional: bool,
     pub type_sig: LateInit<TypeSignature<'a>>,
     pub span: Span<'a>,
 }
@@ -66,6 +67,7 @@ impl<'a> IrLowerable<'a> for crate::ast::node::member_access::MemberAccess<'a> {
             object,
             member_name: LateInit::empty(),
             items,
+            optional: self.optional,
             type_sig: LateInit::empty(),
             span: self.span,
         }
@@ -1,37 +1,61 @@
 use id_arena::{Arena, Id};
 
 use self::{
+    array::{Array, ArrayMemberAccess, ArraySlice, Index},
     assignment::Assignment,
     control_flow::IfStmt,
+    dictionary::{Dictionary, DictionaryIndex, DictionaryMemberAccess},
     enumeration::{Enum, EnumInit, EnumValue},
+    error_handling::{DoCatch, Try},
     escape_block::EscapeBlock,
     expression::Expr,
+    extension::Extension,
     external::ExternalObject,
-    function::{Function, FunctionArg, FunctionCall},
+    function::{Await, Function, FunctionArg, FunctionCall},
+    generics::TypeParam,
+    loops::{ForLoop, LoopJump, WhileLoop},
     member_access::UnresolvedMemberAccess,
+    operator::{BinaryOperation, UnaryOperation},
+    pattern_match::{EnumPattern, LetElse, Match, MatchArm},
+    record::RecordAccess,
     statement::{Stmt, StmtBlock, VarDecl},
+    static_access::StaticAccess,
+    string_interpolation::StringInterpolation,
     structure::{Struct, StructAccess, StructAttr, StructInit, StructInitValue},
     traits::{Trait, TraitFuncAttr},
     tuple::{Tuple, TupleAccess},
+    type_alias::TypeAlias,
 };
 use std::{convert::Into, marker::PhantomData};
 
 use super::context::{IrArenaType, IrCtx};
 
+pub mod array;
 pub mod assignment;
 pub mod control_flow;
+pub mod dictionary;
 pub mod enumeration;
+pub mod error_handling;
 pub mod escape_block;
 pub mod expression;
+pub mod extension;
 pub mod external;
 pub mod function;
+pub mod generics;
 pub mod identifier;
+pub mod loops;
 pub mod member_access;
 pub mod module;
+pub mod operator;
+pub mod pattern_match;
+pub mod record;
 pub mod statement;
+pub mod static_access;
+pub mod string_interpolation;
 pub mod structure;
 pub mod traits;
 pub mod tuple;
+pub mod type_alias;
 pub mod type_signature;
 
 #[derive(Debug, Eq, Hash)]
@@ -147,7 +171,32 @@ register_nodes![
     (var_decls, VarDecl<'a>),
     (mem_accs, UnresolvedMemberAccess<'a>),
     (extern_obj, ExternalObject<'a>),
-    (if_branch, IfStmt<'a>)
+    (type_aliases, TypeAlias<'a>),
+    (if_branch, IfStmt<'a>),
+    (bin_ops, BinaryOperation<'a>),
+    (unary_ops, UnaryOperation<'a>),
+    (matches, Match<'a>),
+    (match_arms, MatchArm<'a>),
+    (enm_patterns, EnumPattern<'a>),
+    (let_elses, LetElse<'a>),
+    (type_params, TypeParam<'a>),
+    (extensions, Extension<'a>),
+    (awaits, Await<'a>),
+    (do_catches, DoCatch<'a>),
+    (tries, Try<'a>),
+    (str_interps, StringInterpolation<'a>),
+    (arrays, Array<'a>),
+    (indexes, Index<'a>),
+    (arr_mem_accs, ArrayMemberAccess<'a>),
+    (arr_slices, ArraySlice<'a>),
+    (dicts, Dictionary<'a>),
+    (dict_indexes, DictionaryIndex<'a>),
+    (dict_mem_accs, DictionaryMemberAccess<'a>),
+    (while_loops, WhileLoop<'a>),
+    (for_loops, ForLoop<'a>),
+    (loop_jumps, LoopJump<'a>),
+    (rec_accs, RecordAccess<'a>),
+    (st_static_accs, StaticAccess<'a>)
 ];
 
 pub trait IrAlloc<'a>
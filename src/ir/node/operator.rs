@@ -0,0 +1,334 @@
+use crate::{
+    error_message::error_formatter::Spanned,
+    ir::context::IrCtx,
+    parser::Span,
+    symbols::symbol_table::{symbol_table_zipper::SymbolTableZipper, SymbolValueItem},
+};
+
+use super::{
+    control_flow::{nil_checked_values, NarrowedBinding, NarrowingReach},
+    expression::Expr,
+    extension::Extension,
+    function::Function,
+    generics::declared_type_param,
+    identifier::{Ident, IdentKey, IdentParent, IdentValue},
+    traits::{Trait, TraitFuncAttr},
+    type_signature::{
+        BuiltinType, TypeEvalError, TypeSignature, TypeSignatureContext, TypeSignatureParent,
+        TypeSignatureValue, Typed,
+    },
+    NodeRef,
+};
+
+pub type BinaryOperator = crate::ast::node::operator::BinaryOperator;
+pub type UnaryOperator = crate::ast::node::operator::UnaryOperator;
+
+#[derive(Debug)]
+pub struct BinaryOperation<'a> {
+    pub lhs: NodeRef<'a, Expr<'a>>,
+    pub operator: BinaryOperator,
+    pub operator_span: Span<'a>,
+    pub rhs: NodeRef<'a, Expr<'a>>,
+    /// The operator declared for the type of the left operand, dispatched to during type checking
+    pub overload: Option<OperatorOverload<'a>>,
+    /// The values narrowed by the left operand inside of the right operand
+    pub narrowed: Vec<NarrowedBinding<'a>>,
+    pub rhs_scope_ident: Option<Ident<'a>>,
+    pub span: Span<'a>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OperatorOverload<'a> {
+    pub func: OverloadedFunc<'a>,
+    /// Whether the result is negated, for a `!=` derived from the declared `==`
+    pub negated: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OverloadedFunc<'a> {
+    /// An operator declared in an extension, or inherited from the default operators of the trait
+    /// the extension conforms to, which is called through the name of the extended type
+    Static(NodeRef<'a, Extension<'a>>, NodeRef<'a, Function<'a>>),
+    /// An operator of a trait called on values typed as the trait, which is only known
+    /// to be declared by the conforming type inside of the default operators of the trait
+    Trait(NodeRef<'a, Trait<'a>>, NodeRef<'a, TraitFuncAttr<'a>>),
+    /// An operator of the trait bounding the type parameter of the left operand,
+    /// which is called through the structure the operand is created from
+    TypeParam(NodeRef<'a, Trait<'a>>, NodeRef<'a, TraitFuncAttr<'a>>),
+}
+
+#[derive(Debug)]
+pub struct UnaryOperation<'a> {
+    pub operator: UnaryOperator,
+    pub operator_span: Span<'a>,
+    pub operand: NodeRef<'a, Expr<'a>>,
+    pub span: Span<'a>,
+}
+
+impl<'a> Spanned<'a> for NodeRef<'a, BinaryOperation<'a>> {
+    fn get_span(&self, ctx: &IrCtx<'a>) -> Option<Span<'a>> {
+        Some(ctx[*self].span.clone())
+    }
+}
+
+impl<'a> Spanned<'a> for NodeRef<'a, UnaryOperation<'a>> {
+    fn get_span(&self, ctx: &IrCtx<'a>) -> Option<Span<'a>> {
+        Some(ctx[*self].span.clone())
+    }
+}
+
+impl<'a> Typed<'a> for NodeRef<'a, BinaryOperation<'a>> {
+    fn eval_type(
+        &self,
+        symbols: &mut SymbolTableZipper<'a>,
+        ctx: &mut IrCtx<'a>,
+    ) -> Result<TypeSignature<'a>, TypeEvalError<'a>> {
+        if let Some(overload) = ctx[*self].overload {
+            return overload.result_type(ctx, *self);
+        }
+
+        let builtin = match ctx[*self].operator {
+            // `+` is defined for both numbers and strings, so the
+            // result has the same type as its operands
+            BinaryOperator::Add => {
+                let lhs = ctx[*self].lhs;
+                return lhs.eval_type(symbols, ctx);
+            }
+            // `??` results in the fallback value when the optional value is absent
+            BinaryOperator::Coalesce => {
+                let rhs = ctx[*self].rhs;
+                return rhs.eval_type(symbols, ctx);
+            }
+            BinaryOperator::Sub
+            | BinaryOperator::Mul
+            | BinaryOperator::Div
+            | BinaryOperator::Mod => BuiltinType::Number,
+            BinaryOperator::Eq
+            | BinaryOperator::NotEq
+            | BinaryOperator::Lt
+            | BinaryOperator::LtEq
+            | BinaryOperator::Gt
+            | BinaryOperator::GtEq
+            | BinaryOperator::And
+            | BinaryOperator::Or => BuiltinType::Boolean,
+            BinaryOperator::HalfOpenRange | BinaryOperator::ClosedRange => BuiltinType::Range,
+        };
+
+        Ok(ctx.get_type_sig(
+            TypeSignatureValue::Builtin(builtin),
+            TypeSignatureContext {
+                parent: TypeSignatureParent::BinaryOperation(*self),
+                type_span: None,
+            }
+            .alloc(),
+        ))
+    }
+}
+
+impl<'a> NodeRef<'a, BinaryOperation<'a>> {
+    /// Narrows the values checked not to be nil by the left operand inside of the right operand,
+    /// as it is only evaluated if the left operand is `true` for `&&`, or `false` for `||`
+    pub fn narrow_rhs(&self, ctx: &mut IrCtx<'a>) {
+        let lhs_outcome = match ctx[*self].operator {
+            BinaryOperator::And => true,
+            BinaryOperator::Or => false,
+            _ => return,
+        };
+
+        let values = nil_checked_values(ctx, ctx[*self].lhs, lhs_outcome);
+        for (i, value) in values.into_iter().enumerate() {
+            let IdentValue::Unresolved(name) = ctx[value.unwrap_ident(ctx)].clone() else {
+                continue;
+            };
+
+            let binding = NarrowedBinding {
+                name: ctx.make_ident(name, IdentParent::OperandNarrowedBindingName(*self, i)),
+                value,
+                type_sig: ctx
+                    .make_type_var(TypeSignatureParent::OperandNarrowedBinding(*self, i))
+                    .into(),
+                reach: NarrowingReach::RightOperand,
+                value_is_mutable: false,
+            };
+            ctx[*self].narrowed.push(binding);
+        }
+
+        if !ctx[*self].narrowed.is_empty() {
+            ctx[*self].rhs_scope_ident =
+                Some(ctx.make_anon_ident(IdentParent::RightOperandScope(*self)));
+        }
+    }
+
+    /// The type of the right operand, evaluated inside of its scope when it has narrowed values
+    pub fn rhs_type(
+        &self,
+        symbols: &mut SymbolTableZipper<'a>,
+        ctx: &mut IrCtx<'a>,
+    ) -> Result<TypeSignature<'a>, TypeEvalError<'a>> {
+        let rhs = ctx[*self].rhs;
+        let Some(scope_ident) = ctx[*self].rhs_scope_ident else {
+            return rhs.eval_type(symbols, ctx);
+        };
+
+        symbols
+            .enter_scope(ctx, scope_ident)
+            .expect("right operand scope should exist");
+        let rhs_type = rhs.eval_type(symbols, ctx);
+        symbols
+            .exit_scope(ctx)
+            .expect("scope should not be global scope");
+
+        rhs_type
+    }
+
+    /// Looks up the operator declared for the type of the left operand, a `!=` that is not
+    /// declared falls back to the negation of `==`
+    pub fn lookup_overload(
+        &self,
+        ctx: &IrCtx<'a>,
+        symbols: &SymbolTableZipper<'a>,
+        lhs_type: &TypeSignature<'a>,
+    ) -> Option<OperatorOverload<'a>> {
+        let operator = ctx[*self].operator;
+        if !operator.is_overloadable() {
+            return None;
+        }
+
+        let overload = |operator, negated| {
+            lookup_overloaded_func(ctx, symbols, lhs_type, operator)
+                .map(|func| OperatorOverload { func, negated })
+        };
+
+        overload(operator, false).or_else(|| match operator {
+            BinaryOperator::NotEq => overload(BinaryOperator::Eq, true),
+            _ => None,
+        })
+    }
+}
+
+fn lookup_overloaded_func<'a>(
+    ctx: &IrCtx<'a>,
+    symbols: &SymbolTableZipper<'a>,
+    type_sig: &TypeSignature<'a>,
+    operator: BinaryOperator,
+) -> Option<OverloadedFunc<'a>> {
+    match &ctx[&type_sig.split_instance(ctx).0] {
+        TypeSignatureValue::Struct { name } | TypeSignatureValue::Enum { name } => {
+            let extensions = symbols.lookup_extensions(ctx, *name);
+
+            extensions
+                .iter()
+                .find_map(|ext| {
+                    let func = ext.lookup_operator(operator, ctx)?;
+                    Some(OverloadedFunc::Static(*ext, func))
+                })
+                .or_else(|| {
+                    extensions.iter().find_map(|ext| {
+                        let tr = ext.conformed_trait(ctx, symbols)?;
+                        let default_impl = ctx[tr.lookup_operator(operator, ctx)?].default_impl?;
+                        Some(OverloadedFunc::Static(*ext, default_impl))
+                    })
+                })
+        }
+        TypeSignatureValue::Trait { name } => match ctx[symbols.lookup(ctx, *name)?] {
+            SymbolValueItem::TraitDecl(tr) => tr
+                .lookup_operator(operator, ctx)
+                .map(|attr| OverloadedFunc::Trait(tr, attr)),
+            _ => None,
+        },
+        TypeSignatureValue::TypeParameter { name } => {
+            let tr = declared_type_param(*name)?.bound_trait(ctx, symbols)?;
+            tr.lookup_operator(operator, ctx)
+                .map(|attr| OverloadedFunc::TypeParam(tr, attr))
+        }
+        _ => None,
+    }
+}
+
+impl<'a> OperatorOverload<'a> {
+    /// The type expected for the right operand, which is the type of the left operand
+    /// for the `Self` argument of an operator of a trait
+    pub fn rhs_type(
+        &self,
+        ctx: &IrCtx<'a>,
+        lhs_type: &TypeSignature<'a>,
+    ) -> Option<TypeSignature<'a>> {
+        let rhs_arg = match self.func {
+            OverloadedFunc::Static(ext, func) if !ctx[ext].operators.contains(&func) => {
+                return Some(lhs_type.clone())
+            }
+            OverloadedFunc::Static(_, func) => *ctx[func].args.get(1)?,
+            OverloadedFunc::Trait(tr, attr) | OverloadedFunc::TypeParam(tr, attr) => {
+                let rhs_arg = *ctx[attr].args.get(1)?;
+                let is_self = matches!(&ctx[&*ctx[rhs_arg].type_sig], TypeSignatureValue::Trait { name }
+                    if IdentKey::idents_eq(ctx, *name, *ctx[tr].name));
+
+                if is_self {
+                    return Some(lhs_type.clone());
+                }
+
+                rhs_arg
+            }
+        };
+
+        Some(ctx[rhs_arg].type_sig.cloned())
+    }
+
+    fn result_type(
+        &self,
+        ctx: &mut IrCtx<'a>,
+        bin_op: NodeRef<'a, BinaryOperation<'a>>,
+    ) -> Result<TypeSignature<'a>, TypeEvalError<'a>> {
+        if self.negated {
+            return Ok(ctx.get_type_sig(
+                TypeSignatureValue::Builtin(BuiltinType::Boolean),
+                TypeSignatureContext {
+                    parent: TypeSignatureParent::BinaryOperation(bin_op),
+                    type_span: None,
+                }
+                .alloc(),
+            ));
+        }
+
+        Ok(match self.func {
+            OverloadedFunc::Static(_, func) => ctx[func].return_type.cloned(),
+            OverloadedFunc::Trait(_, attr) | OverloadedFunc::TypeParam(_, attr) => {
+                attr.return_type(ctx)
+            }
+        })
+    }
+}
+
+impl<'a> NodeRef<'a, Function<'a>> {
+    pub fn overloaded_operator(&self, ctx: &IrCtx<'a>) -> Option<BinaryOperator> {
+        BinaryOperator::overloadable_from_symbol(ctx[*self].name.value(ctx)?)
+    }
+}
+
+impl<'a> NodeRef<'a, TraitFuncAttr<'a>> {
+    pub fn overloaded_operator(&self, ctx: &IrCtx<'a>) -> Option<BinaryOperator> {
+        BinaryOperator::overloadable_from_symbol(ctx[*self].name.value(ctx)?)
+    }
+}
+
+impl<'a> Typed<'a> for NodeRef<'a, UnaryOperation<'a>> {
+    fn eval_type(
+        &self,
+        _symbols: &mut SymbolTableZipper<'a>,
+        ctx: &mut IrCtx<'a>,
+    ) -> Result<TypeSignature<'a>, TypeEvalError<'a>> {
+        let builtin = match ctx[*self].operator {
+            UnaryOperator::Negate => BuiltinType::Number,
+            UnaryOperator::Not => BuiltinType::Boolean,
+        };
+
+        Ok(ctx.get_type_sig(
+            TypeSignatureValue::Builtin(builtin),
+            TypeSignatureContext {
+                parent: TypeSignatureParent::UnaryOperation(*self),
+                type_span: None,
+            }
+            .alloc(),
+        ))
+    }
+}
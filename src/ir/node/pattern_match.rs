@@ -0,0 +1,257 @@
+use crate::{
+    error_message::error_formatter::Spanned,
+    ir::{ast_lowering::IrLowerable, context::IrCtx, late_init::LateInit},
+    parser::Span,
+    symbols::symbol_table::symbol_table_zipper::SymbolTableZipper,
+};
+
+use super::{
+    enumeration::Enum,
+    expression::Expr,
+    identifier::{Ident, IdentParent},
+    statement::{Stmt, StmtBlock},
+    type_signature::{TypeEvalError, TypeSignature, TypeSignatureParent, Typed},
+    IrAlloc, NodeRef,
+};
+
+#[derive(Debug, Clone)]
+pub struct Match<'a> {
+    pub scrutinee: NodeRef<'a, Expr<'a>>,
+    pub arms: Vec<NodeRef<'a, MatchArm<'a>>>,
+    pub type_sig: LateInit<TypeSignature<'a>>,
+    pub span: Span<'a>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MatchArm<'a> {
+    /// `None` for the wildcard arm `_`
+    pub pattern: Option<NodeRef<'a, EnumPattern<'a>>>,
+    pub body: NodeRef<'a, StmtBlock<'a>>,
+    /// The value of the arm, which is the trailing expression of its body
+    pub result: Option<NodeRef<'a, Expr<'a>>>,
+    pub scope_ident: LateInit<Ident<'a>>,
+    pub span: Span<'a>,
+}
+
+#[derive(Debug, Clone)]
+pub struct EnumPattern<'a> {
+    /// Only known once the type of the matched value has been inferred
+    pub enum_name: LateInit<Ident<'a>>,
+    pub enum_value: LateInit<Ident<'a>>,
+    pub bindings: Vec<PatternBinding<'a>>,
+    pub span: Span<'a>,
+}
+
+#[derive(Debug, Clone)]
+pub struct LetElse<'a> {
+    pub pattern: NodeRef<'a, EnumPattern<'a>>,
+    pub value: NodeRef<'a, Expr<'a>>,
+    pub else_body: NodeRef<'a, StmtBlock<'a>>,
+    pub else_scope_ident: LateInit<Ident<'a>>,
+    pub span: Span<'a>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PatternBinding<'a> {
+    /// `None` if the item is ignored using `_`
+    pub name: Option<Ident<'a>>,
+    pub type_sig: LateInit<TypeSignature<'a>>,
+}
+
+impl<'a> NodeRef<'a, Match<'a>> {
+    /// The patterns of all arms that are not a wildcard
+    pub fn patterns(&self, ctx: &IrCtx<'a>) -> Vec<NodeRef<'a, EnumPattern<'a>>> {
+        ctx[*self]
+            .arms
+            .iter()
+            .filter_map(|arm| ctx[*arm].pattern)
+            .collect()
+    }
+}
+
+impl<'a> NodeRef<'a, EnumPattern<'a>> {
+    pub fn lookup_enum(
+        &self,
+        ctx: &IrCtx<'a>,
+        symbols: &SymbolTableZipper<'a>,
+    ) -> Option<NodeRef<'a, Enum<'a>>> {
+        if ctx[*self].enum_name.is_empty() {
+            return None;
+        }
+
+        symbols
+            .lookup(ctx, *ctx[*self].enum_name)
+            .map(|sym| sym.unwrap_enum(ctx))
+    }
+}
+
+impl<'a> Spanned<'a> for NodeRef<'a, Match<'a>> {
+    fn get_span(&self, ctx: &IrCtx<'a>) -> Option<Span<'a>> {
+        Some(ctx[*self].span.clone())
+    }
+}
+
+impl<'a> Spanned<'a> for NodeRef<'a, MatchArm<'a>> {
+    fn get_span(&self, ctx: &IrCtx<'a>) -> Option<Span<'a>> {
+        Some(ctx[*self].span.clone())
+    }
+}
+
+impl<'a> Spanned<'a> for NodeRef<'a, EnumPattern<'a>> {
+    fn get_span(&self, ctx: &IrCtx<'a>) -> Option<Span<'a>> {
+        Some(ctx[*self].span.clone())
+    }
+}
+
+impl<'a> Spanned<'a> for NodeRef<'a, LetElse<'a>> {
+    fn get_span(&self, ctx: &IrCtx<'a>) -> Option<Span<'a>> {
+        Some(ctx[*self].span.clone())
+    }
+}
+
+impl<'a> Typed<'a> for NodeRef<'a, Match<'a>> {
+    fn eval_type(
+        &self,
+        _symbols: &mut SymbolTableZipper<'a>,
+        ctx: &mut IrCtx<'a>,
+    ) -> Result<TypeSignature<'a>, TypeEvalError<'a>> {
+        Ok(ctx[*self].type_sig.cloned())
+    }
+
+    fn specified_type(&self, ctx: &IrCtx<'a>) -> Option<TypeSignature<'a>> {
+        Some(ctx[*self].type_sig.cloned())
+    }
+
+    fn specify_type(
+        &self,
+        ctx: &mut IrCtx<'a>,
+        new_type: TypeSignature<'a>,
+    ) -> Result<(), TypeEvalError<'a>> {
+        ctx[*self].type_sig = new_type.into();
+        Ok(())
+    }
+}
+
+impl<'a> IrLowerable<'a> for crate::ast::node::pattern_match::Match<'a> {
+    type IrType = Match<'a>;
+
+    fn ir_lower(self, ctx: &mut IrCtx<'a>) -> NodeRef<'a, Self::IrType> {
+        let scrutinee = self.scrutinee.ir_lower(ctx);
+        let arms = self.arms.into_iter().map(|arm| arm.ir_lower(ctx)).collect();
+
+        let mtch = Match {
+            scrutinee,
+            arms,
+            type_sig: LateInit::empty(),
+            span: self.span,
+        }
+        .allocate(ctx);
+
+        ctx[mtch].type_sig = ctx.make_type_var(TypeSignatureParent::Match(mtch)).into();
+
+        mtch
+    }
+}
+
+impl<'a> IrLowerable<'a> for crate::ast::node::pattern_match::MatchArm<'a> {
+    type IrType = MatchArm<'a>;
+
+    fn ir_lower(self, ctx: &mut IrCtx<'a>) -> NodeRef<'a, Self::IrType> {
+        let pattern = self.pattern.map(|pattern| pattern.ir_lower(ctx));
+
+        let (body, result) = match self.body {
+            crate::ast::node::pattern_match::MatchArmBody::Expression(expr) => {
+                let body = StmtBlock(Vec::new()).allocate(ctx);
+                (body, Some((*expr).ir_lower(ctx)))
+            }
+            crate::ast::node::pattern_match::MatchArmBody::Block(stmt) => {
+                let body = (*stmt).ir_lower(ctx);
+
+                // a trailing expression statement is the value of the arm
+                let result = match ctx[body].0.last().map(|stmt| ctx[*stmt].clone()) {
+                    Some(Stmt::Expression(expr)) => {
+                        ctx[body].0.pop();
+                        Some(expr)
+                    }
+                    _ => None,
+                };
+
+                (body, result)
+            }
+        };
+
+        let arm = MatchArm {
+            pattern,
+            body,
+            result,
+            scope_ident: LateInit::empty(),
+            span: self.span,
+        }
+        .allocate(ctx);
+
+        ctx[arm].scope_ident = ctx.make_anon_ident(IdentParent::MatchArmScope(arm)).into();
+
+        arm
+    }
+}
+
+impl<'a> IrLowerable<'a> for crate::ast::node::pattern_match::EnumPattern<'a> {
+    type IrType = EnumPattern<'a>;
+
+    fn ir_lower(self, ctx: &mut IrCtx<'a>) -> NodeRef<'a, Self::IrType> {
+        let pattern = EnumPattern {
+            enum_name: LateInit::empty(),
+            enum_value: LateInit::empty(),
+            bindings: Vec::new(),
+            span: self.span,
+        }
+        .allocate(ctx);
+
+        ctx[pattern].enum_value = ctx
+            .make_unresolved_ident(
+                self.enum_value,
+                IdentParent::EnumPatternValueName(pattern).into(),
+            )
+            .into();
+
+        let mut bindings = Vec::with_capacity(self.bindings.len());
+        for (i, binding) in self.bindings.into_iter().enumerate() {
+            bindings.push(PatternBinding {
+                name: binding
+                    .map(|name| ctx.make_ident(name, IdentParent::PatternBindingName(pattern, i))),
+                type_sig: ctx
+                    .make_type_var(TypeSignatureParent::PatternBinding(pattern, i))
+                    .into(),
+            });
+        }
+
+        ctx[pattern].bindings = bindings;
+
+        pattern
+    }
+}
+
+impl<'a> IrLowerable<'a> for crate::ast::node::pattern_match::LetElse<'a> {
+    type IrType = LetElse<'a>;
+
+    fn ir_lower(self, ctx: &mut IrCtx<'a>) -> NodeRef<'a, Self::IrType> {
+        let pattern = self.pattern.ir_lower(ctx);
+        let value = self.value.ir_lower(ctx);
+        let else_body = (*self.else_body).ir_lower(ctx);
+
+        let let_else = LetElse {
+            pattern,
+            value,
+            else_body,
+            else_scope_ident: LateInit::empty(),
+            span: self.span,
+        }
+        .allocate(ctx);
+
+        ctx[let_else].else_scope_ident = ctx
+            .make_anon_ident(IdentParent::LetElseScope(let_else))
+            .into();
+
+        let_else
+    }
+}
@@ -0,0 +1,223 @@
+use crate::{
+    error_message::error_formatter::Spanned, ir::context::IrCtx, parser::Span,
+    symbols::symbol_table::symbol_table_zipper::SymbolTableZipper,
+};
+
+use super::{
+    expression::Expr,
+    function::FunctionCall,
+    identifier::{Ident, IdentParent},
+    member_access::UnresolvedMemberAccess,
+    type_signature::{
+        TypeEvalError, TypeSignature, TypeSignatureContext, TypeSignatureParent,
+        TypeSignatureValue, Typed,
+    },
+    IrAlloc, NodeRef,
+};
+
+/// A field of a record read through its name, e.g. `person.name`
+#[derive(Debug)]
+pub struct RecordAccess<'a> {
+    pub record: NodeRef<'a, Expr<'a>>,
+    pub field_name: Ident<'a>,
+    /// Whether the field is accessed through optional chaining, in which case the
+    /// record may be absent and so may the field
+    pub optional: bool,
+    pub span: Span<'a>,
+}
+
+impl<'a> Spanned<'a> for NodeRef<'a, RecordAccess<'a>> {
+    fn get_span(&self, ctx: &IrCtx<'a>) -> Option<Span<'a>> {
+        Some(ctx[*self].span.clone())
+    }
+}
+
+impl<'a> Typed<'a> for NodeRef<'a, RecordAccess<'a>> {
+    fn eval_type(
+        &self,
+        symbols: &mut SymbolTableZipper<'a>,
+        ctx: &mut IrCtx<'a>,
+    ) -> Result<TypeSignature<'a>, TypeEvalError<'a>> {
+        let record_type = ctx[*self].record.clone().eval_type(symbols, ctx)?;
+        let optional = ctx[*self].optional;
+        let record_type = match &ctx[&record_type] {
+            TypeSignatureValue::Optional(value) if optional => value.clone(),
+            _ => record_type,
+        };
+
+        let field_name = ctx[*self].field_name;
+        let field_type = field_name
+            .value(ctx)
+            .and_then(|name| record_type.record_field_type(ctx, name))
+            .ok_or(TypeEvalError::UnknownIdent(field_name))?;
+
+        // optional chaining does not nest optionals
+        if !optional || matches!(ctx[&field_type], TypeSignatureValue::Optional(_)) {
+            return Ok(field_type);
+        }
+
+        Ok(ctx.get_type_sig(
+            TypeSignatureValue::Optional(field_type),
+            TypeSignatureContext {
+                parent: TypeSignatureParent::Expr(ctx[*self].record),
+                type_span: None,
+            }
+            .alloc(),
+        ))
+    }
+}
+
+impl<'a> NodeRef<'a, UnresolvedMemberAccess<'a>> {
+    /// Resolves the member access into the access of a record field,
+    /// which is called if the member access has arguments, e.g. `location.reload()`
+    pub fn resolve_record_access(
+        &self,
+        ctx: &mut IrCtx<'a>,
+        record: NodeRef<'a, Expr<'a>>,
+    ) -> Expr<'a> {
+        let rec_acc = RecordAccess {
+            record,
+            field_name: *ctx[*self].member_name,
+            optional: ctx[*self].optional,
+            span: ctx[*self].span.clone(),
+        }
+        .allocate(ctx);
+
+        ctx[rec_acc].field_name.parent = IdentParent::RecordAccessFieldName(rec_acc).into();
+
+        match ctx[*self].items.clone() {
+            Some((args, args_span)) => {
+                let func = Expr::RecordAccess(rec_acc).allocate(ctx);
+
+                let func_call = FunctionCall {
+                    func,
+                    arg_labels: vec![None; args.len()],
+                    args,
+                    args_span,
+                    instance: None,
+                    tried: false,
+                }
+                .allocate(ctx);
+
+                Expr::FunctionCall(func_call)
+            }
+            None => Expr::RecordAccess(rec_acc),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::assert_matches;
+
+    use crate::{
+        ir::{
+            node::type_signature::TypeEvalError,
+            test_utils::utils::{lowered_ir, type_check},
+        },
+        type_checker::{check_assignment::AssignmentError, TypeCheckerError},
+    };
+
+    #[test]
+    fn test_record_literal() {
+        let mut ir = lowered_ir(
+            "
+        let bob = { name: \"Bob\", address: { city: \"Berlin\" } }
+        let name: String = bob.name
+        let city: String = bob.address.city
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+
+        let mut ir =
+            lowered_ir("let bob = { name: \"Bob\" }; let name: Number = bob.name").unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ConflictingTypes(_, _))
+        );
+    }
+
+    #[test]
+    fn test_external_record() {
+        let mut ir = lowered_ir(
+            "
+        external location: { href: String, reload: () -> Void }
+        external findUser: (Number) -> { name: String, email: String? }?
+
+        let href: String = location.href
+        location.reload()
+        let email: String? = findUser(1)?.email
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_unknown_record_field() {
+        let mut ir = lowered_ir(
+            "
+        external location: { href: String }
+        let host = location.host
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::TypeEval(TypeEvalError::UnknownIdent(_)))
+        );
+    }
+
+    #[test]
+    fn test_declared_record_type() {
+        let mut ir =
+            lowered_ir("let r: { name: String } = { name: \"a\", age: 3 }; let age = r.age")
+                .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::TypeEval(TypeEvalError::UnknownIdent(_)))
+        );
+
+        for code in [
+            "let r: { name: String } = { name: \"a\", age: 3 }; let q: { name: String, age: Number } = r",
+            "let r: { a: Number? } = { a: 1 }; let z: Number = r.a",
+        ] {
+            let mut ir = lowered_ir(code).unwrap();
+
+            assert_matches!(
+                type_check(&mut ir).1,
+                Err(TypeCheckerError::ConflictingTypes(_, _)),
+                "{code}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_duplicate_record_field() {
+        let mut ir = lowered_ir("let r = { a: 1, a: 2 }").unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::DuplicateField(_, _))
+        );
+    }
+
+    #[test]
+    fn test_record_field_not_assignable() {
+        let mut ir = lowered_ir("var point = { x: 1, y: 2 }; point.x = 3").unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::AssignmentError(
+                _,
+                AssignmentError::NotLValue(_)
+            ))
+        );
+    }
+}
@@ -6,12 +6,17 @@ use crate::{
 use super::{
     control_flow::IfStmt,
     enumeration::Enum,
+    error_handling::DoCatch,
     expression::Expr,
+    extension::Extension,
     external::ExternalObject,
     function::Function,
     identifier::{Ident, IdentParent, Identifiable},
+    loops::{ForLoop, LoopJump, WhileLoop},
+    pattern_match::LetElse,
     structure::Struct,
     traits::Trait,
+    type_alias::TypeAlias,
     type_signature::{Mutability, TypeEvalError, TypeSignature, TypeSignatureParent, Typed},
     IrAlloc, NodeRef,
 };
@@ -19,6 +24,20 @@ use super::{
 #[derive(Debug, Clone)]
 pub struct StmtBlock<'a>(pub Vec<NodeRef<'a, Stmt<'a>>>);
 
+impl<'a> NodeRef<'a, StmtBlock<'a>> {
+    /// Whether the end of the block can never be reached, because every path returns or throws
+    pub fn diverges(&self, ctx: &IrCtx<'a>) -> bool {
+        ctx[*self].0.iter().any(|stmt| match &ctx[*stmt] {
+            Stmt::Return(_) | Stmt::Throw(_) | Stmt::LoopJump(_) => true,
+            Stmt::IfBranch(ifb) => ctx[*ifb]
+                .else_body
+                .is_some_and(|else_body| ctx[*ifb].body.diverges(ctx) && else_body.diverges(ctx)),
+            Stmt::DoCatch(dc) => ctx[*dc].body.diverges(ctx) && ctx[*dc].catch_body.diverges(ctx),
+            _ => false,
+        })
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum Stmt<'a> {
     VariableDecl(NodeRef<'a, VarDecl<'a>>),
@@ -26,10 +45,18 @@ pub enum Stmt<'a> {
     StructDecl(NodeRef<'a, Struct<'a>>),
     EnumDecl(NodeRef<'a, Enum<'a>>),
     TraitDecl(NodeRef<'a, Trait<'a>>),
+    Extension(NodeRef<'a, Extension<'a>>),
     Expression(NodeRef<'a, Expr<'a>>),
     Return(NodeRef<'a, Expr<'a>>),
+    Throw(NodeRef<'a, Expr<'a>>),
     ExternObj(NodeRef<'a, ExternalObject<'a>>),
+    TypeAlias(NodeRef<'a, TypeAlias<'a>>),
     IfBranch(NodeRef<'a, IfStmt<'a>>),
+    LetElse(NodeRef<'a, LetElse<'a>>),
+    DoCatch(NodeRef<'a, DoCatch<'a>>),
+    WhileLoop(NodeRef<'a, WhileLoop<'a>>),
+    ForLoop(NodeRef<'a, ForLoop<'a>>),
+    LoopJump(NodeRef<'a, LoopJump<'a>>),
 }
 
 #[derive(Debug)]
@@ -37,6 +64,8 @@ pub struct VarDecl<'a> {
     pub name: LateInit<Ident<'a>>,
     pub mutability: Mutability,
     pub type_sig: LateInit<TypeSignature<'a>>,
+    /// Whether the type is declared, rather than inferred from the value
+    pub type_declared: bool,
     pub value: NodeRef<'a, Expr<'a>>,
 }
 
@@ -52,6 +81,12 @@ impl<'a> Typed<'a> for NodeRef<'a, VarDecl<'a>> {
         symbols: &mut SymbolTableZipper<'a>,
         ctx: &mut IrCtx<'a>,
     ) -> Result<TypeSignature<'a>, TypeEvalError<'a>> {
+        // the value is checked to be coercible to the declared type, e.g. to an optional type,
+        // so the variable has the declared type rather than the one of its value
+        if ctx[*self].type_declared {
+            return Ok(ctx[*self].type_sig.cloned());
+        }
+
         ctx[*self].value.clone().eval_type(symbols, ctx)
     }
 
@@ -77,6 +112,7 @@ impl<'a> IrLowerable<'a> for crate::ast::node::statement::VarDecl<'a> {
             name: LateInit::empty(),
             mutability: self.mutability,
             type_sig: LateInit::empty(),
+            type_declared: self.type_sig.is_some(),
             value: self.value.ir_lower(ctx),
         }
         .allocate(ctx);
@@ -0,0 +1,381 @@
+use crate::{
+    error_message::error_formatter::Spanned,
+    ir::context::IrCtx,
+    parser::Span,
+    symbols::symbol_table::{symbol_table_zipper::SymbolTableZipper, SymbolValueItem},
+};
+
+use super::{
+    expression::Expr,
+    function::{Function, FunctionCall},
+    member_access::UnresolvedMemberAccess,
+    structure::{Struct, StructAttr},
+    type_signature::{
+        TypeEvalError, TypeSignature, TypeSignatureContext, TypeSignatureParent,
+        TypeSignatureValue, Typed,
+    },
+    IrAlloc, NodeRef,
+};
+
+/// A member declared in an extension of a struct, which belongs to the struct itself
+/// instead of its values
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StaticMember<'a> {
+    /// The `init` called by `Point(x: 1, y: 2)`
+    Init(NodeRef<'a, Function<'a>>),
+    Method(NodeRef<'a, Function<'a>>),
+    Attr(NodeRef<'a, StructAttr<'a>>),
+}
+
+/// The access of a static member through the name of the struct, e.g. `Point.origin`
+#[derive(Debug)]
+pub struct StaticAccess<'a> {
+    pub struct_expr: NodeRef<'a, Expr<'a>>,
+    pub member: StaticMember<'a>,
+    pub span: Span<'a>,
+}
+
+impl<'a> Spanned<'a> for NodeRef<'a, StaticAccess<'a>> {
+    fn get_span(&self, ctx: &IrCtx<'a>) -> Option<Span<'a>> {
+        Some(ctx[*self].span.clone())
+    }
+}
+
+impl<'a> Typed<'a> for NodeRef<'a, StaticAccess<'a>> {
+    fn eval_type(
+        &self,
+        symbols: &mut SymbolTableZipper<'a>,
+        ctx: &mut IrCtx<'a>,
+    ) -> Result<TypeSignature<'a>, TypeEvalError<'a>> {
+        let init = match ctx[*self].member {
+            StaticMember::Init(init) => init,
+            StaticMember::Method(method) => return method.eval_type(symbols, ctx),
+            StaticMember::Attr(attr) => return attr.eval_type(symbols, ctx),
+        };
+
+        // the init is called like a function returning the new value of the struct
+        let struct_expr = ctx[*self].struct_expr;
+        let mut struct_type = struct_expr.eval_type(symbols, ctx)?;
+        let type_ctx = TypeSignatureContext {
+            parent: TypeSignatureParent::Function(init),
+            type_span: None,
+        }
+        .alloc();
+
+        // a generic struct is initialized as an instance of its type parameters,
+        // which are inferred from the arguments of the call
+        if let Some(st) = struct_expr.named_struct(ctx, symbols) {
+            let type_args = ctx[st]
+                .type_params
+                .clone()
+                .into_iter()
+                .map(|param| param.eval_type(symbols, ctx))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            if !type_args.is_empty() {
+                struct_type = ctx.get_type_sig(
+                    TypeSignatureValue::Instance {
+                        base: struct_type,
+                        args: type_args,
+                    },
+                    type_ctx.clone(),
+                );
+            }
+        }
+
+        let args = ctx[init]
+            .args
+            .clone()
+            .into_iter()
+            .map(|arg| arg.eval_type(symbols, ctx))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(ctx.get_type_sig(
+            TypeSignatureValue::Function {
+                args: args.into(),
+                return_type: struct_type.into(),
+                throws: ctx[init].throws.clone(),
+            },
+            type_ctx,
+        ))
+    }
+}
+
+impl<'a> NodeRef<'a, Expr<'a>> {
+    pub fn named_struct(
+        &self,
+        ctx: &IrCtx<'a>,
+        symbols: &SymbolTableZipper<'a>,
+    ) -> Option<NodeRef<'a, Struct<'a>>> {
+        let Expr::Identifier(ident, _) = &ctx[*self] else {
+            return None;
+        };
+
+        match ctx[symbols.lookup(ctx, **ident)?] {
+            SymbolValueItem::StructDecl(st) => Some(st),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> NodeRef<'a, UnresolvedMemberAccess<'a>> {
+    pub fn resolve_static_access(
+        &self,
+        ctx: &mut IrCtx<'a>,
+        symbols: &SymbolTableZipper<'a>,
+        st: NodeRef<'a, Struct<'a>>,
+        struct_expr: NodeRef<'a, Expr<'a>>,
+    ) -> Result<Expr<'a>, TypeEvalError<'a>> {
+        let member_name = *ctx[*self].member_name;
+        let member = st
+            .lookup_static_member(member_name, ctx, symbols)
+            .ok_or(TypeEvalError::UnknownIdent(member_name))?;
+
+        let st_acc = StaticAccess {
+            struct_expr,
+            member,
+            span: ctx[*self].span.clone(),
+        }
+        .allocate(ctx);
+
+        let Some((args, args_span)) = ctx[*self].items.clone() else {
+            return Ok(Expr::StaticAccess(st_acc));
+        };
+
+        let func = Expr::StaticAccess(st_acc).allocate(ctx);
+        let func_call = FunctionCall {
+            func,
+            arg_labels: vec![None; args.len()],
+            args,
+            args_span,
+            instance: None,
+            tried: false,
+        }
+        .allocate(ctx);
+
+        Ok(Expr::FunctionCall(func_call))
+    }
+}
+
+impl<'a> NodeRef<'a, FunctionCall<'a>> {
+    pub fn resolve_init_call(
+        &self,
+        ctx: &mut IrCtx<'a>,
+        symbols: &SymbolTableZipper<'a>,
+        st: NodeRef<'a, Struct<'a>>,
+    ) -> Option<NodeRef<'a, Function<'a>>> {
+        let init = st.lookup_init(&ctx[*self].arg_labels, ctx, symbols)?;
+
+        let struct_expr = ctx[*self].func;
+        let st_acc = StaticAccess {
+            struct_expr,
+            member: StaticMember::Init(init),
+            span: struct_expr.get_span(ctx).unwrap(),
+        }
+        .allocate(ctx);
+
+        ctx[*self].func = Expr::StaticAccess(st_acc).allocate(ctx);
+
+        Some(init)
+    }
+
+    pub fn called_init(&self, ctx: &IrCtx<'a>) -> Option<NodeRef<'a, Function<'a>>> {
+        match &ctx[ctx[*self].func] {
+            Expr::StaticAccess(st_acc) => match ctx[*st_acc].member {
+                StaticMember::Init(init) => Some(init),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::assert_matches;
+
+    use crate::{
+        ir::test_utils::utils::{lowered_ir, resolve_symbols, type_check},
+        symbols::symbol_resolver::{Declaration, SymbolResolutionError},
+        type_checker::{check_extension::ExtensionError, FunctionError, TypeCheckerError},
+    };
+
+    #[test]
+    fn test_init_overloads() {
+        let mut ir = lowered_ir(
+            "
+        struct Point { let x: Number; let y: Number }
+        extend Point {
+            init(x: Number, y: Number) { self.x = x; self.y = y }
+            init(length: Number) { self.x = length; self.y = length }
+        }
+        let a: Point = Point(x: 1, y: 2)
+        let b: Point = Point(length: 3)
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+
+        let mut ir = lowered_ir(
+            "
+        struct Point { let x: Number }
+        extend Point { init(x: Number) { self.x = x } }
+        let a = Point(x: \"1\")
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ConflictingTypes(_, _))
+        );
+    }
+
+    #[test]
+    fn test_unknown_init() {
+        let mut ir = lowered_ir(
+            "
+        struct Point { let x: Number }
+        extend Point { init(x: Number) { self.x = x } }
+        let a = Point(1)
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            resolve_symbols(&mut ir),
+            Err(SymbolResolutionError::UnknownInit { .. })
+        );
+    }
+
+    #[test]
+    fn test_labels_on_function_call() {
+        let mut ir =
+            lowered_ir("func f(a: Number) -> Number { return a }; let b = f(a: 1)").unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::FunctionError(
+                FunctionError::UnexpectedArgLabels(_)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_static_members() {
+        let mut ir = lowered_ir(
+            "
+        struct Point { let x: Number; let y: Number }
+        extend Point {
+            static let origin = Point { x: 0, y: 0 }
+            static var created = 0
+            static func diagonal(length: Number) -> Point {
+                Point.created = Point.created + 1
+                return Point { x: length, y: length }
+            }
+        }
+        let x: Number = Point.origin.x
+        let p: Point = Point.diagonal(2)
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_static_attr_used_before_declaration() {
+        let mut ir = lowered_ir(
+            "
+        struct Point { let x: Number }
+        let x = Point.origin.x
+        extend Point { static let origin = Point { x: 0 } }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            resolve_symbols(&mut ir),
+            Err(SymbolResolutionError::StaticAttrUsedBeforeDeclaration { .. })
+        );
+    }
+
+    #[test]
+    fn test_static_attr_used_in_own_declaration() {
+        for code in [
+            "struct A { let x: Number }
+            extend A { static let a = A.a }",
+            "struct A { let x: Number }
+            extend A { static let a = A.a + 1 }",
+            "struct A { let x: Number }
+            extend A { static let a: Number = A.a }",
+        ] {
+            let mut ir = lowered_ir(code).unwrap();
+
+            assert_matches!(
+                resolve_symbols(&mut ir),
+                Err(SymbolResolutionError::RecursiveDeclaration {
+                    decl: Declaration::StaticAttr(_),
+                    ident_span: _
+                }),
+                "{code}"
+            );
+        }
+
+        let mut ir = lowered_ir(
+            "
+        struct A { let x: Number }
+        extend A {
+            static let a = 1
+            static let b = A.a + 1
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_duplicate_init() {
+        for code in [
+            "struct Point { let x: Number }
+            extend Point {
+                init(x: Number) { self.x = x }
+                init(x: Number) { self.x = x * 2 }
+            }",
+            "struct Point { let x: Number }
+            extend Point { init(x: Number) { self.x = x } }
+            extend Point { init(x: Number) { self.x = x * 2 } }",
+        ] {
+            let mut ir = lowered_ir(code).unwrap();
+            assert_matches!(
+                type_check(&mut ir).1,
+                Err(TypeCheckerError::ExtensionError(
+                    _,
+                    ExtensionError::DuplicateInit(_, _)
+                )),
+                "{code}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_self_in_static_member() {
+        let mut ir = lowered_ir(
+            "
+        struct Point { let x: Number }
+        extend Point {
+            static func double() -> Number { return self.x * 2 }
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::SelfInStaticMember(_))
+        );
+    }
+}
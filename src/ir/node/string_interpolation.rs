@@ -0,0 +1,45 @@
+use crate::{
+    error_message::error_formatter::Spanned,
+    ir::{context::IrCtx, late_init::LateInit},
+    parser::Span,
+};
+
+use super::{expression::Expr, identifier::Ident, NodeRef};
+
+/// The trait that values other than strings, numbers and booleans have to conform to when
+/// they are interpolated. It is not built in, but looked up by its name where the string is
+/// interpolated, so a program declares it with the `toString` javascript calls
+pub const EXPRESSIBLE_TRAIT_NAME: &str = "StringExpressible";
+
+#[derive(Debug)]
+pub struct StringInterpolation<'a> {
+    pub parts: Vec<StringPart<'a>>,
+    /// The trait named `EXPRESSIBLE_TRAIT_NAME` in the scope of the string, if any
+    pub expressible_trait: LateInit<Ident<'a>>,
+    pub span: Span<'a>,
+}
+
+#[derive(Debug, Clone)]
+pub enum StringPart<'a> {
+    Literal(String),
+    Expr(NodeRef<'a, Expr<'a>>),
+}
+
+impl<'a> NodeRef<'a, StringInterpolation<'a>> {
+    pub fn exprs(&self, ctx: &IrCtx<'a>) -> Vec<NodeRef<'a, Expr<'a>>> {
+        ctx[*self]
+            .parts
+            .iter()
+            .filter_map(|part| match part {
+                StringPart::Expr(expr) => Some(*expr),
+                StringPart::Literal(_) => None,
+            })
+            .collect()
+    }
+}
+
+impl<'a> Spanned<'a> for NodeRef<'a, StringInterpolation<'a>> {
+    fn get_span(&self, ctx: &IrCtx<'a>) -> Option<Span<'a>> {
+        Some(ctx[*self].span.clone())
+    }
+}
@@ -2,12 +2,17 @@ use crate::{
     error_message::error_formatter::Spanned,
     ir::{ast_lowering::IrLowerable, context::IrCtx, late_init::LateInit},
     parser::Span,
-    symbols::symbol_table::symbol_table_zipper::SymbolTableZipper,
+    symbols::symbol_table::{symbol_table_zipper::SymbolTableZipper, SymbolValueItem},
 };
 
 use super::{
     expression::Expr,
+    function::{Function, FunctionCall},
+    generics::{declared_type_param, lower_type_params, type_param_names, TypeParam},
     identifier::{Ident, IdentKey, IdentParent, Identifiable},
+    member_access::UnresolvedMemberAccess,
+    static_access::StaticMember,
+    traits::{Trait, TraitFuncAttr},
     type_signature::{
         Mutability, TypeEvalError, TypeSignature, TypeSignatureContext, TypeSignatureParent,
         TypeSignatureValue, Typed,
@@ -18,6 +23,7 @@ use super::{
 #[derive(Debug)]
 pub struct Struct<'a> {
     pub name: LateInit<Ident<'a>>,
+    pub type_params: Vec<NodeRef<'a, TypeParam<'a>>>,
     pub attrs: Vec<NodeRef<'a, StructAttr<'a>>>,
 }
 
@@ -48,6 +54,9 @@ pub struct StructInitValue<'a> {
 pub struct StructAccess<'a> {
     pub struct_expr: NodeRef<'a, Expr<'a>>,
     pub attr_name: Ident<'a>,
+    /// Whether the attribute is accessed through optional chaining, in which case the
+    /// struct may be absent and so may the attribute
+    pub optional: bool,
 }
 
 impl<'a> Spanned<'a> for NodeRef<'a, StructAccess<'a>> {
@@ -68,6 +77,55 @@ impl<'a> NodeRef<'a, Struct<'a>> {
             .find(|attr| IdentKey::idents_eq(ctx, *ctx[**attr].name, ident))
             .map(|attr| *attr)
     }
+
+    /// Looks up a method in the extensions of the struct visible from the current scope,
+    /// falling back to the default implementations of the traits it conforms to
+    pub fn lookup_method(
+        &self,
+        ident: Ident<'a>,
+        ctx: &IrCtx<'a>,
+        symbols: &SymbolTableZipper<'a>,
+    ) -> Option<NodeRef<'a, Function<'a>>> {
+        let extensions = symbols.lookup_extensions(ctx, *ctx[*self].name);
+
+        extensions
+            .iter()
+            .find_map(|ext| ext.lookup_method(ident, ctx))
+            .or_else(|| {
+                extensions
+                    .iter()
+                    .filter_map(|ext| ctx[*ext].trait_name)
+                    .filter_map(|trait_name| match ctx[symbols.lookup(ctx, trait_name)?] {
+                        SymbolValueItem::TraitDecl(tr) => Some(tr),
+                        _ => None,
+                    })
+                    .find_map(|tr| ctx[tr.lookup_attr(ident, ctx)?].default_impl)
+            })
+    }
+
+    pub fn lookup_static_member(
+        &self,
+        ident: Ident<'a>,
+        ctx: &IrCtx<'a>,
+        symbols: &SymbolTableZipper<'a>,
+    ) -> Option<StaticMember<'a>> {
+        symbols
+            .lookup_extensions(ctx, *ctx[*self].name)
+            .iter()
+            .find_map(|ext| ext.lookup_static_member(ident, ctx))
+    }
+
+    pub fn lookup_init(
+        &self,
+        labels: &[Option<&'a str>],
+        ctx: &IrCtx<'a>,
+        symbols: &SymbolTableZipper<'a>,
+    ) -> Option<NodeRef<'a, Function<'a>>> {
+        symbols
+            .lookup_extensions(ctx, *ctx[*self].name)
+            .iter()
+            .find_map(|ext| ext.lookup_init(labels, ctx))
+    }
 }
 
 impl<'a> Identifiable<'a> for Struct<'a> {
@@ -164,8 +222,11 @@ impl<'a> IrLowerable<'a> for crate::ast::node::structure::Struct<'a> {
             .map(|attr| attr.ir_lower(ctx))
             .collect();
 
+        let type_params = lower_type_params(ctx, self.type_params);
+
         let st = Struct {
             name: LateInit::empty(),
+            type_params,
             attrs: ir_attrs,
         }
         .allocate(ctx);
@@ -299,32 +360,174 @@ impl<'a> Typed<'a> for NodeRef<'a, StructAccess<'a>> {
         symbols: &mut SymbolTableZipper<'a>,
         ctx: &mut IrCtx<'a>,
     ) -> Result<TypeSignature<'a>, TypeEvalError<'a>> {
-        self.lookup_attr(ctx, symbols)?.eval_type(symbols, ctx)
+        let attr_type = self.attr_type(ctx, symbols)?;
+
+        // optional chaining does not nest optionals
+        if !ctx[*self].optional || matches!(ctx[&attr_type], TypeSignatureValue::Optional(_)) {
+            return Ok(attr_type);
+        }
+
+        Ok(ctx.get_type_sig(
+            TypeSignatureValue::Optional(attr_type),
+            TypeSignatureContext {
+                parent: TypeSignatureParent::Expr(ctx[*self].struct_expr),
+                type_span: None,
+            }
+            .alloc(),
+        ))
     }
 }
 
 impl<'a> NodeRef<'a, StructAccess<'a>> {
-    pub fn lookup_attr(
+    /// The type of the accessed attribute or method, regardless of optional chaining
+    fn attr_type(
         &self,
         ctx: &mut IrCtx<'a>,
         symbols: &mut SymbolTableZipper<'a>,
-    ) -> Result<NodeRef<'a, StructAttr<'a>>, TypeEvalError<'a>> {
+    ) -> Result<TypeSignature<'a>, TypeEvalError<'a>> {
+        let st = match self.lookup_struct(ctx, symbols) {
+            Ok(st) => st,
+            Err(TypeEvalError::AccessNonStruct(_)) => return self.trait_attr_type(ctx, symbols),
+            Err(err) => return Err(err),
+        };
+        let attr_name = ctx[*self].attr_name;
+
+        let attr_type = match st.lookup_attr(attr_name, ctx) {
+            Some(attr) => attr.eval_type(symbols, ctx)?,
+            None => self.lookup_method(ctx, symbols)?.eval_type(symbols, ctx)?,
+        };
+
+        // attributes and methods of a generic struct are typed by the type arguments of the instance
+        let st_type = self.struct_type(ctx, symbols)?;
+        let (_, type_args) = st_type.split_instance(ctx);
+        if type_args.is_empty() {
+            return Ok(attr_type);
+        }
+
+        let type_params = type_param_names(ctx, &ctx[st].type_params);
+
+        Ok(attr_type.substitute(ctx, &type_params, &type_args))
+    }
+
+    /// The type of the accessed trait method, in which `Self` stands for the type parameter
+    /// bound to the trait
+    fn trait_attr_type(
+        &self,
+        ctx: &mut IrCtx<'a>,
+        symbols: &mut SymbolTableZipper<'a>,
+    ) -> Result<TypeSignature<'a>, TypeEvalError<'a>> {
+        let attr_type = self
+            .lookup_trait_attr(ctx, symbols)?
+            .eval_type(symbols, ctx)?;
+
+        let st_type = self.struct_type(ctx, symbols)?;
+        if !matches!(ctx[&st_type], TypeSignatureValue::TypeParameter { .. }) {
+            return Ok(attr_type);
+        }
+
+        let tr_type = self.lookup_trait(ctx, symbols)?.eval_type(symbols, ctx)?;
+        Ok(attr_type.replace_self(ctx, &tr_type, &st_type))
+    }
+
+    /// The type of the accessed struct, which is unwrapped from its optional for optional chaining
+    pub fn struct_type(
+        &self,
+        ctx: &mut IrCtx<'a>,
+        symbols: &mut SymbolTableZipper<'a>,
+    ) -> Result<TypeSignature<'a>, TypeEvalError<'a>> {
         let st_type = ctx[*self].struct_expr.clone().eval_type(symbols, ctx)?;
-        let struct_name = match &ctx[&st_type] {
+
+        match &ctx[&st_type] {
+            TypeSignatureValue::Optional(value) if ctx[*self].optional => Ok(value.clone()),
+            _ => Ok(st_type),
+        }
+    }
+
+    pub fn lookup_struct(
+        &self,
+        ctx: &mut IrCtx<'a>,
+        symbols: &mut SymbolTableZipper<'a>,
+    ) -> Result<NodeRef<'a, Struct<'a>>, TypeEvalError<'a>> {
+        let st_type = self.struct_type(ctx, symbols)?;
+        let struct_name = match &ctx[&st_type.split_instance(ctx).0] {
             TypeSignatureValue::Struct { name } => *name,
             _ => return Err(TypeEvalError::AccessNonStruct(st_type)),
         };
 
-        let st = symbols
+        Ok(symbols
             .lookup(ctx, struct_name)
             .ok_or(TypeEvalError::UnknownIdent(struct_name))?
-            .unwrap_struct(ctx);
+            .unwrap_struct(ctx))
+    }
+
+    pub fn lookup_attr(
+        &self,
+        ctx: &mut IrCtx<'a>,
+        symbols: &mut SymbolTableZipper<'a>,
+    ) -> Result<NodeRef<'a, StructAttr<'a>>, TypeEvalError<'a>> {
+        let st = self.lookup_struct(ctx, symbols)?;
 
         let attr_name = ctx[*self].attr_name;
         st.lookup_attr(attr_name, ctx)
             .ok_or(TypeEvalError::UnknownIdent(attr_name))
     }
 
+    /// Looks up a method declared in an extension of the accessed struct
+    pub fn lookup_method(
+        &self,
+        ctx: &mut IrCtx<'a>,
+        symbols: &mut SymbolTableZipper<'a>,
+    ) -> Result<NodeRef<'a, Function<'a>>, TypeEvalError<'a>> {
+        let st = self.lookup_struct(ctx, symbols)?;
+
+        let method_name = ctx[*self].attr_name;
+        st.lookup_method(method_name, ctx, symbols)
+            .ok_or(TypeEvalError::UnknownIdent(method_name))
+    }
+
+    /// Whether the accessed member is a method, which has to be bound to the struct
+    /// when it is used as a value instead of being called
+    pub fn is_method(&self, ctx: &mut IrCtx<'a>, symbols: &mut SymbolTableZipper<'a>) -> bool {
+        self.lookup_method(ctx, symbols).is_ok() || self.lookup_trait_attr(ctx, symbols).is_ok()
+    }
+
+    /// Looks up a method of the accessed trait, for `self` inside of the default methods of a trait
+    /// and for values whose type is a type parameter bound to a trait
+    pub fn lookup_trait_attr(
+        &self,
+        ctx: &mut IrCtx<'a>,
+        symbols: &mut SymbolTableZipper<'a>,
+    ) -> Result<NodeRef<'a, TraitFuncAttr<'a>>, TypeEvalError<'a>> {
+        let tr = self.lookup_trait(ctx, symbols)?;
+
+        let attr_name = ctx[*self].attr_name;
+        tr.lookup_attr(attr_name, ctx)
+            .ok_or(TypeEvalError::UnknownIdent(attr_name))
+    }
+
+    fn lookup_trait(
+        &self,
+        ctx: &mut IrCtx<'a>,
+        symbols: &mut SymbolTableZipper<'a>,
+    ) -> Result<NodeRef<'a, Trait<'a>>, TypeEvalError<'a>> {
+        let tr_type = self.struct_type(ctx, symbols)?;
+        match &ctx[&tr_type] {
+            TypeSignatureValue::Trait { name }
+                if ctx[*self].struct_expr.is_trait_self(ctx, symbols) =>
+            {
+                match symbols.lookup(ctx, *name).map(|sym| ctx[sym]) {
+                    Some(SymbolValueItem::TraitDecl(tr)) => Some(tr),
+                    _ => None,
+                }
+            }
+            TypeSignatureValue::TypeParameter { name } => {
+                declared_type_param(*name).and_then(|param| param.bound_trait(ctx, symbols))
+            }
+            _ => None,
+        }
+        .ok_or(TypeEvalError::AccessNonStruct(tr_type))
+    }
+
     pub fn lookup_attr_chain<'c>(
         &self,
         ctx: &mut IrCtx<'a>,
@@ -354,20 +557,87 @@ impl<'a> NodeRef<'a, StructAccess<'a>> {
     }
 }
 
+impl<'a> NodeRef<'a, UnresolvedMemberAccess<'a>> {
+    pub fn resolve_struct_access(
+        &self,
+        ctx: &mut IrCtx<'a>,
+        struct_expr: NodeRef<'a, Expr<'a>>,
+    ) -> Expr<'a> {
+        let st_acc = StructAccess {
+            struct_expr,
+            attr_name: *ctx[*self].member_name,
+            optional: ctx[*self].optional,
+        }
+        .allocate(ctx);
+
+        ctx[st_acc].attr_name.parent = IdentParent::StructAccessAttrName(st_acc).into();
+
+        match ctx[*self].items.clone() {
+            Some((args, args_span)) => {
+                let func = Expr::StructAccess(st_acc).allocate(ctx);
+
+                let func_call = FunctionCall {
+                    func,
+                    arg_labels: vec![None; args.len()],
+                    args,
+                    args_span,
+                    instance: None,
+                    tried: false,
+                }
+                .allocate(ctx);
+
+                Expr::FunctionCall(func_call)
+            }
+            None => Expr::StructAccess(st_acc),
+        }
+    }
+}
+
 impl<'a> NodeRef<'a, StructInit<'a>> {
     pub fn struct_name(&self, ctx: &IrCtx<'a>) -> Option<Ident<'a>> {
-        match &ctx[&*ctx[*self].type_sig] {
+        match &ctx[&ctx[*self].type_sig.split_instance(ctx).0] {
             TypeSignatureValue::Struct { name } => Some(*name),
             _ => None,
         }
     }
+
+    /// Whether the struct init without a struct name is a record literal, `{ IDENT: EXPR, ... }`
+    pub fn is_record(&self, ctx: &IrCtx<'a>) -> bool {
+        matches!(ctx[&*ctx[*self].type_sig], TypeSignatureValue::Record(_))
+    }
+
+    /// The record type made up of the names and the types of the values
+    pub fn values_record_type(
+        &self,
+        ctx: &mut IrCtx<'a>,
+        symbols: &mut SymbolTableZipper<'a>,
+    ) -> Result<TypeSignature<'a>, TypeEvalError<'a>> {
+        let mut fields = Vec::new();
+        for val in ctx[*self].values.clone() {
+            let name = ctx[val].name.value(ctx).unwrap();
+            fields.push((name, ctx[val].value.clone().eval_type(symbols, ctx)?));
+        }
+
+        Ok(ctx.get_record_type_sig(
+            fields,
+            TypeSignatureContext {
+                parent: TypeSignatureParent::StructInit(*self),
+                type_span: None,
+            }
+            .alloc(),
+        ))
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::assert_matches::assert_matches;
+    use std::assert_matches;
 
-    use crate::ir::test_utils::utils::{lowered_ir, type_check};
+    use crate::{
+        ir::test_utils::utils::{lowered_ir, resolve_symbols, type_check},
+        symbols::symbol_resolver::SymbolResolutionError,
+        type_checker::{check_assignment::AssignmentError, TypeCheckerError},
+    };
 
     #[test]
     fn test_nested_struct() {
@@ -389,4 +659,105 @@ mod tests {
 
         assert_matches!(type_check(&mut ir).1, Ok(_))
     }
+
+    #[test]
+    fn test_optional_chaining() {
+        let mut ir = lowered_ir(
+            "
+        struct Address { let city: String }
+        struct Person { let address: Address? }
+
+        func city(person: Person?) -> String? {
+            return person?.address?.city
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+
+        let mut ir = lowered_ir(
+            "
+        struct Person { let name: String }
+        func name(person: Person?) -> String {
+            return person?.name
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ConflictingTypes(_, _))
+        );
+    }
+
+    #[test]
+    fn test_optional_chaining_method_call() {
+        let mut ir = lowered_ir(
+            "
+        struct Counter { let count: Number }
+        extend Counter { func next(step: Number) -> Number { return self.count + step } }
+
+        let counter: Counter? = nil
+        let next: Number? = counter?.next(1)
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+
+        let mut ir = lowered_ir(
+            "
+        struct Counter { let count: Number }
+        extend Counter { func next() -> Number { return self.count + 1 } }
+
+        let counter: Counter? = nil
+        let next: Number = counter?.next()
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ConflictingTypes(_, _))
+        );
+    }
+
+    #[test]
+    fn test_access_optional_without_chaining() {
+        let mut ir = lowered_ir(
+            "
+        struct Person { let name: String }
+        let person: Person? = nil
+        let name = person.name
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            resolve_symbols(&mut ir),
+            Err(SymbolResolutionError::InvalidMemberAccessType { .. })
+        );
+    }
+
+    #[test]
+    fn test_assign_optional_chain() {
+        let mut ir = lowered_ir(
+            "
+        struct Person { var name: String }
+        let person: Person? = Person { name: \"Alice\" }
+        person?.name = \"Bob\"
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::AssignmentError(
+                _,
+                AssignmentError::NotLValue(_)
+            ))
+        );
+    }
 }
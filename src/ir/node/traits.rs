@@ -7,14 +7,16 @@ use crate::{
         node::{identifier::IdentParent, type_signature::TypeSignatureParent, IrAlloc},
     },
     parser::Span,
-    symbols::symbol_table::symbol_table_zipper::SymbolTableZipper,
+    symbols::symbol_table::{symbol_table_zipper::SymbolTableZipper, SymbolValueItem},
 };
 
 use super::{
-    function::FunctionArg,
-    identifier::{Ident, Identifiable},
+    expression::Expr,
+    function::{Function, FunctionArg},
+    identifier::{Ident, IdentKey, Identifiable},
+    operator::BinaryOperator,
     type_signature::{
-        TypeEvalError, TypeSignature, TypeSignatureContext, TypeSignatureValue, Typed,
+        BuiltinType, TypeEvalError, TypeSignature, TypeSignatureContext, TypeSignatureValue, Typed,
     },
     NodeRef,
 };
@@ -23,6 +25,12 @@ use super::{
 pub struct Trait<'a> {
     pub name: LateInit<Ident<'a>>,
     pub attrs: Vec<NodeRef<'a, TraitFuncAttr<'a>>>,
+    pub operators: Vec<NodeRef<'a, TraitFuncAttr<'a>>>,
+    /// The implicit `self` argument of the default methods, typed as the trait itself
+    pub self_name: LateInit<Ident<'a>>,
+    /// The `Self` type, which is the trait itself inside of the trait and
+    /// the conforming type when matching the declarations of an extension
+    pub self_type_name: LateInit<Ident<'a>>,
     pub span: Span<'a>,
 }
 
@@ -31,9 +39,66 @@ pub struct TraitFuncAttr<'a> {
     pub name: LateInit<Ident<'a>>,
     pub args: Vec<NodeRef<'a, FunctionArg<'a>>>,
     pub return_type: Option<TypeSignature<'a>>,
+    pub throws: Option<TypeSignature<'a>>,
+    /// The default implementation, inherited by conforming types that do not override it
+    pub default_impl: Option<NodeRef<'a, Function<'a>>>,
     pub span: Span<'a>,
 }
 
+impl<'a> NodeRef<'a, Trait<'a>> {
+    pub fn lookup_attr(
+        &self,
+        ident: Ident<'a>,
+        ctx: &IrCtx<'a>,
+    ) -> Option<NodeRef<'a, TraitFuncAttr<'a>>> {
+        ctx[*self]
+            .attrs
+            .iter()
+            .find(|attr| IdentKey::idents_eq(ctx, *ctx[**attr].name, ident))
+            .copied()
+    }
+
+    pub fn lookup_operator(
+        &self,
+        operator: BinaryOperator,
+        ctx: &IrCtx<'a>,
+    ) -> Option<NodeRef<'a, TraitFuncAttr<'a>>> {
+        ctx[*self]
+            .operators
+            .iter()
+            .find(|op| op.overloaded_operator(ctx) == Some(operator))
+            .copied()
+    }
+
+    pub fn has_default_impls(&self, ctx: &IrCtx<'a>) -> bool {
+        ctx[*self]
+            .attrs
+            .iter()
+            .any(|attr| ctx[*attr].default_impl.is_some())
+    }
+
+    pub fn has_default_operators(&self, ctx: &IrCtx<'a>) -> bool {
+        ctx[*self]
+            .operators
+            .iter()
+            .any(|op| ctx[*op].default_impl.is_some())
+    }
+}
+
+impl<'a> NodeRef<'a, Expr<'a>> {
+    /// Whether the expression is the `self` of a default method of a trait
+    pub fn is_trait_self(&self, ctx: &IrCtx<'a>, symbols: &SymbolTableZipper<'a>) -> bool {
+        let Expr::Identifier(ident, _) = &ctx[*self] else {
+            return false;
+        };
+
+        matches!(
+            symbols.lookup(ctx, **ident).map(|sym| ctx[sym]),
+            Some(SymbolValueItem::TraitSelfValue(_))
+        )
+    }
+}
+
 impl<'a> Identifiable<'a> for Trait<'a> {
     fn name(&self, _ctx: &IrCtx<'a>) -> Ident<'a> {
         *self.name
@@ -51,12 +116,35 @@ impl<'a> IrLowerable<'a> for crate::ast::node::traits::Trait<'a> {
                 .into_iter()
                 .map(|attr| attr.ir_lower(ctx))
                 .collect(),
-            span: self.span,
+            operators: self
+                .operators
+                .into_iter()
+                .map(|op| op.ir_lower(ctx))
+                .collect(),
+            self_name: LateInit::empty(),
+            self_type_name: LateInit::empty(),
+            span: self.span.clone(),
         }
         .allocate(ctx);
 
         ctx[tr].name = ctx.make_ident(self.name, IdentParent::TraitName(tr)).into();
 
+        let self_name = crate::ast::node::identifier::Ident {
+            span: self.span.clone(),
+            value: "self",
+        };
+        ctx[tr].self_name = ctx
+            .make_ident(self_name, IdentParent::TraitSelfName(tr))
+            .into();
+
+        let self_type_name = crate::ast::node::identifier::Ident {
+            span: self.span,
+            value: "Self",
+        };
+        ctx[tr].self_type_name = ctx
+            .make_ident(self_type_name, IdentParent::TraitSelfTypeName(tr))
+            .into();
+
         tr
     }
 }
@@ -65,10 +153,27 @@ impl<'a> IrLowerable<'a> for crate::ast::node::traits::TraitFuncAttr<'a> {
     type IrType = TraitFuncAttr<'a>;
 
     fn ir_lower(self, ctx: &mut IrCtx<'a>) -> NodeRef<'a, Self::IrType> {
+        // the default implementation is lowered as a regular function with the same signature
+        let default_impl = self.body.map(|body| {
+            crate::ast::node::function::Function {
+                name: Some(self.name.clone()),
+                type_params: Vec::new(),
+                args: self.args.clone(),
+                return_type: self.return_type.clone(),
+                throws: self.throws.clone(),
+                body,
+                is_async: false,
+                span: self.span.clone(),
+            }
+            .ir_lower(ctx)
+        });
+
         let f = TraitFuncAttr {
             name: LateInit::empty(),
             args: Vec::new(),
             return_type: None,
+            throws: None,
+            default_impl,
             span: self.span,
         }
         .allocate(ctx);
@@ -81,6 +186,10 @@ impl<'a> IrLowerable<'a> for crate::ast::node::traits::TraitFuncAttr<'a> {
             .return_type
             .map(|t| t.into_ir_type(ctx, TypeSignatureParent::TraitFuncAttr(f)));
 
+        ctx[f].throws = self
+            .throws
+            .map(|t| t.into_ir_type(ctx, TypeSignatureParent::TraitFuncAttr(f)));
+
         ctx[f].args = self.args.into_iter().map(|arg| arg.ir_lower(ctx)).collect();
 
         f
@@ -105,6 +214,51 @@ impl<'a> Typed<'a> for NodeRef<'a, Trait<'a>> {
     }
 }
 
+impl<'a> Typed<'a> for NodeRef<'a, TraitFuncAttr<'a>> {
+    fn eval_type(
+        &self,
+        symbols: &mut SymbolTableZipper<'a>,
+        ctx: &mut IrCtx<'a>,
+    ) -> Result<TypeSignature<'a>, TypeEvalError<'a>> {
+        let args = ctx[*self]
+            .args
+            .clone()
+            .into_iter()
+            .map(|arg| arg.eval_type(symbols, ctx))
+            .collect::<Result<Vec<_>, _>>()?
+            .into();
+
+        Ok(ctx.get_type_sig(
+            TypeSignatureValue::Function {
+                args,
+                return_type: self.return_type(ctx).into(),
+                throws: ctx[*self].throws.clone(),
+            },
+            TypeSignatureContext {
+                parent: TypeSignatureParent::TraitFuncAttr(*self),
+                type_span: None,
+            }
+            .alloc(),
+        ))
+    }
+}
+
+impl<'a> NodeRef<'a, TraitFuncAttr<'a>> {
+    /// The declared return type, or `Void` if the method does not declare one
+    pub fn return_type(&self, ctx: &IrCtx<'a>) -> TypeSignature<'a> {
+        ctx[*self]
+            .return_type
+            .clone()
+            .unwrap_or_else(|| ctx.get_builtin_type_sig(BuiltinType::Void))
+    }
+}
+
+impl<'a> Spanned<'a> for NodeRef<'a, TraitFuncAttr<'a>> {
+    fn get_span(&self, ctx: &IrCtx<'a>) -> Option<Span<'a>> {
+        Some(ctx[*self].span.clone())
+    }
+}
+
 impl<'a> Spanned<'a> for NodeRef<'a, Trait<'a>> {
     fn get_span(&self, ctx: &IrCtx<'a>) -> Option<Span<'a>> {
         Some(ctx[*self].span.clone())
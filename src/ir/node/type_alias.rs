@@ -0,0 +1,111 @@
+use crate::{
+    error_message::error_formatter::Spanned,
+    ir::{ast_lowering::IrLowerable, context::IrCtx, late_init::LateInit},
+    parser::Span,
+    symbols::symbol_table::symbol_table_zipper::SymbolTableZipper,
+};
+
+use super::{
+    identifier::{Ident, IdentParent, Identifiable},
+    type_signature::{TypeEvalError, TypeSignature, TypeSignatureParent, Typed},
+    IrAlloc, NodeRef,
+};
+
+/// Replaced by the aliased type wherever its name is used
+#[derive(Debug, Clone)]
+pub struct TypeAlias<'a> {
+    pub name: LateInit<Ident<'a>>,
+    pub type_sig: LateInit<TypeSignature<'a>>,
+    pub span: Span<'a>,
+}
+
+impl<'a> Typed<'a> for NodeRef<'a, TypeAlias<'a>> {
+    fn eval_type(
+        &self,
+        _symbols: &mut SymbolTableZipper<'a>,
+        ctx: &mut IrCtx<'a>,
+    ) -> Result<TypeSignature<'a>, TypeEvalError<'a>> {
+        Ok(ctx[*self].type_sig.cloned())
+    }
+}
+
+impl<'a> Spanned<'a> for NodeRef<'a, TypeAlias<'a>> {
+    fn get_span(&self, ctx: &IrCtx<'a>) -> Option<Span<'a>> {
+        Some(ctx[*self].span.clone())
+    }
+}
+
+impl<'a> Identifiable<'a> for TypeAlias<'a> {
+    fn name(&self, _ctx: &IrCtx<'a>) -> Ident<'a> {
+        *self.name
+    }
+}
+
+impl<'a> IrLowerable<'a> for crate::ast::node::type_alias::TypeAlias<'a> {
+    type IrType = TypeAlias<'a>;
+
+    fn ir_lower(self, ctx: &mut IrCtx<'a>) -> NodeRef<'a, Self::IrType> {
+        let alias = TypeAlias {
+            name: LateInit::empty(),
+            type_sig: LateInit::empty(),
+            span: self.span,
+        }
+        .allocate(ctx);
+
+        ctx[alias].name = ctx
+            .make_ident(self.name, IdentParent::TypeAliasName(alias))
+            .into();
+
+        ctx[alias].type_sig = self
+            .type_sig
+            .into_ir_type(ctx, TypeSignatureParent::TypeAlias(alias))
+            .into();
+
+        alias
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::assert_matches;
+
+    use crate::{
+        ir::test_utils::utils::{lowered_ir, type_check},
+        type_checker::TypeCheckerError,
+    };
+
+    #[test]
+    fn test_type_alias() {
+        let mut ir = lowered_ir(
+            "
+        func count(numbers: Array<Number>, matches: Predicate) -> Number {
+            var n = 0
+            for x in numbers { if matches(x) { n = n + 1 } }
+            return n
+        }
+        type Predicate = (Number) -> Boolean
+        let big: Number = count([1, 5, 10], (x) { return x > 3 })
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_type_alias_of_alias() {
+        let mut ir = lowered_ir(
+            "
+        type Point = (Number, Number)
+        type Line = (Point, Point)
+        let line: Line = ((0, 0), (1, \"a\"))
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ConflictingTypes(_, _))
+        );
+    }
+}
@@ -10,17 +10,26 @@ use crate::{
 };
 
 use super::{
+    array::{Array, ArrayMemberAccess, Index},
+    control_flow::IfStmt,
+    dictionary::{Dictionary, DictionaryIndex, DictionaryMemberAccess},
     enumeration::{Enum, EnumInit, EnumValue},
+    error_handling::DoCatch,
     escape_block::EscapeBlock,
     expression::Expr,
     external::ExternalObject,
-    function::{Function, FunctionArg, FunctionCall},
+    function::{Await, Function, FunctionArg, FunctionCall},
+    generics::TypeParam,
     identifier::{Ident, IdentParent},
+    loops::ForLoop,
     member_access::UnresolvedMemberAccess,
+    operator::{BinaryOperation, UnaryOperation},
+    pattern_match::{EnumPattern, Match},
     statement::VarDecl,
     structure::{Struct, StructAttr, StructInit},
     traits::{Trait, TraitFuncAttr},
     tuple::{Tuple, TupleAccess},
+    type_alias::TypeAlias,
     NodeRef,
 };
 
@@ -74,6 +83,8 @@ pub enum TypeSignatureParent<'a> {
     },
     FunctionDefArg(NodeRef<'a, FunctionArg<'a>>),
     FunctionDefReturn(NodeRef<'a, Function<'a>>),
+    FunctionDefThrows(NodeRef<'a, Function<'a>>),
+    FunctionCall(NodeRef<'a, FunctionCall<'a>>),
     Struct(NodeRef<'a, Struct<'a>>),
     StructInit(NodeRef<'a, StructInit<'a>>),
     StructAttr(NodeRef<'a, StructAttr<'a>>),
@@ -86,7 +97,26 @@ pub enum TypeSignatureParent<'a> {
     EscapeBlock(NodeRef<'a, EscapeBlock<'a>>),
     MemberAccess(NodeRef<'a, UnresolvedMemberAccess<'a>>),
     ExternObjType(NodeRef<'a, ExternalObject<'a>>),
+    TypeAlias(NodeRef<'a, TypeAlias<'a>>),
     TraitFuncAttr(NodeRef<'a, TraitFuncAttr<'a>>),
+    BinaryOperation(NodeRef<'a, BinaryOperation<'a>>),
+    UnaryOperation(NodeRef<'a, UnaryOperation<'a>>),
+    Match(NodeRef<'a, Match<'a>>),
+    PatternBinding(NodeRef<'a, EnumPattern<'a>>, usize),
+    NarrowedBinding(NodeRef<'a, IfStmt<'a>>, usize),
+    OperandNarrowedBinding(NodeRef<'a, BinaryOperation<'a>>, usize),
+    ForLoop(NodeRef<'a, ForLoop<'a>>),
+    TypeParam(NodeRef<'a, TypeParam<'a>>),
+    Await(NodeRef<'a, Await<'a>>),
+    DoCatch(NodeRef<'a, DoCatch<'a>>),
+    Array(NodeRef<'a, Array<'a>>),
+    Index(NodeRef<'a, Index<'a>>),
+    ArrayMemberAccess(NodeRef<'a, ArrayMemberAccess<'a>>),
+    Dictionary(NodeRef<'a, Dictionary<'a>>),
+    DictionaryIndex(NodeRef<'a, DictionaryIndex<'a>>),
+    DictionaryMemberAccess(NodeRef<'a, DictionaryMemberAccess<'a>>),
+    /// The generic type of an instance, which is given its type arguments by the instance
+    InstanceBase,
 }
 
 impl<'a> Into<Id<TypeSignatureValue<'a>>> for TypeSignature<'a> {
@@ -109,6 +139,7 @@ pub enum TypeSignatureValue<'a> {
     Function {
         args: LateInit<Vec<TypeSignature<'a>>>,
         return_type: LateInit<TypeSignature<'a>>,
+        throws: Option<TypeSignature<'a>>,
     },
     Struct {
         name: Ident<'a>,
@@ -120,6 +151,22 @@ pub enum TypeSignatureValue<'a> {
     Trait {
         name: Ident<'a>,
     },
+    /// A type parameter of a generic declaration, only equal to itself
+    TypeParameter {
+        name: Ident<'a>,
+    },
+    Instance {
+        base: TypeSignature<'a>,
+        args: Vec<TypeSignature<'a>>,
+    },
+    Dictionary {
+        key: TypeSignature<'a>,
+        value: TypeSignature<'a>,
+    },
+    Optional(TypeSignature<'a>),
+    /// A plain JavaScript object, whose fields are sorted by name
+    /// so that records with the same fields are the same type
+    Record(Vec<(&'a str, TypeSignature<'a>)>),
 }
 
 impl<'a> Spanned<'a> for TypeSignature<'a> {
@@ -133,13 +180,15 @@ impl<'a> Spanned<'a> for TypeSignature<'a> {
             TypeSignatureParent::VarDeclSig(var) => ctx[*var].name.get_span(ctx),
             TypeSignatureParent::Enum(_) => todo!(),
             TypeSignatureParent::EnumValue(_) => todo!(),
-            TypeSignatureParent::EnumInit(_) => todo!(),
+            TypeSignatureParent::EnumInit(enm_init) => enm_init.get_span(ctx),
             TypeSignatureParent::Expr(expr) => expr.get_span(ctx),
             TypeSignatureParent::Function(func) => Some(ctx[*func].span.clone()),
             TypeSignatureParent::FunctionArg { parent_func: _ } => todo!(),
             TypeSignatureParent::FunctionReturn { parent_func: _ } => todo!(),
             TypeSignatureParent::FunctionDefArg(arg) => arg.get_span(ctx),
-            TypeSignatureParent::FunctionDefReturn(_) => todo!(),
+            TypeSignatureParent::FunctionDefReturn(func) => Some(ctx[*func].span.clone()),
+            TypeSignatureParent::FunctionDefThrows(func) => Some(ctx[*func].span.clone()),
+            TypeSignatureParent::FunctionCall(call) => call.get_span(ctx),
             TypeSignatureParent::Struct(st) => st.get_span(ctx),
             TypeSignatureParent::StructInit(st_init) => st_init.get_span(ctx),
             TypeSignatureParent::StructAttr(st_attr) => st_attr.get_span(ctx),
@@ -151,8 +200,33 @@ impl<'a> Spanned<'a> for TypeSignature<'a> {
             TypeSignatureParent::EscapeBlock(esc) => esc.get_span(ctx),
             TypeSignatureParent::MemberAccess(mem_acc) => mem_acc.get_span(ctx),
             TypeSignatureParent::ExternObjType(obj) => obj.get_span(ctx),
-            TypeSignatureParent::TraitFuncAttr(_) => todo!(),
+            TypeSignatureParent::TypeAlias(alias) => alias.get_span(ctx),
+            TypeSignatureParent::TraitFuncAttr(attr) => attr.get_span(ctx),
             TypeSignatureParent::Trait(tr) => tr.get_span(ctx),
+            TypeSignatureParent::BinaryOperation(bin_op) => bin_op.get_span(ctx),
+            TypeSignatureParent::UnaryOperation(unary_op) => unary_op.get_span(ctx),
+            TypeSignatureParent::Match(mtch) => mtch.get_span(ctx),
+            TypeSignatureParent::PatternBinding(pattern, idx) => ctx[*pattern].bindings[*idx]
+                .name
+                .and_then(|name| name.get_span(ctx))
+                .or_else(|| pattern.get_span(ctx)),
+            TypeSignatureParent::NarrowedBinding(ifb, idx) => {
+                ctx[*ifb].narrowed[*idx].name.get_span(ctx)
+            }
+            TypeSignatureParent::OperandNarrowedBinding(bin_op, idx) => {
+                ctx[*bin_op].narrowed[*idx].name.get_span(ctx)
+            }
+            TypeSignatureParent::ForLoop(fl) => ctx[*fl].item_name.get_span(ctx),
+            TypeSignatureParent::TypeParam(param) => param.get_span(ctx),
+            TypeSignatureParent::Await(aw) => aw.get_span(ctx),
+            TypeSignatureParent::DoCatch(dc) => ctx[*dc].error_name.get_span(ctx),
+            TypeSignatureParent::Array(arr) => arr.get_span(ctx),
+            TypeSignatureParent::Index(idx) => idx.get_span(ctx),
+            TypeSignatureParent::ArrayMemberAccess(arr_acc) => arr_acc.get_span(ctx),
+            TypeSignatureParent::Dictionary(dict) => dict.get_span(ctx),
+            TypeSignatureParent::DictionaryIndex(dict_idx) => dict_idx.get_span(ctx),
+            TypeSignatureParent::DictionaryMemberAccess(dict_acc) => dict_acc.get_span(ctx),
+            TypeSignatureParent::InstanceBase => None,
         };
 
         if node_span.is_some() {
@@ -163,14 +237,16 @@ impl<'a> Spanned<'a> for TypeSignature<'a> {
             TypeSignatureValue::Builtin(_) => None,
             TypeSignatureValue::Unresolved(id) => id.get_span(ctx),
             TypeSignatureValue::TypeVariable(_) => todo!(),
-            TypeSignatureValue::Function {
-                args: _,
-                return_type: _,
-            } => todo!(),
+            TypeSignatureValue::Function { .. } => todo!(),
             TypeSignatureValue::Struct { name } => name.get_span(ctx),
             TypeSignatureValue::Enum { name } => name.get_span(ctx),
             TypeSignatureValue::Tuple(_) => todo!(),
             TypeSignatureValue::Trait { name } => name.get_span(ctx),
+            TypeSignatureValue::TypeParameter { name } => name.get_span(ctx),
+            TypeSignatureValue::Instance { base, args: _ } => base.get_span(ctx),
+            TypeSignatureValue::Dictionary { .. } => None,
+            TypeSignatureValue::Optional(value) => value.get_span(ctx),
+            TypeSignatureValue::Record(_) => None,
         }
     }
 }
@@ -186,6 +262,8 @@ pub enum TypeEvalError<'a> {
     AccessNonStruct(TypeSignature<'a>),
     AccessNonTuple(TypeSignature<'a>),
     AccessNonEnum(TypeSignature<'a>),
+    AccessNonArray(TypeSignature<'a>),
+    AccessNonDictionary(TypeSignature<'a>),
     TupleAccessOutOfBounds(NodeRef<'a, TupleAccess<'a>>, TypeSignature<'a>),
     UnknownIdent(Ident<'a>),
 }
@@ -196,30 +274,71 @@ impl<'a> PartialEq for TypeSignatureValue<'a> {
             (Self::Builtin(l0), Self::Builtin(r0)) => l0 == r0,
             (Self::Unresolved(l0), Self::Unresolved(r0)) => l0 == r0,
             (Self::TypeVariable(l0), Self::TypeVariable(r0)) => l0 == r0,
+            (Self::Function { args: l_args, .. }, Self::Function { args: r_args, .. })
+                if l_args.is_empty() || r_args.is_empty() =>
+            {
+                // a function type that is still being built is never equal to another
+                false
+            }
             (
                 Self::Function {
                     args: l_args,
                     return_type: l_return_type,
+                    throws: l_throws,
                 },
                 Self::Function {
                     args: r_args,
                     return_type: r_return_type,
+                    throws: r_throws,
                 },
             ) => {
-                (**l_args)
-                    .clone()
-                    .into_iter()
-                    .zip(r_args.deref())
-                    .all(|(l, r)| l == *r)
+                l_args.len() == r_args.len()
+                    && (**l_args)
+                        .clone()
+                        .into_iter()
+                        .zip(r_args.deref())
+                        .all(|(l, r)| l == *r)
                     && l_return_type == r_return_type
+                    && l_throws == r_throws
             }
             (Self::Struct { name: l_name }, Self::Struct { name: r_name }) => l_name == r_name,
             (Self::Enum { name: l_name }, Self::Enum { name: r_name }) => l_name == r_name,
-            (Self::Tuple(l0), Self::Tuple(r0)) => (**l0)
-                .clone()
-                .into_iter()
-                .zip(r0.deref())
-                .all(|(l, r)| l == *r),
+            (Self::Trait { name: l_name }, Self::Trait { name: r_name }) => l_name == r_name,
+            (Self::TypeParameter { name: l_name }, Self::TypeParameter { name: r_name }) => {
+                l_name == r_name
+            }
+            (
+                Self::Instance {
+                    base: l_base,
+                    args: l_args,
+                },
+                Self::Instance {
+                    base: r_base,
+                    args: r_args,
+                },
+            ) => l_base == r_base && l_args == r_args,
+            (
+                Self::Dictionary {
+                    key: l_key,
+                    value: l_value,
+                },
+                Self::Dictionary {
+                    key: r_key,
+                    value: r_value,
+                },
+            ) => l_key == r_key && l_value == r_value,
+            (Self::Optional(l0), Self::Optional(r0)) => l0 == r0,
+            (Self::Record(l0), Self::Record(r0)) => l0 == r0,
+            // like function types, a tuple type that is still being built is never equal to another
+            (Self::Tuple(l0), Self::Tuple(r0)) if l0.is_empty() || r0.is_empty() => false,
+            (Self::Tuple(l0), Self::Tuple(r0)) => {
+                l0.len() == r0.len()
+                    && (**l0)
+                        .clone()
+                        .into_iter()
+                        .zip(r0.deref())
+                        .all(|(l, r)| l == *r)
+            }
             _ => false,
         }
     }
@@ -232,7 +351,7 @@ impl<'a> crate::ast::node::type_signature::TypeSignature<'a> {
         parent: TypeSignatureParent<'a>,
     ) -> TypeSignature<'a> {
         let type_ctx = TypeSignatureContext {
-            parent: parent,
+            parent: parent.clone(),
             type_span: Some(self.span),
         }
         .alloc();
@@ -251,14 +370,30 @@ impl<'a> crate::ast::node::type_signature::TypeSignature<'a> {
 
                 type_sig
             }
+            crate::ast::node::type_signature::TypeSignatureValue::Instance { base, args } => {
+                let base = crate::ast::node::type_signature::TypeSignature {
+                    span: base.span.clone(),
+                    value: crate::ast::node::type_signature::TypeSignatureValue::Base(base),
+                }
+                .into_ir_type(ctx, TypeSignatureParent::InstanceBase);
+
+                let args = args
+                    .into_iter()
+                    .map(|arg| arg.into_ir_type(ctx, parent.clone()))
+                    .collect();
+
+                ctx.get_type_sig(TypeSignatureValue::Instance { base, args }, type_ctx)
+            }
             crate::ast::node::type_signature::TypeSignatureValue::Function {
                 args,
                 return_type,
+                throws,
             } => {
                 let func = ctx.get_type_sig(
                     TypeSignatureValue::Function {
                         args: LateInit::empty(),
                         return_type: LateInit::empty(),
+                        throws: None,
                     },
                     type_ctx,
                 );
@@ -286,13 +421,24 @@ impl<'a> crate::ast::node::type_signature::TypeSignature<'a> {
                     )
                     .into();
 
+                let new_throws = throws.map(|throws| {
+                    throws.into_ir_type(
+                        ctx,
+                        TypeSignatureParent::FunctionReturn {
+                            parent_func: func.clone(),
+                        },
+                    )
+                });
+
                 match &mut ctx[&func] {
                     TypeSignatureValue::Function {
                         args: ags,
                         return_type: ret_type,
+                        throws: thrown_type,
                     } => {
                         *ags = new_args;
                         *ret_type = new_return;
+                        *thrown_type = new_throws;
                     }
                     _ => unreachable!(),
                 }
@@ -331,6 +477,25 @@ impl<'a> crate::ast::node::type_signature::TypeSignature<'a> {
 
                 tup
             }
+            crate::ast::node::type_signature::TypeSignatureValue::Dictionary { key, value } => {
+                let key = key.into_ir_type(ctx, parent.clone());
+                let value = value.into_ir_type(ctx, parent);
+
+                ctx.get_type_sig(TypeSignatureValue::Dictionary { key, value }, type_ctx)
+            }
+            crate::ast::node::type_signature::TypeSignatureValue::Optional(value) => {
+                let value = value.into_ir_type(ctx, parent);
+
+                ctx.get_type_sig(TypeSignatureValue::Optional(value), type_ctx)
+            }
+            crate::ast::node::type_signature::TypeSignatureValue::Record(fields) => {
+                let fields = fields
+                    .into_iter()
+                    .map(|(name, t)| (name.value, t.into_ir_type(ctx, parent.clone())))
+                    .collect();
+
+                ctx.get_record_type_sig(fields, type_ctx)
+            }
         }
     }
 }
@@ -341,14 +506,21 @@ impl<'a> TypeSignature<'a> {
             TypeSignatureValue::Builtin(builtin) => builtin.name().to_owned(),
             TypeSignatureValue::Unresolved(id) => id.value(ctx).unwrap().to_owned(),
             TypeSignatureValue::TypeVariable(_var) => "[unknown]".to_owned(),
-            TypeSignatureValue::Function { args, return_type } => format!(
-                "({}) -> {}",
+            TypeSignatureValue::Function {
+                args,
+                return_type,
+                throws,
+            } => format!(
+                "({}){} -> {}",
                 (*args)
                     .clone()
                     .into_iter()
                     .map(|arg| arg.format(ctx))
                     .intersperse(", ".to_owned())
                     .collect::<String>(),
+                throws
+                    .map(|throws| format!(" throws({})", throws.format(ctx)))
+                    .unwrap_or_default(),
                 return_type.format(ctx)
             ),
             TypeSignatureValue::Struct { name } => format!("[struct {}]", name.value(ctx).unwrap()),
@@ -363,8 +535,225 @@ impl<'a> TypeSignature<'a> {
                     .collect::<String>()
             ),
             TypeSignatureValue::Trait { name } => format!("[trait {}]", name.value(ctx).unwrap()),
+            TypeSignatureValue::TypeParameter { name } => name.value(ctx).unwrap().to_owned(),
+            TypeSignatureValue::Instance { base, args } => {
+                let args = args
+                    .into_iter()
+                    .map(|arg| arg.format(ctx))
+                    .intersperse(", ".to_owned())
+                    .collect::<String>();
+
+                match &ctx[&base] {
+                    TypeSignatureValue::Struct { name } => {
+                        format!("[struct {}<{}>]", name.value(ctx).unwrap(), args)
+                    }
+                    TypeSignatureValue::Enum { name } => {
+                        format!("[enum {}<{}>]", name.value(ctx).unwrap(), args)
+                    }
+                    _ => format!("{}<{}>", base.format(ctx), args),
+                }
+            }
+            TypeSignatureValue::Dictionary { key, value } => {
+                format!("[{}: {}]", key.format(ctx), value.format(ctx))
+            }
+            TypeSignatureValue::Optional(value) => format!("{}?", value.format(ctx)),
+            TypeSignatureValue::Record(fields) if fields.is_empty() => "{}".to_owned(),
+            TypeSignatureValue::Record(fields) => format!(
+                "{{ {} }}",
+                fields
+                    .into_iter()
+                    .map(|(name, t)| format!("{name}: {}", t.format(ctx)))
+                    .intersperse(", ".to_owned())
+                    .collect::<String>()
+            ),
         }
     }
+
+    /// Splits an instance of a generic type into its base type and type arguments,
+    /// any other type is returned without arguments
+    pub fn split_instance(&self, ctx: &IrCtx<'a>) -> (TypeSignature<'a>, Vec<TypeSignature<'a>>) {
+        match &ctx[self] {
+            TypeSignatureValue::Instance { base, args } => (base.clone(), args.clone()),
+            _ => (self.clone(), Vec::new()),
+        }
+    }
+
+    /// The type of the value a `Promise<T>` resolves to
+    pub fn promised_type(&self, ctx: &IrCtx<'a>) -> Option<TypeSignature<'a>> {
+        match &ctx[self] {
+            TypeSignatureValue::Instance { base, args } => match ctx[base] {
+                TypeSignatureValue::Builtin(BuiltinType::Promise) => args.first().cloned(),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// The type of the elements of an `Array<T>`
+    pub fn array_element_type(&self, ctx: &IrCtx<'a>) -> Option<TypeSignature<'a>> {
+        match &ctx[self] {
+            TypeSignatureValue::Instance { base, args } => match ctx[base] {
+                TypeSignatureValue::Builtin(BuiltinType::Array) => args.first().cloned(),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// The key and value types of a `[K: V]` dictionary
+    pub fn dictionary_types(
+        &self,
+        ctx: &IrCtx<'a>,
+    ) -> Option<(TypeSignature<'a>, TypeSignature<'a>)> {
+        match &ctx[self] {
+            TypeSignatureValue::Dictionary { key, value } => Some((key.clone(), value.clone())),
+            _ => None,
+        }
+    }
+
+    /// The type of the field with the given name of a record type
+    pub fn record_field_type(&self, ctx: &IrCtx<'a>, name: &str) -> Option<TypeSignature<'a>> {
+        match &ctx[self] {
+            TypeSignatureValue::Record(fields) => fields
+                .iter()
+                .find(|(field, _)| *field == name)
+                .map(|(_, t)| t.clone()),
+            _ => None,
+        }
+    }
+
+    /// The type of the errors thrown by a function type, `None` if it can not throw
+    pub fn thrown_type(&self, ctx: &IrCtx<'a>) -> Option<TypeSignature<'a>> {
+        match &ctx[self] {
+            TypeSignatureValue::Function { throws, .. } => throws.clone(),
+            _ => None,
+        }
+    }
+
+    pub fn type_parameters(&self, ctx: &IrCtx<'a>) -> Vec<Ident<'a>> {
+        fn collect<'a>(type_sig: &TypeSignature<'a>, ctx: &IrCtx<'a>, params: &mut Vec<Ident<'a>>) {
+            match &ctx[type_sig] {
+                TypeSignatureValue::TypeParameter { name } if !params.contains(name) => {
+                    params.push(*name);
+                }
+                TypeSignatureValue::Function {
+                    args,
+                    return_type,
+                    throws,
+                } => {
+                    args.iter().for_each(|arg| collect(arg, ctx, params));
+                    collect(return_type, ctx, params);
+                    throws.iter().for_each(|t| collect(t, ctx, params));
+                }
+                TypeSignatureValue::Tuple(types) => {
+                    types.iter().for_each(|t| collect(t, ctx, params));
+                }
+                TypeSignatureValue::Instance { base: _, args } => {
+                    args.iter().for_each(|arg| collect(arg, ctx, params));
+                }
+                TypeSignatureValue::Dictionary { key, value } => {
+                    collect(key, ctx, params);
+                    collect(value, ctx, params);
+                }
+                TypeSignatureValue::Optional(value) => collect(value, ctx, params),
+                TypeSignatureValue::Record(fields) => {
+                    fields.iter().for_each(|(_, t)| collect(t, ctx, params));
+                }
+                _ => {}
+            }
+        }
+
+        let mut params = Vec::new();
+        collect(self, ctx, &mut params);
+        params
+    }
+
+    /// Replaces the type parameters `params` with the types in `args`
+    pub fn substitute(
+        &self,
+        ctx: &mut IrCtx<'a>,
+        params: &[Ident<'a>],
+        args: &[TypeSignature<'a>],
+    ) -> TypeSignature<'a> {
+        self.replace(ctx, &|ctx, type_sig| match &ctx[type_sig] {
+            TypeSignatureValue::TypeParameter { name } => params
+                .iter()
+                .position(|param| param == name)
+                .map(|idx| args[idx].clone()),
+            _ => None,
+        })
+    }
+
+    /// Replaces `Self`, the type of the trait `trait_type` inside of its declaration,
+    /// with the type that conforms to it
+    pub fn replace_self(
+        &self,
+        ctx: &mut IrCtx<'a>,
+        trait_type: &TypeSignature<'a>,
+        self_type: &TypeSignature<'a>,
+    ) -> TypeSignature<'a> {
+        self.replace(ctx, &|_, type_sig| {
+            (type_sig == trait_type).then(|| self_type.clone())
+        })
+    }
+
+    /// Replaces the types for which `replacement` returns a new type,
+    /// inside of the types this type is composed of as well
+    fn replace(
+        &self,
+        ctx: &mut IrCtx<'a>,
+        replacement: &impl Fn(&IrCtx<'a>, &TypeSignature<'a>) -> Option<TypeSignature<'a>>,
+    ) -> TypeSignature<'a> {
+        if let Some(new_type) = replacement(ctx, self) {
+            return new_type;
+        }
+
+        let replace_all = |ctx: &mut IrCtx<'a>, types: &Vec<TypeSignature<'a>>| {
+            types
+                .iter()
+                .map(|t| t.replace(ctx, replacement))
+                .collect::<Vec<_>>()
+        };
+
+        let new_value = match ctx[self].clone() {
+            TypeSignatureValue::Function {
+                args,
+                return_type,
+                throws,
+            } => TypeSignatureValue::Function {
+                args: replace_all(ctx, &args).into(),
+                return_type: return_type.replace(ctx, replacement).into(),
+                throws: throws.map(|t| t.replace(ctx, replacement)),
+            },
+            TypeSignatureValue::Tuple(types) => {
+                TypeSignatureValue::Tuple(replace_all(ctx, &types).into())
+            }
+            TypeSignatureValue::Instance { base, args } => TypeSignatureValue::Instance {
+                base,
+                args: replace_all(ctx, &args),
+            },
+            TypeSignatureValue::Dictionary { key, value } => TypeSignatureValue::Dictionary {
+                key: key.replace(ctx, replacement),
+                value: value.replace(ctx, replacement),
+            },
+            TypeSignatureValue::Optional(value) => {
+                TypeSignatureValue::Optional(value.replace(ctx, replacement))
+            }
+            TypeSignatureValue::Record(fields) => TypeSignatureValue::Record(
+                fields
+                    .into_iter()
+                    .map(|(name, t)| (name, t.replace(ctx, replacement)))
+                    .collect(),
+            ),
+            _ => return self.clone(),
+        };
+
+        if new_value == ctx[self] {
+            return self.clone();
+        }
+
+        ctx.get_type_sig(new_value, self.context.clone())
+    }
 }
 
 #[allow(unused_variables)]
@@ -396,6 +785,12 @@ pub enum BuiltinType {
     Number,
     Boolean,
     Void,
+    /// The result of an async function, instantiated with the type of the resolved value
+    Promise,
+    /// A JavaScript array, instantiated with the type of the elements
+    Array,
+    /// The numbers between two bounds, counting up in steps of one
+    Range,
 }
 
 pub const BUILTIN_TYPES: &'static [BuiltinType] = &[
@@ -403,6 +798,9 @@ pub const BUILTIN_TYPES: &'static [BuiltinType] = &[
     BuiltinType::Number,
     BuiltinType::Boolean,
     BuiltinType::Void,
+    BuiltinType::Promise,
+    BuiltinType::Array,
+    BuiltinType::Range,
 ];
 
 impl BuiltinType {
@@ -412,6 +810,9 @@ impl BuiltinType {
             BuiltinType::Number => "Number",
             BuiltinType::Boolean => "Boolean",
             BuiltinType::Void => "Void",
+            BuiltinType::Promise => "Promise",
+            BuiltinType::Array => "Array",
+            BuiltinType::Range => "Range",
         }
     }
 }
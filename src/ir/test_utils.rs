@@ -1,6 +1,6 @@
 //#[cfg(test)]
 pub mod utils {
-    use std::assert_matches::assert_matches;
+    use std::assert_matches;
 
     use crate::{
         code_gen::format_ir,
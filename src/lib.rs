@@ -1,6 +1,7 @@
 #![feature(map_try_insert)]
 #![feature(iter_intersperse)]
 #![feature(associated_type_defaults)]
+#![allow(stable_features)]
 #![feature(assert_matches)]
 #![feature(hash_set_entry)]
 #![deny(rust_2018_idioms)]
@@ -1,30 +1,41 @@
 use nom::{
+    branch::alt,
     bytes::complete::tag,
     combinator::{map, opt},
-    sequence::{preceded, tuple},
+    error::context,
+    sequence::{pair, preceded, terminated, tuple},
 };
 
-use crate::ast::node::control_flow::IfStmt;
+use crate::ast::node::{control_flow::IfStmt, expression::Expr, identifier::Ident};
 
 use super::{
-    expression::expression, spaced, span, statement::statement, surround_brackets, BracketType,
-    Input, Res,
+    expression::expression, identifier::identifier, pattern_match::let_pattern, spaced, span,
+    statement::statement, surround_brackets, ws, BracketType, Input, Res,
 };
 
 pub fn if_branch(i: Input<'_>) -> Res<Input<'_>, IfStmt<'_>> {
-    // if EXPR "{" STMT "}" [ else "{" STMT "}" ]
+    // if ( EXPR | let ENUM_PATTERN "=" EXPR | let IDENT "=" EXPR ) "{" STMT "}" [ else "{" STMT "}" ]
 
     map(
         span(tuple((
-            preceded(spaced(tag("if")), expression),
+            preceded(
+                spaced(tag("if")),
+                alt((
+                    map(let_pattern, |(pattern, expr)| (Some(pattern), None, expr)),
+                    map(let_binding, |(binding, expr)| (None, Some(binding), expr)),
+                    map(expression, |expr| (None, None, expr)),
+                )),
+            ),
             surround_brackets(BracketType::Curly, statement),
             opt(preceded(
                 spaced(tag("else")),
                 surround_brackets(BracketType::Curly, statement),
             )),
         ))),
-        |(span, (expr, stmt, else_stmt))| IfStmt {
+        |(span, ((pattern, binding, expr), stmt, else_stmt))| IfStmt {
             condition: expr,
+            pattern,
+            binding,
             span,
             body: Box::new(stmt),
             else_body: else_stmt.map(Box::new),
@@ -32,9 +43,21 @@ pub fn if_branch(i: Input<'_>) -> Res<Input<'_>, IfStmt<'_>> {
     )(i)
 }
 
+fn let_binding(i: Input<'_>) -> Res<Input<'_>, (Ident<'_>, Expr<'_>)> {
+    // let IDENT "=" EXPR
+
+    context(
+        "let binding",
+        pair(
+            preceded(spaced(terminated(tag("let"), ws)), spaced(identifier)),
+            preceded(spaced(tag("=")), expression),
+        ),
+    )(i)
+}
+
 #[cfg(test)]
 mod tests {
-    use std::assert_matches::assert_matches;
+    use std::assert_matches;
 
     use crate::{
         ast::node::{
@@ -67,4 +90,29 @@ mod tests {
         );
         assert!(if_br.else_body.is_some());
     }
+
+    #[test]
+    fn test_if_let() {
+        let if_br = if_branch(new_input("if let .v4(a, _, c, d) = ipValue { return a }"))
+            .unwrap()
+            .1;
+
+        assert_matches!(if_br.condition.value, ExprValue::Identifier(_));
+
+        let pattern = if_br.pattern.unwrap();
+        assert_eq!(pattern.enum_value.value, "v4");
+        assert_eq!(pattern.bindings.len(), 4);
+        assert!(if_br.else_body.is_none());
+    }
+
+    #[test]
+    fn test_if_let_binding() {
+        let if_br = if_branch(new_input("if let car = maybeCar { return car }"))
+            .unwrap()
+            .1;
+
+        assert_matches!(if_br.condition.value, ExprValue::Identifier(_));
+        assert_eq!(if_br.binding.unwrap().value, "car");
+        assert!(if_br.pattern.is_none());
+    }
 }
@@ -4,25 +4,32 @@ use nom::{
     combinator::{map, opt},
     error::context,
     multi::{separated_list0, separated_list1},
-    sequence::{pair, preceded},
+    sequence::{pair, preceded, tuple},
 };
 
 use crate::ast::node::enumeration::{Enum, EnumValue};
 
 use super::{
-    identifier::identifier, spaced, surround_brackets, type_signature::type_signature, BracketType,
-    Input, Res,
+    identifier::identifier,
+    spaced, surround_brackets,
+    type_signature::{type_params, type_signature},
+    BracketType, Input, Res,
 };
 
 pub fn enumeration(i: Input<'_>) -> Res<Input<'_>, Enum<'_>> {
-    // enum IDENT "{" ENUM_VALUE* "}"
+    // enum IDENT [TYPE_PARAMS] "{" ENUM_VALUE* "}"
 
     map(
-        pair(
+        tuple((
             preceded(spaced(tag("enum")), identifier),
+            type_params,
             surround_brackets(BracketType::Curly, enum_values),
-        ),
-        move |(name, values)| Enum { name, values },
+        )),
+        move |(name, type_params, values)| Enum {
+            name,
+            type_params,
+            values,
+        },
     )(i)
 }
 
@@ -51,7 +58,7 @@ fn enum_values(i: Input<'_>) -> Res<Input<'_>, Vec<EnumValue<'_>>> {
 #[cfg(test)]
 mod tests {
     use crate::{
-        ast::test_utils::{test_ident, test_type_sig},
+        ast::test_utils::{test_ident, test_type_param, test_type_sig},
         parser::new_input,
     };
 
@@ -85,6 +92,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_generic_enum() {
+        let enm = enumeration(new_input("enum Option<T> { some(T); none }"))
+            .unwrap()
+            .1;
+
+        assert_eq!(enm.name, test_ident("Option"));
+        assert_eq!(enm.type_params, vec![test_type_param("T")]);
+        assert_eq!(enm.values[0].items, vec![test_type_sig("T")]);
+    }
+
     #[test]
     fn test_enum_no_args() {
         let enm = enumeration(new_input("enum Test {\nfirst\nsecond\nlast }"))
@@ -0,0 +1,98 @@
+use nom::{
+    bytes::complete::tag,
+    combinator::map,
+    error::context,
+    sequence::{preceded, tuple},
+};
+
+use crate::ast::node::{error_handling::DoCatch, statement::StmtValue};
+
+use super::{
+    expression::expression, identifier::identifier, spaced, span, statement::statement,
+    surround_brackets, ws, BracketType, Input, Res,
+};
+
+pub fn do_catch(i: Input<'_>) -> Res<Input<'_>, DoCatch<'_>> {
+    // do "{" STMT "}" catch IDENT "{" STMT "}"
+
+    map(
+        span(tuple((
+            preceded(
+                spaced(tag("do")),
+                surround_brackets(BracketType::Curly, statement),
+            ),
+            preceded(spaced(tuple((tag("catch"), ws))), identifier),
+            context(
+                "catch body",
+                surround_brackets(BracketType::Curly, statement),
+            ),
+        ))),
+        |(span, (body, error_name, catch_body))| DoCatch {
+            body: Box::new(body),
+            error_name,
+            catch_body: Box::new(catch_body),
+            span,
+        },
+    )(i)
+}
+
+pub fn stmt_throw(i: Input<'_>) -> Res<Input<'_>, StmtValue<'_>> {
+    // throw EXPR
+
+    context(
+        "throw",
+        map(
+            preceded(spaced(tuple((tag("throw"), ws))), expression),
+            StmtValue::Throw,
+        ),
+    )(i)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::assert_matches;
+
+    use crate::{
+        ast::{
+            node::{expression::ExprValue, statement::Stmt},
+            test_utils::test_ident,
+        },
+        parser::new_input,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_do_catch() {
+        let dc = do_catch(new_input(
+            "do { try parse(input) } catch err { return err }",
+        ))
+        .unwrap()
+        .1;
+
+        assert_eq!(dc.error_name, test_ident("err"));
+        assert_matches!(
+            *dc.body,
+            Stmt {
+                span: _,
+                value: StmtValue::Expression(_)
+            }
+        );
+        assert_matches!(
+            *dc.catch_body,
+            Stmt {
+                span: _,
+                value: StmtValue::Return(_)
+            }
+        );
+    }
+
+    #[test]
+    fn test_throw() {
+        assert_matches!(
+            stmt_throw(new_input("throw ParseError { line: 1 }")).unwrap().1,
+            StmtValue::Throw(expr) if matches!(expr.value, ExprValue::StructInit(_))
+        );
+        assert!(stmt_throw(new_input("thrower()")).is_err());
+    }
+}
@@ -48,7 +48,7 @@ pub fn escape_block_content<'a>(i: Input<'a>) -> Res<Input<'a>, &'a str> {
 
 #[cfg(test)]
 mod tests {
-    use std::assert_matches::assert_matches;
+    use std::assert_matches;
 
     use crate::{ast::test_utils::test_type_sig, parser::new_input};
 
@@ -2,71 +2,214 @@ use std::num::ParseIntError;
 
 use nom::{
     branch::alt,
-    bytes::complete::{tag, take_until},
-    character::complete::{char as char_parser, digit1},
-    combinator::{map, opt, recognize},
+    bytes::complete::tag,
+    character::complete::{char as char_parser, digit1, multispace0, multispace1, one_of, space0},
+    combinator::{map, not, opt, value},
     error::context,
-    multi::{fold_many0, separated_list0},
-    sequence::{delimited, pair, preceded},
+    multi::{fold_many0, separated_list0, separated_list1},
+    sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
 };
 use nom_locate::position;
 
 use crate::ast::node::{
+    array::{Array, Index},
     assignment::Assignment,
+    dictionary::Dictionary,
+    error_handling::Try,
     expression::{Expr, ExprValue},
-    function::FunctionCall,
+    function::{Await, FunctionCall},
     identifier::Ident,
     member_access::MemberAccess,
+    operator::{BinaryOperation, BinaryOperator, UnaryOperation, UnaryOperator},
     tuple::{Tuple, TupleAccess},
 };
 
 use super::{
-    decimal, escape_block::escape_block, function::function_expr, identifier::identifier, spaced,
-    span, structure::struct_init_expr, surround_brackets, BracketType, Input, Res, Span,
+    escape_block::escape_block, function::function_expr, identifier::identifier,
+    number_literal::number_literal, pattern_match::pattern_match, spaced, span,
+    string_literal::string_literal, structure::struct_init_expr, surround_brackets, BracketType,
+    Input, Res, Span,
 };
 
 pub fn expression(i: Input<'_>) -> Res<Input<'_>, Expr<'_>> {
-    let (i_next, expr) = context(
-        "expression",
-        map(
-            span(alt((
-                map(struct_init_expr, ExprValue::StructInit),
-                map(identifier, ExprValue::Identifier),
-                expr_anon_member_access,
-                expr_string_literal,
-                expr_number_literal,
-                expr_boolean_literal,
-                function_expr,
-                expr_tuple,
-                map(escape_block, ExprValue::EscapeBlock),
-            ))),
-            |(span, value)| Expr { span, value },
-        ),
+    let (i_next, expr) = context("expression", |i| expr_binary_operation(i, 0))(i.clone())?;
+
+    tail_assignments(&expr, i_next, i)
+}
+
+fn expr_operand(i: Input<'_>) -> Res<Input<'_>, Expr<'_>> {
+    alt((expr_unary_operation, expr_await, expr_try, expr_primary))(i)
+}
+
+fn expr_primary(i: Input<'_>) -> Res<Input<'_>, Expr<'_>> {
+    let (i_next, expr) = map(
+        span(alt((
+            map(pattern_match, |mtch| ExprValue::Match(Box::new(mtch))),
+            map(struct_init_expr, ExprValue::StructInit),
+            map(identifier, ExprValue::Identifier),
+            expr_anon_member_access,
+            string_literal,
+            number_literal,
+            expr_boolean_literal,
+            expr_nil_literal,
+            function_expr,
+            expr_grouping,
+            expr_tuple,
+            expr_dictionary,
+            expr_array,
+            map(escape_block, ExprValue::EscapeBlock),
+        ))),
+        |(span, value)| Expr { span, value },
     )(i.clone())?;
 
-    expr_tail(&expr, i_next, i)
+    expr_tail_chain(expr, i_next, i)
+}
+
+fn expr_binary_operation(i: Input<'_>, min_precedence: u8) -> Res<Input<'_>, Expr<'_>> {
+    // OPERAND ( BINARY_OPERATOR OPERAND )*
+    // operators are parsed by precedence climbing, where every operator
+    // with a lower precedence than `min_precedence` ends the current operand
+
+    let i_start = i.clone();
+    let (mut i, mut lhs) = expr_operand(i)?;
+
+    loop {
+        let Ok((i_op, (operator_span, operator))) =
+            preceded(space0, span(binary_operator))(i.clone())
+        else {
+            break;
+        };
+
+        if operator.precedence() < min_precedence {
+            break;
+        }
+
+        let (i_rhs, rhs) = preceded(multispace0, |i| {
+            expr_binary_operation(i, operator.precedence() + 1)
+        })(i_op)?;
+        let (i_rhs, end) = position(i_rhs)?;
+
+        lhs = Expr {
+            span: Span::new(i_start.clone(), end),
+            value: ExprValue::BinaryOperation(Box::new(BinaryOperation {
+                lhs,
+                operator,
+                operator_span,
+                rhs,
+            })),
+        };
+        i = i_rhs;
+    }
+
+    Ok((i, lhs))
+}
+
+pub fn binary_operator(i: Input<'_>) -> Res<Input<'_>, BinaryOperator> {
+    context(
+        "binary operator",
+        alt((
+            value(BinaryOperator::Eq, tag("==")),
+            value(BinaryOperator::NotEq, tag("!=")),
+            value(BinaryOperator::LtEq, tag("<=")),
+            value(BinaryOperator::GtEq, tag(">=")),
+            value(BinaryOperator::And, tag("&&")),
+            value(BinaryOperator::Or, tag("||")),
+            value(BinaryOperator::Coalesce, tag("??")),
+            value(BinaryOperator::HalfOpenRange, tag("..<")),
+            value(BinaryOperator::ClosedRange, tag("...")),
+            value(BinaryOperator::Lt, tag("<")),
+            value(BinaryOperator::Gt, tag(">")),
+            value(BinaryOperator::Add, tag("+")),
+            value(
+                BinaryOperator::Sub,
+                terminated(tag("-"), not(char_parser('>'))),
+            ),
+            value(BinaryOperator::Mul, tag("*")),
+            // a slash followed by another slash or a star starts a comment
+            value(BinaryOperator::Div, terminated(tag("/"), not(one_of("/*")))),
+            value(BinaryOperator::Mod, tag("%")),
+        )),
+    )(i)
+}
+
+fn expr_unary_operation(i: Input<'_>) -> Res<Input<'_>, Expr<'_>> {
+    // UNARY_OPERATOR OPERAND
+
+    map(
+        span(pair(
+            preceded(multispace0, span(unary_operator)),
+            expr_operand,
+        )),
+        |(span, ((operator_span, operator), operand))| Expr {
+            span,
+            value: ExprValue::UnaryOperation(Box::new(UnaryOperation {
+                operator,
+                operator_span,
+                operand,
+            })),
+        },
+    )(i)
+}
+
+fn expr_await(i: Input<'_>) -> Res<Input<'_>, Expr<'_>> {
+    // "await" OPERAND
+
+    map(
+        span(preceded(
+            preceded(multispace0, pair(tag("await"), multispace1)),
+            expr_operand,
+        )),
+        |(span, expr)| Expr {
+            span,
+            value: ExprValue::Await(Box::new(Await { expr })),
+        },
+    )(i)
+}
+
+fn expr_try(i: Input<'_>) -> Res<Input<'_>, Expr<'_>> {
+    // "try" OPERAND
+
+    map(
+        span(preceded(
+            preceded(multispace0, pair(tag("try"), multispace1)),
+            expr_operand,
+        )),
+        |(span, expr)| Expr {
+            span,
+            value: ExprValue::Try(Box::new(Try { expr })),
+        },
+    )(i)
+}
+
+fn unary_operator(i: Input<'_>) -> Res<Input<'_>, UnaryOperator> {
+    context(
+        "unary operator",
+        alt((
+            value(UnaryOperator::Negate, char_parser('-')),
+            value(UnaryOperator::Not, char_parser('!')),
+        )),
+    )(i)
 }
 
 enum ExprTailChain<'a> {
     FuncCall {
         args: Vec<Expr<'a>>,
+        arg_labels: Vec<Option<Ident<'a>>>,
         args_span: Span<'a>,
     },
     MemberAccess {
         member_name: Ident<'a>,
         items: Option<(Span<'a>, Vec<Expr<'a>>)>,
+        optional: bool,
         span: Span<'a>,
     },
     TupleAccess {
         attr: usize,
         span: Span<'a>,
     },
-}
-
-fn expr_tail<'a>(base: &Expr<'a>, i: Input<'a>, i_start: Input<'a>) -> Res<Input<'a>, Expr<'a>> {
-    let (i_next, base) = expr_tail_chain(base.clone(), i, i_start.clone())?;
-
-    tail_assignments(&base, i_next, i_start)
+    Index {
+        index: Box<Expr<'a>>,
+    },
 }
 
 fn tail_assignments<'a>(
@@ -94,27 +237,37 @@ fn expr_tail_chain<'a>(
 ) -> Res<Input<'a>, Expr<'a>> {
     fold_many0(
         pair(
-            alt((tail_func_call, tail_member_access, tail_tuple_access)),
+            alt((
+                tail_func_call,
+                tail_member_access,
+                tail_tuple_access,
+                tail_index,
+            )),
             position,
         ),
         || base.clone(),
         |acc, (expr_tail, end)| {
             let expr_val = match expr_tail {
-                ExprTailChain::FuncCall { args, args_span } => {
-                    ExprValue::FunctionCall(Box::new(FunctionCall {
-                        func: acc,
-                        args,
-                        args_span,
-                    }))
-                }
+                ExprTailChain::FuncCall {
+                    args,
+                    arg_labels,
+                    args_span,
+                } => ExprValue::FunctionCall(Box::new(FunctionCall {
+                    func: acc,
+                    args,
+                    arg_labels,
+                    args_span,
+                })),
                 ExprTailChain::MemberAccess {
                     member_name,
                     items,
+                    optional,
                     span,
                 } => ExprValue::MemberAccess(Box::new(MemberAccess {
                     object: Some(acc),
                     member_name,
                     items,
+                    optional,
                     span,
                 })),
                 ExprTailChain::TupleAccess { attr, span } => ExprValue::TupleAccess(TupleAccess {
@@ -122,6 +275,10 @@ fn expr_tail_chain<'a>(
                     attr,
                     span,
                 }),
+                ExprTailChain::Index { index } => ExprValue::Index(Box::new(Index {
+                    array: acc,
+                    index: *index,
+                })),
             };
 
             Expr {
@@ -141,20 +298,42 @@ pub fn expr_args(i: Input<'_>) -> Res<Input<'_>, Vec<Expr<'_>>> {
 }
 
 fn tail_func_call(i: Input<'_>) -> Res<Input<'_>, ExprTailChain<'_>> {
-    map(span(expr_args), |(args_span, args)| {
-        ExprTailChain::FuncCall { args, args_span }
-    })(i)
+    // "(" [ IDENT ":" ] EXPR , ... ")"
+
+    let labelled_arg = pair(
+        opt(terminated(identifier, spaced(char_parser(':')))),
+        expression,
+    );
+
+    map(
+        span(surround_brackets(
+            BracketType::Round,
+            separated_list0(spaced(tag(",")), labelled_arg),
+        )),
+        |(args_span, labelled_args)| {
+            let (arg_labels, args) = labelled_args.into_iter().unzip();
+            ExprTailChain::FuncCall {
+                args,
+                arg_labels,
+                args_span,
+            }
+        },
+    )(i)
 }
 
 fn tail_member_access(i: Input<'_>) -> Res<Input<'_>, ExprTailChain<'_>> {
+    // ( "." | "?." ) IDENT [ "(" EXPR+ ")" ]
+
     map(
-        span(pair(
-            preceded(spaced(tag(".")), identifier),
+        span(tuple((
+            spaced(alt((value(true, tag("?.")), value(false, tag("."))))),
+            identifier,
             opt(span(expr_args)),
-        )),
-        |(span, (member_name, items))| ExprTailChain::MemberAccess {
+        ))),
+        |(span, (optional, member_name, items))| ExprTailChain::MemberAccess {
             member_name,
             items,
+            optional,
             span,
         },
     )(i)
@@ -172,17 +351,20 @@ fn tail_tuple_access(i: Input<'_>) -> Res<Input<'_>, ExprTailChain<'_>> {
     Ok((i, ExprTailChain::TupleAccess { attr, span }))
 }
 
-pub fn expr_string_literal(i: Input<'_>) -> Res<Input<'_>, ExprValue<'_>> {
-    let string_value = take_until("\"");
+fn tail_index(i: Input<'_>) -> Res<Input<'_>, ExprTailChain<'_>> {
+    // "[" EXPR "]"
+    // the bracket has to follow the expression directly, so that an array literal
+    // on the next line starts a new statement
 
-    return delimited(char_parser('\"'), string_value, char_parser('\"'))(i)
-        .map(|(i, str_val)| (i, ExprValue::StringLiteral(&str_val)));
-}
-
-pub fn expr_number_literal(i: Input<'_>) -> Res<Input<'_>, ExprValue<'_>> {
     map(
-        recognize(pair(decimal, opt(pair(char_parser('.'), opt(decimal))))),
-        |num| ExprValue::NumberLiteral(num.parse().unwrap()),
+        delimited(
+            char_parser('['),
+            spaced(expression),
+            context("closing bracket", char_parser(']')),
+        ),
+        |index| ExprTailChain::Index {
+            index: Box::new(index),
+        },
     )(i)
 }
 
@@ -196,6 +378,21 @@ pub fn expr_boolean_literal(i: Input<'_>) -> Res<Input<'_>, ExprValue<'_>> {
     )(i)
 }
 
+pub fn expr_nil_literal(i: Input<'_>) -> Res<Input<'_>, ExprValue<'_>> {
+    context("nil", value(ExprValue::NilLiteral, tag("nil")))(i)
+}
+
+pub fn expr_grouping(i: Input<'_>) -> Res<Input<'_>, ExprValue<'_>> {
+    // "(" EXPR ")"
+
+    context(
+        "grouping",
+        map(surround_brackets(BracketType::Round, expression), |expr| {
+            expr.value
+        }),
+    )(i)
+}
+
 pub fn expr_tuple(i: Input<'_>) -> Res<Input<'_>, ExprValue<'_>> {
     context(
         "tuple expression",
@@ -209,6 +406,42 @@ pub fn expr_tuple(i: Input<'_>) -> Res<Input<'_>, ExprValue<'_>> {
     )(i)
 }
 
+pub fn expr_array(i: Input<'_>) -> Res<Input<'_>, ExprValue<'_>> {
+    // "[" EXPR* "]"
+
+    context(
+        "array expression",
+        map(
+            surround_brackets(
+                BracketType::Square,
+                separated_list0(spaced(tag(",")), expression),
+            ),
+            |items| ExprValue::Array(Array { items }),
+        ),
+    )(i)
+}
+
+pub fn expr_dictionary(i: Input<'_>) -> Res<Input<'_>, ExprValue<'_>> {
+    // "[" ( EXPR ":" EXPR )+ "]" | "[" ":" "]"
+
+    context(
+        "dictionary expression",
+        map(
+            surround_brackets(
+                BracketType::Square,
+                alt((
+                    separated_list1(
+                        spaced(tag(",")),
+                        separated_pair(expression, spaced(tag(":")), expression),
+                    ),
+                    value(Vec::new(), spaced(tag(":"))),
+                )),
+            ),
+            |entries| ExprValue::Dictionary(Dictionary { entries }),
+        ),
+    )(i)
+}
+
 pub fn expr_anon_member_access(i: Input<'_>) -> Res<Input<'_>, ExprValue<'_>> {
     // "." IDENT [ "(" EXPR+ ")" ]
 
@@ -222,6 +455,7 @@ pub fn expr_anon_member_access(i: Input<'_>) -> Res<Input<'_>, ExprValue<'_>> {
                 object: None,
                 member_name,
                 items,
+                optional: false,
                 span,
             }))
         },
@@ -230,10 +464,10 @@ pub fn expr_anon_member_access(i: Input<'_>) -> Res<Input<'_>, ExprValue<'_>> {
 
 #[cfg(test)]
 mod tests {
-    use std::assert_matches::assert_matches;
+    use std::assert_matches;
 
     use crate::{
-        ast::test_utils::test_ident,
+        ast::{node::string_interpolation::StringPart, test_utils::test_ident},
         parser::{new_input, Span},
     };
 
@@ -271,12 +505,51 @@ mod tests {
                         fragment: "\"hello\"",
                         source: _
                     },
-                    value: ExprValue::StringLiteral("hello")
+                    value: ExprValue::StringLiteral(value)
                 }
-            ))
+            )) if value == "hello"
+        );
+
+        assert!(string_literal(new_input("\"hello not closed")).is_err());
+    }
+
+    #[test]
+    fn test_expr_string_interpolation() {
+        let parts = match expression(new_input("\"Hello \\(name)! \\( 1 + 2 )\"")) {
+            Ok((
+                _,
+                Expr {
+                    value: ExprValue::StringInterpolation(interp),
+                    ..
+                },
+            )) => interp.parts,
+            res => panic!("expected string interpolation, got {res:?}"),
+        };
+
+        assert_matches!(
+            parts.as_slice(),
+            [
+                StringPart::Literal(hello),
+                StringPart::Expr(name),
+                StringPart::Literal(exclamation),
+                StringPart::Expr(sum),
+            ] if hello == "Hello "
+                && matches!(name.value, ExprValue::Identifier(_))
+                && exclamation == "! "
+                && matches!(sum.value, ExprValue::BinaryOperation(_))
         );
 
-        assert!(expr_string_literal(new_input("\"hello not closed")).is_err());
+        assert_matches!(
+            expression(new_input("\"\"")),
+            Ok((
+                _,
+                Expr {
+                    value: ExprValue::StringLiteral(value),
+                    ..
+                }
+            )) if value.is_empty()
+        );
+        assert!(string_literal(new_input("\"Hello \\(name\"")).is_err());
     }
 
     #[test]
@@ -317,6 +590,7 @@ mod tests {
                     object: _,
                     member_name,
                     items,
+                    optional: _,
                     span: _,
                 } => {
                     assert_eq!(items.unwrap().1.len(), 1);
@@ -393,6 +667,79 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_expr_array() {
+        match expression(new_input("[1, 2, 3]")).unwrap().1 {
+            Expr {
+                span,
+                value: ExprValue::Array(Array { items }),
+            } => {
+                assert_eq!(span.fragment, "[1, 2, 3]");
+                assert_eq!(items.len(), 3);
+                assert_matches!(items[0].value, ExprValue::NumberLiteral(_));
+            }
+            _ => assert!(false),
+        }
+
+        assert_matches!(
+            expression(new_input("[]")).unwrap().1.value,
+            ExprValue::Array(Array { items }) if items.is_empty()
+        );
+    }
+
+    #[test]
+    fn test_expr_dictionary() {
+        match expression(new_input("[\"one\": 1, \"two\": 1 + 1]"))
+            .unwrap()
+            .1
+        {
+            Expr {
+                span,
+                value: ExprValue::Dictionary(Dictionary { entries }),
+            } => {
+                assert_eq!(span.fragment, "[\"one\": 1, \"two\": 1 + 1]");
+                assert_eq!(entries.len(), 2);
+                assert_matches!(entries[0].0.value, ExprValue::StringLiteral(_));
+                assert_matches!(entries[1].1.value, ExprValue::BinaryOperation(_));
+            }
+            _ => assert!(false),
+        }
+
+        assert_matches!(
+            expression(new_input("[ : ]")).unwrap().1.value,
+            ExprValue::Dictionary(Dictionary { entries }) if entries.is_empty()
+        );
+        assert_matches!(
+            expression(new_input("[1, 2]")).unwrap().1.value,
+            ExprValue::Array(_)
+        );
+    }
+
+    #[test]
+    fn test_expr_index() {
+        match expression(new_input("values[i + 1][0]")).unwrap().1 {
+            Expr {
+                span,
+                value: ExprValue::Index(index),
+            } => {
+                assert_eq!(span.fragment, "values[i + 1][0]");
+                assert_matches!(index.index.value, ExprValue::NumberLiteral(_));
+                assert_matches!(
+                    index.array.value,
+                    ExprValue::Index(ref inner)
+                        if matches!(inner.index.value, ExprValue::BinaryOperation(_))
+                );
+            }
+            _ => assert!(false),
+        }
+
+        // an array on the next line is not an index
+        assert_matches!(
+            expression(new_input("values\n[0]")).unwrap().1.value,
+            ExprValue::Identifier(_)
+        );
+    }
+
     #[test]
     fn test_expr_enum_init() {
         let expr = expression(new_input("IPAddress.v4(192, 168, 0, 1)"))
@@ -419,4 +766,181 @@ mod tests {
             expr => assert!(false, "Expected EnumInit expression, got {expr:?}"),
         }
     }
+
+    #[test]
+    fn test_expr_binary_operation_precedence() {
+        let expr = expression(new_input("1 + 2 * 3 - 4")).unwrap().1;
+        assert_eq!(expr.span.fragment, "1 + 2 * 3 - 4");
+
+        match expr.value {
+            ExprValue::BinaryOperation(sub) => {
+                assert_eq!(sub.operator, BinaryOperator::Sub);
+                assert_matches!(sub.rhs.value, ExprValue::NumberLiteral(_));
+
+                match sub.lhs.value {
+                    ExprValue::BinaryOperation(add) => {
+                        assert_eq!(add.operator, BinaryOperator::Add);
+                        assert_eq!(add.rhs.span.fragment, "2 * 3");
+                        assert_matches!(
+                            add.rhs.value,
+                            ExprValue::BinaryOperation(op) if op.operator == BinaryOperator::Mul
+                        );
+                    }
+                    _ => assert!(false),
+                }
+            }
+            _ => assert!(false),
+        }
+    }
+
+    #[test]
+    fn test_expr_logical_operators() {
+        let expr = expression(new_input("a < b && b <= c || !d")).unwrap().1;
+
+        match expr.value {
+            ExprValue::BinaryOperation(or) => {
+                assert_eq!(or.operator, BinaryOperator::Or);
+                assert_eq!(or.lhs.span.fragment, "a < b && b <= c");
+                assert_eq!(or.rhs.span.fragment, "!d");
+                assert_matches!(
+                    or.rhs.value,
+                    ExprValue::UnaryOperation(op) if op.operator == UnaryOperator::Not
+                );
+            }
+            _ => assert!(false),
+        }
+    }
+
+    #[test]
+    fn test_expr_coalesce() {
+        let expr = expression(new_input("a ?? b + 1 == nil")).unwrap().1;
+
+        match expr.value {
+            ExprValue::BinaryOperation(eq) => {
+                assert_eq!(eq.operator, BinaryOperator::Eq);
+                assert_matches!(eq.rhs.value, ExprValue::NilLiteral);
+                match eq.lhs.value {
+                    ExprValue::BinaryOperation(coalesce) => {
+                        assert_eq!(coalesce.operator, BinaryOperator::Coalesce);
+                        assert_eq!(coalesce.rhs.span.fragment, "b + 1");
+                    }
+                    _ => assert!(false),
+                }
+            }
+            _ => assert!(false),
+        }
+    }
+
+    #[test]
+    fn test_expr_range() {
+        let expr = expression(new_input("i < 0..<n + 1")).unwrap().1;
+
+        match expr.value {
+            ExprValue::BinaryOperation(lt) => {
+                assert_eq!(lt.operator, BinaryOperator::Lt);
+                match lt.rhs.value {
+                    ExprValue::BinaryOperation(range) => {
+                        assert_eq!(range.operator, BinaryOperator::HalfOpenRange);
+                        assert_eq!(range.rhs.span.fragment, "n + 1");
+                    }
+                    _ => assert!(false),
+                }
+            }
+            _ => assert!(false),
+        }
+
+        let expr = expression(new_input("1...3")).unwrap().1;
+        assert_matches!(
+            expr.value,
+            ExprValue::BinaryOperation(op) if op.operator == BinaryOperator::ClosedRange
+        );
+    }
+
+    #[test]
+    fn test_expr_optional_chaining() {
+        let expr = expression(new_input("a?.b.c")).unwrap().1;
+
+        match expr.value {
+            ExprValue::MemberAccess(c) => {
+                assert!(!c.optional);
+                assert_eq!(c.member_name, test_ident("c"));
+                assert_matches!(
+                    c.object.unwrap().value,
+                    ExprValue::MemberAccess(b) if b.optional && b.member_name == test_ident("b")
+                );
+            }
+            _ => assert!(false),
+        }
+    }
+
+    #[test]
+    fn test_expr_grouping() {
+        let expr = expression(new_input("-(a + b) * c.d")).unwrap().1;
+
+        match expr.value {
+            ExprValue::BinaryOperation(mul) => {
+                assert_eq!(mul.operator, BinaryOperator::Mul);
+                assert_eq!(mul.lhs.span.fragment, "-(a + b)");
+                assert_matches!(mul.rhs.value, ExprValue::MemberAccess(_));
+
+                match mul.lhs.value {
+                    ExprValue::UnaryOperation(neg) => {
+                        assert_eq!(neg.operator, UnaryOperator::Negate);
+                        assert_matches!(neg.operand.value, ExprValue::BinaryOperation(_));
+                    }
+                    _ => assert!(false),
+                }
+            }
+            _ => assert!(false),
+        }
+    }
+
+    #[test]
+    fn test_expr_equality_assignment() {
+        let expr = expression(new_input("a = b == c")).unwrap().1;
+
+        match expr.value {
+            ExprValue::Assignment(asg) => assert_matches!(
+                asg.rhs.value,
+                ExprValue::BinaryOperation(op) if op.operator == BinaryOperator::Eq
+            ),
+            _ => assert!(false),
+        }
+    }
+
+    #[test]
+    fn test_expr_await() {
+        let expr = expression(new_input("await load(url) + 1")).unwrap().1;
+
+        match expr.value {
+            ExprValue::BinaryOperation(add) => {
+                assert_eq!(add.lhs.span.fragment, "await load(url)");
+
+                match add.lhs.value {
+                    ExprValue::Await(aw) => {
+                        assert_matches!(aw.expr.value, ExprValue::FunctionCall(_))
+                    }
+                    _ => assert!(false),
+                }
+            }
+            _ => assert!(false),
+        }
+    }
+
+    #[test]
+    fn test_expr_try() {
+        let expr = expression(new_input("try parse(input).value")).unwrap().1;
+
+        match expr.value {
+            ExprValue::Try(tr) => {
+                assert_matches!(tr.expr.value, ExprValue::MemberAccess(_))
+            }
+            _ => assert!(false),
+        }
+
+        assert_matches!(
+            expression(new_input("tryParse(input)")).unwrap().1.value,
+            ExprValue::FunctionCall(_)
+        );
+    }
 }
@@ -0,0 +1,164 @@
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    combinator::{map, opt},
+    error::context,
+    multi::separated_list0,
+    sequence::{pair, preceded, tuple},
+};
+
+use crate::ast::node::{extension::Extension, function::Function, structure::StructAttr};
+
+use super::{
+    function::{function_decl, init_decl, operator_decl},
+    identifier::identifier,
+    spaced, span,
+    structure::struct_attr,
+    surround_brackets, ws, BracketType, Input, Res,
+};
+
+enum ExtensionMember<'a> {
+    Method(Function<'a>),
+    StaticMethod(Function<'a>),
+    StaticAttr(Box<StructAttr<'a>>),
+    Init(Function<'a>),
+    Operator(Function<'a>),
+}
+
+pub fn extension(i: Input<'_>) -> Res<Input<'_>, Extension<'_>> {
+    // "extend" IDENT [: IDENT] { MEMBER <<; | \n> MEMBER>* }
+
+    context(
+        "extension",
+        map(
+            pair(
+                span(pair(
+                    preceded(spaced(tuple((tag("extend"), ws))), identifier),
+                    opt(preceded(spaced(tag(":")), identifier)),
+                )),
+                surround_brackets(
+                    BracketType::Curly,
+                    separated_list0(alt((tag(";"), tag("\n"))), extension_member),
+                ),
+            ),
+            |((span, (name, trait_name)), members)| {
+                let mut ext = Extension {
+                    name,
+                    trait_name,
+                    methods: Vec::new(),
+                    static_methods: Vec::new(),
+                    static_attrs: Vec::new(),
+                    inits: Vec::new(),
+                    operators: Vec::new(),
+                    span,
+                };
+
+                for member in members {
+                    match member {
+                        ExtensionMember::Method(method) => ext.methods.push(method),
+                        ExtensionMember::StaticMethod(method) => ext.static_methods.push(method),
+                        ExtensionMember::StaticAttr(attr) => ext.static_attrs.push(*attr),
+                        ExtensionMember::Init(init) => ext.inits.push(init),
+                        ExtensionMember::Operator(op) => ext.operators.push(op),
+                    }
+                }
+
+                ext
+            },
+        ),
+    )(i)
+}
+
+fn extension_member(i: Input<'_>) -> Res<Input<'_>, ExtensionMember<'_>> {
+    // FUNC_DECL | "static" FUNC_DECL | "static" STRUCT_ATTR | INIT_DECL | OPERATOR_DECL
+
+    let static_keyword = || spaced(tuple((tag("static"), ws)));
+
+    alt((
+        map(init_decl, ExtensionMember::Init),
+        map(operator_decl, ExtensionMember::Operator),
+        map(
+            preceded(static_keyword(), function_decl),
+            ExtensionMember::StaticMethod,
+        ),
+        map(preceded(static_keyword(), struct_attr), |attr| {
+            ExtensionMember::StaticAttr(Box::new(attr))
+        }),
+        map(function_decl, ExtensionMember::Method),
+    ))(i)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ast::test_utils::test_ident, parser::new_input};
+
+    use super::*;
+
+    #[test]
+    fn test_extension() {
+        let ext = extension(new_input(
+            "extend Test: Printable {
+                func print() { return 1 }
+
+                func add(a: Number) -> Number { return a }
+            }",
+        ))
+        .unwrap()
+        .1;
+
+        assert_eq!(ext.name, test_ident("Test"));
+        assert_eq!(ext.trait_name, Some(test_ident("Printable")));
+        assert_eq!(ext.methods.len(), 2);
+    }
+
+    #[test]
+    fn test_extension_static_members() {
+        let ext = extension(new_input(
+            "extend Point {
+                init(x: Number, y: Number) { self.x = x; self.y = y }
+                init() { self.x = 0; self.y = 0 }
+
+                static let origin = Point(x: 0, y: 0)
+                static func unit() -> Point { return Point(x: 1, y: 1) }
+
+                func length() -> Number { return self.x + self.y }
+            }",
+        ))
+        .unwrap()
+        .1;
+
+        assert_eq!(ext.inits.len(), 2);
+        assert_eq!(ext.inits[0].args.len(), 2);
+        assert!(ext.inits[1].args.is_empty());
+        assert_eq!(ext.static_attrs.len(), 1);
+        assert_eq!(ext.static_attrs[0].name, test_ident("origin"));
+        assert_eq!(ext.static_methods.len(), 1);
+        assert_eq!(ext.static_methods[0].name, Some(test_ident("unit")));
+        assert_eq!(ext.methods.len(), 1);
+    }
+
+    #[test]
+    fn test_extension_operators() {
+        let ext = extension(new_input(
+            "extend Point: Equatable {
+                static func == (lhs: Point, rhs: Point) -> Boolean { return lhs.x == rhs.x }
+                static func + (lhs: Point, rhs: Point) -> Point { return lhs }
+                static func zero() -> Point { return Point(x: 0) }
+            }",
+        ))
+        .unwrap()
+        .1;
+
+        assert_eq!(ext.operators.len(), 2);
+        assert_eq!(ext.operators[1].name.as_ref().unwrap().value, "+");
+        assert_eq!(ext.static_methods.len(), 1);
+    }
+
+    #[test]
+    fn test_extension_without_trait() {
+        let ext = extension(new_input("extend Test {}")).unwrap().1;
+
+        assert_eq!(ext.trait_name, None);
+        assert!(ext.methods.is_empty());
+    }
+}
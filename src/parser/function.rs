@@ -1,6 +1,6 @@
 use nom::{
     bytes::complete::tag,
-    combinator::{map, opt},
+    combinator::{map, opt, verify},
     error::context,
     multi::separated_list0,
     sequence::{pair, preceded, tuple},
@@ -9,74 +9,153 @@ use nom::{
 use crate::ast::node::{
     expression::ExprValue,
     function::{Function, FunctionArg},
+    generics::TypeParam,
     identifier::Ident,
+    operator::BinaryOperator,
     type_signature::TypeSignature,
 };
 
 use super::{
-    identifier::identifier, spaced, span, statement::statement, surround_brackets,
-    type_signature::type_signature, ws, BracketType, Input, Res, Span,
+    expression::binary_operator,
+    identifier::identifier,
+    spaced, span,
+    statement::statement,
+    surround_brackets,
+    type_signature::{type_params, type_signature},
+    ws, BracketType, Input, Res, Span,
 };
 
 pub fn function_decl(i: Input<'_>) -> Res<Input<'_>, Function<'_>> {
-    // func IDENT "(" FUNC_ARGS ")" [-> RETURN_SIG] "{" BODY "}"
+    // ["async"] func IDENT [TYPE_PARAMS] "(" FUNC_ARGS ")" [THROWS_SIG] [-> RETURN_SIG] "{" BODY "}"
 
     map(
-        pair(
+        tuple((
+            async_keyword,
             function_signature,
             context(
                 "function body",
                 surround_brackets(BracketType::Curly, statement),
             ),
-        ),
-        |((name, args, return_type, span), body)| Function {
+        )),
+        |(is_async, (name, type_params, args, throws, return_type, span), body)| Function {
             name: Some(name),
+            type_params,
             args,
             return_type,
+            throws,
             body: Box::new(body),
+            is_async,
             span,
         },
     )(i)
 }
 
+pub fn init_decl(i: Input<'_>) -> Res<Input<'_>, Function<'_>> {
+    // "init" "(" FUNC_ARGS ")" [THROWS_SIG] "{" BODY "}"
+
+    context(
+        "init declaration",
+        map(
+            pair(
+                span(tuple((
+                    preceded(
+                        spaced(tag("init")),
+                        surround_brackets(BracketType::Round, function_args),
+                    ),
+                    throws_signature,
+                ))),
+                context(
+                    "init body",
+                    surround_brackets(BracketType::Curly, statement),
+                ),
+            ),
+            |((span, (args, throws)), body)| Function {
+                name: None,
+                type_params: vec![],
+                args,
+                return_type: None,
+                throws,
+                body: Box::new(body),
+                is_async: false,
+                span,
+            },
+        ),
+    )(i)
+}
+
+pub fn operator_decl(i: Input<'_>) -> Res<Input<'_>, Function<'_>> {
+    // OPERATOR_SIGNATURE "{" BODY "}"
+
+    context(
+        "operator declaration",
+        map(
+            pair(
+                operator_signature,
+                context(
+                    "operator body",
+                    surround_brackets(BracketType::Curly, statement),
+                ),
+            ),
+            |((name, type_params, args, throws, return_type, span), body)| Function {
+                name: Some(name),
+                type_params,
+                args,
+                return_type,
+                throws,
+                body: Box::new(body),
+                is_async: false,
+                span,
+            },
+        ),
+    )(i)
+}
+
 pub fn function_expr(i: Input<'_>) -> Res<Input<'_>, ExprValue<'_>> {
-    // "(" FUNC_ARGS ")" [-> RETURN_SIG] "{" BODY "}"
+    // ["async"] "(" FUNC_ARGS ")" [THROWS_SIG] [-> RETURN_SIG] "{" BODY "}"
 
     map(
         pair(
-            span(pair(
+            span(tuple((
+                async_keyword,
                 surround_brackets(BracketType::Round, function_args),
+                throws_signature,
                 return_signature,
-            )),
+            ))),
             context(
                 "function body",
                 surround_brackets(BracketType::Curly, statement),
             ),
         ),
-        |((span, (args, return_type)), body)| {
+        |((span, (is_async, args, throws, return_type)), body)| {
             ExprValue::Function(Function {
                 name: None,
+                type_params: vec![],
                 args,
                 return_type,
+                throws,
                 body: Box::new(body),
+                is_async,
                 span,
             })
         },
     )(i)
 }
 
-pub fn function_signature(
-    i: Input<'_>,
-) -> Res<
-    Input<'_>,
-    (
-        Ident<'_>,
-        Vec<FunctionArg<'_>>,
-        Option<TypeSignature<'_>>,
-        Span<'_>,
-    ),
-> {
-    // func IDENT "(" FUNC_ARGS ")" [-> RETURN_SIG]
+fn async_keyword(i: Input<'_>) -> Res<Input<'_>, bool> {
+    map(opt(spaced(tuple((tag("async"), ws)))), |kw| kw.is_some())(i)
+}
+
+pub type FunctionSignature<'a> = (
+    Ident<'a>,
+    Vec<TypeParam<'a>>,
+    Vec<FunctionArg<'a>>,
+    Option<TypeSignature<'a>>,
+    Option<TypeSignature<'a>>,
+    Span<'a>,
+);
+
+pub fn function_signature(i: Input<'_>) -> Res<Input<'_>, FunctionSignature<'_>> {
+    // func IDENT [TYPE_PARAMS] "(" FUNC_ARGS ")" [THROWS_SIG] [-> RETURN_SIG]
 
     map(
         span(tuple((
@@ -84,10 +163,58 @@ pub fn function_signature(
                 spaced(tuple((tag("func"), ws))),
                 context("function name", identifier),
             ),
+            type_params,
             surround_brackets(BracketType::Round, function_args),
+            throws_signature,
             return_signature,
         ))),
-        |(span, (name, args, return_type))| (name, args, return_type, span),
+        |(span, (name, type_params, args, throws, return_type))| {
+            (name, type_params, args, throws, return_type, span)
+        },
+    )(i)
+}
+
+pub fn operator_signature(i: Input<'_>) -> Res<Input<'_>, FunctionSignature<'_>> {
+    // "static" func OPERATOR "(" FUNC_ARGS ")" [THROWS_SIG] -> RETURN_SIG
+
+    map(
+        span(tuple((
+            preceded(
+                pair(spaced(tuple((tag("static"), ws))), spaced(tag("func"))),
+                context("operator", operator_name),
+            ),
+            surround_brackets(BracketType::Round, function_args),
+            throws_signature,
+            context(
+                "operator return signature",
+                preceded(spaced(tag("->")), type_signature),
+            ),
+        ))),
+        |(span, (name, args, throws, return_type))| {
+            (name, vec![], args, throws, Some(return_type), span)
+        },
+    )(i)
+}
+
+fn operator_name(i: Input<'_>) -> Res<Input<'_>, Ident<'_>> {
+    map(
+        span(verify(binary_operator, BinaryOperator::is_overloadable)),
+        |(span, operator)| Ident {
+            span,
+            value: operator.symbol(),
+        },
+    )(i)
+}
+
+pub fn throws_signature(i: Input<'_>) -> Res<Input<'_>, Option<TypeSignature<'_>>> {
+    // "throws" "(" TYPE_SIG ")"
+
+    context(
+        "throws signature",
+        opt(preceded(
+            spaced(tag("throws")),
+            surround_brackets(BracketType::Round, type_signature),
+        )),
     )(i)
 }
 
@@ -126,12 +253,12 @@ fn function_arg(i: Input<'_>) -> Res<Input<'_>, FunctionArg<'_>> {
 
 #[cfg(test)]
 mod tests {
-    use std::assert_matches::assert_matches;
+    use std::assert_matches;
 
     use crate::{
         ast::{
             node::{expression::Expr, identifier::Ident},
-            test_utils::{test_ident, test_type_sig},
+            test_utils::{test_ident, test_type_param, test_type_sig},
         },
         ir::{
             node::{identifier::IdentKey, statement::Stmt},
@@ -166,6 +293,21 @@ mod tests {
         assert_eq!(func.args[1].type_sig, Some(test_type_sig("Number")));
     }
 
+    #[test]
+    fn test_generic_function_decl() {
+        let func = function_decl(new_input("func first<T, U>(a: T, b: U) -> T { return a }"))
+            .unwrap()
+            .1;
+
+        assert_eq!(func.name, Some(test_ident("first")));
+        assert_eq!(
+            func.type_params,
+            vec![test_type_param("T"), test_type_param("U")]
+        );
+        assert_eq!(func.args[0].type_sig, Some(test_type_sig("T")));
+        assert_eq!(func.return_type, Some(test_type_sig("T")));
+    }
+
     #[test]
     fn test_function_expr() {
         let func_expr = function_expr(new_input("(a: Number, b: Number) {}"))
@@ -185,6 +327,44 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_async_function_decl() {
+        let func = function_decl(new_input("async func load() -> String {}"))
+            .unwrap()
+            .1;
+
+        assert_eq!(func.name, Some(test_ident("load")));
+        assert!(func.is_async);
+        assert!(!function_decl(new_input("func f() {}")).unwrap().1.is_async);
+    }
+
+    #[test]
+    fn test_throwing_function_decl() {
+        let func = function_decl(new_input(
+            "func parse(input: String) throws(ParseError) -> Number {}",
+        ))
+        .unwrap()
+        .1;
+
+        assert_eq!(func.throws, Some(test_type_sig("ParseError")));
+        assert_eq!(func.return_type, Some(test_type_sig("Number")));
+    }
+
+    #[test]
+    fn test_async_function_expr() {
+        let func_expr = function_expr(new_input("async (url: String) {}"))
+            .unwrap()
+            .1;
+
+        match func_expr {
+            ExprValue::Function(func) => {
+                assert!(func.is_async);
+                assert_eq!(func.args[0].name, test_ident("url"));
+            }
+            _ => assert!(false),
+        }
+    }
+
     #[test]
     fn test_function_var_assignment() {
         let ir = lowered_ir("let f = (a: Number, b: Number) {}").unwrap();
@@ -231,6 +411,50 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_labelled_function_call() {
+        let func_call = expression(new_input("Point(x: 1, y)")).unwrap().1.value;
+
+        match func_call {
+            ExprValue::FunctionCall(func_call) => {
+                assert_eq!(func_call.args.len(), 2);
+                assert_eq!(func_call.arg_labels, vec![Some(test_ident("x")), None]);
+            }
+            _ => assert!(false),
+        }
+    }
+
+    #[test]
+    fn test_init_decl() {
+        let init = init_decl(new_input("init(x: Number) throws(String) { self.x = x }"))
+            .unwrap()
+            .1;
+
+        assert!(init.name.is_none());
+        assert!(init.return_type.is_none());
+        assert!(init.throws.is_some());
+        assert_eq!(init.args.len(), 1);
+    }
+
+    #[test]
+    fn test_operator_decl() {
+        let op = operator_decl(new_input(
+            "static func == (lhs: Point, rhs: Point) -> Boolean { return lhs.x == rhs.x }",
+        ))
+        .unwrap()
+        .1;
+
+        assert_eq!(op.name.unwrap().value, "==");
+        assert_eq!(op.args.len(), 2);
+        assert!(op.return_type.is_some());
+
+        // the logical operators are only defined for booleans
+        assert!(operator_decl(new_input(
+            "static func && (lhs: Point, rhs: Point) -> Boolean { return true }"
+        ))
+        .is_err());
+    }
+
     #[test]
     fn test_double_function_call() {
         let expr = expression(new_input("f(10)(20)")).unwrap().1;
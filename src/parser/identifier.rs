@@ -11,7 +11,9 @@ use crate::ast::node::identifier::Ident;
 use super::{span, Input, Res};
 
 const RESERVED_KEYWORDS: &'static [&str] = &[
-    "trait", "struct", "func", "return", "let", "var", "true", "false", "if", "else",
+    "trait", "struct", "extend", "func", "return", "let", "var", "true", "false", "if", "else",
+    "match", "async", "await", "throws", "throw", "try", "do", "catch", "nil", "while", "for",
+    "in", "break", "continue",
 ];
 
 pub fn identifier(i: Input<'_>) -> Res<Input<'_>, Ident<'_>> {
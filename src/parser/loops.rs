@@ -0,0 +1,148 @@
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{char, multispace0, satisfy, space1},
+    combinator::{map, not, opt, value},
+    error::context,
+    sequence::{preceded, terminated, tuple},
+};
+
+use crate::ast::node::{
+    identifier::Ident,
+    loops::{ForLoop, LoopJump, LoopJumpKind, WhileLoop},
+};
+
+use super::{
+    expression::expression, identifier::identifier, spaced, span, statement::statement,
+    surround_brackets, ws, BracketType, Input, Res,
+};
+
+pub fn while_loop(i: Input<'_>) -> Res<Input<'_>, WhileLoop<'_>> {
+    // [ IDENT ":" ] while EXPR "{" STMT "}"
+
+    context(
+        "while loop",
+        map(
+            span(tuple((
+                loop_label,
+                preceded(spaced(terminated(tag("while"), ws)), expression),
+                surround_brackets(BracketType::Curly, statement),
+            ))),
+            |(span, (label, condition, body))| WhileLoop {
+                label,
+                condition,
+                body: Box::new(body),
+                span,
+            },
+        ),
+    )(i)
+}
+
+pub fn for_loop(i: Input<'_>) -> Res<Input<'_>, ForLoop<'_>> {
+    // [ IDENT ":" ] for IDENT in EXPR "{" STMT "}"
+
+    context(
+        "for loop",
+        map(
+            span(tuple((
+                loop_label,
+                preceded(spaced(terminated(tag("for"), ws)), identifier),
+                preceded(spaced(terminated(tag("in"), ws)), expression),
+                surround_brackets(BracketType::Curly, statement),
+            ))),
+            |(span, (label, item_name, sequence, body))| ForLoop {
+                label,
+                item_name,
+                sequence,
+                body: Box::new(body),
+                span,
+            },
+        ),
+    )(i)
+}
+
+pub fn loop_jump(i: Input<'_>) -> Res<Input<'_>, LoopJump<'_>> {
+    // ( break | continue ) [ IDENT ]
+
+    let keyword = alt((
+        value(LoopJumpKind::Break, tag("break")),
+        value(LoopJumpKind::Continue, tag("continue")),
+    ));
+
+    context(
+        "loop jump",
+        map(
+            span(tuple((
+                preceded(
+                    multispace0,
+                    terminated(keyword, not(satisfy(|c| c.is_alphanumeric() || c == '_'))),
+                ),
+                // the label has to be on the same line as the keyword
+                opt(preceded(space1, identifier)),
+            ))),
+            |(span, (kind, label))| LoopJump { kind, label, span },
+        ),
+    )(i)
+}
+
+fn loop_label(i: Input<'_>) -> Res<Input<'_>, Option<Ident<'_>>> {
+    opt(terminated(identifier, spaced(char(':'))))(i)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::assert_matches;
+
+    use crate::{
+        ast::{
+            node::{expression::ExprValue, statement::StmtValue},
+            test_utils::test_ident,
+        },
+        parser::new_input,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_while_loop() {
+        let wl = while_loop(new_input("while i < 10 { i = i + 1 }"))
+            .unwrap()
+            .1;
+
+        assert!(wl.label.is_none());
+        assert_matches!(wl.condition.value, ExprValue::BinaryOperation(_));
+        assert_matches!(wl.body.value, StmtValue::Expression(_));
+    }
+
+    #[test]
+    fn test_for_loop() {
+        let fl = for_loop(new_input("rows: for row in table { continue rows }"))
+            .unwrap()
+            .1;
+
+        assert_eq!(fl.label, Some(test_ident("rows")));
+        assert_eq!(fl.item_name, test_ident("row"));
+        assert_matches!(fl.sequence.value, ExprValue::Identifier(_));
+        assert_matches!(
+            fl.body.value,
+            StmtValue::LoopJump(LoopJump {
+                kind: LoopJumpKind::Continue,
+                label: Some(_),
+                span: _
+            })
+        );
+    }
+
+    #[test]
+    fn test_loop_jump() {
+        let jump = loop_jump(new_input("break")).unwrap().1;
+        assert_eq!(jump.kind, LoopJumpKind::Break);
+        assert!(jump.label.is_none());
+
+        let (rest, jump) = loop_jump(new_input("break\nouter()")).unwrap();
+        assert!(jump.label.is_none());
+        assert_eq!(*rest.fragment(), "\nouter()");
+
+        assert!(loop_jump(new_input("breakpoint")).is_err());
+    }
+}
@@ -3,7 +3,7 @@ use std::{cmp::Ordering, fmt::Display};
 use nom::{
     bytes::complete::tag,
     character::complete::{digit1, multispace0, multispace1},
-    error::VerboseError,
+    error::{VerboseError, VerboseErrorKind},
     sequence::{delimited, preceded},
     IResult,
 };
@@ -11,18 +11,27 @@ use nom_locate::{position, LocatedSpan};
 
 use crate::ast::AST;
 
+use self::number_literal::NumberLiteralError;
+
 pub mod comment;
 pub mod control_flow;
 pub mod enumeration;
+pub mod error_handling;
 pub mod escape_block;
 pub mod expression;
+pub mod extension;
 pub mod external;
 pub mod function;
 pub mod identifier;
+pub mod loops;
 pub mod module;
+pub mod number_literal;
+pub mod pattern_match;
 pub mod statement;
+pub mod string_literal;
 pub mod structure;
 pub mod traits;
+pub mod type_alias;
 pub mod type_signature;
 
 pub fn parse_ast(input: &str) -> Result<AST<'_>, ParserError<'_>> {
@@ -36,11 +45,40 @@ pub fn parse_ast(input: &str) -> Result<AST<'_>, ParserError<'_>> {
 pub enum ParserError<'a> {
     NomErr(VerboseError<Input<'a>>),
     EarlyTermination(),
+    /// An unknown or malformed escape sequence in a string literal
+    InvalidEscape(Span<'a>),
+    /// A line of a multi-line string that is indented less than the closing quotes
+    InsufficientIndentation(Span<'a>),
+    /// A number literal which is malformed or cannot be represented
+    InvalidNumber(Span<'a>, NumberLiteralError),
 }
 
 impl<'a> From<VerboseError<Input<'a>>> for ParserError<'a> {
     fn from(value: VerboseError<Input<'a>>) -> Self {
-        ParserError::NomErr(value)
+        let (input, context) = match value.errors.first() {
+            Some((input, VerboseErrorKind::Context(context))) => (input, *context),
+            _ => return ParserError::NomErr(value),
+        };
+
+        let span = Span {
+            line: input.location_line() as usize,
+            offset: input.get_utf8_column(),
+            fragment: input.fragment(),
+            source: input.extra.source,
+        };
+
+        if context == string_literal::INVALID_ESCAPE {
+            return ParserError::InvalidEscape(span);
+        }
+
+        if context == string_literal::INSUFFICIENT_INDENTATION {
+            return ParserError::InsufficientIndentation(span);
+        }
+
+        match NumberLiteralError::from_context(context) {
+            Some(err) => ParserError::InvalidNumber(span, err),
+            None => ParserError::NomErr(value),
+        }
     }
 }
 
@@ -49,6 +87,15 @@ impl<'a> Display for ParserError<'a> {
         match self {
             ParserError::NomErr(err) => err.fmt(f),
             ParserError::EarlyTermination() => write!(f, "early termination"),
+            ParserError::InvalidEscape(span) => {
+                write!(f, "invalid escape sequence `{}`", span.fragment)
+            }
+            ParserError::InsufficientIndentation(_) => {
+                write!(f, "insufficient indentation of a line in a multi-line string")
+            }
+            ParserError::InvalidNumber(span, err) => {
+                write!(f, "{} `{}`", err.context(), span.fragment)
+            }
         }
     }
 }
@@ -89,6 +136,7 @@ pub enum BracketType {
     Round,
     Square,
     Curly,
+    Angle,
 }
 
 impl BracketType {
@@ -97,6 +145,7 @@ impl BracketType {
             BracketType::Round => "(",
             BracketType::Square => "[",
             BracketType::Curly => "{",
+            BracketType::Angle => "<",
         }
     }
 
@@ -105,6 +154,7 @@ impl BracketType {
             BracketType::Round => ")",
             BracketType::Square => "]",
             BracketType::Curly => "}",
+            BracketType::Angle => ">",
         }
     }
 }
@@ -178,6 +228,14 @@ impl<'a> Span<'a> {
         }
     }
 
+    /// Whether the other span lies inside of this one, when both are spans of the same source
+    pub fn contains(&self, other: &Span<'a>) -> bool {
+        let range = self.fragment.as_bytes().as_ptr_range();
+        let other_range = other.fragment.as_bytes().as_ptr_range();
+
+        range.start <= other_range.start && other_range.end <= range.end
+    }
+
     pub fn empty() -> Span<'a> {
         Span {
             line: 0,
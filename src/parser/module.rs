@@ -9,14 +9,14 @@ pub fn module<'a>(i: Input<'a>) -> Result<Module<'a>, ParserError<'a>> {
         Ok((_, stmt)) => Ok(Module { stmt }),
         Err(err) => match err {
             nom::Err::Incomplete(_size) => Err(ParserError::EarlyTermination()),
-            nom::Err::Error(e) | nom::Err::Failure(e) => Err(ParserError::NomErr(e)),
+            nom::Err::Error(e) | nom::Err::Failure(e) => Err(e.into()),
         },
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::assert_matches::assert_matches;
+    use std::assert_matches;
 
     use crate::{ast::node::statement::StmtValue, parser::new_input};
 
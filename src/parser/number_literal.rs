@@ -0,0 +1,275 @@
+use nom::{
+    branch::alt,
+    bytes::complete::{tag_no_case, take_while, take_while1},
+    character::complete::{char as char_parser, digit1, one_of},
+    combinator::{not, opt, recognize},
+    error::{VerboseError, VerboseErrorKind},
+    sequence::{pair, terminated, tuple},
+};
+
+use crate::ast::node::expression::ExprValue;
+
+use super::{Input, Res};
+
+/// Why a number literal was rejected, the input of the parser failure covers the literal
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NumberLiteralError {
+    /// Digits which do not belong to the radix, or misplaced digit separators
+    Malformed,
+    /// A value too large or too small to be represented as a number
+    OutOfRange,
+    /// An integer which cannot be represented exactly as a number
+    PrecisionLoss,
+}
+
+impl NumberLiteralError {
+    const MALFORMED: &'static str = "malformed number literal";
+    const OUT_OF_RANGE: &'static str = "number literal out of range";
+    const PRECISION_LOSS: &'static str = "number literal loses precision";
+
+    /// The context of the parser failure for the error
+    pub fn context(&self) -> &'static str {
+        match self {
+            NumberLiteralError::Malformed => Self::MALFORMED,
+            NumberLiteralError::OutOfRange => Self::OUT_OF_RANGE,
+            NumberLiteralError::PrecisionLoss => Self::PRECISION_LOSS,
+        }
+    }
+
+    pub fn from_context(context: &str) -> Option<Self> {
+        match context {
+            Self::MALFORMED => Some(NumberLiteralError::Malformed),
+            Self::OUT_OF_RANGE => Some(NumberLiteralError::OutOfRange),
+            Self::PRECISION_LOSS => Some(NumberLiteralError::PrecisionLoss),
+            _ => None,
+        }
+    }
+}
+
+pub fn number_literal(i: Input<'_>) -> Res<Input<'_>, ExprValue<'_>> {
+    // <"0x" | "0b" | "0o"> <ALPHANUMERIC | "_">+
+    // | DIGITS <"." DIGITS?>? <"e" <"+" | "-">? DIGITS>?
+
+    alt((radix_literal, decimal_literal))(i)
+}
+
+fn radix_literal(i: Input<'_>) -> Res<Input<'_>, ExprValue<'_>> {
+    let (rest, (prefix, digits)) = pair(
+        alt((tag_no_case("0x"), tag_no_case("0b"), tag_no_case("0o"))),
+        // recognize every alphanumeric so that digits outside of the radix are reported
+        take_while(|c: char| c.is_ascii_alphanumeric() || c == '_'),
+    )(i.clone())?;
+
+    let radix = match prefix.fragment().to_ascii_lowercase().as_str() {
+        "0x" => 16,
+        "0b" => 2,
+        _ => 8,
+    };
+
+    if !valid_separators(digits.fragment()) {
+        return number_failure(i, rest, NumberLiteralError::Malformed);
+    }
+
+    let value = match u128::from_str_radix(&digits.fragment().replace('_', ""), radix) {
+        Ok(value) => value,
+        Err(err) => match err.kind() {
+            std::num::IntErrorKind::PosOverflow => {
+                return number_failure(i, rest, NumberLiteralError::PrecisionLoss)
+            }
+            _ => return number_failure(i, rest, NumberLiteralError::Malformed),
+        },
+    };
+
+    match exact_integer(value) {
+        Some(value) => Ok((rest, ExprValue::NumberLiteral(value))),
+        None => number_failure(i, rest, NumberLiteralError::PrecisionLoss),
+    }
+}
+
+fn decimal_literal(i: Input<'_>) -> Res<Input<'_>, ExprValue<'_>> {
+    let (rest, (integer, fraction, exponent)) = tuple((
+        recognize(pair(digit1, digits_with_separators)),
+        // a second dot starts a range instead of a fraction
+        opt(pair(
+            terminated(char_parser('.'), not(char_parser('.'))),
+            digits_with_separators,
+        )),
+        opt(recognize(tuple((
+            one_of("eE"),
+            opt(one_of("+-")),
+            take_while1(|c: char| c.is_ascii_digit() || c == '_'),
+        )))),
+    ))(i.clone())?;
+
+    let exponent_digits = exponent
+        .as_ref()
+        .map(|exponent| exponent.fragment().trim_start_matches(['e', 'E', '+', '-']));
+
+    let separators_valid = valid_separators(integer.fragment())
+        && fraction.as_ref().is_none_or(|(_, fraction)| {
+            fraction.fragment().is_empty() || valid_separators(fraction.fragment())
+        })
+        && exponent_digits.is_none_or(valid_separators);
+
+    if !separators_valid {
+        return number_failure(i, rest, NumberLiteralError::Malformed);
+    }
+
+    let literal_len = rest.location_offset() - i.location_offset();
+    let literal = i.fragment()[..literal_len].replace('_', "");
+    let value: f64 = literal.parse().expect("number literal should be valid");
+
+    let mantissa = literal.split(['e', 'E']).next().unwrap_or_default();
+    let underflows = value == 0.0 && mantissa.contains(|c: char| ('1'..='9').contains(&c));
+
+    if value.is_infinite() || underflows {
+        return number_failure(i, rest, NumberLiteralError::OutOfRange);
+    }
+
+    // integers must be exact, fractions and exponents are rounded to the nearest number
+    if fraction.is_none() && exponent.is_none() {
+        let exact = literal.parse::<u128>().ok().and_then(exact_integer);
+
+        if exact.is_none() {
+            return number_failure(i, rest, NumberLiteralError::PrecisionLoss);
+        }
+    }
+
+    Ok((rest, ExprValue::NumberLiteral(value)))
+}
+
+fn digits_with_separators(i: Input<'_>) -> Res<Input<'_>, Input<'_>> {
+    take_while(|c: char| c.is_ascii_digit() || c == '_')(i)
+}
+
+/// Digit separators are only allowed between two digits
+fn valid_separators(digits: &str) -> bool {
+    !digits.is_empty()
+        && !digits.starts_with('_')
+        && !digits.ends_with('_')
+        && !digits.contains("__")
+}
+
+/// The integer as a number, `None` if it cannot be represented exactly
+fn exact_integer(value: u128) -> Option<f64> {
+    let number = value as f64;
+
+    match number as u128 == value {
+        true => Some(number),
+        false => None,
+    }
+}
+
+fn number_failure<'a, O>(
+    start: Input<'a>,
+    end: Input<'a>,
+    error: NumberLiteralError,
+) -> Res<Input<'a>, O> {
+    let len = end.location_offset() - start.location_offset();
+    let (_, literal) = nom::bytes::complete::take::<_, _, VerboseError<Input<'_>>>(len)(start)?;
+
+    Err(nom::Err::Failure(VerboseError {
+        errors: vec![(literal, VerboseErrorKind::Context(error.context()))],
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::error::VerboseErrorKind;
+
+    use crate::{ast::node::expression::ExprValue, parser::new_input};
+
+    use super::{number_literal, NumberLiteralError};
+
+    fn number_value(input: &str) -> f64 {
+        match number_literal(new_input(input)) {
+            Ok((_, ExprValue::NumberLiteral(value))) => value,
+            res => panic!("expected number literal, got {res:?}"),
+        }
+    }
+
+    fn number_error(input: &str) -> (String, NumberLiteralError) {
+        match number_literal(new_input(input)) {
+            Err(nom::Err::Failure(err)) => match &err.errors[0] {
+                (literal, VerboseErrorKind::Context(context)) => (
+                    literal.fragment().to_string(),
+                    NumberLiteralError::from_context(context).unwrap(),
+                ),
+                _ => panic!("expected number literal error, got {err:?}"),
+            },
+            res => panic!("expected number literal error, got {res:?}"),
+        }
+    }
+
+    #[test]
+    fn test_radix_literals() {
+        assert_eq!(number_value("0xFF"), 255.0);
+        assert_eq!(number_value("0b1010"), 10.0);
+        assert_eq!(number_value("0o17"), 15.0);
+        assert_eq!(number_value("0xdead_beef"), 3735928559.0);
+    }
+
+    #[test]
+    fn test_decimal_literals() {
+        assert_eq!(number_value("1_000_000"), 1000000.0);
+        assert_eq!(number_value("1e-9"), 1e-9);
+        assert_eq!(number_value("2.5E+3"), 2500.0);
+        assert_eq!(number_value("3.141_592"), 3.141592);
+        assert_eq!(number_value("9007199254740992"), 9007199254740992.0);
+    }
+
+    #[test]
+    fn test_literal_before_range() {
+        let (rest, _) = number_literal(new_input("1...3")).unwrap();
+        assert_eq!(*rest.fragment(), "...3");
+
+        let (rest, _) = number_literal(new_input("0..<3")).unwrap();
+        assert_eq!(*rest.fragment(), "..<3");
+    }
+
+    #[test]
+    fn test_malformed_literals() {
+        assert_eq!(
+            number_error("0b102"),
+            ("0b102".to_string(), NumberLiteralError::Malformed)
+        );
+        assert_eq!(
+            number_error("0x"),
+            ("0x".to_string(), NumberLiteralError::Malformed)
+        );
+        assert_eq!(
+            number_error("1__000"),
+            ("1__000".to_string(), NumberLiteralError::Malformed)
+        );
+        assert_eq!(
+            number_error("1_.5"),
+            ("1_.5".to_string(), NumberLiteralError::Malformed)
+        );
+    }
+
+    #[test]
+    fn test_unrepresentable_literals() {
+        assert_eq!(
+            number_error("1e400"),
+            ("1e400".to_string(), NumberLiteralError::OutOfRange)
+        );
+        assert_eq!(
+            number_error("1e-400"),
+            ("1e-400".to_string(), NumberLiteralError::OutOfRange)
+        );
+        assert_eq!(
+            number_error("9007199254740993"),
+            (
+                "9007199254740993".to_string(),
+                NumberLiteralError::PrecisionLoss
+            )
+        );
+        assert_eq!(
+            number_error("0xFFFF_FFFF_FFFF_FFFF"),
+            (
+                "0xFFFF_FFFF_FFFF_FFFF".to_string(),
+                NumberLiteralError::PrecisionLoss
+            )
+        );
+    }
+}
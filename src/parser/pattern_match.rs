@@ -0,0 +1,219 @@
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    combinator::{map, opt, verify},
+    error::context,
+    multi::{many0, separated_list0},
+    sequence::{pair, preceded, terminated, tuple},
+};
+
+use crate::ast::node::{
+    expression::Expr,
+    identifier::Ident,
+    pattern_match::{EnumPattern, LetElse, Match, MatchArm, MatchArmBody},
+    statement::Stmt,
+};
+
+use super::{
+    expression::expression,
+    identifier::identifier,
+    spaced, span,
+    statement::{statement, stmt_return},
+    surround_brackets, ws, BracketType, Input, Res,
+};
+
+pub fn pattern_match(i: Input<'_>) -> Res<Input<'_>, Match<'_>> {
+    // match EXPR "{" MATCH_ARM* "}"
+
+    context(
+        "match",
+        map(
+            span(pair(
+                preceded(spaced(terminated(tag("match"), ws)), expression),
+                surround_brackets(
+                    BracketType::Curly,
+                    many0(terminated(match_arm, opt(spaced(tag(","))))),
+                ),
+            )),
+            |(span, (scrutinee, arms))| Match {
+                scrutinee,
+                arms,
+                span,
+            },
+        ),
+    )(i)
+}
+
+fn match_arm(i: Input<'_>) -> Res<Input<'_>, MatchArm<'_>> {
+    // ( ENUM_PATTERN | _ ) "=>" ( EXPR | "{" STMT "}" | return EXPR )
+
+    context(
+        "match arm",
+        map(
+            span(pair(
+                alt((map(enum_pattern, Some), map(wildcard, |_| None))),
+                preceded(spaced(tag("=>")), match_arm_body),
+            )),
+            |(span, (pattern, body))| MatchArm {
+                pattern,
+                body,
+                span,
+            },
+        ),
+    )(i)
+}
+
+fn match_arm_body(i: Input<'_>) -> Res<Input<'_>, MatchArmBody<'_>> {
+    alt((
+        map(surround_brackets(BracketType::Curly, statement), |stmt| {
+            MatchArmBody::Block(Box::new(stmt))
+        }),
+        map(span(stmt_return), |(span, value)| {
+            MatchArmBody::Block(Box::new(Stmt { span, value }))
+        }),
+        map(expression, |expr| MatchArmBody::Expression(Box::new(expr))),
+    ))(i)
+}
+
+pub fn enum_pattern(i: Input<'_>) -> Res<Input<'_>, EnumPattern<'_>> {
+    // "." IDENT [ "(" ( IDENT | _ )+ ")" ]
+
+    context(
+        "enum pattern",
+        map(
+            span(tuple((
+                preceded(spaced(tag(".")), identifier),
+                opt(surround_brackets(
+                    BracketType::Round,
+                    separated_list0(spaced(tag(",")), pattern_binding),
+                )),
+            ))),
+            |(span, (enum_value, bindings))| EnumPattern {
+                enum_value,
+                bindings: bindings.unwrap_or_default(),
+                span,
+            },
+        ),
+    )(i)
+}
+
+pub fn let_else(i: Input<'_>) -> Res<Input<'_>, LetElse<'_>> {
+    // let ENUM_PATTERN "=" EXPR else "{" STMT "}"
+
+    context(
+        "let else",
+        map(
+            span(pair(
+                let_pattern,
+                preceded(
+                    spaced(tag("else")),
+                    surround_brackets(BracketType::Curly, statement),
+                ),
+            )),
+            |(span, ((pattern, value), else_body))| LetElse {
+                pattern,
+                value,
+                else_body: Box::new(else_body),
+                span,
+            },
+        ),
+    )(i)
+}
+
+pub fn let_pattern(i: Input<'_>) -> Res<Input<'_>, (EnumPattern<'_>, Expr<'_>)> {
+    // let ENUM_PATTERN "=" EXPR
+
+    context(
+        "let pattern",
+        pair(
+            preceded(spaced(terminated(tag("let"), ws)), enum_pattern),
+            preceded(spaced(tag("=")), expression),
+        ),
+    )(i)
+}
+
+fn pattern_binding(i: Input<'_>) -> Res<Input<'_>, Option<Ident<'_>>> {
+    alt((map(wildcard, |_| None), map(identifier, Some)))(i)
+}
+
+fn wildcard(i: Input<'_>) -> Res<Input<'_>, Ident<'_>> {
+    verify(identifier, |ident: &Ident<'_>| ident.value == "_")(i)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::assert_matches;
+
+    use crate::{
+        ast::node::{expression::ExprValue, statement::StmtValue},
+        parser::new_input,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_match_arms() {
+        let mtch = pattern_match(new_input(
+            "match ip {
+                .v4(a, _, c, d) => a + c,
+                .v6(addr) => { let x = addr; x }
+                _ => return 0
+            }",
+        ))
+        .unwrap()
+        .1;
+
+        assert_matches!(mtch.scrutinee.value, ExprValue::Identifier(_));
+        assert_eq!(mtch.arms.len(), 3);
+
+        let pattern = mtch.arms[0].pattern.as_ref().unwrap();
+        assert_eq!(pattern.enum_value.value, "v4");
+        assert_eq!(
+            pattern
+                .bindings
+                .iter()
+                .map(|b| b.as_ref().map(|id| id.value))
+                .collect::<Vec<_>>(),
+            vec![Some("a"), None, Some("c"), Some("d")]
+        );
+        assert_matches!(
+            &mtch.arms[0].body,
+            MatchArmBody::Expression(expr) if matches!(expr.value, ExprValue::BinaryOperation(_))
+        );
+
+        assert_matches!(
+            &mtch.arms[1].body,
+            MatchArmBody::Block(stmt) if matches!(stmt.value, StmtValue::Compound(_))
+        );
+
+        assert!(mtch.arms[2].pattern.is_none());
+        assert_matches!(
+            &mtch.arms[2].body,
+            MatchArmBody::Block(stmt) if matches!(stmt.value, StmtValue::Return(_))
+        );
+    }
+
+    #[test]
+    fn test_match_comma_separated_arms() {
+        let mtch = pattern_match(new_input("match x { .a => 1, .b => 2 }"))
+            .unwrap()
+            .1;
+
+        assert_eq!(mtch.arms.len(), 2);
+        assert!(mtch.arms[1].pattern.as_ref().unwrap().bindings.is_empty());
+    }
+
+    #[test]
+    fn test_let_else() {
+        let let_else = let_else(new_input(
+            "let .v4(a, b, _, d) = ipValue else { return \"error\" }",
+        ))
+        .unwrap()
+        .1;
+
+        assert_eq!(let_else.pattern.enum_value.value, "v4");
+        assert_eq!(let_else.pattern.bindings.len(), 4);
+        assert_matches!(let_else.value.value, ExprValue::Identifier(_));
+        assert_matches!(let_else.else_body.value, StmtValue::Return(_));
+    }
+}
@@ -1,7 +1,7 @@
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::streaming::char,
+    character::{complete::multispace0, streaming::char},
     combinator::{map, opt},
     error::context,
     multi::separated_list0,
@@ -17,16 +17,29 @@ use crate::{
 };
 
 use super::{
-    comment::comment, control_flow::if_branch, enumeration::enumeration, external::external_object,
-    function::function_decl, identifier::identifier, spaced, span, structure::structure,
-    traits::trait_decl, type_signature::type_signature, ws, Input, Res,
+    comment::comment,
+    control_flow::if_branch,
+    enumeration::enumeration,
+    error_handling::{do_catch, stmt_throw},
+    extension::extension,
+    external::external_object,
+    function::function_decl,
+    identifier::identifier,
+    loops::{for_loop, loop_jump, while_loop},
+    pattern_match::let_else,
+    spaced, span,
+    structure::structure,
+    traits::trait_decl,
+    type_alias::type_alias,
+    type_signature::type_signature,
+    ws, Input, Res,
 };
 
 pub fn statement<'a>(i: Input<'a>) -> Res<Input<'a>, Stmt<'a>> {
     // STMT <<; | \n> STMT>* [;]
 
     let (i, (span, mut stmts)) = span(separated_list0(
-        alt((tag(";"), tag("\n"))),
+        terminated(alt((tag(";"), tag("\n"))), multispace0),
         single_statement,
     ))(i)?;
 
@@ -53,13 +66,21 @@ pub fn single_statement(i: Input<'_>) -> Res<Input<'_>, Stmt<'_>> {
         map(
             span(alt((
                 map(comment, StmtValue::Comment),
+                map(let_else, StmtValue::LetElse),
                 map(variable_decl, StmtValue::VariableDecl),
                 map(function_decl, StmtValue::FunctionDecl),
                 map(structure, StmtValue::StructDecl),
                 map(enumeration, StmtValue::EnumDecl),
+                map(extension, StmtValue::Extension),
                 map(external_object, StmtValue::ExternObj),
+                map(type_alias, StmtValue::TypeAlias),
                 stmt_return,
+                stmt_throw,
                 map(if_branch, StmtValue::IfBranch),
+                map(do_catch, StmtValue::DoCatch),
+                map(while_loop, StmtValue::WhileLoop),
+                map(for_loop, StmtValue::ForLoop),
+                map(loop_jump, StmtValue::LoopJump),
                 map(expression, StmtValue::Expression),
                 map(trait_decl, StmtValue::TraitDecl),
             ))),
@@ -118,7 +139,7 @@ pub fn stmt_return(i: Input<'_>) -> Res<Input<'_>, StmtValue<'_>> {
 
 #[cfg(test)]
 mod tests {
-    use std::assert_matches::assert_matches;
+    use std::assert_matches;
 
     use crate::{
         ast::node::{
@@ -174,11 +195,11 @@ mod tests {
                                 fragment: "\"John\"",
                                 source: _
                             },
-                            value: ExprValue::StringLiteral("John")
+                            value: ExprValue::StringLiteral(value)
                         }
                     })
                 }
-            ))
+            )) if value == "John"
         );
     }
 
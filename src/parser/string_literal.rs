@@ -0,0 +1,367 @@
+use nom::{
+    branch::alt,
+    bytes::complete::{is_not, tag, take, take_while},
+    character::complete::{char as char_parser, line_ending, one_of, space0},
+    combinator::{map, not, opt, recognize},
+    error::{context, VerboseError, VerboseErrorKind},
+    multi::{many0, many1},
+    sequence::{delimited, terminated, tuple},
+    Slice,
+};
+
+use crate::ast::node::{
+    expression::{Expr, ExprValue},
+    string_interpolation::{StringInterpolation, StringPart},
+};
+
+use super::{expression::expression, spaced, Input, Res};
+
+/// The context of the parser failure for an invalid escape sequence,
+/// the input of the failure covers the escape sequence
+pub const INVALID_ESCAPE: &str = "invalid escape sequence";
+
+/// The context of the parser failure for a line of a multi-line string that is indented less
+/// than the closing quotes, the input of the failure covers the line
+pub const INSUFFICIENT_INDENTATION: &str = "insufficient indentation";
+
+/// A piece of a string literal, before the escape sequences are joined with the raw characters
+enum Segment<'a> {
+    Raw(Input<'a>),
+    Escaped(char),
+    Interpolation(Box<Expr<'a>>),
+}
+
+pub fn string_literal(i: Input<'_>) -> Res<Input<'_>, ExprValue<'_>> {
+    // MULTILINE_STRING | "\"" <STRING_CHARS | ESCAPE | "\(" EXPR ")">* "\""
+
+    alt((multiline_string, single_line_string))(i)
+}
+
+fn single_line_string(i: Input<'_>) -> Res<Input<'_>, ExprValue<'_>> {
+    map(
+        delimited(
+            char_parser('"'),
+            many0(|i| segment(i, is_not("\"\\\n"))),
+            context("closing quote", char_parser('"')),
+        ),
+        |segments| string_value(segments, ""),
+    )(i)
+}
+
+fn multiline_string(i: Input<'_>) -> Res<Input<'_>, ExprValue<'_>> {
+    // "\"\"\"" NEWLINE <MULTILINE_CHARS | ESCAPE | "\(" EXPR ")">* "\"\"\""
+
+    let (i, mut segments) = delimited(
+        tuple((tag("\"\"\""), space0, line_ending)),
+        many0(|i| segment(i, multiline_chars)),
+        context("closing quotes", tag("\"\"\"")),
+    )(i)?;
+
+    // the line of the closing quotes is not part of the string, its indentation is removed
+    // from every line of the string
+    let segment_count = segments.len();
+    let indent = match segments.last_mut() {
+        Some(Segment::Raw(raw)) => {
+            let raw_str: &str = raw.fragment();
+            let line_start = raw_str.rfind('\n').map(|idx| idx + 1);
+            let closing_line = &raw_str[line_start.unwrap_or(0)..];
+
+            if closing_line.chars().all(|c| c == ' ' || c == '\t')
+                && (line_start.is_some() || segment_count == 1)
+            {
+                *raw = raw.slice(..line_start.map_or(0, |start| start - 1));
+                closing_line
+            } else {
+                ""
+            }
+        }
+        _ => "",
+    };
+
+    if let Some(line) = insufficiently_indented_line(&segments, indent) {
+        return Err(nom::Err::Failure(VerboseError {
+            errors: vec![(line, VerboseErrorKind::Context(INSUFFICIENT_INDENTATION))],
+        }));
+    }
+
+    Ok((i, string_value(segments, indent)))
+}
+
+/// The first line of a multi-line string that does not start with the indentation
+/// of the closing quotes, blank lines do not need to be indented
+fn insufficiently_indented_line<'a>(segments: &[Segment<'a>], indent: &str) -> Option<Input<'a>> {
+    let mut at_line_start = true;
+
+    for segment in segments {
+        match segment {
+            Segment::Raw(raw) => {
+                let mut line_start = 0;
+
+                for line in raw.fragment().split_inclusive('\n') {
+                    let is_blank = line.trim().is_empty() && line.ends_with('\n');
+
+                    if at_line_start && !is_blank && !line.starts_with(indent) {
+                        let line_len = line.trim_end_matches(['\r', '\n']).len();
+                        return Some(raw.slice(line_start..line_start + line_len));
+                    }
+
+                    line_start += line.len();
+                    at_line_start = line.ends_with('\n');
+                }
+            }
+            Segment::Escaped(_) | Segment::Interpolation(_) => at_line_start = false,
+        }
+    }
+
+    None
+}
+
+/// The characters of a multi-line string up to the closing quotes or the next escape
+fn multiline_chars(i: Input<'_>) -> Res<Input<'_>, Input<'_>> {
+    recognize(many1(alt((
+        is_not("\"\\"),
+        terminated(tag("\""), not(tag("\"\""))),
+    ))))(i)
+}
+
+fn segment<'a, F>(i: Input<'a>, raw_chars: F) -> Res<Input<'a>, Segment<'a>>
+where
+    F: FnMut(Input<'a>) -> Res<Input<'a>, Input<'a>>,
+{
+    alt((
+        map(
+            delimited(tag("\\("), spaced(expression), char_parser(')')),
+            |expr| Segment::Interpolation(Box::new(expr)),
+        ),
+        map(escape, Segment::Escaped),
+        map(raw_chars, Segment::Raw),
+    ))(i)
+}
+
+fn escape(i: Input<'_>) -> Res<Input<'_>, char> {
+    // "\" <"n" | "r" | "t" | "0" | "\"" | "'" | "\" | "u{" HEX{1,6} "}">
+
+    let (rest, _) = char_parser('\\')(i.clone())?;
+
+    if let Ok((rest, c)) = one_of::<_, _, VerboseError<Input<'_>>>("nrt0\"'\\")(rest.clone()) {
+        let decoded = match c {
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            '0' => '\0',
+            c => c,
+        };
+
+        return Ok((rest, decoded));
+    }
+
+    if let Ok((rest, unicode)) = unicode_escape(rest.clone()) {
+        let decoded = unicode
+            .fragment()
+            .strip_prefix("u{")
+            .and_then(|digits| digits.strip_suffix('}'))
+            .filter(|digits| (1..=6).contains(&digits.len()))
+            .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+            .and_then(char::from_u32);
+
+        return match decoded {
+            Some(decoded) => Ok((rest, decoded)),
+            None => invalid_escape(i, rest),
+        };
+    }
+
+    // an unknown escape covers the backslash and the escaped character
+    match rest.fragment().chars().next() {
+        Some(c) if c != '\n' => {
+            let (end, _) = take::<_, _, VerboseError<Input<'_>>>(c.len_utf8())(rest)?;
+            invalid_escape(i, end)
+        }
+        _ => invalid_escape(i, rest),
+    }
+}
+
+/// Recognizes `u{HEX}` leniently, so that malformed unicode escapes can be reported as a whole
+fn unicode_escape(i: Input<'_>) -> Res<Input<'_>, Input<'_>> {
+    recognize(tuple((
+        char_parser('u'),
+        opt(char_parser('{')),
+        take_while(|c: char| c.is_ascii_alphanumeric()),
+        opt(char_parser('}')),
+    )))(i)
+}
+
+/// Fails the parser with the escape sequence between `start` and `end`
+fn invalid_escape<'a, O>(start: Input<'a>, end: Input<'a>) -> Res<Input<'a>, O> {
+    let len = end.location_offset() - start.location_offset();
+    let (_, escape) = take::<_, _, VerboseError<Input<'_>>>(len)(start)?;
+
+    Err(nom::Err::Failure(VerboseError {
+        errors: vec![(escape, VerboseErrorKind::Context(INVALID_ESCAPE))],
+    }))
+}
+
+/// Joins the segments into the parts of the string,
+/// removing the given indentation from the start of every line that is not blank
+fn string_value<'a>(segments: Vec<Segment<'a>>, indent: &str) -> ExprValue<'a> {
+    let mut parts = Vec::new();
+    let mut literal = String::new();
+    let mut at_line_start = true;
+
+    for segment in segments {
+        match segment {
+            Segment::Raw(raw) => {
+                for line in raw.fragment().split_inclusive('\n') {
+                    let line = match at_line_start {
+                        true => line
+                            .strip_prefix(indent)
+                            .unwrap_or_else(|| line.trim_start_matches([' ', '\t'])),
+                        false => line,
+                    };
+
+                    literal.push_str(line);
+                    at_line_start = line.ends_with('\n');
+                }
+            }
+            Segment::Escaped(c) => {
+                literal.push(c);
+                at_line_start = false;
+            }
+            Segment::Interpolation(expr) => {
+                if !literal.is_empty() {
+                    parts.push(StringPart::Literal(std::mem::take(&mut literal)));
+                }
+
+                parts.push(StringPart::Expr(expr));
+                at_line_start = false;
+            }
+        }
+    }
+
+    if parts.is_empty() {
+        return ExprValue::StringLiteral(literal);
+    }
+
+    if !literal.is_empty() {
+        parts.push(StringPart::Literal(literal));
+    }
+
+    ExprValue::StringInterpolation(StringInterpolation { parts })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::assert_matches;
+
+    use nom::error::VerboseErrorKind;
+
+    use crate::{
+        ast::node::{expression::ExprValue, string_interpolation::StringPart},
+        parser::new_input,
+    };
+
+    use super::{string_literal, INSUFFICIENT_INDENTATION, INVALID_ESCAPE};
+
+    fn literal_value(input: &str) -> String {
+        match string_literal(new_input(input)) {
+            Ok((_, ExprValue::StringLiteral(value))) => value,
+            res => panic!("expected string literal, got {res:?}"),
+        }
+    }
+
+    fn invalid_escape(input: &str) -> String {
+        match string_literal(new_input(input)) {
+            Err(nom::Err::Failure(err)) => match &err.errors[0] {
+                (escape, VerboseErrorKind::Context(INVALID_ESCAPE)) => {
+                    escape.fragment().to_string()
+                }
+                _ => panic!("expected invalid escape, got {err:?}"),
+            },
+            res => panic!("expected invalid escape, got {res:?}"),
+        }
+    }
+
+    #[test]
+    fn test_escapes() {
+        assert_eq!(literal_value("\"say \\\"hi\\\"\""), "say \"hi\"");
+        assert_eq!(literal_value("\"a\\nb\\tc\\\\d\\0\""), "a\nb\tc\\d\0");
+        assert_eq!(literal_value("\"\\u{1F600} \\u{e9}\""), "\u{1F600} \u{e9}");
+    }
+
+    #[test]
+    fn test_invalid_escapes() {
+        assert_eq!(invalid_escape("\"a \\q b\""), "\\q");
+        assert_eq!(invalid_escape("\"\\u{D800}\""), "\\u{D800}");
+        assert_eq!(invalid_escape("\"\\u{1234567}\""), "\\u{1234567}");
+        assert_eq!(invalid_escape("\"\\u{zz}\""), "\\u{zz}");
+        assert_eq!(invalid_escape("\"\\u12\""), "\\u12");
+    }
+
+    #[test]
+    fn test_single_line_string_unclosed() {
+        assert!(string_literal(new_input("\"line\nbreak\"")).is_err());
+    }
+
+    #[test]
+    fn test_multiline_string() {
+        assert_eq!(
+            literal_value(
+                "\"\"\"
+            first \"quoted\"
+              indented
+
+            last\\n
+            \"\"\""
+            ),
+            "first \"quoted\"\n  indented\n\nlast\n"
+        );
+
+        assert_eq!(literal_value("\"\"\"\n\"\"\""), "");
+    }
+
+    #[test]
+    fn test_multiline_string_insufficient_indentation() {
+        for (input, line) in [
+            ("\"\"\"\n    a\n  b\n    \"\"\"", "  b"),
+            ("\"\"\"\n    a\n  \\(b)\n    \"\"\"", "  "),
+            ("\"\"\"\r\n    a\r\nb\r\n    \"\"\"", "b"),
+        ] {
+            match string_literal(new_input(input)) {
+                Err(nom::Err::Failure(err)) => assert_matches!(
+                    &err.errors[0],
+                    (span, VerboseErrorKind::Context(INSUFFICIENT_INDENTATION))
+                        if *span.fragment() == line && span.location_line() == 3
+                ),
+                res => panic!("expected insufficient indentation, got {res:?}"),
+            }
+        }
+
+        assert_eq!(
+            literal_value("\"\"\"\n    a\n  \n    b\n    \"\"\""),
+            "a\n\nb"
+        );
+    }
+
+    #[test]
+    fn test_multiline_string_interpolation() {
+        let parts = match string_literal(new_input(
+            "\"\"\"
+            Hello \\(name),
+            bye
+            \"\"\"",
+        )) {
+            Ok((_, ExprValue::StringInterpolation(interp))) => interp.parts,
+            res => panic!("expected string interpolation, got {res:?}"),
+        };
+
+        assert_matches!(
+            parts.as_slice(),
+            [
+                StringPart::Literal(hello),
+                StringPart::Expr(name),
+                StringPart::Literal(bye),
+            ] if hello == "Hello "
+                && matches!(name.value, ExprValue::Identifier(_))
+                && bye == ",\nbye"
+        );
+    }
+}
@@ -11,21 +11,31 @@ use nom::{
 use crate::ast::node::structure::{Struct, StructAttr, StructInit, StructInitValue};
 
 use super::{
-    expression::expression, identifier::identifier, spaced, span, statement::mutability_specifier,
-    surround_brackets, type_signature::type_signature, ws, BracketType, Input, Res,
+    expression::expression,
+    identifier::identifier,
+    spaced, span,
+    statement::mutability_specifier,
+    surround_brackets,
+    type_signature::{type_params, type_signature},
+    ws, BracketType, Input, Res,
 };
 
 pub fn structure<'a>(i: Input<'a>) -> Res<Input<'a>, Struct<'a>> {
-    // "struct" IDENT { STRUCT_ATTRS }
+    // "struct" IDENT [TYPE_PARAMS] { STRUCT_ATTRS }
 
     context(
         "structure declaration",
         map(
             tuple((
                 preceded(spaced(tuple((tag("struct"), ws))), identifier),
+                type_params,
                 surround_brackets(BracketType::Curly, struct_attrs),
             )),
-            move |(name, attrs)| Struct { name, attrs },
+            move |(name, type_params, attrs)| Struct {
+                name,
+                type_params,
+                attrs,
+            },
         ),
     )(i)
 }
@@ -34,41 +44,41 @@ pub fn struct_attrs<'a>(i: Input<'a>) -> Res<Input<'a>, Vec<StructAttr<'a>>> {
     // ATTR <; ATTR>*
     // ATTR <\n ATTR>*
 
-    let struct_attr = move |i: Input<'a>| -> Res<Input<'a>, StructAttr<'a>> {
-        // (val | var) IDENT [ : TYPE_SIG ] [ = EXPR ]
-
-        map(
-            context(
-                "structure attribute",
-                span(tuple((
-                    mutability_specifier,
-                    context("attribute identifier", identifier),
-                    context(
-                        "attribute type signature",
-                        opt(preceded(spaced(char(':')), type_signature)),
-                    ),
-                    opt(preceded(
-                        spaced(char('=')),
-                        context("attribute default value", expression),
-                    )),
-                ))),
-            ),
-            |(span, (mutability, name, type_sig, default_value))| StructAttr {
-                name,
-                mutability,
-                type_sig: type_sig,
-                default_value,
-                span: span,
-            },
-        )(i)
-    };
-
     separated_list0(
         alt((tag(";"), tag("\n"))),
         context("struct attribute", struct_attr),
     )(i)
 }
 
+pub fn struct_attr(i: Input<'_>) -> Res<Input<'_>, StructAttr<'_>> {
+    // (val | var) IDENT [ : TYPE_SIG ] [ = EXPR ]
+
+    map(
+        context(
+            "structure attribute",
+            span(tuple((
+                mutability_specifier,
+                context("attribute identifier", identifier),
+                context(
+                    "attribute type signature",
+                    opt(preceded(spaced(char(':')), type_signature)),
+                ),
+                opt(preceded(
+                    spaced(char('=')),
+                    context("attribute default value", expression),
+                )),
+            ))),
+        ),
+        |(span, (mutability, name, type_sig, default_value))| StructAttr {
+            name,
+            mutability,
+            type_sig,
+            default_value,
+            span,
+        },
+    )(i)
+}
+
 pub fn struct_init_expr(i: Input<'_>) -> Res<Input<'_>, StructInit<'_>> {
     // IDENT "{" <IDENT: EXPR> , ... "}"
 
@@ -96,7 +106,7 @@ pub fn struct_init_expr(i: Input<'_>) -> Res<Input<'_>, StructInit<'_>> {
 
 #[cfg(test)]
 mod tests {
-    use std::assert_matches::assert_matches;
+    use std::assert_matches;
 
     use crate::{
         ast::{
@@ -106,7 +116,7 @@ mod tests {
                 member_access::MemberAccess,
                 type_signature::Mutability,
             },
-            test_utils::{test_ident, test_type_sig},
+            test_utils::{test_ident, test_type_param, test_type_sig},
         },
         parser::new_input,
     };
@@ -128,6 +138,23 @@ mod tests {
         assert_eq!(st.attrs[0].span.fragment, "let attr: String");
     }
 
+    #[test]
+    fn test_generic_struct() {
+        let st = structure(new_input(
+            "struct Pair<A, B> { let first: A; let second: B }",
+        ))
+        .unwrap()
+        .1;
+
+        assert_eq!(st.name, test_ident("Pair"));
+        assert_eq!(
+            st.type_params,
+            vec![test_type_param("A"), test_type_param("B")]
+        );
+        assert_eq!(st.attrs[0].type_sig, Some(test_type_sig("A")));
+        assert_eq!(st.attrs[1].type_sig, Some(test_type_sig("B")));
+    }
+
     #[test]
     fn test_struct_init() {
         let struct_init = expression(new_input("StructName { attr: true }"))
@@ -170,6 +197,7 @@ mod tests {
                     object,
                     member_name,
                     items,
+                    optional: _,
                     span: _,
                 } => {
                     assert_matches!(
@@ -1,7 +1,7 @@
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    combinator::map,
+    combinator::{map, opt, verify},
     multi::separated_list0,
     sequence::{pair, preceded},
 };
@@ -9,10 +9,18 @@ use nom::{
 use crate::ast::node::traits::{Trait, TraitFuncAttr};
 
 use super::{
-    function::function_signature, identifier::identifier, spaced, span, surround_brackets,
-    BracketType, Input, Res,
+    function::{function_signature, operator_signature, FunctionSignature},
+    identifier::identifier,
+    spaced, span,
+    statement::statement,
+    surround_brackets, BracketType, Input, Res,
 };
 
+enum TraitMember<'a> {
+    Method(TraitFuncAttr<'a>),
+    Operator(TraitFuncAttr<'a>),
+}
+
 pub fn trait_decl(i: Input<'_>) -> Res<Input<'_>, Trait<'_>> {
     // trait IDENT '{' TRAIT_ATTR+ '}'
 
@@ -21,31 +29,69 @@ pub fn trait_decl(i: Input<'_>) -> Res<Input<'_>, Trait<'_>> {
             preceded(spaced(tag("trait")), identifier),
             surround_brackets(BracketType::Curly, trait_attrs),
         )),
-        |(span, (ident, attrs))| Trait {
-            name: ident,
-            attrs,
-            span,
+        |(span, (ident, members))| {
+            let mut tr = Trait {
+                name: ident,
+                attrs: Vec::new(),
+                operators: Vec::new(),
+                span,
+            };
+
+            for member in members {
+                match member {
+                    TraitMember::Method(attr) => tr.attrs.push(attr),
+                    TraitMember::Operator(attr) => tr.operators.push(attr),
+                }
+            }
+
+            tr
         },
     )(i)
 }
 
-pub fn trait_attrs<'a>(i: Input<'a>) -> Res<Input<'a>, Vec<TraitFuncAttr<'a>>> {
+fn trait_attrs<'a>(i: Input<'a>) -> Res<Input<'a>, Vec<TraitMember<'a>>> {
     // ATTR <; ATTR>*
     // ATTR <\n ATTR>*
+    // ATTR = <FUNC_SIGNATURE | OPERATOR_SIGNATURE> ["{" BODY "}"]
 
     separated_list0(
         alt((tag(";"), tag("\n"))),
-        map(function_signature, |(ident, func_args, ret_type, span)| {
-            TraitFuncAttr {
-                name: ident,
-                args: func_args,
-                return_type: ret_type,
-                span,
-            }
-        }),
+        alt((
+            map(trait_func_attr(operator_signature), TraitMember::Operator),
+            // trait methods can not be generic
+            map(
+                trait_func_attr(verify(
+                    function_signature,
+                    |(_, type_params, _, _, _, _)| type_params.is_empty(),
+                )),
+                TraitMember::Method,
+            ),
+        )),
     )(i)
 }
 
+fn trait_func_attr<'a, F>(
+    signature: F,
+) -> impl FnMut(Input<'a>) -> Res<Input<'a>, TraitFuncAttr<'a>>
+where
+    F: FnMut(Input<'a>) -> Res<Input<'a>, FunctionSignature<'a>>,
+{
+    map(
+        pair(
+            signature,
+            opt(surround_brackets(BracketType::Curly, statement)),
+        ),
+        |((ident, _, func_args, throws, ret_type, span), body)| TraitFuncAttr {
+            name: ident,
+            args: func_args,
+            return_type: ret_type,
+            throws,
+            body: body.map(Box::new),
+            span,
+        },
+    )
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -64,4 +110,43 @@ mod tests {
         assert_eq!(tr.name, test_ident("Additive"));
         assert_eq!(tr.attrs.len(), 1);
     }
+
+    #[test]
+    fn test_trait_default_method() {
+        let tr = trait_decl(new_input(
+            "trait Printable {
+                func print()
+                func describe() -> String { return \"printable\" }
+            }",
+        ))
+        .unwrap()
+        .1;
+
+        assert_eq!(tr.attrs.len(), 2);
+        assert!(tr.attrs[0].body.is_none());
+        assert!(tr.attrs[1].body.is_some());
+    }
+
+    #[test]
+    fn test_trait_operators() {
+        let tr = trait_decl(new_input(
+            "trait Equatable {
+                static func == (lhs: Self, rhs: Self) -> Boolean
+                static func != (lhs: Self, rhs: Self) -> Boolean { return !(lhs == rhs) }
+                func describe() -> String
+            }",
+        ))
+        .unwrap()
+        .1;
+
+        assert_eq!(tr.attrs.len(), 1);
+        assert_eq!(tr.operators.len(), 2);
+        assert!(tr.operators[0].body.is_none());
+        assert_eq!(tr.operators[1].name.value, "!=");
+    }
+
+    #[test]
+    fn test_trait_generic_method() {
+        assert!(trait_decl(new_input("trait Mapper { func map<T>(a: T) -> T }")).is_err());
+    }
 }
@@ -0,0 +1,58 @@
+use nom::{
+    bytes::complete::tag,
+    character::complete::multispace0,
+    combinator::map,
+    error::context,
+    sequence::{pair, preceded, tuple},
+};
+
+use crate::ast::node::type_alias::TypeAlias;
+
+use super::{identifier::identifier, spaced, span, type_signature::type_signature, ws, Input, Res};
+
+pub fn type_alias(i: Input<'_>) -> Res<Input<'_>, TypeAlias<'_>> {
+    // type NAME = TYPE_SIG
+
+    context(
+        "type alias",
+        map(
+            span(pair(
+                preceded(tuple((multispace0, tag("type"), ws)), identifier),
+                preceded(spaced(tag("=")), type_signature),
+            )),
+            |(span, (name, type_sig))| TypeAlias {
+                name,
+                type_sig,
+                span,
+            },
+        ),
+    )(i)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::assert_matches;
+
+    use crate::{
+        ast::{node::type_signature::TypeSignatureValue, test_utils::test_ident},
+        parser::new_input,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_type_alias() {
+        let alias = type_alias(new_input("type Predicate = (Number) -> Boolean"))
+            .unwrap()
+            .1;
+
+        assert_eq!(alias.name, test_ident("Predicate"));
+        assert_matches!(alias.type_sig.value, TypeSignatureValue::Function { .. });
+    }
+
+    #[test]
+    fn test_type_as_identifier() {
+        assert!(type_alias(new_input("type = 3")).is_err());
+        assert!(type_alias(new_input("typeName = 3")).is_err());
+    }
+}
@@ -1,37 +1,110 @@
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    combinator::map,
+    character::complete::char as char_parser,
+    combinator::{map, opt},
     error::context,
-    multi::separated_list0,
-    sequence::{preceded, tuple},
+    multi::{fold_many0, separated_list0, separated_list1},
+    sequence::{pair, preceded, separated_pair, tuple},
 };
+use nom_locate::position;
 
-use crate::ast::node::type_signature::{TypeSignature, TypeSignatureValue};
+use crate::ast::node::{
+    generics::TypeParam,
+    type_signature::{TypeSignature, TypeSignatureValue},
+};
 
-use super::{identifier::identifier, spaced, span, surround_brackets, BracketType, Input, Res};
+use super::{
+    function::throws_signature, identifier::identifier, spaced, span, surround_brackets,
+    BracketType, Input, Res, Span,
+};
 
 pub fn type_signature(i: Input<'_>) -> Res<Input<'_>, TypeSignature<'_>> {
-    context(
+    let (i_next, type_sig) = context(
         "type signature",
-        alt((type_sig_func, type_sig_base, type_sig_tuple)),
+        alt((
+            type_sig_func,
+            type_sig_base,
+            type_sig_tuple,
+            type_sig_dictionary,
+            type_sig_record,
+        )),
+    )(i.clone())?;
+
+    type_sig_optional(type_sig, i_next, i)
+}
+
+fn type_sig_optional<'a>(
+    base: TypeSignature<'a>,
+    i: Input<'a>,
+    i_start: Input<'a>,
+) -> Res<Input<'a>, TypeSignature<'a>> {
+    // TYPE_SIG "?"
+    // the question mark has to follow the type directly
+
+    fold_many0(
+        pair(char_parser('?'), position),
+        || base.clone(),
+        |acc, (_, end)| TypeSignature {
+            span: Span::new(i_start.clone(), end),
+            value: TypeSignatureValue::Optional(Box::new(acc)),
+        },
     )(i)
 }
 
 fn type_sig_base(i: Input<'_>) -> Res<Input<'_>, TypeSignature<'_>> {
-    // IDENT
+    // IDENT [ "<" TYPE_SIG , ... ">" ]
 
     context(
         "base type",
-        map(identifier, |id| TypeSignature {
-            span: id.span.clone(),
-            value: TypeSignatureValue::Base(id),
-        }),
+        map(
+            span(pair(
+                identifier,
+                opt(surround_brackets(
+                    BracketType::Angle,
+                    separated_list1(spaced(tag(",")), type_signature),
+                )),
+            )),
+            |(span, (id, args))| match args {
+                Some(args) => TypeSignature {
+                    span,
+                    value: TypeSignatureValue::Instance { base: id, args },
+                },
+                None => TypeSignature {
+                    span: id.span.clone(),
+                    value: TypeSignatureValue::Base(id),
+                },
+            },
+        ),
+    )(i)
+}
+
+pub fn type_params(i: Input<'_>) -> Res<Input<'_>, Vec<TypeParam<'_>>> {
+    // [ "<" TYPE_PARAM , ... ">" ]
+
+    context(
+        "type parameters",
+        map(
+            opt(surround_brackets(
+                BracketType::Angle,
+                separated_list1(spaced(tag(",")), type_param),
+            )),
+            Option::unwrap_or_default,
+        ),
+    )(i)
+}
+
+fn type_param(i: Input<'_>) -> Res<Input<'_>, TypeParam<'_>> {
+    // IDENT [: IDENT]
+
+    map(
+        pair(identifier, opt(preceded(spaced(tag(":")), identifier))),
+        |(name, bound)| TypeParam { name, bound },
     )(i)
 }
 
 fn type_sig_func(i: Input<'_>) -> Res<Input<'_>, TypeSignature<'_>> {
-    // "(" TYPE_SIG , ... ")" "->" TYPE_SIG
+    // "(" TYPE_SIG , ... ")" [THROWS_SIG] "->" TYPE_SIG
 
     context(
         "function type",
@@ -41,13 +114,15 @@ fn type_sig_func(i: Input<'_>) -> Res<Input<'_>, TypeSignature<'_>> {
                     BracketType::Round,
                     separated_list0(spaced(tag(",")), type_signature),
                 ),
+                throws_signature,
                 preceded(spaced(tag("->")), type_signature),
             ))),
-            |(span, (args, return_type))| TypeSignature {
+            |(span, (args, throws, return_type))| TypeSignature {
                 span,
                 value: TypeSignatureValue::Function {
                     args,
                     return_type: Box::new(return_type),
+                    throws: throws.map(Box::new),
                 },
             },
         ),
@@ -72,10 +147,52 @@ fn type_sig_tuple(i: Input<'_>) -> Res<Input<'_>, TypeSignature<'_>> {
     )(i)
 }
 
+fn type_sig_dictionary(i: Input<'_>) -> Res<Input<'_>, TypeSignature<'_>> {
+    // "[" TYPE_SIG ":" TYPE_SIG "]"
+
+    context(
+        "dictionary type",
+        map(
+            span(surround_brackets(
+                BracketType::Square,
+                separated_pair(type_signature, spaced(tag(":")), type_signature),
+            )),
+            |(span, (key, value))| TypeSignature {
+                span,
+                value: TypeSignatureValue::Dictionary {
+                    key: Box::new(key),
+                    value: Box::new(value),
+                },
+            },
+        ),
+    )(i)
+}
+
+fn type_sig_record(i: Input<'_>) -> Res<Input<'_>, TypeSignature<'_>> {
+    // "{" IDENT ":" TYPE_SIG , ... "}"
+
+    context(
+        "record type",
+        map(
+            span(surround_brackets(
+                BracketType::Curly,
+                separated_list0(
+                    spaced(tag(",")),
+                    separated_pair(identifier, spaced(tag(":")), type_signature),
+                ),
+            )),
+            |(span, fields)| TypeSignature {
+                span,
+                value: TypeSignatureValue::Record(fields),
+            },
+        ),
+    )(i)
+}
+
 #[cfg(test)]
 mod tests {
 
-    use std::assert_matches::assert_matches;
+    use std::assert_matches;
 
     use crate::{
         ast::{node::identifier::Ident, test_utils::test_type_sig},
@@ -110,7 +227,10 @@ mod tests {
         match func_type {
             TypeSignature {
                 span,
-                value: TypeSignatureValue::Function { args, return_type },
+                value:
+                    TypeSignatureValue::Function {
+                        args, return_type, ..
+                    },
             } => {
                 assert_eq!(span.fragment, "() -> Void");
                 assert!(args.is_empty());
@@ -130,7 +250,10 @@ mod tests {
         match func_type {
             TypeSignature {
                 span,
-                value: TypeSignatureValue::Function { args, return_type },
+                value:
+                    TypeSignatureValue::Function {
+                        args, return_type, ..
+                    },
             } => {
                 assert_eq!(span.fragment, "(Number, (Number) -> Boolean) -> Boolean");
                 assert_eq!(args.len(), 2);
@@ -139,7 +262,10 @@ mod tests {
                 match &args[1] {
                     TypeSignature {
                         span,
-                        value: TypeSignatureValue::Function { args, return_type },
+                        value:
+                            TypeSignatureValue::Function {
+                                args, return_type, ..
+                            },
                     } => {
                         assert_eq!(span.fragment, "(Number) -> Boolean");
                         assert_eq!(args.len(), 1);
@@ -175,4 +301,120 @@ mod tests {
             _ => assert!(false),
         }
     }
+
+    #[test]
+    fn test_instance_type() {
+        let instance = type_signature(new_input("Pair<Number, Option<String>>"))
+            .unwrap()
+            .1;
+
+        match instance {
+            TypeSignature {
+                span,
+                value: TypeSignatureValue::Instance { base, args },
+            } => {
+                assert_eq!(span.fragment, "Pair<Number, Option<String>>");
+                assert_eq!(base.value, "Pair");
+                assert_eq!(args.len(), 2);
+                assert_eq!(args[0], test_type_sig("Number"));
+                assert_matches!(
+                    &args[1].value,
+                    TypeSignatureValue::Instance { base, args } if base.value == "Option" && args.len() == 1
+                );
+            }
+            _ => assert!(false),
+        }
+    }
+
+    #[test]
+    fn test_dictionary_type() {
+        let dict = type_signature(new_input("[String: (Number) -> Boolean]"))
+            .unwrap()
+            .1;
+
+        match dict {
+            TypeSignature {
+                span,
+                value: TypeSignatureValue::Dictionary { key, value },
+            } => {
+                assert_eq!(span.fragment, "[String: (Number) -> Boolean]");
+                assert_eq!(*key, test_type_sig("String"));
+                assert_matches!(value.value, TypeSignatureValue::Function { .. });
+            }
+            _ => assert!(false),
+        }
+
+        assert_matches!(
+            type_signature(new_input("[String: [Number: Boolean]]")).unwrap().1.value,
+            TypeSignatureValue::Dictionary { value, .. }
+                if matches!(value.value, TypeSignatureValue::Dictionary { .. })
+        );
+    }
+
+    #[test]
+    fn test_optional_type() {
+        let optional = type_signature(new_input("Array<Number?>?")).unwrap().1;
+
+        match optional {
+            TypeSignature {
+                span,
+                value: TypeSignatureValue::Optional(value),
+            } => {
+                assert_eq!(span.fragment, "Array<Number?>?");
+                assert_matches!(
+                    value.value,
+                    TypeSignatureValue::Instance { args, .. }
+                        if matches!(args[0].value, TypeSignatureValue::Optional(_))
+                );
+            }
+            _ => assert!(false),
+        }
+
+        assert_matches!(
+            type_signature(new_input("() -> String?")).unwrap().1.value,
+            TypeSignatureValue::Function { return_type, .. }
+                if matches!(return_type.value, TypeSignatureValue::Optional(_))
+        );
+    }
+
+    #[test]
+    fn test_record_type() {
+        let record = type_signature(new_input("{ name: String, age: Number? }"))
+            .unwrap()
+            .1;
+
+        match record {
+            TypeSignature {
+                span,
+                value: TypeSignatureValue::Record(fields),
+            } => {
+                assert_eq!(span.fragment, "{ name: String, age: Number? }");
+                assert_eq!(fields.len(), 2);
+                assert_eq!(fields[0].0.value, "name");
+                assert_eq!(fields[0].1, test_type_sig("String"));
+                assert_eq!(fields[1].0.value, "age");
+                assert_matches!(fields[1].1.value, TypeSignatureValue::Optional(_));
+            }
+            _ => assert!(false),
+        }
+
+        assert_eq!(
+            type_signature(new_input("{}")).unwrap().1.value,
+            TypeSignatureValue::Record(Vec::new())
+        );
+    }
+
+    #[test]
+    fn test_type_params() {
+        let (_, params) = type_params(new_input("<K, V>")).unwrap();
+        assert_eq!(params.len(), 2);
+        assert_eq!(params[0].name.value, "K");
+        assert_eq!(params[1].name.value, "V");
+
+        let (_, params) = type_params(new_input("<T: Combinable>")).unwrap();
+        assert_eq!(params[0].name.value, "T");
+        assert_eq!(params[0].bound.as_ref().unwrap().value, "Combinable");
+
+        assert!(type_params(new_input("(x: Number)")).unwrap().1.is_empty());
+    }
 }
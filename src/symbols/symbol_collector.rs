@@ -1,7 +1,16 @@
 use crate::ir::{
     context::IrCtx,
     ir_walker::{IrWalker, ScopeValue},
-    node::{statement::Stmt, type_signature::BUILTIN_TYPES, NodeRef},
+    node::{
+        control_flow::{IfBranchBody, IfStmt, NarrowingReach},
+        error_handling::DoCatchBody,
+        generics::TypeParam,
+        identifier::{Ident, IdentKey},
+        pattern_match::{EnumPattern, LetElse},
+        statement::Stmt,
+        type_signature::BUILTIN_TYPES,
+        NodeRef,
+    },
 };
 
 use super::symbol_table::{SymbolCollectionError, SymbolTable, SymbolValueItem};
@@ -37,12 +46,24 @@ impl<'a> IrWalker<'a> for SymbolCollector {
         match value {
             ScopeValue::Func(func) => {
                 parent.insert(ctx, SymbolValueItem::FuncDecl(func))?;
+                for param in ctx[func].type_params.clone() {
+                    new_scope.insert(ctx, SymbolValueItem::TypeParam(param))?;
+                }
                 for arg in ctx[func].args.clone() {
                     new_scope.insert(ctx, SymbolValueItem::FuncArg(arg))?;
                 }
             }
             ScopeValue::Struct(st) => {
                 parent.insert(ctx, SymbolValueItem::StructDecl(st))?;
+                for param in ctx[st].type_params.clone() {
+                    new_scope.insert(ctx, SymbolValueItem::TypeParam(param))?;
+                }
+                insert_type_params_in_extensions(
+                    ctx,
+                    parent,
+                    *ctx[st].name,
+                    &ctx[st].type_params.clone(),
+                )?;
                 for attr in ctx[st].attrs.clone() {
                     new_scope.insert(ctx, SymbolValueItem::StructAttr(attr))?;
                 }
@@ -52,15 +73,68 @@ impl<'a> IrWalker<'a> for SymbolCollector {
             }
             ScopeValue::Enum(enm) => {
                 parent.insert(ctx, SymbolValueItem::EnumDecl(enm))?;
+                for param in ctx[enm].type_params.clone() {
+                    new_scope.insert(ctx, SymbolValueItem::TypeParam(param))?;
+                }
+                insert_type_params_in_extensions(
+                    ctx,
+                    parent,
+                    *ctx[enm].name,
+                    &ctx[enm].type_params.clone(),
+                )?;
                 for val in ctx[enm].values.clone() {
                     new_scope.insert(ctx, SymbolValueItem::EnumValue(val))?;
                 }
             }
             ScopeValue::IfBranch(ifb, branch) => {
                 parent.insert(ctx, SymbolValueItem::IfBranch(ifb, branch))?;
+                if let (Some(pattern), IfBranchBody::MainBody) = (ctx[ifb].pattern, branch) {
+                    insert_pattern_bindings(ctx, &mut new_scope, pattern)?;
+                }
+                insert_narrowed_bindings(ctx, &mut new_scope, ifb, NarrowingReach::Branch(branch))?;
             }
             ScopeValue::Trait(tr) => {
                 parent.insert(ctx, SymbolValueItem::TraitDecl(tr))?;
+                new_scope.insert(ctx, SymbolValueItem::TraitSelfValue(tr))?;
+                new_scope.insert(ctx, SymbolValueItem::TraitSelfType(tr))?;
+            }
+            ScopeValue::MatchArm(arm) => {
+                parent.insert(ctx, SymbolValueItem::MatchArm(arm))?;
+                if let Some(pattern) = ctx[arm].pattern {
+                    insert_pattern_bindings(ctx, &mut new_scope, pattern)?;
+                }
+            }
+            ScopeValue::LetElse(_) => {}
+            ScopeValue::Extension(ext) => {
+                parent.insert(ctx, SymbolValueItem::Extension(ext))?;
+                new_scope.insert(ctx, SymbolValueItem::SelfValue(ext))?;
+                new_scope.insert(ctx, SymbolValueItem::SelfType(ext))?;
+
+                // the extensions of a type declared later get its type parameters once it is
+                let extended = parent
+                    .lookup_global_table(ctx, *ctx[ext].name)
+                    .map(|sym| ctx[*sym]);
+                let type_params = match extended {
+                    Some(SymbolValueItem::StructDecl(st)) => ctx[st].type_params.clone(),
+                    Some(SymbolValueItem::EnumDecl(enm)) => ctx[enm].type_params.clone(),
+                    _ => Vec::new(),
+                };
+                for param in type_params {
+                    new_scope.insert(ctx, SymbolValueItem::TypeParam(param))?;
+                }
+            }
+            ScopeValue::DoCatch(_, DoCatchBody::DoBody) => {}
+            ScopeValue::DoCatch(dc, DoCatchBody::CatchBody) => {
+                new_scope.insert(ctx, SymbolValueItem::CatchBinding(dc))?;
+            }
+            ScopeValue::WhileLoop(_) => {}
+            ScopeValue::ForLoop(fl) => {
+                new_scope.insert(ctx, SymbolValueItem::LoopItem(fl))?;
+            }
+            ScopeValue::RightOperand(bin_op) => {
+                for i in 0..ctx[bin_op].narrowed.len() {
+                    new_scope.insert(ctx, SymbolValueItem::OperandNarrowedBinding(bin_op, i))?;
+                }
             }
         }
 
@@ -86,6 +160,27 @@ impl<'a> IrWalker<'a> for SymbolCollector {
                 .insert_scope(ctx, ctx[ifb].branch_ident(branch), child)
                 .map(|_| ()),
             ScopeValue::Trait(tr) => parent.insert_scope(ctx, *ctx[tr].name, child).map(|_| ()),
+            ScopeValue::MatchArm(arm) => parent
+                .insert_scope(ctx, *ctx[arm].scope_ident, child)
+                .map(|_| ()),
+            ScopeValue::LetElse(let_else) => parent
+                .insert_scope(ctx, *ctx[let_else].else_scope_ident, child)
+                .map(|_| ()),
+            ScopeValue::Extension(ext) => parent
+                .insert_scope(ctx, *ctx[ext].scope_ident, child)
+                .map(|_| ()),
+            ScopeValue::DoCatch(dc, body) => parent
+                .insert_scope(ctx, ctx[dc].body_ident(body), child)
+                .map(|_| ()),
+            ScopeValue::WhileLoop(wl) => parent
+                .insert_scope(ctx, *ctx[wl].body_scope_ident, child)
+                .map(|_| ()),
+            ScopeValue::ForLoop(fl) => parent
+                .insert_scope(ctx, *ctx[fl].body_scope_ident, child)
+                .map(|_| ()),
+            ScopeValue::RightOperand(bin_op) => parent
+                .insert_scope(ctx, ctx[bin_op].rhs_scope_ident.unwrap(), child)
+                .map(|_| ()),
         }
     }
 
@@ -108,14 +203,107 @@ impl<'a> IrWalker<'a> for SymbolCollector {
                     .insert(ctx, SymbolValueItem::ExternalObject(ext_obj))
                     .map(|_| ())
             }
+            Stmt::TypeAlias(alias) => {
+                let alias = *alias;
+                scope
+                    .insert(ctx, SymbolValueItem::TypeAlias(alias))
+                    .map(|_| ())
+            }
             _ => Ok(()),
         }
     }
+
+    fn visit_if_stmt(
+        &mut self,
+        ctx: &mut IrCtx<'a>,
+        scope: &mut SymbolTable<'a>,
+        ifb: NodeRef<'a, IfStmt<'a>>,
+    ) -> Result<(), Self::Error> {
+        insert_narrowed_bindings(ctx, scope, ifb, NarrowingReach::AfterIf)
+    }
+
+    fn visit_let_else(
+        &mut self,
+        ctx: &mut IrCtx<'a>,
+        scope: &mut SymbolTable<'a>,
+        let_else: NodeRef<'a, LetElse<'a>>,
+    ) -> Result<(), Self::Error> {
+        let pattern = ctx[let_else].pattern;
+        for (i, binding) in ctx[pattern].bindings.clone().into_iter().enumerate() {
+            if binding.name.is_some() {
+                scope.insert(ctx, SymbolValueItem::LetElseBinding(let_else, i))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn insert_pattern_bindings<'a>(
+    ctx: &mut IrCtx<'a>,
+    scope: &mut SymbolTable<'a>,
+    pattern: NodeRef<'a, EnumPattern<'a>>,
+) -> Result<(), SymbolCollectionError<'a>> {
+    for (i, binding) in ctx[pattern].bindings.clone().into_iter().enumerate() {
+        if binding.name.is_some() {
+            scope.insert(ctx, SymbolValueItem::PatternBinding(pattern, i))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn insert_narrowed_bindings<'a>(
+    ctx: &mut IrCtx<'a>,
+    scope: &mut SymbolTable<'a>,
+    ifb: NodeRef<'a, IfStmt<'a>>,
+    reach: NarrowingReach,
+) -> Result<(), SymbolCollectionError<'a>> {
+    for (i, binding) in ctx[ifb].narrowed.clone().into_iter().enumerate() {
+        if binding.reach == reach {
+            scope.insert(ctx, SymbolValueItem::NarrowedBinding(ifb, i))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Inserts the type parameters of a generic struct or enum into the scopes of the extensions
+/// declared before it, as they are available inside of the methods of every extension
+fn insert_type_params_in_extensions<'a>(
+    ctx: &mut IrCtx<'a>,
+    parent: &mut SymbolTable<'a>,
+    name: Ident<'a>,
+    type_params: &[NodeRef<'a, TypeParam<'a>>],
+) -> Result<(), SymbolCollectionError<'a>> {
+    let extensions = parent
+        .scope_global_table
+        .values()
+        .filter_map(|sym| match ctx[*sym] {
+            SymbolValueItem::Extension(ext) if IdentKey::idents_eq(ctx, *ctx[ext].name, name) => {
+                Some(ext)
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    for ext in extensions {
+        let key = IdentKey::from_ident(ctx, *ctx[ext].scope_ident);
+        let Some(ext_scope) = parent.scopes.get_mut(&key) else {
+            continue;
+        };
+
+        for param in type_params {
+            ext_scope.insert(ctx, SymbolValueItem::TypeParam(*param))?;
+        }
+    }
+
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
-    use std::assert_matches::assert_matches;
+    use std::assert_matches;
 
     use crate::{
         ir::test_utils::utils::{collect_symbols, lowered_ir},
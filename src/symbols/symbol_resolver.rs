@@ -1,37 +1,201 @@
-use std::assert_matches::debug_assert_matches;
+use std::debug_assert_matches;
 
 use crate::{
+    error_message::error_formatter::Spanned,
     ir::{
         context::IrCtx,
         ir_walker::{IrWalkable, IrWalker, ScopeValue},
         node::{
+            array::{ArrayMember, ArrayMemberAccess, ArraySlice},
+            control_flow::{IfStmt, NarrowedBinding, NarrowingReach},
+            dictionary::{DictionaryIndex, DictionaryMember, DictionaryMemberAccess},
             enumeration::{Enum, EnumInit},
             expression::Expr,
             function::FunctionCall,
             identifier::{Ident, IdentParent, IdentValue, Identifiable},
+            loops::ForLoop,
             member_access::UnresolvedMemberAccess,
+            operator::BinaryOperation,
             statement::{Stmt, VarDecl},
-            structure::StructAccess,
-            type_signature::{TypeEvalError, TypeSignature, TypeSignatureValue, Typed},
+            static_access::{StaticAccess, StaticMember},
+            structure::{Struct, StructAttr},
+            type_alias::TypeAlias,
+            type_signature::{
+                BuiltinType, Mutability, TypeEvalError, TypeSignature, TypeSignatureContext,
+                TypeSignatureParent, TypeSignatureValue, Typed,
+            },
             IrAlloc, NodeRef,
         },
     },
     parser::Span,
 };
 
-use super::symbol_table::{symbol_table_zipper::SymbolTableZipper, SymbolTable};
+use super::symbol_table::{symbol_table_zipper::SymbolTableZipper, SymbolTable, SymbolValueItem};
 
 #[derive(Debug)]
 pub struct SymbolResolver<'a> {
     pub symbols: SymbolTableZipper<'a>,
+    /// The type aliases whose aliased types are currently being resolved
+    resolving_aliases: Vec<NodeRef<'a, TypeAlias<'a>>>,
 }
 
 impl<'a> SymbolResolver<'a> {
     pub fn new(symbols: SymbolTable<'a>) -> Self {
         Self {
             symbols: symbols.into(),
+            resolving_aliases: Vec::new(),
         }
     }
+
+    /// Resolves the aliased type of a type alias, which may be used before it is declared
+    fn resolve_type_alias(
+        &mut self,
+        ctx: &mut IrCtx<'a>,
+        scope: &mut (),
+        alias: NodeRef<'a, TypeAlias<'a>>,
+        used_type_sig: &TypeSignature<'a>,
+    ) -> Result<TypeSignature<'a>, SymbolResolutionError<'a>> {
+        if self.resolving_aliases.contains(&alias) {
+            return Err(SymbolResolutionError::RecursiveDeclaration {
+                decl: Declaration::TypeAlias(alias),
+                ident_span: used_type_sig.get_span(ctx).unwrap(),
+            });
+        }
+
+        self.resolving_aliases.push(alias);
+        let aliased_type = ctx[alias].type_sig.cloned().walk(self, ctx, scope);
+        self.resolving_aliases.pop();
+
+        let aliased_type = aliased_type?;
+        ctx[alias].type_sig = aliased_type.clone().into();
+        Ok(aliased_type)
+    }
+
+    /// The number of type arguments an instance of the type has to be given
+    fn type_param_count(&self, ctx: &IrCtx<'a>, type_sig: &TypeSignature<'a>) -> usize {
+        match ctx[type_sig] {
+            TypeSignatureValue::Struct { name } => self
+                .symbols
+                .lookup(ctx, name)
+                .map_or(0, |st| ctx[st.unwrap_struct(ctx)].type_params.len()),
+            TypeSignatureValue::Enum { name } => self
+                .symbols
+                .lookup(ctx, name)
+                .map_or(0, |enm| ctx[enm.unwrap_enum(ctx)].type_params.len()),
+            TypeSignatureValue::Builtin(BuiltinType::Promise | BuiltinType::Array) => 1,
+            _ => 0,
+        }
+    }
+
+    /// Gives the narrowed bindings the unwrapped type of their value when it is already known,
+    /// such that members can be accessed on them
+    fn narrow_types(
+        &mut self,
+        ctx: &mut IrCtx<'a>,
+        ifb: NodeRef<'a, IfStmt<'a>>,
+        reach: NarrowingReach,
+    ) -> Result<(), SymbolResolutionError<'a>> {
+        for (i, binding) in ctx[ifb].narrowed.clone().into_iter().enumerate() {
+            if binding.reach != reach {
+                continue;
+            }
+
+            // the binding of `if let` names the unwrapped value, instead of narrowing it
+            if !(i == 0 && ctx[ifb].unwraps_condition) && self.is_mutable_var(ctx, binding.value) {
+                ctx[ifb].narrowed[i].value_is_mutable = true;
+                continue;
+            }
+
+            if let Some(inner) = self.unwrapped_type(ctx, &binding)? {
+                ctx[ifb].narrowed[i].type_sig = inner.into();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Gives the values narrowed by the left operand their unwrapped type inside of the right one
+    fn narrow_operand_types(
+        &mut self,
+        ctx: &mut IrCtx<'a>,
+        bin_op: NodeRef<'a, BinaryOperation<'a>>,
+    ) -> Result<(), SymbolResolutionError<'a>> {
+        for (i, binding) in ctx[bin_op].narrowed.clone().into_iter().enumerate() {
+            if self.is_mutable_var(ctx, binding.value) {
+                ctx[bin_op].narrowed[i].value_is_mutable = true;
+                continue;
+            }
+
+            if let Some(inner) = self.unwrapped_type(ctx, &binding)? {
+                ctx[bin_op].narrowed[i].type_sig = inner.into();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Whether the identifier refers to a `var`, which is not narrowed as it can be reassigned
+    /// by the narrowed code, or by the functions it calls
+    fn is_mutable_var(&self, ctx: &IrCtx<'a>, value: NodeRef<'a, Expr<'a>>) -> bool {
+        match self.symbols.lookup(ctx, value.unwrap_ident(ctx)) {
+            Some(sym) => match ctx[sym] {
+                SymbolValueItem::VarDecl(var) => ctx[var].mutability == Mutability::Mutable,
+                _ => false,
+            },
+            None => false,
+        }
+    }
+
+    /// The type wrapped by the optional value of the binding, if its type is already known
+    fn unwrapped_type(
+        &mut self,
+        ctx: &mut IrCtx<'a>,
+        binding: &NarrowedBinding<'a>,
+    ) -> Result<Option<TypeSignature<'a>>, SymbolResolutionError<'a>> {
+        let value_type = binding
+            .value
+            .eval_type(&mut self.symbols, ctx)
+            .map_err(SymbolResolutionError::TypeEval)?;
+
+        Ok(match &ctx[&value_type] {
+            TypeSignatureValue::Optional(inner) => Some(inner.clone()),
+            _ => None,
+        })
+    }
+
+    /// Gives the loop item the element type of the sequence, or the tuple of the key and
+    /// value of a dictionary, so that members can be accessed on it inside of the loop body
+    fn resolve_loop_item_type(
+        &mut self,
+        ctx: &mut IrCtx<'a>,
+        fl: NodeRef<'a, ForLoop<'a>>,
+    ) -> Result<(), SymbolResolutionError<'a>> {
+        let sequence_type = ctx[fl]
+            .sequence
+            .clone()
+            .eval_type(&mut self.symbols, ctx)
+            .map_err(SymbolResolutionError::TypeEval)?;
+
+        if let TypeSignatureValue::Builtin(BuiltinType::Range) = ctx[&sequence_type] {
+            ctx[fl].counts_range = true;
+            ctx[fl].item_type = ctx.get_builtin_type_sig(BuiltinType::Number).into();
+        } else if let Some(element_type) = sequence_type.array_element_type(ctx) {
+            ctx[fl].item_type = element_type.into();
+        } else if let Some((key, value)) = sequence_type.dictionary_types(ctx) {
+            ctx[fl].item_type = ctx
+                .get_type_sig(
+                    TypeSignatureValue::Tuple(vec![key, value].into()),
+                    TypeSignatureContext {
+                        parent: TypeSignatureParent::ForLoop(fl),
+                        type_span: None,
+                    }
+                    .alloc(),
+                )
+                .into();
+        }
+
+        Ok(())
+    }
 }
 
 #[derive(Debug)]
@@ -45,10 +209,33 @@ pub enum SymbolResolutionError<'a> {
         mem_acc: NodeRef<'a, UnresolvedMemberAccess<'a>>,
         obj_type: TypeSignature<'a>,
     },
+    /// A declaration that is used inside of itself
     RecursiveDeclaration {
-        var_decl: NodeRef<'a, VarDecl<'a>>,
+        decl: Declaration<'a>,
         ident_span: Span<'a>,
     },
+    /// An instance of a generic type with the wrong number of type arguments
+    TypeArgCountMismatch {
+        type_sig: TypeSignature<'a>,
+        expected: usize,
+    },
+    UnknownInit {
+        call: NodeRef<'a, FunctionCall<'a>>,
+        st: NodeRef<'a, Struct<'a>>,
+    },
+    /// A static attribute used before it is declared, when it has not been assigned yet
+    StaticAttrUsedBeforeDeclaration {
+        st_acc: NodeRef<'a, StaticAccess<'a>>,
+        attr: NodeRef<'a, StructAttr<'a>>,
+    },
+}
+
+/// A declaration that can refer to itself
+#[derive(Debug, Clone, Copy)]
+pub enum Declaration<'a> {
+    Var(NodeRef<'a, VarDecl<'a>>),
+    TypeAlias(NodeRef<'a, TypeAlias<'a>>),
+    StaticAttr(NodeRef<'a, StructAttr<'a>>),
 }
 
 impl<'a> IrWalker<'a> for SymbolResolver<'a> {
@@ -61,6 +248,15 @@ impl<'a> IrWalker<'a> for SymbolResolver<'a> {
         _parent: &mut Self::Scope,
         value: ScopeValue<'a>,
     ) -> Result<(), Self::Error> {
+        match value {
+            ScopeValue::IfBranch(ifb, branch) => {
+                self.narrow_types(ctx, ifb, NarrowingReach::Branch(branch))?
+            }
+            ScopeValue::ForLoop(fl) => self.resolve_loop_item_type(ctx, fl)?,
+            ScopeValue::RightOperand(bin_op) => self.narrow_operand_types(ctx, bin_op)?,
+            _ => {}
+        }
+
         value.visit_scope_begin(ctx, &mut self.symbols);
         Ok(())
     }
@@ -88,6 +284,15 @@ impl<'a> IrWalker<'a> for SymbolResolver<'a> {
         Ok(())
     }
 
+    fn visit_if_stmt(
+        &mut self,
+        ctx: &mut IrCtx<'a>,
+        _scope: &mut Self::Scope,
+        ifb: NodeRef<'a, IfStmt<'a>>,
+    ) -> Result<(), Self::Error> {
+        self.narrow_types(ctx, ifb, NarrowingReach::AfterIf)
+    }
+
     fn visit_ident(
         &mut self,
         ctx: &mut IrCtx<'a>,
@@ -101,7 +306,7 @@ impl<'a> IrWalker<'a> for SymbolResolver<'a> {
     fn visit_type_sig(
         &mut self,
         ctx: &mut IrCtx<'a>,
-        _scope: &mut Self::Scope,
+        scope: &mut Self::Scope,
         type_sig: crate::ir::node::type_signature::TypeSignature<'a>,
     ) -> Result<crate::ir::node::type_signature::TypeSignature<'a>, Self::Error> {
         let updated_type_sig = match ctx[&type_sig].clone() {
@@ -113,14 +318,42 @@ impl<'a> IrWalker<'a> for SymbolResolver<'a> {
                             TypeEvalError::UnknownIdent(ident),
                         ))?;
 
-                let mut new_type = sym_val
-                    .eval_type(&mut self.symbols, ctx)
-                    .map_err(SymbolResolutionError::TypeEval)?;
+                let mut new_type = match ctx[sym_val] {
+                    SymbolValueItem::TypeAlias(alias) => {
+                        self.resolve_type_alias(ctx, scope, alias, &type_sig)?
+                    }
+                    _ => sym_val
+                        .eval_type(&mut self.symbols, ctx)
+                        .map_err(SymbolResolutionError::TypeEval)?,
+                };
 
                 new_type.context = type_sig.context;
 
+                // a generic type written without its type arguments, which are only inferred
+                // for the type of a struct init
+                let expected = self.type_param_count(ctx, &new_type);
+                let given_by_parent = matches!(
+                    new_type.context.parent,
+                    TypeSignatureParent::InstanceBase | TypeSignatureParent::StructInit(_)
+                );
+                if expected > 0 && !given_by_parent {
+                    return Err(SymbolResolutionError::TypeArgCountMismatch {
+                        type_sig: new_type,
+                        expected,
+                    });
+                }
+
                 new_type
             }
+            TypeSignatureValue::Instance { base, args } => {
+                let expected = self.type_param_count(ctx, &base);
+
+                if args.len() != expected {
+                    return Err(SymbolResolutionError::TypeArgCountMismatch { type_sig, expected });
+                }
+
+                type_sig
+            }
             _ => type_sig,
         };
 
@@ -143,9 +376,18 @@ impl<'a> IrWalker<'a> for SymbolResolver<'a> {
                     .value
                     .walk(&mut ident_searcher, ctx, scope)
                     .map_err(|span| SymbolResolutionError::RecursiveDeclaration {
-                        var_decl: var_decl,
+                        decl: Declaration::Var(var_decl),
                         ident_span: span,
                     })?;
+
+                // the items of an array literal are known to be coerced to the declared
+                // element type before any of them is inferred
+                if let Expr::Array(arr) = ctx[ctx[var_decl].value] {
+                    if ctx[var_decl].type_declared {
+                        let declared = ctx[var_decl].type_sig.cloned();
+                        arr.expect_type(ctx, &declared);
+                    }
+                }
             }
             _ => {}
         }
@@ -170,37 +412,66 @@ impl<'a> IrWalker<'a> for SymbolResolver<'a> {
                     }
                 };
 
-                let obj_type = obj
-                    .eval_type(&mut self.symbols, ctx)
-                    .map_err(SymbolResolutionError::TypeEval)?;
+                // members accessed through the name of a struct are static members
+                if let Some(st) = obj.named_struct(ctx, &self.symbols) {
+                    ctx[expr] = mem_acc
+                        .resolve_static_access(ctx, &self.symbols, st, obj)
+                        .map_err(SymbolResolutionError::TypeEval)?;
+
+                    // static attributes are assigned where they are declared, like variables
+                    if let Expr::StaticAccess(st_acc) = ctx[expr] {
+                        if let StaticMember::Attr(attr) = ctx[st_acc].member {
+                            let st_acc_span = st_acc.get_span(ctx).unwrap();
+                            let in_own_value = ctx[attr].default_value.is_some_and(|value| {
+                                value.get_span(ctx).unwrap().contains(&st_acc_span)
+                            });
+
+                            if in_own_value {
+                                return Err(SymbolResolutionError::RecursiveDeclaration {
+                                    decl: Declaration::StaticAttr(attr),
+                                    ident_span: st_acc_span,
+                                });
+                            }
 
-                let new_expr = match &ctx[&obj_type] {
-                    TypeSignatureValue::Struct { name: _ } => {
-                        let st_acc = StructAccess {
-                            struct_expr: obj,
-                            attr_name: *ctx[mem_acc].member_name,
+                            if attr.get_span(ctx) > Some(st_acc_span) {
+                                return Err(
+                                    SymbolResolutionError::StaticAttrUsedBeforeDeclaration {
+                                        st_acc,
+                                        attr,
+                                    },
+                                );
+                            }
                         }
-                        .allocate(ctx);
+                    }
+                    expr.walk(self, ctx, scope)?;
 
-                        ctx[st_acc].attr_name.parent =
-                            IdentParent::StructAccessAttrName(st_acc).into();
+                    return Ok(());
+                }
 
-                        if let Some((args, args_span)) = ctx[mem_acc].items.clone() {
-                            let st_acc_expr = Expr::StructAccess(st_acc).allocate(ctx);
+                let obj_type = obj
+                    .eval_type(&mut self.symbols, ctx)
+                    .map_err(SymbolResolutionError::TypeEval)?;
 
-                            let func_call = FunctionCall {
-                                func: st_acc_expr,
-                                args,
-                                args_span,
-                            }
-                            .allocate(ctx);
+                // optional chaining accesses the members of the wrapped struct
+                let optional = ctx[mem_acc].optional;
+                let member_obj_type = match &ctx[&obj_type] {
+                    TypeSignatureValue::Optional(value) if optional => value.clone(),
+                    _ => obj_type.clone(),
+                };
 
-                            Expr::FunctionCall(func_call)
-                        } else {
-                            Expr::StructAccess(st_acc)
-                        }
+                let new_expr = match &ctx[&member_obj_type.split_instance(ctx).0] {
+                    TypeSignatureValue::Struct { name: _ }
+                    | TypeSignatureValue::TypeParameter { name: _ } => {
+                        mem_acc.resolve_struct_access(ctx, obj)
                     }
-                    TypeSignatureValue::Enum { name } => {
+                    // only the `self` of the default methods of a trait has the members of the
+                    // trait, the name of the trait itself is not a value
+                    TypeSignatureValue::Trait { name: _ }
+                        if obj.is_trait_self(ctx, &self.symbols) =>
+                    {
+                        mem_acc.resolve_struct_access(ctx, obj)
+                    }
+                    TypeSignatureValue::Enum { name } if !optional => {
                         let items = match ctx[mem_acc].items.clone() {
                             Some((items, span)) => (Some(items), Some(span)),
                             None => (None, None),
@@ -211,10 +482,18 @@ impl<'a> IrWalker<'a> for SymbolResolver<'a> {
                             enum_value: *ctx[mem_acc].member_name,
                             items: items.0.unwrap_or_default(),
                             items_span: items.1,
+                            type_args: Vec::new(),
                             span: ctx[mem_acc].span.clone(),
                         }
                         .allocate(ctx);
 
+                        let enm = enm_init.lookup_enum(ctx, &self.symbols).ok_or(
+                            SymbolResolutionError::TypeEval(TypeEvalError::UnknownIdent(
+                                ctx[enm_init].enum_name,
+                            )),
+                        )?;
+                        enm_init.instantiate(ctx, enm, Vec::new());
+
                         ctx[enm_init].enum_name.parent =
                             IdentParent::EnumInitEnumName(enm_init).into();
 
@@ -223,6 +502,90 @@ impl<'a> IrWalker<'a> for SymbolResolver<'a> {
 
                         Expr::EnumInit(enm_init)
                     }
+                    TypeSignatureValue::Builtin(BuiltinType::Array) if !optional => {
+                        let member_name = *ctx[mem_acc].member_name;
+                        let member = member_name
+                            .value(ctx)
+                            .and_then(ArrayMember::from_name)
+                            .ok_or(SymbolResolutionError::TypeEval(
+                                TypeEvalError::UnknownIdent(member_name),
+                            ))?;
+
+                        let arr_acc = ArrayMemberAccess {
+                            array: obj,
+                            member,
+                            mapped_type: None,
+                            span: ctx[mem_acc].span.clone(),
+                        }
+                        .allocate(ctx);
+
+                        if member == ArrayMember::Map {
+                            ctx[arr_acc].mapped_type = Some(
+                                ctx.make_type_var(TypeSignatureParent::ArrayMemberAccess(arr_acc)),
+                            );
+                        }
+
+                        if let Some((args, args_span)) = ctx[mem_acc].items.clone() {
+                            let arr_acc_expr = Expr::ArrayMemberAccess(arr_acc).allocate(ctx);
+
+                            let func_call = FunctionCall {
+                                func: arr_acc_expr,
+                                arg_labels: vec![None; args.len()],
+                                args,
+                                args_span,
+                                instance: None,
+                                tried: false,
+                            }
+                            .allocate(ctx);
+
+                            Expr::FunctionCall(func_call)
+                        } else {
+                            Expr::ArrayMemberAccess(arr_acc)
+                        }
+                    }
+                    TypeSignatureValue::Dictionary { .. } if !optional => {
+                        let member_name = *ctx[mem_acc].member_name;
+                        let member = member_name
+                            .value(ctx)
+                            .and_then(DictionaryMember::from_name)
+                            .ok_or(SymbolResolutionError::TypeEval(
+                                TypeEvalError::UnknownIdent(member_name),
+                            ))?;
+
+                        let dict_acc = DictionaryMemberAccess {
+                            dictionary: obj,
+                            member,
+                            span: ctx[mem_acc].span.clone(),
+                        }
+                        .allocate(ctx);
+
+                        if let Some((args, args_span)) = ctx[mem_acc].items.clone() {
+                            let dict_acc_expr =
+                                Expr::DictionaryMemberAccess(dict_acc).allocate(ctx);
+
+                            let func_call = FunctionCall {
+                                func: dict_acc_expr,
+                                arg_labels: vec![None; args.len()],
+                                args,
+                                args_span,
+                                instance: None,
+                                tried: false,
+                            }
+                            .allocate(ctx);
+
+                            Expr::FunctionCall(func_call)
+                        } else {
+                            Expr::DictionaryMemberAccess(dict_acc)
+                        }
+                    }
+                    TypeSignatureValue::Record(_) => mem_acc.resolve_record_access(ctx, obj),
+                    // the type of the object is only known once it is inferred, e.g. for record
+                    // literals, or once the type of a declaration further down is resolved, e.g.
+                    // for the values returned by its methods, so the member access is resolved
+                    // by the type checker
+                    TypeSignatureValue::TypeVariable(_) | TypeSignatureValue::Unresolved(_) => {
+                        return Ok(())
+                    }
                     _ => {
                         return Err(SymbolResolutionError::InvalidMemberAccessType {
                             mem_acc,
@@ -237,6 +600,62 @@ impl<'a> IrWalker<'a> for SymbolResolver<'a> {
 
                 Ok(())
             }
+            Expr::Index(idx) => {
+                let idx = *idx;
+                let (obj, index) = (ctx[idx].array, ctx[idx].index);
+                let obj_type = obj
+                    .eval_type(&mut self.symbols, ctx)
+                    .map_err(SymbolResolutionError::TypeEval)?;
+                let index_type = index
+                    .eval_type(&mut self.symbols, ctx)
+                    .map_err(SymbolResolutionError::TypeEval)?;
+
+                // indexing with a range slices the array
+                if let TypeSignatureValue::Builtin(BuiltinType::Range) = ctx[&index_type] {
+                    let slice = ArraySlice {
+                        array: obj,
+                        range: index,
+                        span: ctx[idx].span.clone(),
+                    }
+                    .allocate(ctx);
+
+                    ctx[expr] = Expr::ArraySlice(slice);
+                    return Ok(());
+                }
+
+                // indexing a dictionary looks up the value of a key
+                if let TypeSignatureValue::Dictionary { .. } = ctx[&obj_type] {
+                    let dict_idx = DictionaryIndex {
+                        dictionary: obj,
+                        key: ctx[idx].index,
+                        span: ctx[idx].span.clone(),
+                    }
+                    .allocate(ctx);
+
+                    ctx[expr] = Expr::DictionaryIndex(dict_idx);
+                }
+
+                Ok(())
+            }
+            Expr::FunctionCall(call) => {
+                let call = *call;
+
+                // calling the name of a struct calls the init selected by the labels
+                if let Some(st) = ctx[call].func.named_struct(ctx, &self.symbols) {
+                    call.resolve_init_call(ctx, &self.symbols, st)
+                        .ok_or(SymbolResolutionError::UnknownInit { call, st })?;
+                }
+
+                Ok(())
+            }
+            Expr::Try(tr) => {
+                // the operand is already resolved, so method calls are function calls by now
+                if let Some(call) = tr.call(ctx) {
+                    ctx[call].tried = true;
+                }
+
+                Ok(())
+            }
             _ => Ok(()),
         }
     }
@@ -270,27 +689,38 @@ pub fn resolve_ident<'a>(
                 }
             }
             IdentParent::StructAccessAttrName(st_access) => {
-                let st_attr = st_access.lookup_attr(ctx, symbols).map_err(|_| {
-                    SymbolResolutionError::TypeEval(TypeEvalError::UnknownIdent(ident))
-                })?;
-
-                match ctx[&*ctx[st_attr].type_sig].clone() {
-                    TypeSignatureValue::Unresolved(type_ident) => {
-                        let resolved_type_sig = symbols
-                            .lookup(ctx, type_ident)
-                            .ok_or(SymbolResolutionError::TypeEval(
-                                TypeEvalError::UnknownIdent(type_ident),
-                            ))?
-                            .clone()
-                            .eval_type(symbols, ctx)
-                            .map_err(SymbolResolutionError::TypeEval)?;
+                match st_access.lookup_attr(ctx, symbols) {
+                    Ok(st_attr) => {
+                        if let TypeSignatureValue::Unresolved(type_ident) =
+                            ctx[&*ctx[st_attr].type_sig].clone()
+                        {
+                            let resolved_type_sig = symbols
+                                .lookup(ctx, type_ident)
+                                .ok_or(SymbolResolutionError::TypeEval(
+                                    TypeEvalError::UnknownIdent(type_ident),
+                                ))?
+                                .clone()
+                                .eval_type(symbols, ctx)
+                                .map_err(SymbolResolutionError::TypeEval)?;
+
+                            ctx[st_attr].type_sig = resolved_type_sig.into();
+                        }
 
-                        ctx[st_attr].type_sig = resolved_type_sig.into();
+                        Some(*ctx[st_attr].name)
                     }
-                    _ => {}
+                    // members that are not attributes are methods from an extension,
+                    // or methods of the trait when accessing `self` in a default method
+                    Err(_) => match st_access.lookup_method(ctx, symbols) {
+                        Ok(method) => Some(*ctx[method].name),
+                        Err(_) => {
+                            let attr = st_access.lookup_trait_attr(ctx, symbols).map_err(|_| {
+                                SymbolResolutionError::TypeEval(TypeEvalError::UnknownIdent(ident))
+                            })?;
+
+                            Some(*ctx[attr].name)
+                        }
+                    },
                 }
-
-                Some(*ctx[st_attr].name)
             }
             IdentParent::EnumInitValueName(enm_init) => {
                 let enm_name = ctx[enm_init].enum_name;
@@ -312,6 +742,8 @@ pub fn resolve_ident<'a>(
                 Some(*ctx[enm_val].name)
             }
             IdentParent::MemberAccessMemberName(_) => None,
+            // resolved once the type of the matched value is known
+            IdentParent::EnumPatternValueName(_) => None,
             _ => {
                 let sym_id = symbols
                     .lookup(ctx, ident)
@@ -364,11 +796,14 @@ impl<'a> IrWalker<'a> for SearchIdentWalker<'a> {
 
 #[cfg(test)]
 mod tests {
-    use std::assert_matches::assert_matches;
+    use std::assert_matches;
 
-    use crate::ir::test_utils::utils::{lowered_ir, resolve_symbols};
+    use crate::ir::{
+        node::type_signature::TypeEvalError,
+        test_utils::utils::{lowered_ir, resolve_symbols},
+    };
 
-    use super::SymbolResolutionError;
+    use super::{Declaration, SymbolResolutionError};
 
     #[test]
     fn test_self_referencing_var_decl() {
@@ -376,7 +811,7 @@ mod tests {
         assert_matches!(
             resolve_symbols(&mut ir),
             Err(SymbolResolutionError::RecursiveDeclaration {
-                var_decl: _,
+                decl: Declaration::Var(_),
                 ident_span: _
             })
         )
@@ -388,9 +823,118 @@ mod tests {
         assert_matches!(
             resolve_symbols(&mut ir),
             Err(SymbolResolutionError::RecursiveDeclaration {
-                var_decl: _,
+                decl: Declaration::Var(_),
                 ident_span: _
             })
         )
     }
+
+    #[test]
+    fn test_self_referencing_type_alias() {
+        let mut ir = lowered_ir("type Tree = Array<Tree>").unwrap();
+        assert_matches!(
+            resolve_symbols(&mut ir),
+            Err(SymbolResolutionError::RecursiveDeclaration {
+                decl: Declaration::TypeAlias(_),
+                ident_span: _
+            })
+        )
+    }
+
+    #[test]
+    fn test_indirect_self_referencing_type_alias() {
+        let mut ir = lowered_ir("type A = (Number, B); type B = () -> A").unwrap();
+        assert_matches!(
+            resolve_symbols(&mut ir),
+            Err(SymbolResolutionError::RecursiveDeclaration {
+                decl: Declaration::TypeAlias(_),
+                ident_span: _
+            })
+        )
+    }
+
+    #[test]
+    fn test_if_let_binding_outside_branch() {
+        let mut ir = lowered_ir(
+            "
+        enum Foo { a(Number); b }
+        let foo: Foo = .b
+        if let .a(num) = foo { let y = num }
+        let x = num
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            resolve_symbols(&mut ir),
+            Err(SymbolResolutionError::TypeEval(
+                TypeEvalError::UnknownIdent(_)
+            ))
+        )
+    }
+
+    #[test]
+    fn test_let_else_binding_in_else_body() {
+        let mut ir = lowered_ir(
+            "
+        enum Foo { a(Number); b }
+        func f(foo: Foo) -> Number {
+            let .a(num) = foo else { return num }
+            return num
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            resolve_symbols(&mut ir),
+            Err(SymbolResolutionError::TypeEval(
+                TypeEvalError::UnknownIdent(_)
+            ))
+        )
+    }
+
+    #[test]
+    fn test_type_arg_count_mismatch() {
+        let mut ir = lowered_ir(
+            "
+        struct Pair<A, B> { let first: A; let second: B }
+        func first(pair: Pair<Number>) -> Number { return pair.first }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            resolve_symbols(&mut ir),
+            Err(SymbolResolutionError::TypeArgCountMismatch { expected: 2, .. })
+        )
+    }
+
+    #[test]
+    fn test_missing_type_args() {
+        let mut ir = lowered_ir(
+            "
+        struct Box<T> { let value: T }
+        let b: Box = Box { value: 1 }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            resolve_symbols(&mut ir),
+            Err(SymbolResolutionError::TypeArgCountMismatch { expected: 1, .. })
+        )
+    }
+
+    #[test]
+    fn test_type_param_out_of_scope() {
+        let mut ir = lowered_ir("func f<T>(a: T) {}; let x: T = 1").unwrap();
+
+        assert_matches!(
+            resolve_symbols(&mut ir),
+            Err(SymbolResolutionError::TypeEval(
+                TypeEvalError::UnknownIdent(_)
+            ))
+        )
+    }
 }
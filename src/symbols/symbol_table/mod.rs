@@ -5,14 +5,21 @@ use id_arena::Id;
 use crate::ir::{
     context::{IrArenaType, IrCtx},
     node::{
-        control_flow::{IfBranchBody, IfStmt},
+        control_flow::{IfBranchBody, IfStmt, NarrowingReach},
         enumeration::{Enum, EnumValue},
+        error_handling::DoCatch,
+        extension::Extension,
         external::ExternalObject,
         function::{Function, FunctionArg},
+        generics::TypeParam,
         identifier::{Ident, IdentKey, Identifiable, ResolvedIdentValue},
+        loops::ForLoop,
+        operator::BinaryOperation,
+        pattern_match::{EnumPattern, LetElse, MatchArm},
         statement::VarDecl,
         structure::{Struct, StructAttr, StructInit},
         traits::Trait,
+        type_alias::TypeAlias,
         type_signature::{TypeEvalError, TypeSignature, Typed},
         NodeRef,
     },
@@ -76,8 +83,29 @@ pub enum SymbolValueItem<'a> {
     EnumDecl(NodeRef<'a, Enum<'a>>),
     EnumValue(NodeRef<'a, EnumValue<'a>>),
     ExternalObject(NodeRef<'a, ExternalObject<'a>>),
+    TypeAlias(NodeRef<'a, TypeAlias<'a>>),
     IfBranch(NodeRef<'a, IfStmt<'a>>, IfBranchBody),
     TraitDecl(NodeRef<'a, Trait<'a>>),
+    MatchArm(NodeRef<'a, MatchArm<'a>>),
+    PatternBinding(NodeRef<'a, EnumPattern<'a>>, usize),
+    LetElseBinding(NodeRef<'a, LetElse<'a>>, usize),
+    /// An optional value that is known not to be nil
+    NarrowedBinding(NodeRef<'a, IfStmt<'a>>, usize),
+    /// An optional value that is known not to be nil in the right operand of `&&` or `||`
+    OperandNarrowedBinding(NodeRef<'a, BinaryOperation<'a>>, usize),
+    TypeParam(NodeRef<'a, TypeParam<'a>>),
+    Extension(NodeRef<'a, Extension<'a>>),
+    /// The implicit `self` inside of the methods of an extension
+    SelfValue(NodeRef<'a, Extension<'a>>),
+    /// The implicit `self` inside of the default methods of a trait
+    TraitSelfValue(NodeRef<'a, Trait<'a>>),
+    /// The `Self` type inside of an extension, naming the extended type
+    SelfType(NodeRef<'a, Extension<'a>>),
+    /// The `Self` type inside of a trait, naming the conforming type
+    TraitSelfType(NodeRef<'a, Trait<'a>>),
+    CatchBinding(NodeRef<'a, DoCatch<'a>>),
+    /// The item of the sequence inside of a for loop body
+    LoopItem(NodeRef<'a, ForLoop<'a>>),
 }
 
 impl<'a> Into<Id<SymbolValueItem<'a>>> for SymbolValue<'a> {
@@ -117,8 +145,29 @@ impl<'a> Identifiable<'a> for SymbolValueItem<'a> {
             SymbolValueItem::EnumDecl(enm) => ctx[*enm].name(ctx),
             SymbolValueItem::EnumValue(enm_val) => ctx[*enm_val].name(ctx),
             SymbolValueItem::ExternalObject(obj) => ctx[*obj].name(ctx),
+            SymbolValueItem::TypeAlias(alias) => ctx[*alias].name(ctx),
             SymbolValueItem::IfBranch(ifb, branch) => ctx[*ifb].branch_ident(*branch),
             SymbolValueItem::TraitDecl(tr) => ctx[*tr].name(ctx),
+            SymbolValueItem::MatchArm(arm) => *ctx[*arm].scope_ident,
+            SymbolValueItem::PatternBinding(pattern, idx) => ctx[*pattern].bindings[*idx]
+                .name
+                .expect("ignored pattern items are not symbols"),
+            SymbolValueItem::LetElseBinding(let_else, idx) => ctx[ctx[*let_else].pattern].bindings
+                [*idx]
+                .name
+                .expect("ignored pattern items are not symbols"),
+            SymbolValueItem::NarrowedBinding(ifb, idx) => ctx[*ifb].narrowed[*idx].name,
+            SymbolValueItem::OperandNarrowedBinding(bin_op, idx) => {
+                ctx[*bin_op].narrowed[*idx].name
+            }
+            SymbolValueItem::TypeParam(param) => ctx[*param].name(ctx),
+            SymbolValueItem::Extension(ext) => *ctx[*ext].scope_ident,
+            SymbolValueItem::SelfValue(ext) => *ctx[*ext].self_name,
+            SymbolValueItem::TraitSelfValue(tr) => *ctx[*tr].self_name,
+            SymbolValueItem::SelfType(ext) => *ctx[*ext].self_type_name,
+            SymbolValueItem::TraitSelfType(tr) => *ctx[*tr].self_type_name,
+            SymbolValueItem::CatchBinding(dc) => *ctx[*dc].error_name,
+            SymbolValueItem::LoopItem(fl) => *ctx[*fl].item_name,
         }
     }
 }
@@ -136,8 +185,20 @@ impl<'a> SymbolValue<'a> {
             SymbolValueItem::EnumDecl(_) => "enum",
             SymbolValueItem::EnumValue(_) => "enum value",
             SymbolValueItem::ExternalObject(_) => "external object",
+            SymbolValueItem::TypeAlias(_) => "type alias",
             SymbolValueItem::IfBranch(_, _) => "if branch",
             SymbolValueItem::TraitDecl(_) => "trait",
+            SymbolValueItem::MatchArm(_) => "match arm",
+            SymbolValueItem::PatternBinding(_, _) => "pattern binding",
+            SymbolValueItem::LetElseBinding(_, _) => "pattern binding",
+            SymbolValueItem::NarrowedBinding(_, _)
+            | SymbolValueItem::OperandNarrowedBinding(_, _) => "narrowed value",
+            SymbolValueItem::TypeParam(_) => "type parameter",
+            SymbolValueItem::Extension(_) => "extension",
+            SymbolValueItem::SelfValue(_) | SymbolValueItem::TraitSelfValue(_) => "self",
+            SymbolValueItem::SelfType(_) | SymbolValueItem::TraitSelfType(_) => "Self",
+            SymbolValueItem::CatchBinding(_) => "caught error",
+            SymbolValueItem::LoopItem(_) => "loop item",
         }
     }
 }
@@ -152,10 +213,24 @@ impl<'a> IrArenaType<'a> for SymbolValueItem<'a> {
     }
 }
 
-impl SymbolValueItem<'_> {
-    fn is_order_dependent(&self) -> bool {
+impl<'a> SymbolValueItem<'a> {
+    fn is_order_dependent(&self, ctx: &IrCtx<'a>) -> bool {
+        match self {
+            SymbolValueItem::VarDecl(_) | SymbolValueItem::LetElseBinding(_, _) => true,
+            SymbolValueItem::NarrowedBinding(ifb, idx) => {
+                ctx[*ifb].narrowed[*idx].reach == NarrowingReach::AfterIf
+            }
+            _ => false,
+        }
+    }
+
+    /// Narrowed bindings of a `var` are skipped by lookups, so its name refers to the `var` itself
+    pub(super) fn is_hidden(&self, ctx: &IrCtx<'a>) -> bool {
         match self {
-            SymbolValueItem::VarDecl(_) => true,
+            SymbolValueItem::NarrowedBinding(ifb, idx) => ctx[*ifb].narrowed[*idx].value_is_mutable,
+            SymbolValueItem::OperandNarrowedBinding(bin_op, idx) => {
+                ctx[*bin_op].narrowed[*idx].value_is_mutable
+            }
             _ => false,
         }
     }
@@ -183,8 +258,30 @@ impl<'a> Typed<'a> for SymbolValue<'a> {
             SymbolValueItem::EnumDecl(enm) => enm.eval_type(symbols, ctx),
             SymbolValueItem::EnumValue(enm_val) => enm_val.eval_type(symbols, ctx),
             SymbolValueItem::ExternalObject(obj) => obj.eval_type(symbols, ctx),
+            SymbolValueItem::TypeAlias(alias) => alias.eval_type(symbols, ctx),
             SymbolValueItem::IfBranch(_, _) => unreachable!(),
             SymbolValueItem::TraitDecl(tr) => tr.eval_type(symbols, ctx),
+            SymbolValueItem::MatchArm(_) => unreachable!(),
+            SymbolValueItem::PatternBinding(pattern, idx) => {
+                Ok(ctx[pattern].bindings[idx].type_sig.cloned())
+            }
+            SymbolValueItem::LetElseBinding(let_else, idx) => {
+                Ok(ctx[ctx[let_else].pattern].bindings[idx].type_sig.cloned())
+            }
+            SymbolValueItem::NarrowedBinding(ifb, idx) => {
+                Ok(ctx[ifb].narrowed[idx].type_sig.cloned())
+            }
+            SymbolValueItem::OperandNarrowedBinding(bin_op, idx) => {
+                Ok(ctx[bin_op].narrowed[idx].type_sig.cloned())
+            }
+            SymbolValueItem::TypeParam(param) => param.eval_type(symbols, ctx),
+            SymbolValueItem::Extension(_) => unreachable!(),
+            SymbolValueItem::SelfValue(ext) => ext.eval_type(symbols, ctx),
+            SymbolValueItem::TraitSelfValue(tr) => tr.eval_type(symbols, ctx),
+            SymbolValueItem::SelfType(ext) => ext.eval_type(symbols, ctx),
+            SymbolValueItem::TraitSelfType(tr) => tr.eval_type(symbols, ctx),
+            SymbolValueItem::CatchBinding(dc) => dc.eval_type(symbols, ctx),
+            SymbolValueItem::LoopItem(fl) => fl.eval_type(symbols, ctx),
         }
     }
 
@@ -200,8 +297,30 @@ impl<'a> Typed<'a> for SymbolValue<'a> {
             SymbolValueItem::EnumDecl(enm) => enm.specified_type(ctx),
             SymbolValueItem::EnumValue(enm_val) => enm_val.specified_type(ctx),
             SymbolValueItem::ExternalObject(obj) => obj.specified_type(ctx),
+            SymbolValueItem::TypeAlias(alias) => alias.specified_type(ctx),
             SymbolValueItem::IfBranch(_, _) => unreachable!(),
             SymbolValueItem::TraitDecl(tr) => tr.specified_type(ctx),
+            SymbolValueItem::MatchArm(_) => unreachable!(),
+            SymbolValueItem::PatternBinding(pattern, idx) => {
+                Some(ctx[pattern].bindings[idx].type_sig.cloned())
+            }
+            SymbolValueItem::LetElseBinding(let_else, idx) => {
+                Some(ctx[ctx[let_else].pattern].bindings[idx].type_sig.cloned())
+            }
+            SymbolValueItem::NarrowedBinding(ifb, idx) => {
+                Some(ctx[ifb].narrowed[idx].type_sig.cloned())
+            }
+            SymbolValueItem::OperandNarrowedBinding(bin_op, idx) => {
+                Some(ctx[bin_op].narrowed[idx].type_sig.cloned())
+            }
+            SymbolValueItem::TypeParam(param) => param.specified_type(ctx),
+            SymbolValueItem::Extension(_) => unreachable!(),
+            SymbolValueItem::SelfValue(ext) => ext.specified_type(ctx),
+            SymbolValueItem::TraitSelfValue(tr) => tr.specified_type(ctx),
+            SymbolValueItem::SelfType(ext) => ext.specified_type(ctx),
+            SymbolValueItem::TraitSelfType(tr) => tr.specified_type(ctx),
+            SymbolValueItem::CatchBinding(dc) => dc.specified_type(ctx),
+            SymbolValueItem::LoopItem(fl) => fl.specified_type(ctx),
         }
     }
 
@@ -221,8 +340,35 @@ impl<'a> Typed<'a> for SymbolValue<'a> {
             SymbolValueItem::EnumDecl(enm) => enm.specify_type(ctx, new_type),
             SymbolValueItem::EnumValue(enm_val) => enm_val.specify_type(ctx, new_type),
             SymbolValueItem::ExternalObject(obj) => obj.specify_type(ctx, new_type),
+            SymbolValueItem::TypeAlias(alias) => alias.specify_type(ctx, new_type),
             SymbolValueItem::IfBranch(_, _) => unreachable!(),
             SymbolValueItem::TraitDecl(tr) => tr.specify_type(ctx, new_type),
+            SymbolValueItem::MatchArm(_) => unreachable!(),
+            SymbolValueItem::PatternBinding(pattern, idx) => {
+                ctx[pattern].bindings[idx].type_sig = new_type.into();
+                Ok(())
+            }
+            SymbolValueItem::LetElseBinding(let_else, idx) => {
+                let pattern = ctx[let_else].pattern;
+                ctx[pattern].bindings[idx].type_sig = new_type.into();
+                Ok(())
+            }
+            SymbolValueItem::NarrowedBinding(ifb, idx) => {
+                ctx[ifb].narrowed[idx].type_sig = new_type.into();
+                Ok(())
+            }
+            SymbolValueItem::OperandNarrowedBinding(bin_op, idx) => {
+                ctx[bin_op].narrowed[idx].type_sig = new_type.into();
+                Ok(())
+            }
+            SymbolValueItem::TypeParam(param) => param.specify_type(ctx, new_type),
+            SymbolValueItem::Extension(_) => unreachable!(),
+            SymbolValueItem::SelfValue(ext) => ext.specify_type(ctx, new_type),
+            SymbolValueItem::TraitSelfValue(tr) => tr.specify_type(ctx, new_type),
+            SymbolValueItem::SelfType(ext) => ext.specify_type(ctx, new_type),
+            SymbolValueItem::TraitSelfType(tr) => tr.specify_type(ctx, new_type),
+            SymbolValueItem::CatchBinding(dc) => dc.specify_type(ctx, new_type),
+            SymbolValueItem::LoopItem(fl) => fl.specify_type(ctx, new_type),
         }
     }
 }
@@ -235,7 +381,7 @@ impl<'a> SymbolTable<'a> {
     ) -> Result<SymbolValue<'a>, SymbolCollectionError<'a>> {
         let new_sym = ctx.make_symbol(val);
         let val = &ctx[new_sym];
-        if val.is_order_dependent() {
+        if val.is_order_dependent(ctx) {
             self.ordered_symbols.push_back(new_sym);
         } else {
             let ident = val.name(ctx);
@@ -289,12 +435,13 @@ impl<'a> SymbolTable<'a> {
     ) -> Option<&SymbolValue<'a>> {
         self.scope_global_table
             .get(&IdentKey::from_ident(ctx, ident))
+            .filter(|sym| !ctx[**sym].is_hidden(ctx))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::assert_matches::assert_matches;
+    use std::assert_matches;
 
     use crate::ir::{
         node::expression::Expr,
@@ -1,9 +1,13 @@
 use crate::ir::{
     context::IrCtx,
-    node::identifier::{Ident, IdentKey, Identifiable},
+    node::{
+        extension::Extension,
+        identifier::{Ident, IdentKey, Identifiable},
+        NodeRef,
+    },
 };
 
-use super::{SymbolCollectionError, SymbolTable, SymbolValue};
+use super::{SymbolCollectionError, SymbolTable, SymbolValue, SymbolValueItem};
 
 #[derive(Debug)]
 struct SymbolTableZipperBreadcrumb<'a> {
@@ -72,10 +76,6 @@ impl<'a> SymbolTableZipper<'a> {
         }
 
         for scope in self.breadcrumb.iter().rev() {
-            if let Some(value) = scope.sym_table.lookup_global_table(ctx, ident) {
-                return Some(*value);
-            }
-
             if let Some(value) = SymbolTableZipper::locate_visited_symbol(
                 ctx,
                 &scope.sym_table,
@@ -84,11 +84,35 @@ impl<'a> SymbolTableZipper<'a> {
             ) {
                 return Some(*value);
             }
+
+            if let Some(value) = scope.sym_table.lookup_global_table(ctx, ident) {
+                return Some(*value);
+            }
         }
 
         return None;
     }
 
+    /// Finds the extensions of the type with the given name, that are visible from the current scope
+    pub fn lookup_extensions(
+        &self,
+        ctx: &IrCtx<'a>,
+        name: Ident<'a>,
+    ) -> Vec<NodeRef<'a, Extension<'a>>> {
+        std::iter::once(&self.cursor)
+            .chain(self.breadcrumb.iter().rev().map(|scope| &scope.sym_table))
+            .flat_map(|sym_table| sym_table.scope_global_table.values())
+            .filter_map(|sym| match ctx[*sym] {
+                SymbolValueItem::Extension(ext)
+                    if IdentKey::idents_eq(ctx, *ctx[ext].name, name) =>
+                {
+                    Some(ext)
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
     fn locate_visited_symbol<'b>(
         ctx: &IrCtx<'a>,
         sym_table: &'b SymbolTable<'a>,
@@ -100,7 +124,9 @@ impl<'a> SymbolTableZipper<'a> {
             .iter()
             .take(visited_symbols)
             .rev()
-            .find(|sym| IdentKey::idents_eq(ctx, ctx[**sym].name(ctx), ident))
+            .find(|sym| {
+                IdentKey::idents_eq(ctx, ctx[**sym].name(ctx), ident) && !ctx[**sym].is_hidden(ctx)
+            })
     }
 
     pub fn lookup_current_scope(
@@ -108,11 +134,14 @@ impl<'a> SymbolTableZipper<'a> {
         ctx: &IrCtx<'a>,
         ident: Ident<'a>,
     ) -> Option<&SymbolValue<'a>> {
-        if let Some(sym) = self.cursor.lookup_global_table(ctx, ident) {
+        // declarations shadow the symbols available from the entire scope once visited
+        if let Some(sym) =
+            SymbolTableZipper::locate_visited_symbol(ctx, &self.cursor, self.visited_symbols, ident)
+        {
             return Some(sym);
         }
 
-        SymbolTableZipper::locate_visited_symbol(ctx, &self.cursor, self.visited_symbols, ident)
+        self.cursor.lookup_global_table(ctx, ident)
     }
 
     pub fn visit_next_symbol(&mut self, _ctx: &IrCtx<'a>) {
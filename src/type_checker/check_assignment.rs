@@ -5,6 +5,7 @@ use crate::{
             assignment::Assignment,
             expression::Expr,
             identifier::Ident,
+            static_access::StaticMember,
             type_signature::{Mutability, TypeEvalError},
             NodeRef,
         },
@@ -28,6 +29,7 @@ fn check_assignment_expr<'a>(
     symbols: &mut SymbolTableZipper<'a>,
     asg: NodeRef<'a, Assignment<'a>>,
     expr: NodeRef<'a, Expr<'a>>,
+    in_init: bool,
 ) -> Result<(), TypeCheckerError<'a>> {
     match ctx[expr].clone() {
         Expr::Identifier(ident, _) => {
@@ -47,6 +49,8 @@ fn check_assignment_expr<'a>(
                         ));
                     }
                 }
+                // attributes of `self` are assignable, given that the attribute is mutable
+                SymbolValueItem::SelfValue(_) if ctx[asg].lhs != expr => {}
                 _ => {
                     return Err(TypeCheckerError::AssignmentError(
                         asg,
@@ -55,20 +59,48 @@ fn check_assignment_expr<'a>(
                 }
             }
         }
+        // an optional chain may not reach the attribute to assign to
+        Expr::StructAccess(st_access) if ctx[st_access].optional => {
+            return Err(TypeCheckerError::AssignmentError(
+                asg,
+                AssignmentError::NotLValue(ctx[asg].lhs),
+            ));
+        }
         Expr::StructAccess(st_access) => {
             let attr = st_access
                 .lookup_attr(ctx, symbols)
                 .map_err(TypeCheckerError::TypeEval)?;
 
-            if ctx[attr].mutability == Mutability::Immutable {
+            // an init assigns the attributes of `self` before they become immutable
+            let assigned_by_init = in_init && is_self(ctx, symbols, ctx[st_access].struct_expr);
+
+            if ctx[attr].mutability == Mutability::Immutable && !assigned_by_init {
                 return Err(TypeCheckerError::AssignmentError(
                     asg,
                     AssignmentError::ImmutableAssignment(ctx[st_access].attr_name),
                 ));
             }
 
-            check_assignment_expr(ctx, symbols, asg, ctx[st_access].struct_expr)?;
+            check_assignment_expr(ctx, symbols, asg, ctx[st_access].struct_expr, in_init)?;
         }
+        Expr::StaticAccess(st_acc) => match ctx[st_acc].member {
+            StaticMember::Attr(attr) if ctx[attr].mutability == Mutability::Mutable => {}
+            StaticMember::Attr(attr) => {
+                return Err(TypeCheckerError::AssignmentError(
+                    asg,
+                    AssignmentError::ImmutableAssignment(*ctx[attr].name),
+                ));
+            }
+            StaticMember::Init(_) | StaticMember::Method(_) => {
+                return Err(TypeCheckerError::AssignmentError(
+                    asg,
+                    AssignmentError::NotLValue(ctx[asg].lhs),
+                ));
+            }
+        },
+        // the elements of an array and the entries of a dictionary can be set like with
+        // `push` and `set`, through any binding
+        Expr::Index(_) | Expr::DictionaryIndex(_) => {}
         _ => {
             return Err(TypeCheckerError::AssignmentError(
                 asg,
@@ -80,22 +112,40 @@ fn check_assignment_expr<'a>(
     return Ok(());
 }
 
+pub fn is_self<'a>(
+    ctx: &IrCtx<'a>,
+    symbols: &SymbolTableZipper<'a>,
+    expr: NodeRef<'a, Expr<'a>>,
+) -> bool {
+    let Expr::Identifier(ident, _) = &ctx[expr] else {
+        return false;
+    };
+
+    symbols
+        .lookup(ctx, **ident)
+        .is_some_and(|sym| matches!(ctx[sym], SymbolValueItem::SelfValue(_)))
+}
+
 pub fn check_assignment<'a>(
     ctx: &mut IrCtx<'a>,
     symbols: &mut SymbolTableZipper<'a>,
     asg: NodeRef<'a, Assignment<'a>>,
+    in_init: bool,
 ) -> Result<(), TypeCheckerError<'a>> {
     // only assign to:
     // - variable
     // - (nested) struct attribute
+    // - static attribute
+    // - array element
+    // - dictionary entry
     // with properties: mutable, same type
 
-    check_assignment_expr(ctx, symbols, asg, ctx[asg].lhs)
+    check_assignment_expr(ctx, symbols, asg, ctx[asg].lhs, in_init)
 }
 
 #[cfg(test)]
 mod tests {
-    use std::assert_matches::assert_matches;
+    use std::assert_matches;
 
     use crate::ir::test_utils::utils::{lowered_ir, type_check};
 
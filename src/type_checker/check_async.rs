@@ -0,0 +1,173 @@
+use crate::ir::{
+    context::IrCtx,
+    node::{
+        function::{Await, Function},
+        NodeRef,
+    },
+};
+
+use super::TypeCheckerError;
+
+/// Checks that the `await` expression is used directly inside of an async function
+pub fn check_await<'a>(
+    ctx: &IrCtx<'a>,
+    aw: NodeRef<'a, Await<'a>>,
+    func: Option<NodeRef<'a, Function<'a>>>,
+) -> Result<(), TypeCheckerError<'a>> {
+    match func {
+        Some(func) if ctx[func].is_async => Ok(()),
+        _ => Err(TypeCheckerError::AwaitOutsideAsync(aw, func)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::assert_matches;
+
+    use crate::{
+        ir::test_utils::utils::{lowered_ir, type_check},
+        type_checker::TypeCheckerError,
+    };
+
+    #[test]
+    fn test_async_function() {
+        let mut ir = lowered_ir(
+            "
+        external fetch: (String) -> Promise<String>
+        async func load(url: String) -> Number {
+            let text = await fetch(url)
+            return 1
+        }
+        let loaded: Promise<Number> = load(\"url\")
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_async_lambda_return_type() {
+        let mut ir = lowered_ir(
+            "
+        external fetch: (String) -> Promise<String>
+        let fetchData: (String) -> Promise<String> = async (url) {
+            let text = await fetch(url)
+            return text
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_await_member_access() {
+        let mut ir = lowered_ir(
+            "
+        struct Response { let status: Number }
+        external fetch: (String) -> Promise<Response>
+        async func fetchStatus(url: String) -> Number {
+            let response = await fetch(url)
+            return response.status
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_await_value_type() {
+        let mut ir = lowered_ir(
+            "
+        external fetch: (String) -> Promise<String>
+        async func load() { let n: Number = await fetch(\"url\") }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ConflictingTypes(_, _))
+        );
+    }
+
+    #[test]
+    fn test_await_non_promise() {
+        let mut ir = lowered_ir("async func load() { let n = await 1 }").unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ConflictingTypes(_, _))
+        );
+    }
+
+    #[test]
+    fn test_async_return_not_promise() {
+        let mut ir = lowered_ir(
+            "
+        async func load() -> Number { return 1 }
+        let n: Number = load()
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ConflictingTypes(_, _))
+        );
+    }
+
+    #[test]
+    fn test_await_outside_async() {
+        let mut ir = lowered_ir(
+            "
+        external fetch: (String) -> Promise<String>
+        func load() -> String { return await fetch(\"url\") }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::AwaitOutsideAsync(_, Some(_)))
+        );
+    }
+
+    #[test]
+    fn test_await_in_nested_lambda() {
+        let mut ir = lowered_ir(
+            "
+        external fetch: (String) -> Promise<String>
+        async func load() {
+            let f = () { let text = await fetch(\"url\") }
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::AwaitOutsideAsync(_, Some(_)))
+        );
+    }
+
+    #[test]
+    fn test_await_top_level() {
+        let mut ir = lowered_ir(
+            "
+        external fetch: (String) -> Promise<String>
+        let text = await fetch(\"url\")
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::AwaitOutsideAsync(_, None))
+        );
+    }
+}
@@ -0,0 +1,78 @@
+use crate::{
+    ir::{
+        context::IrCtx,
+        node::{
+            dictionary::Dictionary,
+            type_signature::{BuiltinType, TypeSignatureValue, Typed},
+            NodeRef,
+        },
+    },
+    symbols::symbol_table::symbol_table_zipper::SymbolTableZipper,
+};
+
+use super::TypeCheckerError;
+
+/// Checks that the keys of a dictionary are numbers, strings or booleans,
+/// as a `Map` compares any other key by reference
+pub fn check_dictionary<'a>(
+    ctx: &mut IrCtx<'a>,
+    symbols: &mut SymbolTableZipper<'a>,
+    dict: NodeRef<'a, Dictionary<'a>>,
+) -> Result<(), TypeCheckerError<'a>> {
+    let dict_type = dict
+        .eval_type(symbols, ctx)
+        .map_err(TypeCheckerError::TypeEval)?;
+
+    let key_type = match &ctx[&dict_type] {
+        TypeSignatureValue::Dictionary { key, .. } => key.clone(),
+        _ => unreachable!("a dictionary literal is typed as a dictionary"),
+    };
+
+    match &ctx[&key_type] {
+        TypeSignatureValue::Builtin(
+            BuiltinType::Number | BuiltinType::String | BuiltinType::Boolean,
+        ) => Ok(()),
+        TypeSignatureValue::TypeVariable(_) => Ok(()),
+        _ => Err(TypeCheckerError::InvalidDictionaryKey(dict, key_type)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::assert_matches;
+
+    use crate::{
+        ir::test_utils::utils::{lowered_ir, type_check},
+        type_checker::TypeCheckerError,
+    };
+
+    #[test]
+    fn test_dictionary_keys() {
+        let mut ir = lowered_ir(
+            "
+        let ages = [\"alice\": 31]
+        let squares = [2: 4, 3: 9]
+        let flags: [Boolean: String] = [:]
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+
+        for code in [
+            "let d = [(1, 2): \"a\"]",
+            "struct P { let x: Number }; let d = [P { x: 1 }: \"a\"]",
+            "struct P { let x: Number }; let d: [P: String] = [:]",
+            "let d = [[1]: \"a\"]",
+            "let key: Number? = 1; let d = [key: \"a\"]",
+        ] {
+            let mut ir = lowered_ir(code).unwrap();
+
+            assert_matches!(
+                type_check(&mut ir).1,
+                Err(TypeCheckerError::InvalidDictionaryKey(_, _)),
+                "{code}"
+            );
+        }
+    }
+}
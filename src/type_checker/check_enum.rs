@@ -41,7 +41,7 @@ pub fn check_enum_init<'a>(
 
 #[cfg(test)]
 mod tests {
-    use std::assert_matches::assert_matches;
+    use std::assert_matches;
 
     use crate::ir::test_utils::utils::{lowered_ir, type_check};
 
@@ -13,6 +13,7 @@ pub fn check_expr_ident<'a>(
     symbols: &mut SymbolTableZipper<'a>,
     expr: NodeRef<'a, Expr<'a>>,
     ident: Ident<'a>,
+    in_static: bool,
 ) -> Result<(), TypeCheckerError<'a>> {
     let sym_val = symbols
         .lookup(ctx, ident)
@@ -20,9 +21,15 @@ pub fn check_expr_ident<'a>(
             ident,
         )))?;
 
+    if in_static && matches!(ctx[sym_val], SymbolValueItem::SelfValue(_)) {
+        return Err(TypeCheckerError::SelfInStaticMember(expr));
+    }
+
     let non_expr = match ctx[sym_val] {
         SymbolValueItem::BuiltinType(_) => true,
         SymbolValueItem::StructDecl(_) => true,
+        SymbolValueItem::TypeParam(_) => true,
+        SymbolValueItem::SelfType(_) | SymbolValueItem::TraitSelfType(_) => true,
         _ => false,
     };
 
@@ -35,7 +42,7 @@ pub fn check_expr_ident<'a>(
 
 #[cfg(test)]
 mod tests {
-    use std::assert_matches::assert_matches;
+    use std::assert_matches;
 
     use crate::{
         ir::test_utils::utils::{lowered_ir, type_check},
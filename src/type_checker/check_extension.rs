@@ -0,0 +1,718 @@
+use crate::{
+    ir::{
+        context::IrCtx,
+        node::{
+            extension::Extension,
+            function::Function,
+            generics::{declared_type_param, TypeParam},
+            identifier::{Ident, IdentKey},
+            structure::StructAttr,
+            traits::{Trait, TraitFuncAttr},
+            type_signature::{
+                BuiltinType, TypeEvalError, TypeSignature, TypeSignatureValue, Typed,
+            },
+            NodeRef,
+        },
+    },
+    symbols::symbol_table::{symbol_table_zipper::SymbolTableZipper, SymbolValue, SymbolValueItem},
+};
+
+use super::TypeCheckerError;
+
+#[derive(Debug)]
+pub enum ExtensionError<'a> {
+    NonStructExtension(SymbolValue<'a>),
+    /// An enum extended with anything but operators, as its values can not hold methods
+    EnumMethods(SymbolValue<'a>),
+    /// The conformance of the extension is not a trait
+    NonTraitConformance(SymbolValue<'a>),
+    MissingTraitMethod(NodeRef<'a, TraitFuncAttr<'a>>),
+    TraitMethodMismatch(NodeRef<'a, TraitFuncAttr<'a>>, NodeRef<'a, Function<'a>>),
+    DuplicateInit(NodeRef<'a, Function<'a>>, NodeRef<'a, Function<'a>>),
+    /// A member with the name of a member of another extension of the same type,
+    /// only one of which would be reachable at runtime
+    DuplicateMember(Ident<'a>, Ident<'a>),
+    /// A method with the name of an attribute, which would be shadowed by the attribute
+    MethodShadowedByAttr(NodeRef<'a, StructAttr<'a>>, NodeRef<'a, Function<'a>>),
+    /// A conformance to a trait the type already conforms to in the given extension
+    DuplicateConformance(NodeRef<'a, Extension<'a>>),
+    /// A static attribute needs a value, as there is no init to assign it
+    StaticAttrWithoutValue(NodeRef<'a, StructAttr<'a>>),
+    /// An operator not taking two arguments, the first of which being the extended type
+    InvalidOperatorArgs(NodeRef<'a, Function<'a>>),
+    NonBooleanComparison(NodeRef<'a, Function<'a>>),
+}
+
+pub fn check_extension<'a>(
+    ctx: &mut IrCtx<'a>,
+    symbols: &mut SymbolTableZipper<'a>,
+    ext: NodeRef<'a, Extension<'a>>,
+) -> Result<(), TypeCheckerError<'a>> {
+    let ext_name = *ctx[ext].name;
+    let ext_sym = symbols
+        .lookup(ctx, ext_name)
+        .ok_or(TypeCheckerError::TypeEval(TypeEvalError::UnknownIdent(
+            ext_name,
+        )))?;
+
+    let is_enum = match ctx[ext_sym] {
+        SymbolValueItem::StructDecl(_) => false,
+        SymbolValueItem::EnumDecl(_) => true,
+        _ => {
+            return Err(TypeCheckerError::ExtensionError(
+                ext,
+                ExtensionError::NonStructExtension(ext_sym),
+            ))
+        }
+    };
+
+    let has_members = !ctx[ext].methods.is_empty()
+        || !ctx[ext].static_methods.is_empty()
+        || !ctx[ext].static_attrs.is_empty()
+        || !ctx[ext].inits.is_empty()
+        || ext
+            .conformed_trait(ctx, symbols)
+            .is_some_and(|tr| !ctx[tr].attrs.is_empty());
+
+    if is_enum && has_members {
+        return Err(TypeCheckerError::ExtensionError(
+            ext,
+            ExtensionError::EnumMethods(ext_sym),
+        ));
+    }
+
+    let self_type = ext
+        .eval_type(symbols, ctx)
+        .map_err(TypeCheckerError::TypeEval)?;
+    for op in ctx[ext].operators.clone() {
+        check_operator_decl(ctx, op, &self_type)
+            .map_err(|err| TypeCheckerError::ExtensionError(ext, err))?;
+    }
+
+    // the inits of the other extensions are assigned to the struct as well
+    let earlier_inits = earlier_extensions(ctx, symbols, ext)
+        .into_iter()
+        .flat_map(|other| ctx[other].inits.clone())
+        .collect::<Vec<_>>();
+    let inits = ctx[ext].inits.clone();
+    for (i, init) in inits.iter().enumerate() {
+        let arg_names = init.arg_names(ctx);
+
+        if let Some(other) = earlier_inits
+            .iter()
+            .chain(&inits[..i])
+            .find(|other| other.arg_names(ctx) == arg_names)
+        {
+            return Err(TypeCheckerError::ExtensionError(
+                ext,
+                ExtensionError::DuplicateInit(*other, *init),
+            ));
+        }
+    }
+
+    check_member_names(ctx, symbols, ext, ext_sym)
+        .map_err(|err| TypeCheckerError::ExtensionError(ext, err))?;
+
+    if let Some(attr) = ctx[ext]
+        .static_attrs
+        .iter()
+        .find(|attr| ctx[**attr].default_value.is_none())
+    {
+        return Err(TypeCheckerError::ExtensionError(
+            ext,
+            ExtensionError::StaticAttrWithoutValue(*attr),
+        ));
+    }
+
+    let trait_name = match ctx[ext].trait_name {
+        Some(trait_name) => trait_name,
+        None => return Ok(()),
+    };
+
+    if let Some(other) = earlier_extensions(ctx, symbols, ext)
+        .into_iter()
+        .find(|other| {
+            ctx[*other]
+                .trait_name
+                .is_some_and(|other_trait| IdentKey::idents_eq(ctx, other_trait, trait_name))
+        })
+    {
+        return Err(TypeCheckerError::ExtensionError(
+            ext,
+            ExtensionError::DuplicateConformance(other),
+        ));
+    }
+
+    let trait_sym = symbols
+        .lookup(ctx, trait_name)
+        .ok_or(TypeCheckerError::TypeEval(TypeEvalError::UnknownIdent(
+            trait_name,
+        )))?;
+
+    let tr = match ctx[trait_sym] {
+        SymbolValueItem::TraitDecl(tr) => tr,
+        _ => {
+            return Err(TypeCheckerError::ExtensionError(
+                ext,
+                ExtensionError::NonTraitConformance(trait_sym),
+            ))
+        }
+    };
+
+    let trait_type = tr
+        .eval_type(symbols, ctx)
+        .map_err(TypeCheckerError::TypeEval)?;
+    let self_types = SelfTypes {
+        ext: &self_type,
+        tr: &trait_type,
+    };
+
+    // Check that every method of the trait is implemented with the same signature,
+    // methods with a default implementation are inherited if not overridden
+    for attr in ctx[tr].attrs.clone() {
+        let method = match ext.lookup_method(*ctx[attr].name, ctx) {
+            Some(method) => method,
+            None if ctx[attr].default_impl.is_some() => continue,
+            None => {
+                return Err(TypeCheckerError::ExtensionError(
+                    ext,
+                    ExtensionError::MissingTraitMethod(attr),
+                ))
+            }
+        };
+
+        if !signatures_match(ctx, attr, method, &self_types) {
+            return Err(TypeCheckerError::ExtensionError(
+                ext,
+                ExtensionError::TraitMethodMismatch(attr, method),
+            ));
+        }
+    }
+
+    // The operators of the trait are looked up by their symbol instead of their name
+    for attr in ctx[tr].operators.clone() {
+        let operator = attr
+            .overloaded_operator(ctx)
+            .and_then(|operator| ext.lookup_operator(operator, ctx));
+
+        let operator = match operator {
+            Some(operator) => operator,
+            None if ctx[attr].default_impl.is_some() => continue,
+            None => {
+                return Err(TypeCheckerError::ExtensionError(
+                    ext,
+                    ExtensionError::MissingTraitMethod(attr),
+                ))
+            }
+        };
+
+        if !signatures_match(ctx, attr, operator, &self_types) {
+            return Err(TypeCheckerError::ExtensionError(
+                ext,
+                ExtensionError::TraitMethodMismatch(attr, operator),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that the members of the extension are declared once among all extensions
+/// of the extended type, and that methods do not share the name of an attribute
+fn check_member_names<'a>(
+    ctx: &IrCtx<'a>,
+    symbols: &SymbolTableZipper<'a>,
+    ext: NodeRef<'a, Extension<'a>>,
+    ext_sym: SymbolValue<'a>,
+) -> Result<(), ExtensionError<'a>> {
+    if let SymbolValueItem::StructDecl(st) = ctx[ext_sym] {
+        for method in ctx[ext].methods.iter() {
+            if let Some(attr) = st.lookup_attr(*ctx[*method].name, ctx) {
+                return Err(ExtensionError::MethodShadowedByAttr(attr, *method));
+            }
+        }
+    }
+
+    let earlier_names = earlier_extensions(ctx, symbols, ext)
+        .into_iter()
+        .flat_map(|other| member_names(ctx, other))
+        .collect::<Vec<_>>();
+
+    for name in member_names(ctx, ext) {
+        if let Some(first) = earlier_names
+            .iter()
+            .find(|first| IdentKey::idents_eq(ctx, **first, name))
+        {
+            return Err(ExtensionError::DuplicateMember(*first, name));
+        }
+    }
+
+    Ok(())
+}
+
+/// The names of the methods, static members and operators of the extension
+fn member_names<'a>(ctx: &IrCtx<'a>, ext: NodeRef<'a, Extension<'a>>) -> Vec<Ident<'a>> {
+    let funcs = ctx[ext]
+        .methods
+        .iter()
+        .chain(ctx[ext].static_methods.iter())
+        .chain(ctx[ext].operators.iter())
+        .map(|func| *ctx[*func].name);
+    let attrs = ctx[ext].static_attrs.iter().map(|attr| *ctx[*attr].name);
+
+    funcs.chain(attrs).collect()
+}
+
+/// The other extensions of the extended type that are declared before the extension
+fn earlier_extensions<'a>(
+    ctx: &IrCtx<'a>,
+    symbols: &SymbolTableZipper<'a>,
+    ext: NodeRef<'a, Extension<'a>>,
+) -> Vec<NodeRef<'a, Extension<'a>>> {
+    let mut extensions = symbols
+        .lookup_extensions(ctx, *ctx[ext].name)
+        .into_iter()
+        .filter(|other| ctx[*other].span < ctx[ext].span)
+        .collect::<Vec<_>>();
+
+    extensions.sort_by(|a, b| ctx[*a].span.cmp(&ctx[*b].span));
+    extensions
+}
+
+fn check_operator_decl<'a>(
+    ctx: &mut IrCtx<'a>,
+    op: NodeRef<'a, Function<'a>>,
+    self_type: &TypeSignature<'a>,
+) -> Result<(), ExtensionError<'a>> {
+    let args = &ctx[op].args;
+    if args.len() != 2 || *ctx[args[0]].type_sig != *self_type {
+        return Err(ExtensionError::InvalidOperatorArgs(op));
+    }
+
+    let is_comparison = op
+        .overloaded_operator(ctx)
+        .is_some_and(|operator| operator.is_comparison());
+
+    let boolean = ctx.get_builtin_type_sig(BuiltinType::Boolean);
+    if is_comparison && *ctx[op].return_type != boolean {
+        return Err(ExtensionError::NonBooleanComparison(op));
+    }
+
+    Ok(())
+}
+
+/// Checks if the type conforms to the trait bound of the type parameter
+pub fn conforms_to_bound<'a>(
+    ctx: &IrCtx<'a>,
+    symbols: &SymbolTableZipper<'a>,
+    type_sig: &TypeSignature<'a>,
+    param: NodeRef<'a, TypeParam<'a>>,
+) -> bool {
+    match param.bound_trait(ctx, symbols) {
+        Some(tr) => conforms_to(ctx, symbols, type_sig, tr),
+        None => true,
+    }
+}
+
+pub fn conforms_to<'a>(
+    ctx: &IrCtx<'a>,
+    symbols: &SymbolTableZipper<'a>,
+    type_sig: &TypeSignature<'a>,
+    tr: NodeRef<'a, Trait<'a>>,
+) -> bool {
+    let lookup_trait = |name: Ident<'a>| match ctx[symbols.lookup(ctx, name)?] {
+        SymbolValueItem::TraitDecl(tr) => Some(tr),
+        _ => None,
+    };
+
+    match &ctx[&type_sig.split_instance(ctx).0] {
+        TypeSignatureValue::Struct { name } | TypeSignatureValue::Enum { name } => symbols
+            .lookup_extensions(ctx, *name)
+            .into_iter()
+            .filter_map(|ext| ctx[ext].trait_name)
+            .any(|trait_name| lookup_trait(trait_name) == Some(tr)),
+        TypeSignatureValue::Trait { name } => lookup_trait(*name) == Some(tr),
+        TypeSignatureValue::TypeParameter { name } => {
+            declared_type_param(*name).and_then(|param| param.bound_trait(ctx, symbols)) == Some(tr)
+        }
+        _ => false,
+    }
+}
+
+/// The types of the extension, and of `Self` inside of the trait it conforms to
+struct SelfTypes<'a, 'b> {
+    ext: &'b TypeSignature<'a>,
+    tr: &'b TypeSignature<'a>,
+}
+
+fn signatures_match<'a>(
+    ctx: &mut IrCtx<'a>,
+    attr: NodeRef<'a, TraitFuncAttr<'a>>,
+    method: NodeRef<'a, Function<'a>>,
+    self_types: &SelfTypes<'a, '_>,
+) -> bool {
+    // an async method has to be declared as returning a promise by the trait
+    let method_return_type = method.result_type(ctx);
+
+    // `Self` in the trait stands for the extended type
+    let types_match = |attr_type: &TypeSignature<'a>, method_type: &TypeSignature<'a>| {
+        attr_type == method_type || (attr_type == self_types.tr && method_type == self_types.ext)
+    };
+
+    let attr_args = &ctx[attr].args;
+    let method_args = &ctx[method].args;
+
+    attr_args.len() == method_args.len()
+        && attr_args
+            .iter()
+            .zip(method_args)
+            .all(|(attr_arg, method_arg)| {
+                types_match(&ctx[*attr_arg].type_sig, &ctx[*method_arg].type_sig)
+            })
+        && types_match(&attr.return_type(ctx), &method_return_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::assert_matches;
+
+    use crate::{
+        ir::{
+            node::type_signature::TypeEvalError,
+            test_utils::utils::{lowered_ir, resolve_symbols, type_check},
+        },
+        symbols::symbol_resolver::SymbolResolutionError,
+        type_checker::TypeCheckerError,
+    };
+
+    use super::ExtensionError;
+
+    #[test]
+    fn test_trait_conformance() {
+        let mut ir = lowered_ir(
+            "
+        struct Test { let value: Number }
+        trait Describe { func describe(prefix: String) -> String; func reset() }
+        extend Test: Describe {
+            func describe(prefix: String) -> String { return prefix }
+            func reset() {}
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_trait_default_method() {
+        let mut ir = lowered_ir(
+            "
+        struct Test { let value: Number }
+        trait Describe {
+            func name() -> String
+            func describe(prefix: String) -> String { return prefix + self.name() }
+        }
+        extend Test: Describe { func name() -> String { return \"test\" } }
+        let description: String = Test { value: 1 }.describe(\"a \")
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_trait_default_method_self() {
+        let mut ir = lowered_ir(
+            "
+        trait Describe {
+            func describe() -> String { return self.value }
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            resolve_symbols(&mut ir),
+            Err(SymbolResolutionError::TypeEval(
+                TypeEvalError::UnknownIdent(_)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_trait_member_through_trait_name() {
+        let mut ir = lowered_ir(
+            "
+        trait Tr { func f() -> Number }
+        let s = Tr.f
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            resolve_symbols(&mut ir),
+            Err(SymbolResolutionError::InvalidMemberAccessType { .. })
+        );
+
+        let mut ir = lowered_ir(
+            "
+        trait Tr {
+            func g() -> Number
+            func f() -> Number { return self.g() }
+        }
+        let s = Tr.f()
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            resolve_symbols(&mut ir),
+            Err(SymbolResolutionError::InvalidMemberAccessType { .. })
+        );
+    }
+
+    #[test]
+    fn test_trait_missing_method() {
+        let mut ir = lowered_ir(
+            "
+        struct Test { let value: Number }
+        trait Describe { func describe() -> String }
+        extend Test: Describe {}
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ExtensionError(
+                _,
+                ExtensionError::MissingTraitMethod(_)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_trait_method_mismatch() {
+        let mut ir = lowered_ir(
+            "
+        struct Test { let value: Number }
+        trait Describe { func describe(prefix: String) -> String }
+        extend Test: Describe {
+            func describe(prefix: Number) -> String { return \"test\" }
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ExtensionError(
+                _,
+                ExtensionError::TraitMethodMismatch(_, _)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_trait_async_method() {
+        let mut ir = lowered_ir(
+            "
+        struct Test { let value: Number }
+        trait Load { func load() -> Promise<Number>; func reset() -> Number }
+        extend Test: Load {
+            async func load() -> Number { return self.value }
+            async func reset() -> Number { return 0 }
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ExtensionError(
+                _,
+                ExtensionError::TraitMethodMismatch(_, _)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_extend_non_trait() {
+        let mut ir = lowered_ir(
+            "
+        struct Test { let value: Number }
+        struct Other { let value: Number }
+        extend Test: Other {}
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ExtensionError(
+                _,
+                ExtensionError::NonTraitConformance(_)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_duplicate_conformance() {
+        let mut ir = lowered_ir(
+            "
+        trait Describable { func describe() -> String }
+        struct Car { let model: String }
+        extend Car: Describable { func describe() -> String { return self.model } }
+        extend Car: Describable {}
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ExtensionError(
+                _,
+                ExtensionError::DuplicateConformance(_)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_trait_missing_operator() {
+        let mut ir = lowered_ir(
+            "
+        struct Test { let value: Number }
+        trait Equatable { static func == (lhs: Self, rhs: Self) -> Boolean }
+        extend Test: Equatable {}
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ExtensionError(
+                _,
+                ExtensionError::MissingTraitMethod(_)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_trait_operator_mismatch() {
+        let mut ir = lowered_ir(
+            "
+        struct Test { let value: Number }
+        trait Equatable { static func == (lhs: Self, rhs: Self) -> Boolean }
+        extend Test: Equatable {
+            static func == (lhs: Test, rhs: Number) -> Boolean { return lhs.value == rhs }
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ExtensionError(
+                _,
+                ExtensionError::TraitMethodMismatch(_, _)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_invalid_operator_decl() {
+        let mut ir = lowered_ir(
+            "
+        struct Test { let value: Number }
+        extend Test { static func + (lhs: Number, rhs: Test) -> Number { return lhs } }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ExtensionError(
+                _,
+                ExtensionError::InvalidOperatorArgs(_)
+            ))
+        );
+
+        let mut ir = lowered_ir(
+            "
+        struct Test { let value: Number }
+        extend Test { static func < (lhs: Test, rhs: Test) -> Number { return 0 } }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ExtensionError(
+                _,
+                ExtensionError::NonBooleanComparison(_)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_duplicate_member_across_extensions() {
+        for code in [
+            "struct Car { let model: String }
+            extend Car { func describe() -> String { return self.model } }
+            extend Car { func describe() -> Number { return 1 } }",
+            "struct Car { let model: String }
+            extend Car { static let standard = Car { model: \"Volvo\" } }
+            extend Car { static func standard() -> Number { return 1 } }",
+        ] {
+            let mut ir = lowered_ir(code).unwrap();
+            assert_matches!(
+                type_check(&mut ir).1,
+                Err(TypeCheckerError::ExtensionError(
+                    _,
+                    ExtensionError::DuplicateMember(_, _)
+                )),
+                "{code}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_method_shadowed_by_attr() {
+        let mut ir = lowered_ir(
+            "
+        struct Car { let model: String }
+        extend Car { func model() -> String { return \"Volvo\" } }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ExtensionError(
+                _,
+                ExtensionError::MethodShadowedByAttr(_, _)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_extend_enum() {
+        let mut ir = lowered_ir(
+            "
+        enum Level { low; high }
+        extend Level { func describe() -> String { return \"level\" } }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ExtensionError(
+                _,
+                ExtensionError::EnumMethods(_)
+            ))
+        );
+    }
+}
@@ -0,0 +1,702 @@
+use crate::{
+    ir::{
+        context::IrCtx,
+        ir_walker::{IrWalkable, IrWalker, ScopeValue},
+        node::{
+            expression::Expr,
+            extension::Extension,
+            function::Function,
+            identifier::IdentKey,
+            statement::{Stmt, StmtBlock},
+            structure::{Struct, StructAttr},
+            type_signature::Mutability,
+            NodeRef,
+        },
+    },
+    symbols::symbol_table::{symbol_table_zipper::SymbolTableZipper, SymbolValueItem},
+};
+
+use super::{check_assignment::is_self, TypeCheckerError};
+
+#[derive(Debug)]
+pub enum InitError<'a> {
+    UnassignedAttribute(NodeRef<'a, StructAttr<'a>>),
+    UnassignedBeforeReturn(NodeRef<'a, StructAttr<'a>>, NodeRef<'a, Expr<'a>>),
+    /// An attribute without a default value that is read before it is assigned on every path,
+    /// given together with the `self` it is read from
+    ReadBeforeAssignment(NodeRef<'a, StructAttr<'a>>, NodeRef<'a, Expr<'a>>),
+    /// A `self` used other than to assign or read one of its attributes, while the given
+    /// attribute without a default value may not be assigned yet
+    SelfUsedBeforeAssignment(NodeRef<'a, StructAttr<'a>>, NodeRef<'a, Expr<'a>>),
+    /// An attribute declared with `let` that is assigned while it may already be assigned,
+    /// given together with the assignment
+    AssignedTwice(NodeRef<'a, StructAttr<'a>>, NodeRef<'a, Expr<'a>>),
+}
+
+/// Checks that every attribute without a default value is assigned on every path
+/// through the init before it is read, before `self` is used otherwise and before the init returns,
+/// and that the attributes declared with `let` are assigned at most once
+pub fn check_init<'a>(
+    ctx: &mut IrCtx<'a>,
+    symbols: &mut SymbolTableZipper<'a>,
+    ext: NodeRef<'a, Extension<'a>>,
+    init: NodeRef<'a, Function<'a>>,
+) -> Result<(), TypeCheckerError<'a>> {
+    // extensions of anything but a struct are reported by the extension check
+    let st = match symbols.lookup(ctx, *ctx[ext].name).map(|sym| ctx[sym]) {
+        Some(SymbolValueItem::StructDecl(st)) => st,
+        _ => return Ok(()),
+    };
+
+    // the default values are assigned before the body of the init runs
+    let assigned = Assigned {
+        definitely: Vec::new(),
+        possibly: ctx[st]
+            .attrs
+            .iter()
+            .copied()
+            .filter(|attr| ctx[*attr].default_value.is_some())
+            .collect(),
+    };
+
+    let flow = InitFlow { symbols, st };
+
+    let assigned = flow
+        .assigned_in_block(ctx, ctx[init].body, assigned, false)
+        .map_err(|err| TypeCheckerError::InitError(init, err))?;
+
+    match assigned.and_then(|assigned| flow.unassigned(ctx, &assigned)) {
+        Some(attr) => Err(TypeCheckerError::InitError(
+            init,
+            InitError::UnassignedAttribute(attr),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// The attributes assigned so far on a path through the init
+#[derive(Debug, Clone)]
+struct Assigned<'a> {
+    definitely: Vec<NodeRef<'a, StructAttr<'a>>>,
+    /// The attributes assigned on any path, including those with a default value
+    possibly: Vec<NodeRef<'a, StructAttr<'a>>>,
+}
+
+impl<'a> Assigned<'a> {
+    fn merge(self, other: Self) -> Self {
+        let mut possibly = self.possibly;
+        possibly.extend(
+            other
+                .possibly
+                .into_iter()
+                .filter(|attr| !possibly.contains(attr))
+                .collect::<Vec<_>>(),
+        );
+
+        Assigned {
+            definitely: self
+                .definitely
+                .into_iter()
+                .filter(|attr| other.definitely.contains(attr))
+                .collect(),
+            possibly,
+        }
+    }
+}
+
+struct InitFlow<'a, 'b> {
+    symbols: &'b SymbolTableZipper<'a>,
+    st: NodeRef<'a, Struct<'a>>,
+}
+
+impl<'a, 'b> InitFlow<'a, 'b> {
+    /// The attributes assigned at the end of the block, or `None` if the end of the block
+    /// can not be reached. Inside of a loop the block may run more than once
+    fn assigned_in_block(
+        &self,
+        ctx: &mut IrCtx<'a>,
+        block: NodeRef<'a, StmtBlock<'a>>,
+        mut assigned: Assigned<'a>,
+        in_loop: bool,
+    ) -> Result<Option<Assigned<'a>>, InitError<'a>> {
+        for stmt in ctx[block].0.clone() {
+            self.check_reads(ctx, stmt, &assigned)?;
+
+            match ctx[stmt] {
+                Stmt::Expression(expr) | Stmt::Throw(expr) => {
+                    match self.assigned_in_expr(ctx, expr, assigned, in_loop)? {
+                        Some(after) => assigned = after,
+                        None => return Ok(None),
+                    }
+
+                    if let Stmt::Throw(_) = ctx[stmt] {
+                        return Ok(None);
+                    }
+                }
+                Stmt::VariableDecl(var_decl) => {
+                    let value = ctx[var_decl].value;
+                    match self.assigned_in_expr(ctx, value, assigned, in_loop)? {
+                        Some(after) => assigned = after,
+                        None => return Ok(None),
+                    }
+                }
+                Stmt::Return(expr) => {
+                    let Some(assigned) = self.assigned_in_expr(ctx, expr, assigned, in_loop)?
+                    else {
+                        return Ok(None);
+                    };
+
+                    if let Some(attr) = self.unassigned(ctx, &assigned) {
+                        return Err(InitError::UnassignedBeforeReturn(attr, expr));
+                    }
+
+                    return Ok(None);
+                }
+                Stmt::LoopJump(_) => return Ok(None),
+                Stmt::IfBranch(ifb) => {
+                    let body =
+                        self.assigned_in_block(ctx, ctx[ifb].body, assigned.clone(), in_loop)?;
+
+                    // without an else branch the attributes of the body may not be assigned
+                    let Some(else_body) = ctx[ifb].else_body else {
+                        if let Some(body) = body {
+                            assigned = assigned.merge(body);
+                        }
+                        continue;
+                    };
+
+                    let else_body =
+                        self.assigned_in_block(ctx, else_body, assigned.clone(), in_loop)?;
+
+                    assigned = match (body, else_body) {
+                        (Some(body), Some(else_body)) => body.merge(else_body),
+                        (Some(branch), None) | (None, Some(branch)) => branch,
+                        (None, None) => return Ok(None),
+                    };
+                }
+                Stmt::DoCatch(dc) => {
+                    // the catch body starts from before the do body, which may throw at any point,
+                    // so it may also follow any of the assignments of the do body
+                    let body =
+                        self.assigned_in_block(ctx, ctx[dc].body, assigned.clone(), in_loop)?;
+
+                    let mut before_catch = assigned.clone();
+                    if let Some(body) = &body {
+                        before_catch.possibly = body.possibly.clone();
+                    }
+                    let catch_body =
+                        self.assigned_in_block(ctx, ctx[dc].catch_body, before_catch, in_loop)?;
+
+                    assigned = match (body, catch_body) {
+                        (Some(body), Some(catch_body)) => body.merge(catch_body),
+                        (Some(branch), None) | (None, Some(branch)) => branch,
+                        (None, None) => return Ok(None),
+                    };
+                }
+                // the bodies may not run, so their assignments are not counted after them
+                Stmt::LetElse(let_else) => {
+                    let else_body = ctx[let_else].else_body;
+                    self.assigned_in_block(ctx, else_body, assigned.clone(), in_loop)?;
+                }
+                Stmt::WhileLoop(wl) => {
+                    if let Some(body) =
+                        self.assigned_in_block(ctx, ctx[wl].body, assigned.clone(), true)?
+                    {
+                        assigned = assigned.merge(body);
+                    }
+                }
+                Stmt::ForLoop(fl) => {
+                    if let Some(body) =
+                        self.assigned_in_block(ctx, ctx[fl].body, assigned.clone(), true)?
+                    {
+                        assigned = assigned.merge(body);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(Some(assigned))
+    }
+
+    /// The attributes assigned after the expression, whose matches branch like if statements,
+    /// or `None` if the end of the expression can not be reached
+    fn assigned_in_expr(
+        &self,
+        ctx: &mut IrCtx<'a>,
+        expr: NodeRef<'a, Expr<'a>>,
+        assigned: Assigned<'a>,
+        in_loop: bool,
+    ) -> Result<Option<Assigned<'a>>, InitError<'a>> {
+        match ctx[expr] {
+            Expr::Match(mtch) => {
+                let mut after_arms: Option<Assigned<'a>> = None;
+
+                for arm in ctx[mtch].arms.clone() {
+                    let body = ctx[arm].body;
+                    let Some(mut arm_assigned) =
+                        self.assigned_in_block(ctx, body, assigned.clone(), in_loop)?
+                    else {
+                        continue;
+                    };
+
+                    if let Some(result) = ctx[arm].result {
+                        self.check_expr_reads(ctx, result, &arm_assigned)?;
+                        match self.assigned_in_expr(ctx, result, arm_assigned, in_loop)? {
+                            Some(after) => arm_assigned = after,
+                            None => continue,
+                        }
+                    }
+
+                    after_arms = Some(match after_arms {
+                        Some(after_arms) => after_arms.merge(arm_assigned),
+                        None => arm_assigned,
+                    });
+                }
+
+                Ok(after_arms)
+            }
+            Expr::Assignment(asg) => {
+                let rhs = ctx[asg].rhs;
+                let Some(mut assigned) = self.assigned_in_expr(ctx, rhs, assigned, in_loop)? else {
+                    return Ok(None);
+                };
+
+                if let Some(attr) = self.assigned_attr(ctx, expr) {
+                    if ctx[attr].mutability == Mutability::Immutable
+                        && (in_loop || assigned.possibly.contains(&attr))
+                    {
+                        return Err(InitError::AssignedTwice(attr, expr));
+                    }
+
+                    assigned.definitely.push(attr);
+                    assigned.possibly.push(attr);
+                }
+
+                Ok(Some(assigned))
+            }
+            _ => Ok(Some(assigned)),
+        }
+    }
+
+    /// Checks that the expressions of the statement, not counting the blocks it contains,
+    /// only read attributes that are assigned on every path, and only use `self` otherwise
+    /// once all of its attributes are assigned
+    fn check_reads(
+        &self,
+        ctx: &mut IrCtx<'a>,
+        stmt: NodeRef<'a, Stmt<'a>>,
+        assigned: &Assigned<'a>,
+    ) -> Result<(), InitError<'a>> {
+        let expr = match ctx[stmt] {
+            Stmt::VariableDecl(var_decl) => ctx[var_decl].value,
+            Stmt::Expression(expr) | Stmt::Return(expr) | Stmt::Throw(expr) => expr,
+            Stmt::IfBranch(ifb) => ctx[ifb].condition,
+            Stmt::LetElse(let_else) => ctx[let_else].value,
+            Stmt::WhileLoop(wl) => ctx[wl].condition,
+            Stmt::ForLoop(fl) => ctx[fl].sequence,
+            _ => return Ok(()),
+        };
+
+        self.check_expr_reads(ctx, expr, assigned)
+    }
+
+    /// Checks the reads of the expression, not counting the arms of the matches it contains,
+    /// which are checked with the attributes assigned inside of them
+    fn check_expr_reads(
+        &self,
+        ctx: &mut IrCtx<'a>,
+        expr: NodeRef<'a, Expr<'a>>,
+        assigned: &Assigned<'a>,
+    ) -> Result<(), InitError<'a>> {
+        let mut searcher = SearchReadWalker {
+            symbols: self.symbols,
+            self_exprs: Vec::new(),
+            accesses: Vec::new(),
+            assigned_exprs: Vec::new(),
+        };
+        // the walker only collects the uses of `self`, so it does not fail
+        let _ = expr.walk(&mut searcher, ctx, &mut false);
+
+        for self_expr in searcher.self_exprs {
+            let access = searcher
+                .accesses
+                .iter()
+                .find(|(struct_expr, _)| *struct_expr == self_expr)
+                .map(|(_, st_acc)| *st_acc);
+
+            if access.is_some_and(|st_acc| searcher.assigned_exprs.contains(&st_acc)) {
+                continue;
+            }
+
+            let attr = access.and_then(|st_acc| match ctx[st_acc] {
+                Expr::StructAccess(st_acc) => self.st.lookup_attr(ctx[st_acc].attr_name, ctx),
+                _ => None,
+            });
+
+            match attr {
+                Some(attr) if ctx[attr].default_value.is_some() => {}
+                Some(attr) if assigned.definitely.contains(&attr) => {}
+                Some(attr) => return Err(InitError::ReadBeforeAssignment(attr, self_expr)),
+                // calling a method or passing `self` on may read any of its attributes
+                None => {
+                    if let Some(attr) = self.unassigned(ctx, assigned) {
+                        return Err(InitError::SelfUsedBeforeAssignment(attr, self_expr));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn assigned_attr(
+        &self,
+        ctx: &IrCtx<'a>,
+        expr: NodeRef<'a, Expr<'a>>,
+    ) -> Option<NodeRef<'a, StructAttr<'a>>> {
+        let Expr::Assignment(asg) = &ctx[expr] else {
+            return None;
+        };
+
+        let Expr::StructAccess(st_acc) = &ctx[ctx[*asg].lhs] else {
+            return None;
+        };
+
+        if !is_self(ctx, self.symbols, ctx[*st_acc].struct_expr) {
+            return None;
+        }
+
+        ctx[self.st]
+            .attrs
+            .iter()
+            .copied()
+            .find(|attr| IdentKey::idents_eq(ctx, *ctx[*attr].name, ctx[*st_acc].attr_name))
+    }
+
+    /// The attributes without a default value
+    fn required(&self, ctx: &IrCtx<'a>) -> Vec<NodeRef<'a, StructAttr<'a>>> {
+        ctx[self.st]
+            .attrs
+            .iter()
+            .copied()
+            .filter(|attr| ctx[*attr].default_value.is_none())
+            .collect()
+    }
+
+    fn unassigned(
+        &self,
+        ctx: &IrCtx<'a>,
+        assigned: &Assigned<'a>,
+    ) -> Option<NodeRef<'a, StructAttr<'a>>> {
+        self.required(ctx)
+            .into_iter()
+            .find(|attr| !assigned.definitely.contains(attr))
+    }
+}
+
+/// Walker collecting the uses of `self` in a subtree, together with the accesses of its members
+/// and the expressions assigned to, which are not read
+struct SearchReadWalker<'a, 'b> {
+    symbols: &'b SymbolTableZipper<'a>,
+    self_exprs: Vec<NodeRef<'a, Expr<'a>>>,
+    accesses: Vec<(NodeRef<'a, Expr<'a>>, NodeRef<'a, Expr<'a>>)>,
+    assigned_exprs: Vec<NodeRef<'a, Expr<'a>>>,
+}
+
+impl<'a> IrWalker<'a> for SearchReadWalker<'a, '_> {
+    /// Whether the scope is inside of a match arm
+    type Scope = bool;
+
+    fn visit_scope_begin(
+        &mut self,
+        _ctx: &mut IrCtx<'a>,
+        parent: &mut Self::Scope,
+        value: ScopeValue<'a>,
+    ) -> Result<Self::Scope, Self::Error> {
+        Ok(*parent || matches!(value, ScopeValue::MatchArm(_)))
+    }
+
+    fn visit_expr(
+        &mut self,
+        ctx: &mut IrCtx<'a>,
+        scope: &mut Self::Scope,
+        expr: NodeRef<'a, Expr<'a>>,
+    ) -> Result<(), Self::Error> {
+        if *scope {
+            return Ok(());
+        }
+
+        match &ctx[expr] {
+            _ if is_self(ctx, self.symbols, expr) => self.self_exprs.push(expr),
+            Expr::StructAccess(st_acc) => self.accesses.push((ctx[*st_acc].struct_expr, expr)),
+            Expr::Assignment(asg) => self.assigned_exprs.push(ctx[*asg].lhs),
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::assert_matches;
+
+    use crate::{
+        ir::test_utils::utils::{lowered_ir, type_check},
+        type_checker::TypeCheckerError,
+    };
+
+    use super::InitError;
+
+    #[test]
+    fn test_init_assigns_attributes() {
+        let mut ir = lowered_ir(
+            "
+        struct Point { let x: Number; let y: Number; let label = \"point\" }
+        extend Point {
+            init(x: Number, y: Number) {
+                self.x = x
+                if x > y { self.y = x } else { self.y = y }
+            }
+        }
+        let p = Point(x: 1, y: 2)
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_init_unassigned_attribute() {
+        let mut ir = lowered_ir(
+            "
+        struct Point { let x: Number; let y: Number }
+        extend Point {
+            init(x: Number) {
+                self.x = x
+                if x > 0 { self.y = x }
+            }
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::InitError(
+                _,
+                InitError::UnassignedAttribute(_)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_init_throws_before_assigning() {
+        let mut ir = lowered_ir(
+            "
+        struct Size { let value: Number }
+        extend Size {
+            init(value: Number) throws(String) {
+                if value < 0 { throw \"negative\" }
+                self.value = value
+            }
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_init_reads_unassigned_attribute() {
+        let mut ir = lowered_ir(
+            "
+        struct Point { let x: Number; let y: Number }
+        extend Point {
+            init(v: Number) {
+                self.y = self.x
+                self.x = v
+            }
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::InitError(
+                _,
+                InitError::ReadBeforeAssignment(_, _)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_init_reads_assigned_attribute() {
+        let mut ir = lowered_ir(
+            "
+        struct Point { let x: Number; var y: Number; let start = 1 }
+        extend Point {
+            init(v: Number) {
+                if v > 0 { self.x = v } else { self.x = 0 }
+                self.y = self.x + self.start
+                while self.y > 10 { self.y = self.y - 1 }
+            }
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_init_uses_self_before_assigning() {
+        let mut ir = lowered_ir(
+            "
+        struct Point { let x: Number; let y: Number }
+        func describe(p: Point) {}
+        extend Point {
+            func sum() -> Number { return self.x + self.y }
+            init(v: Number) {
+                self.x = v
+                self.y = self.sum()
+            }
+            init(w: Number) {
+                self.x = w
+                describe(self)
+                self.y = w
+            }
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::InitError(
+                _,
+                InitError::SelfUsedBeforeAssignment(_, _)
+            ))
+        );
+
+        let mut ir = lowered_ir(
+            "
+        struct Point { let x: Number; let y: Number }
+        func describe(p: Point) {}
+        extend Point {
+            func sum() -> Number { return self.x + self.y }
+            init(v: Number) {
+                self.x = v
+                self.y = v
+                describe(self)
+                let total = self.sum()
+            }
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_init_assigns_let_attribute_twice() {
+        let mut ir = lowered_ir(
+            "
+        struct Point { let x: Number }
+        extend Point {
+            init(v: Number) {
+                self.x = v
+                if v < 0 { self.x = 0 }
+            }
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::InitError(
+                _,
+                InitError::AssignedTwice(_, _)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_init_assigns_let_attribute_in_loop() {
+        let mut ir = lowered_ir(
+            "
+        struct Point { let x: Number }
+        extend Point {
+            init(v: Number) {
+                self.x = 0
+                for i in 0..<v { self.x = i }
+            }
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::InitError(
+                _,
+                InitError::AssignedTwice(_, _)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_init_assigns_attribute_in_match_arms() {
+        let mut ir = lowered_ir(
+            "
+        enum E { a; b }
+        struct S { let v: Number }
+        extend S {
+            init(e: E) {
+                match e {
+                    .a => { self.v = 1 },
+                    .b => { self.v = 2 }
+                }
+            }
+        }
+        let s = S(e: E.a)
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_init_assigns_let_attribute_after_match() {
+        let mut ir = lowered_ir(
+            "
+        enum E { a; b }
+        struct S { let v: Number }
+        extend S {
+            init(e: E) {
+                match e {
+                    _ => { self.v = 1 }
+                }
+                self.v = 2
+            }
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::InitError(
+                _,
+                InitError::AssignedTwice(_, _)
+            ))
+        );
+    }
+}
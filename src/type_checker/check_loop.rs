@@ -0,0 +1,240 @@
+use crate::ir::{
+    context::IrCtx,
+    ir_walker::ScopeValue,
+    node::{
+        identifier::IdentKey,
+        loops::{Loop, LoopJump},
+        NodeRef,
+    },
+};
+
+use super::TypeCheckerError;
+
+/// The loops a `break` or `continue` inside of the scope can jump out of, innermost last
+///
+/// Functions start without any loops, the arms of matches are checked to be generated as
+/// a `switch` by the `break` or `continue` leaving them
+pub fn enclosing_loops_of_scope<'a>(value: &ScopeValue<'a>, parent: &[Loop<'a>]) -> Vec<Loop<'a>> {
+    match value {
+        ScopeValue::WhileLoop(wl) => [parent, &[Loop::While(*wl)]].concat(),
+        ScopeValue::ForLoop(fl) => [parent, &[Loop::For(*fl)]].concat(),
+        ScopeValue::Func(_)
+        | ScopeValue::Struct(_)
+        | ScopeValue::Enum(_)
+        | ScopeValue::Trait(_)
+        | ScopeValue::Extension(_) => Vec::new(),
+        ScopeValue::StructInit(_)
+        | ScopeValue::IfBranch(_, _)
+        | ScopeValue::MatchArm(_)
+        | ScopeValue::LetElse(_)
+        | ScopeValue::DoCatch(_, _)
+        | ScopeValue::RightOperand(_) => parent.to_vec(),
+    }
+}
+
+/// Checks that the `break` or `continue` is inside of a loop, and that a loop with
+/// its label encloses it, returning the index of the loop it jumps out of
+pub fn check_loop_jump<'a>(
+    ctx: &IrCtx<'a>,
+    jump: NodeRef<'a, LoopJump<'a>>,
+    enclosing_loops: &[Loop<'a>],
+) -> Result<usize, TypeCheckerError<'a>> {
+    if enclosing_loops.is_empty() {
+        return Err(TypeCheckerError::LoopJumpOutsideLoop(jump));
+    }
+
+    match ctx[jump].label {
+        Some(label) => enclosing_loops
+            .iter()
+            .rposition(|lp| {
+                lp.label(ctx)
+                    .is_some_and(|lp_label| IdentKey::idents_eq(ctx, lp_label, label))
+            })
+            .ok_or(TypeCheckerError::UnknownLoopLabel(jump)),
+        None => Ok(enclosing_loops.len() - 1),
+    }
+}
+
+/// Checks that a loop of the scope is not labelled like one of the loops enclosing it,
+/// as javascript does not allow a label to be declared again inside of its statement
+pub fn check_loop_label<'a>(
+    ctx: &IrCtx<'a>,
+    value: &ScopeValue<'a>,
+    enclosing_loops: &[Loop<'a>],
+) -> Result<(), TypeCheckerError<'a>> {
+    let lp = match value {
+        ScopeValue::WhileLoop(wl) => Loop::While(*wl),
+        ScopeValue::ForLoop(fl) => Loop::For(*fl),
+        _ => return Ok(()),
+    };
+
+    let Some(label) = lp.label(ctx) else {
+        return Ok(());
+    };
+
+    match enclosing_loops.iter().find(|outer| {
+        outer
+            .label(ctx)
+            .is_some_and(|outer_label| IdentKey::idents_eq(ctx, outer_label, label))
+    }) {
+        Some(outer) => Err(TypeCheckerError::DuplicateLoopLabel(lp, *outer)),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::assert_matches;
+
+    use crate::{
+        ir::test_utils::utils::{lowered_ir, type_check},
+        type_checker::TypeCheckerError,
+    };
+
+    #[test]
+    fn test_jump_inside_loop() {
+        let mut ir = lowered_ir(
+            "
+        func nextIndex(i: Number) -> Number? {
+            if i < 10 { return i + 1 }
+            return nil
+        }
+        var i = 0
+        while true {
+            i = i + 1
+            if i < 3 { continue }
+            if let i = nextIndex(i) {} else { break }
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_jump_outside_loop() {
+        let mut ir = lowered_ir("func f() { break }").unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::LoopJumpOutsideLoop(_))
+        );
+    }
+
+    #[test]
+    fn test_jump_in_nested_lambda() {
+        let mut ir = lowered_ir("while true { let f = () { continue } }").unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::LoopJumpOutsideLoop(_))
+        );
+    }
+
+    #[test]
+    fn test_jump_in_match_arm() {
+        let mut ir = lowered_ir(
+            "
+        enum Light { red; green }
+        let light: Light = .red
+        while true {
+            match light {
+                .red => { break }
+                .green => {}
+            }
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_jump_in_match_expr_arm() {
+        let mut ir = lowered_ir(
+            "
+        enum Light { red; green }
+        let light: Light = .red
+        while true {
+            let wait = 1 + match light {
+                .red => { continue }
+                .green => 0
+            }
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::LoopJumpInMatchExpr(_, _))
+        );
+    }
+
+    #[test]
+    fn test_labelled_jump() {
+        let mut ir = lowered_ir(
+            "
+        rows: for row in [[1, 2], [3, 4]] {
+            for cell in row {
+                if cell == 3 { break rows }
+            }
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_unknown_label() {
+        let mut ir = lowered_ir(
+            "
+        rows: for row in [[1, 2], [3, 4]] {
+            for cell in row { continue cells }
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::UnknownLoopLabel(_))
+        );
+    }
+
+    #[test]
+    fn test_nested_loops_same_label() {
+        let mut ir = lowered_ir(
+            "
+        outer: while true {
+            for i in 0..<3 {
+                outer: while true { break outer }
+            }
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::DuplicateLoopLabel(_, _))
+        );
+    }
+
+    #[test]
+    fn test_sibling_loops_same_label() {
+        let mut ir = lowered_ir(
+            "
+        outer: while true { break outer }
+        outer: for i in 0..<3 { continue outer }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+}
@@ -0,0 +1,502 @@
+use crate::{
+    ir::{
+        context::IrCtx,
+        node::{
+            control_flow::IfStmt,
+            enumeration::{Enum, EnumValue},
+            expression::Expr,
+            pattern_match::{EnumPattern, LetElse, Match, MatchArm},
+            statement::Stmt,
+            type_signature::{TypeEvalError, TypeSignatureValue, Typed},
+            NodeRef,
+        },
+    },
+    symbols::{
+        symbol_resolver::SymbolResolutionError,
+        symbol_table::symbol_table_zipper::SymbolTableZipper,
+    },
+};
+
+use super::TypeCheckerError;
+
+/// The match that is the whole value of the statement, which is generated as a `switch`
+/// statement instead of a closure
+pub fn direct_match<'a>(
+    ctx: &IrCtx<'a>,
+    stmt: NodeRef<'a, Stmt<'a>>,
+) -> Option<NodeRef<'a, Match<'a>>> {
+    let value = match ctx[stmt] {
+        Stmt::Expression(expr) | Stmt::Return(expr) => expr,
+        Stmt::VariableDecl(var_decl) => ctx[var_decl].value,
+        _ => return None,
+    };
+
+    match ctx[value] {
+        Expr::Match(mtch) => Some(mtch),
+        _ => None,
+    }
+}
+
+pub fn check_match<'a>(
+    ctx: &mut IrCtx<'a>,
+    symbols: &mut SymbolTableZipper<'a>,
+    mtch: NodeRef<'a, Match<'a>>,
+) -> Result<(), TypeCheckerError<'a>> {
+    let arms = ctx[mtch].arms.clone();
+
+    let Some(enm) = scrutinee_enum(ctx, symbols, ctx[mtch].scrutinee)? else {
+        return Ok(());
+    };
+
+    // enum values matched so far, together with the arm matching them
+    let mut matched: Vec<(NodeRef<'a, EnumValue<'a>>, NodeRef<'a, MatchArm<'a>>)> = Vec::new();
+    let mut wildcard = None;
+
+    for arm in arms {
+        if let Some(wildcard) = wildcard {
+            return Err(TypeCheckerError::UnreachableMatchArm(arm, Some(wildcard)));
+        }
+
+        let Some(pattern) = ctx[arm].pattern else {
+            if matched.len() == ctx[enm].values.len() {
+                return Err(TypeCheckerError::UnreachableMatchArm(arm, None));
+            }
+
+            wildcard = Some(arm);
+            continue;
+        };
+
+        let enm_val = check_enum_pattern(ctx, enm, pattern)?;
+
+        // bindings always match, so a value can only be matched once
+        if let Some((_, prev_arm)) = matched.iter().find(|(val, _)| *val == enm_val) {
+            return Err(TypeCheckerError::UnreachableMatchArm(arm, Some(*prev_arm)));
+        }
+
+        matched.push((enm_val, arm));
+    }
+
+    if wildcard.is_none() {
+        let missing = ctx[enm]
+            .values
+            .iter()
+            .filter(|val| !matched.iter().any(|(matched_val, _)| matched_val == *val))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        if !missing.is_empty() {
+            return Err(TypeCheckerError::NonExhaustiveMatch(mtch, missing));
+        }
+    }
+
+    Ok(())
+}
+
+pub fn check_if_let<'a>(
+    ctx: &mut IrCtx<'a>,
+    symbols: &mut SymbolTableZipper<'a>,
+    ifb: NodeRef<'a, IfStmt<'a>>,
+) -> Result<(), TypeCheckerError<'a>> {
+    let Some(pattern) = ctx[ifb].pattern else {
+        return Ok(());
+    };
+
+    if let Some(enm) = scrutinee_enum(ctx, symbols, ctx[ifb].condition)? {
+        check_enum_pattern(ctx, enm, pattern)?;
+    }
+
+    Ok(())
+}
+
+pub fn check_let_else<'a>(
+    ctx: &mut IrCtx<'a>,
+    symbols: &mut SymbolTableZipper<'a>,
+    let_else: NodeRef<'a, LetElse<'a>>,
+) -> Result<(), TypeCheckerError<'a>> {
+    if let Some(enm) = scrutinee_enum(ctx, symbols, ctx[let_else].value)? {
+        check_enum_pattern(ctx, enm, ctx[let_else].pattern)?;
+    }
+
+    // the bindings would be uninitialized if execution continued after the else body
+    if !ctx[let_else].else_body.diverges(ctx) {
+        return Err(TypeCheckerError::LetElseNotDiverging(let_else));
+    }
+
+    Ok(())
+}
+
+/// The enum being matched on, or `None` if its type could not be inferred
+fn scrutinee_enum<'a>(
+    ctx: &mut IrCtx<'a>,
+    symbols: &mut SymbolTableZipper<'a>,
+    scrutinee: NodeRef<'a, Expr<'a>>,
+) -> Result<Option<NodeRef<'a, Enum<'a>>>, TypeCheckerError<'a>> {
+    let scrutinee_type = scrutinee
+        .eval_type(symbols, ctx)
+        .map_err(TypeCheckerError::TypeEval)?;
+
+    let enm_name = match &ctx[&scrutinee_type.split_instance(ctx).0] {
+        TypeSignatureValue::Enum { name } => *name,
+        // reported as an undeterminable type
+        TypeSignatureValue::TypeVariable(_) => return Ok(None),
+        _ => return Err(TypeCheckerError::MatchNonEnum(scrutinee, scrutinee_type)),
+    };
+
+    let enm = symbols
+        .lookup(ctx, enm_name)
+        .ok_or(TypeCheckerError::TypeEval(TypeEvalError::UnknownIdent(
+            enm_name,
+        )))?
+        .unwrap_enum(ctx);
+
+    Ok(Some(enm))
+}
+
+fn check_enum_pattern<'a>(
+    ctx: &IrCtx<'a>,
+    enm: NodeRef<'a, Enum<'a>>,
+    pattern: NodeRef<'a, EnumPattern<'a>>,
+) -> Result<NodeRef<'a, EnumValue<'a>>, TypeCheckerError<'a>> {
+    let enm_val = enm
+        .lookup_value(ctx, *ctx[pattern].enum_value)
+        .ok_or(TypeCheckerError::SymbolResolutionError(
+            SymbolResolutionError::UnknownEnumValue {
+                enm,
+                enum_value: *ctx[pattern].enum_value,
+            },
+        ))?
+        .1;
+
+    if ctx[enm_val].items.len() != ctx[pattern].bindings.len() {
+        return Err(TypeCheckerError::EnumPatternArgCountMismatch(
+            pattern, enm_val,
+        ));
+    }
+
+    Ok(enm_val)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::assert_matches;
+
+    use crate::ir::test_utils::utils::{lowered_ir, type_check};
+
+    use super::*;
+
+    #[test]
+    fn test_match_binding_type() {
+        let mut ir = lowered_ir(
+            "
+        enum IpAddress { v4(Number, Number, Number, Number); v6(String) }
+        let ip: IpAddress = .v6(\"::1\")
+        let description: String = match ip {
+            .v4(a, _, _, _) => { let first: Number = a; \"v4\" }
+            .v6(addr) => addr
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_match_conflicting_arm_types() {
+        let mut ir = lowered_ir(
+            "
+        enum Foo { a(Number); b }
+        let foo: Foo = .b
+        let x = match foo {
+            .a(num) => num,
+            .b => \"none\"
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ConflictingTypes(_, _))
+        );
+    }
+
+    #[test]
+    fn test_match_function_return() {
+        let mut ir = lowered_ir(
+            "
+        enum Foo { a(Number); b }
+        func unwrap(foo: Foo) -> Number {
+            match foo {
+                .a(num) => { return num }
+                _ => {}
+            }
+            return 0
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_match_pattern_arg_count_mismatch() {
+        let mut ir = lowered_ir(
+            "
+        enum Foo { a(Number, Number) }
+        let foo: Foo = .a(1, 2)
+        let x = match foo {
+            .a(num) => num,
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::EnumPatternArgCountMismatch(_, _))
+        );
+    }
+
+    #[test]
+    fn test_match_non_enum() {
+        let mut ir = lowered_ir(
+            "
+        let foo = 42
+        let x = match foo {
+            .a(num) => num,
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::MatchNonEnum(_, _))
+        );
+
+        let mut ir = lowered_ir("let n = 1; let x = match n { _ => 1 }").unwrap();
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::MatchNonEnum(_, _))
+        );
+    }
+
+    #[test]
+    fn test_match_missing_values() {
+        let mut ir = lowered_ir(
+            "
+        enum Foo { a; b(Number); c }
+        let foo: Foo = .a
+        let x = match foo {
+            .b(num) => num
+        }
+        ",
+        )
+        .unwrap();
+
+        let (_, res) = type_check(&mut ir);
+        assert_matches!(
+            res,
+            Err(TypeCheckerError::NonExhaustiveMatch(_, missing)) if missing.len() == 2
+        );
+    }
+
+    #[test]
+    fn test_match_wildcard_exhaustive() {
+        let mut ir = lowered_ir(
+            "
+        enum Foo { a; b(Number); c }
+        let foo: Foo = .a
+        let x = match foo {
+            .b(num) => num,
+            _ => 0
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_match_duplicate_value_unreachable() {
+        let mut ir = lowered_ir(
+            "
+        enum Foo { a(Number); b }
+        let foo: Foo = .b
+        let x = match foo {
+            .a(num) => num,
+            .b => 0,
+            .a(_) => 1
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::UnreachableMatchArm(_, Some(_)))
+        );
+    }
+
+    #[test]
+    fn test_match_arm_after_wildcard_unreachable() {
+        let mut ir = lowered_ir(
+            "
+        enum Foo { a(Number); b }
+        let foo: Foo = .b
+        let x = match foo {
+            _ => 0,
+            .b => 1
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::UnreachableMatchArm(_, Some(_)))
+        );
+    }
+
+    #[test]
+    fn test_match_wildcard_after_all_values_unreachable() {
+        let mut ir = lowered_ir(
+            "
+        enum Foo { a(Number); b }
+        let foo: Foo = .b
+        let x = match foo {
+            .a(num) => num,
+            .b => 0,
+            _ => 1
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::UnreachableMatchArm(_, None))
+        );
+    }
+
+    #[test]
+    fn test_if_let_binding_type() {
+        let mut ir = lowered_ir(
+            "
+        enum IpAddress { v4(Number, Number, Number, Number); v6(String) }
+        func sum(ip: IpAddress) -> Number {
+            if let .v4(a, b, c, d) = ip {
+                return a + b + c + d
+            }
+            return 0
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_if_let_non_enum() {
+        let mut ir = lowered_ir(
+            "
+        let foo = true
+        if let .a(num) = foo { let x = num }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::MatchNonEnum(_, _))
+        );
+    }
+
+    #[test]
+    fn test_let_else_binding_type() {
+        let mut ir = lowered_ir(
+            "
+        enum IpAddress { v4(Number, Number, Number, Number); v6(String) }
+        func describe(ipValue: IpAddress) -> String {
+            let .v4(a, b, c, d) = ipValue else { return \"error\" }
+            let sum: Number = a + b + c + d
+            return \"ok\"
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_let_else_not_diverging() {
+        let mut ir = lowered_ir(
+            "
+        enum Foo { a(Number); b }
+        func f(foo: Foo) -> Number {
+            let .a(num) = foo else { let x = 1 }
+            return num
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::LetElseNotDiverging(_))
+        );
+    }
+
+    #[test]
+    fn test_return_in_match_arm() {
+        let mut ir = lowered_ir(
+            "
+        enum E { a; b }
+        func f(e: E) -> Number {
+            let x = match e { .a => { return 5 }, .b => { let y = 1; y } }
+            match e { .a => { return 2 }, .b => {} }
+            return match e { .a => { return 3 }, .b => x }
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_return_in_match_expr_arm() {
+        let mut ir = lowered_ir(
+            "
+        enum E { a; b }
+        func f(e: E) -> Number {
+            return 1 + match e { .a => { return 5 }, .b => 1 }
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ReturnInMatchExpr(_, _))
+        );
+
+        // a return of a nested function does not leave the match
+        let mut ir = lowered_ir(
+            "
+        enum E { a; b }
+        func f(e: E) -> Number {
+            return 1 + match e { .a => { let g = () { return 2 }; 5 }, .b => 1 }
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+}
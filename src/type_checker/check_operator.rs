@@ -0,0 +1,363 @@
+use crate::{
+    ir::{
+        context::IrCtx,
+        node::{
+            expression::Expr,
+            function::Function,
+            operator::{BinaryOperation, BinaryOperator, OverloadedFunc},
+            type_signature::{BuiltinType, TypeSignature, TypeSignatureValue, Typed},
+            NodeRef,
+        },
+    },
+    symbols::symbol_table::symbol_table_zipper::SymbolTableZipper,
+};
+
+use super::TypeCheckerError;
+
+pub fn check_binary_operation<'a>(
+    ctx: &mut IrCtx<'a>,
+    symbols: &mut SymbolTableZipper<'a>,
+    bin_op: NodeRef<'a, BinaryOperation<'a>>,
+    func: Option<NodeRef<'a, Function<'a>>>,
+) -> Result<(), TypeCheckerError<'a>> {
+    // inside of the default operators of a trait the conforming type is the one
+    // they are called through, elsewhere it is not known which type declares the operator
+    match ctx[bin_op].overload.map(|overload| overload.func) {
+        Some(OverloadedFunc::Trait(tr, _)) => {
+            let in_default_operator = ctx[tr]
+                .operators
+                .iter()
+                .any(|op| func.is_some() && ctx[*op].default_impl == func);
+
+            return if in_default_operator {
+                Ok(())
+            } else {
+                Err(TypeCheckerError::TraitOperatorCall(bin_op, tr))
+            };
+        }
+        Some(OverloadedFunc::Static(_, _) | OverloadedFunc::TypeParam(_, _)) => return Ok(()),
+        None => {}
+    }
+
+    let lhs = ctx[bin_op].lhs;
+    let rhs = ctx[bin_op].rhs;
+
+    match ctx[bin_op].operator {
+        // the operands of `+` and the ordering comparisons
+        // can be either numbers or strings, both sides having the same type
+        BinaryOperator::Add
+        | BinaryOperator::Lt
+        | BinaryOperator::LtEq
+        | BinaryOperator::Gt
+        | BinaryOperator::GtEq => {
+            let lhs_type = lhs
+                .eval_type(symbols, ctx)
+                .map_err(TypeCheckerError::TypeEval)?;
+
+            match &ctx[&lhs_type] {
+                TypeSignatureValue::Builtin(BuiltinType::Number | BuiltinType::String) => Ok(()),
+                TypeSignatureValue::TypeVariable(_) => Ok(()),
+                _ => Err(TypeCheckerError::InvalidOperandType(bin_op, lhs_type)),
+            }
+        }
+        // only an optional value can be absent and fall back to the right operand
+        BinaryOperator::Coalesce => {
+            let lhs_type = lhs
+                .eval_type(symbols, ctx)
+                .map_err(TypeCheckerError::TypeEval)?;
+
+            match &ctx[&lhs_type] {
+                TypeSignatureValue::Optional(_) | TypeSignatureValue::TypeVariable(_) => Ok(()),
+                _ => Err(TypeCheckerError::InvalidOperandType(bin_op, lhs_type)),
+            }
+        }
+        // any optional can be compared to `nil`, as an absent value is `undefined`
+        BinaryOperator::Eq | BinaryOperator::NotEq
+            if matches!(ctx[lhs], Expr::NilLiteral(_, _))
+                || matches!(ctx[rhs], Expr::NilLiteral(_, _)) =>
+        {
+            Ok(())
+        }
+        // `===` compares anything but primitives by reference, and the values of
+        // enums and tuples are created anew as arrays, so only primitives are compared
+        BinaryOperator::Eq | BinaryOperator::NotEq => {
+            let lhs_type = lhs
+                .eval_type(symbols, ctx)
+                .map_err(TypeCheckerError::TypeEval)?;
+
+            if is_primitive(ctx, &lhs_type) {
+                Ok(())
+            } else {
+                Err(TypeCheckerError::InvalidOperandType(bin_op, lhs_type))
+            }
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Whether the values of the type are compared by value with `===`
+fn is_primitive<'a>(ctx: &IrCtx<'a>, type_sig: &TypeSignature<'a>) -> bool {
+    match &ctx[type_sig] {
+        TypeSignatureValue::Builtin(
+            BuiltinType::Number | BuiltinType::String | BuiltinType::Boolean,
+        ) => true,
+        TypeSignatureValue::Optional(value) => is_primitive(ctx, value),
+        TypeSignatureValue::TypeVariable(_) => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::assert_matches;
+
+    use crate::{
+        ir::test_utils::utils::{lowered_ir, type_check},
+        type_checker::TypeCheckerError,
+    };
+
+    #[test]
+    fn test_add_strings() {
+        let mut ir = lowered_ir("let greeting: String = \"Hello, \" + \"World\"").unwrap();
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_add_booleans() {
+        let mut ir = lowered_ir("let x = true + false").unwrap();
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::InvalidOperandType(_, _))
+        );
+    }
+
+    #[test]
+    fn test_range_bounds() {
+        let mut ir = lowered_ir("let range: Range = 1..<10").unwrap();
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+
+        let mut ir = lowered_ir("let range = 1...\"10\"").unwrap();
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ConflictingTypes(_, _))
+        );
+    }
+
+    #[test]
+    fn test_compare_structs() {
+        let mut ir = lowered_ir(
+            "struct Foo {}
+            let a = Foo {}
+            let x = a < a",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::InvalidOperandType(_, _))
+        );
+    }
+
+    #[test]
+    fn test_equality_structs() {
+        let mut ir = lowered_ir(
+            "struct Foo {}
+            let a = Foo {}
+            let x: Boolean = a == a",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::InvalidOperandType(_, _))
+        );
+    }
+
+    #[test]
+    fn test_equality_enums_and_tuples() {
+        let mut ir = lowered_ir(
+            "enum L { low; high }
+            let x: L = .low
+            let same = x == .low",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::InvalidOperandType(_, _))
+        );
+
+        let mut ir = lowered_ir("let same = (1, 2) != (1, 2)").unwrap();
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::InvalidOperandType(_, _))
+        );
+    }
+
+    #[test]
+    fn test_equality_value_and_optional() {
+        let mut ir = lowered_ir(
+            "let a: Number? = 1
+            let same: Boolean = a == 1 && 2 != a
+            let name: String? = nil
+            let named = \"Bob\" == name",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+
+        let mut ir = lowered_ir("let a: Number? = 1; let same = a == \"1\"").unwrap();
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ConflictingTypes(_, _))
+        );
+    }
+
+    #[test]
+    fn test_coalesce_non_optional() {
+        let mut ir = lowered_ir("let a: Number = 1; let b = a ?? 2").unwrap();
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::InvalidOperandType(_, _))
+        );
+    }
+
+    #[test]
+    fn test_equality_primitives() {
+        let mut ir = lowered_ir(
+            "let a: Number? = 1
+            let b: Number? = 2
+            let same = a == b && \"a\" != \"b\" && true == false
+            enum L { low; high }
+            let c: L? = nil
+            let absent = c == nil",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_overloaded_operators() {
+        let mut ir = lowered_ir(
+            "struct Point { let x: Number; let y: Number }
+            extend Point {
+                static func + (lhs: Point, rhs: Point) -> Point {
+                    return Point { x: lhs.x + rhs.x, y: lhs.y + rhs.y }
+                }
+                static func < (lhs: Point, rhs: Number) -> Boolean { return lhs.x < rhs }
+            }
+            let a = Point { x: 1, y: 2 }
+            let b: Point = a + a
+            let x: Boolean = b < 3",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_overloaded_operator_rhs() {
+        let mut ir = lowered_ir(
+            "struct Point { let x: Number }
+            extend Point {
+                static func + (lhs: Point, rhs: Point) -> Point { return Point { x: lhs.x + rhs.x } }
+            }
+            let a = Point { x: 1 } + 2",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ConflictingTypes(_, _))
+        );
+    }
+
+    #[test]
+    fn test_operator_not_overloaded() {
+        for operator in ["-", "*", "/", "%", "..<", "&&"] {
+            let code = format!(
+                "struct Point {{ let x: Number }}
+                extend Point {{
+                    static func + (lhs: Point, rhs: Point) -> Point {{ return Point {{ x: lhs.x + rhs.x }} }}
+                }}
+                let a = Point {{ x: 1 }}
+                let b = a {operator} a"
+            );
+            let mut ir = lowered_ir(&code).unwrap();
+
+            assert_matches!(
+                type_check(&mut ir).1,
+                Err(TypeCheckerError::InvalidOperandType(_, _)),
+                "{operator}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_trait_default_operator() {
+        let mut ir = lowered_ir(
+            "trait Equatable {
+                static func == (lhs: Self, rhs: Self) -> Boolean
+                static func != (lhs: Self, rhs: Self) -> Boolean { return !(lhs == rhs) }
+            }
+            enum Level { low; high }
+            extend Level: Equatable {
+                static func == (lhs: Level, rhs: Level) -> Boolean { return true }
+            }
+            let x: Boolean = Level.low != Level.high",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_trait_operator_on_type_param() {
+        let mut ir = lowered_ir(
+            "trait Equatable { static func == (lhs: Self, rhs: Self) -> Boolean }
+            struct Point { let x: Number }
+            extend Point: Equatable {
+                static func == (lhs: Self, rhs: Self) -> Boolean { return lhs.x == rhs.x }
+            }
+            func same<T: Equatable>(a: T, b: T) -> Boolean { return a == b }
+            let x: Boolean = same(Point { x: 1 }, Point { x: 2 })",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_trait_operator_on_trait_value() {
+        let mut ir = lowered_ir(
+            "trait Equatable { static func == (lhs: Self, rhs: Self) -> Boolean }
+            func same(a: Equatable, b: Equatable) -> Boolean { return a == b }",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::TraitOperatorCall(_, _))
+        );
+    }
+
+    #[test]
+    fn test_operator_bound_enum_arg() {
+        let mut ir = lowered_ir(
+            "trait Equatable { static func == (lhs: Self, rhs: Self) -> Boolean }
+            enum Level { low; high }
+            extend Level: Equatable {
+                static func == (lhs: Level, rhs: Level) -> Boolean { return true }
+            }
+            func same<T: Equatable>(a: T, b: T) -> Boolean { return a == b }
+            let x: Boolean = same(Level.low, Level.high)",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::OperatorBoundNonStruct(_, _))
+        );
+    }
+}
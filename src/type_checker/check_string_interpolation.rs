@@ -0,0 +1,168 @@
+use crate::{
+    ir::{
+        context::IrCtx,
+        node::{
+            string_interpolation::StringInterpolation,
+            traits::Trait,
+            type_signature::{BuiltinType, TypeSignatureValue, Typed},
+            NodeRef,
+        },
+    },
+    symbols::symbol_table::{symbol_table_zipper::SymbolTableZipper, SymbolValueItem},
+};
+
+use super::{check_extension::conforms_to, TypeCheckerError};
+
+/// Checks that every interpolated value is a string, number or boolean,
+/// or conforms to the `StringExpressible` trait, which has to declare `func toString() -> String`
+pub fn check_string_interpolation<'a>(
+    ctx: &mut IrCtx<'a>,
+    symbols: &mut SymbolTableZipper<'a>,
+    interp: NodeRef<'a, StringInterpolation<'a>>,
+) -> Result<(), TypeCheckerError<'a>> {
+    let expressible_trait = symbols
+        .lookup(ctx, *ctx[interp].expressible_trait)
+        .and_then(|sym| match ctx[sym] {
+            SymbolValueItem::TraitDecl(tr) => Some(tr),
+            _ => None,
+        });
+
+    for expr in interp.exprs(ctx) {
+        let type_sig = expr
+            .eval_type(symbols, ctx)
+            .map_err(TypeCheckerError::TypeEval)?;
+
+        let is_expressible = match &ctx[&type_sig] {
+            TypeSignatureValue::Builtin(
+                BuiltinType::String | BuiltinType::Number | BuiltinType::Boolean,
+            ) => true,
+            _ => match expressible_trait {
+                Some(tr) if !declares_to_string(ctx, tr) => {
+                    return Err(TypeCheckerError::InvalidExpressibleTrait(tr, expr));
+                }
+                Some(tr) => conforms_to(ctx, symbols, &type_sig, tr),
+                None => false,
+            },
+        };
+
+        if !is_expressible {
+            return Err(TypeCheckerError::NonStringExpressible(expr, type_sig));
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether the trait declares the `toString` method javascript calls when converting
+/// a value to a string, which takes no arguments and returns a string
+fn declares_to_string<'a>(ctx: &IrCtx<'a>, tr: NodeRef<'a, Trait<'a>>) -> bool {
+    ctx[tr].attrs.iter().any(|attr| {
+        ctx[*attr].name.value(ctx) == Some("toString")
+            && ctx[*attr].args.is_empty()
+            && ctx[*attr].throws.is_none()
+            && matches!(
+                ctx[&attr.return_type(ctx)],
+                TypeSignatureValue::Builtin(BuiltinType::String)
+            )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::assert_matches;
+
+    use crate::{
+        ir::test_utils::utils::{lowered_ir, type_check},
+        type_checker::TypeCheckerError,
+    };
+
+    #[test]
+    fn test_interpolate_builtins() {
+        let mut ir = lowered_ir(
+            "
+        let name = \"World\"
+        let greeting: String = \"Hello \\(name), \\(1 + 2) \\(true)\"
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_interpolate_expressible() {
+        let mut ir = lowered_ir(
+            "
+        trait StringExpressible { func toString() -> String }
+        struct Point { let x: Number; let y: Number }
+        extend Point: StringExpressible {
+            func toString() -> String { return \"(\\(self.x), \\(self.y))\" }
+        }
+        let description = \"point \\(Point { x: 1, y: 2 })\"
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_interpolate_bounded_type_param() {
+        let mut ir = lowered_ir(
+            "
+        trait StringExpressible { func toString() -> String }
+        func describe<T: StringExpressible>(value: T) -> String { return \"value: \\(value)\" }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_interpolate_non_expressible() {
+        let mut ir = lowered_ir(
+            "
+        trait StringExpressible { func toString() -> String }
+        struct Point { let x: Number; let y: Number }
+        let description = \"point \\(Point { x: 1, y: 2 })\"
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::NonStringExpressible(_, _))
+        );
+    }
+
+    #[test]
+    fn test_interpolate_without_trait() {
+        let mut ir = lowered_ir("let description = \"tuple \\((1, 2))\"").unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::NonStringExpressible(_, _))
+        );
+    }
+
+    #[test]
+    fn test_interpolate_invalid_expressible_trait() {
+        let mut ir = lowered_ir(
+            "
+        trait StringExpressible { func describe() -> String }
+        struct Point { let x: Number; let y: Number }
+        extend Point: StringExpressible {
+            func describe() -> String { return \"point\" }
+        }
+        let description = \"point \\(Point { x: 1, y: 2 })\"
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::InvalidExpressibleTrait(_, _))
+        );
+    }
+}
@@ -28,6 +28,23 @@ pub fn check_struct_init<'a>(
     symbols: &mut SymbolTableZipper<'a>,
     st_init: NodeRef<'a, StructInit<'a>>,
 ) -> Result<(), TypeCheckerError<'a>> {
+    // a value given twice would overwrite the first one in the javascript object
+    let values = ctx[st_init].values.clone();
+    for (i, value) in values.iter().enumerate() {
+        let name = *ctx[*value].name;
+        if let Some(first) = values[..i]
+            .iter()
+            .find(|val| IdentKey::idents_eq(ctx, *ctx[**val].name, name))
+        {
+            return Err(TypeCheckerError::DuplicateField(*first, *value));
+        }
+    }
+
+    // the values of a record literal are checked against its type during inference
+    if st_init.is_record(ctx) {
+        return Ok(());
+    }
+
     let st_name = st_init.struct_name(ctx).ok_or_else(|| {
         TypeCheckerError::UndeterminableTypes(vec![UndeterminableType {
             span: st_init.get_span(ctx).unwrap(),
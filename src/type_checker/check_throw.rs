@@ -0,0 +1,357 @@
+use crate::{
+    ir::{
+        context::IrCtx,
+        ir_walker::ScopeValue,
+        node::{
+            error_handling::{DoCatch, DoCatchBody, Try},
+            expression::Expr,
+            function::{Function, FunctionCall},
+            type_signature::{TypeSignature, TypeSignatureValue},
+            NodeRef,
+        },
+    },
+    symbols::symbol_table::{symbol_table_zipper::SymbolTableZipper, SymbolValueItem},
+};
+
+use super::TypeCheckerError;
+
+/// Where the errors thrown inside of a scope are handled
+#[derive(Debug, Clone, Copy)]
+pub enum ThrowTarget<'a> {
+    Function(NodeRef<'a, Function<'a>>),
+    DoCatch(NodeRef<'a, DoCatch<'a>>),
+}
+
+impl<'a> ThrowTarget<'a> {
+    /// The throw target inside of the scope, given the target of the parent scope
+    pub fn of_scope(value: &ScopeValue<'a>, parent: Option<ThrowTarget<'a>>) -> Option<Self> {
+        match value {
+            ScopeValue::Func(func) => Some(ThrowTarget::Function(*func)),
+            ScopeValue::DoCatch(dc, DoCatchBody::DoBody) => Some(ThrowTarget::DoCatch(*dc)),
+            ScopeValue::Struct(_)
+            | ScopeValue::Enum(_)
+            | ScopeValue::Trait(_)
+            | ScopeValue::Extension(_) => None,
+            ScopeValue::StructInit(_)
+            | ScopeValue::IfBranch(_, _)
+            | ScopeValue::MatchArm(_)
+            | ScopeValue::LetElse(_)
+            | ScopeValue::DoCatch(_, DoCatchBody::CatchBody)
+            | ScopeValue::WhileLoop(_)
+            | ScopeValue::ForLoop(_)
+            | ScopeValue::RightOperand(_) => parent,
+        }
+    }
+
+    /// The type of the errors the target accepts, `None` if it does not accept any
+    pub fn thrown_type(&self, ctx: &IrCtx<'a>) -> Option<TypeSignature<'a>> {
+        match self {
+            ThrowTarget::Function(func) => ctx[*func].throws.clone(),
+            ThrowTarget::DoCatch(dc) => Some((*ctx[*dc].error_type).clone()),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ThrowError<'a> {
+    MissingTry(NodeRef<'a, FunctionCall<'a>>),
+    /// A `try` whose operand is not a call to a throwing function
+    TryNonThrowing(NodeRef<'a, Try<'a>>),
+    /// An error thrown outside of a do body or throwing function, together with the
+    /// enclosing function if any
+    Unhandled(NodeRef<'a, Expr<'a>>, Option<NodeRef<'a, Function<'a>>>),
+    NothingThrown(NodeRef<'a, DoCatch<'a>>),
+}
+
+pub fn check_call<'a>(
+    ctx: &mut IrCtx<'a>,
+    symbols: &mut SymbolTableZipper<'a>,
+    call: NodeRef<'a, FunctionCall<'a>>,
+) -> Result<(), TypeCheckerError<'a>> {
+    let callee_type = call
+        .callee_type(symbols, ctx)
+        .map_err(TypeCheckerError::TypeEval)?;
+
+    if callee_type.thrown_type(ctx).is_some() && !ctx[call].tried {
+        return Err(TypeCheckerError::ThrowError(ThrowError::MissingTry(call)));
+    }
+
+    Ok(())
+}
+
+pub fn check_try<'a>(
+    ctx: &mut IrCtx<'a>,
+    symbols: &mut SymbolTableZipper<'a>,
+    expr: NodeRef<'a, Expr<'a>>,
+    tr: NodeRef<'a, Try<'a>>,
+    target: Option<ThrowTarget<'a>>,
+    func: Option<NodeRef<'a, Function<'a>>>,
+) -> Result<(), TypeCheckerError<'a>> {
+    let throws = match tr.call(ctx) {
+        Some(call) => call
+            .callee_type(symbols, ctx)
+            .map_err(TypeCheckerError::TypeEval)?
+            .thrown_type(ctx)
+            .is_some(),
+        None => false,
+    };
+
+    if !throws {
+        return Err(TypeCheckerError::ThrowError(ThrowError::TryNonThrowing(tr)));
+    }
+
+    check_handled(ctx, expr, target, func)
+}
+
+/// Checks that the errors thrown by the expression are handled by the enclosing scope
+pub fn check_handled<'a>(
+    ctx: &IrCtx<'a>,
+    expr: NodeRef<'a, Expr<'a>>,
+    target: Option<ThrowTarget<'a>>,
+    func: Option<NodeRef<'a, Function<'a>>>,
+) -> Result<(), TypeCheckerError<'a>> {
+    match target.and_then(|target| target.thrown_type(ctx)) {
+        Some(_) => Ok(()),
+        None => Err(TypeCheckerError::ThrowError(ThrowError::Unhandled(
+            expr, func,
+        ))),
+    }
+}
+
+/// Checks that an error can be thrown inside of the do body, either by a typed throw or by
+/// Javascript that is not type checked, in which case the caught error may be of any type
+pub fn check_do_catch<'a>(
+    ctx: &IrCtx<'a>,
+    dc: NodeRef<'a, DoCatch<'a>>,
+    throws_untyped: bool,
+) -> Result<(), TypeCheckerError<'a>> {
+    match ctx[&*ctx[dc].error_type] {
+        TypeSignatureValue::TypeVariable(_) if !throws_untyped => {
+            Err(TypeCheckerError::ThrowError(ThrowError::NothingThrown(dc)))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Whether the expression runs Javascript that is not type checked, an escape block or a call
+/// to an external function, which may throw exceptions that are not declared
+pub fn throws_untyped<'a>(
+    ctx: &IrCtx<'a>,
+    symbols: &SymbolTableZipper<'a>,
+    expr: NodeRef<'a, Expr<'a>>,
+) -> bool {
+    match ctx[expr] {
+        Expr::EscapeBlock(_) => true,
+        Expr::FunctionCall(call) => is_external(ctx, symbols, ctx[call].func),
+        _ => false,
+    }
+}
+
+fn is_external<'a>(
+    ctx: &IrCtx<'a>,
+    symbols: &SymbolTableZipper<'a>,
+    expr: NodeRef<'a, Expr<'a>>,
+) -> bool {
+    match &ctx[expr] {
+        Expr::Identifier(ident, _) => matches!(
+            symbols.lookup(ctx, **ident).map(|sym| ctx[sym]),
+            Some(SymbolValueItem::ExternalObject(_))
+        ),
+        Expr::RecordAccess(rec_acc) => is_external(ctx, symbols, ctx[*rec_acc].record),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::assert_matches;
+
+    use crate::{
+        ir::test_utils::utils::{lowered_ir, type_check},
+        type_checker::TypeCheckerError,
+    };
+
+    use super::ThrowError;
+
+    #[test]
+    fn test_try_propagates() {
+        let mut ir = lowered_ir(
+            "
+        func parse(text: String) throws(String) -> Number { throw \"invalid\" }
+        func double(text: String) throws(String) -> Number { return try parse(text) * 2 }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_do_catch() {
+        let mut ir = lowered_ir(
+            "
+        func parse(text: String) throws(String) -> Number { throw \"invalid\" }
+        func parseOrZero(text: String) -> Number {
+            do {
+                return try parse(text)
+            } catch e {
+                let message: String = e
+                return 0
+            }
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_throwing_lambda() {
+        let mut ir = lowered_ir(
+            "
+        let parse: (String) throws(Number) -> Number = (text) throws(Number) { throw 1 }
+        do {
+            let n = try parse(\"1\")
+        } catch code {
+            let c: Number = code
+        }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_try_only_throwing() {
+        let mut ir = lowered_ir(
+            "
+        func fail() throws(String) { throw \"failed\" }
+        async func failLater() throws(String) { throw \"failed\" }
+        func retry() throws(String) { let x = try fail() }
+        async func retryLater() throws(String) { let x = try await failLater() }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+
+        let mut ir = lowered_ir(
+            "
+        func fail() throws(String) { throw \"failed\" }
+        let f: () throws(String) -> Number = fail
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ConflictingTypes(_, _))
+        );
+    }
+
+    #[test]
+    fn test_call_missing_try() {
+        let mut ir = lowered_ir(
+            "
+        func parse(text: String) throws(String) -> Number { throw \"invalid\" }
+        func double(text: String) throws(String) -> Number { return parse(text) * 2 }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ThrowError(ThrowError::MissingTry(_)))
+        );
+    }
+
+    #[test]
+    fn test_try_non_throwing() {
+        let mut ir = lowered_ir(
+            "
+        func parse(text: String) -> Number { return 1 }
+        func double(text: String) throws(String) -> Number { return try parse(text) * 2 }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ThrowError(ThrowError::TryNonThrowing(_)))
+        );
+    }
+
+    #[test]
+    fn test_unhandled_throw() {
+        let mut ir = lowered_ir("func fail() { throw \"failed\" }").unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ThrowError(ThrowError::Unhandled(
+                _,
+                Some(_)
+            )))
+        );
+    }
+
+    #[test]
+    fn test_unhandled_try() {
+        let mut ir = lowered_ir(
+            "
+        func parse(text: String) throws(String) -> Number { throw \"invalid\" }
+        let n = try parse(\"1\")
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ThrowError(ThrowError::Unhandled(_, None)))
+        );
+    }
+
+    #[test]
+    fn test_thrown_type_mismatch() {
+        let mut ir = lowered_ir("func fail() throws(String) { throw 1 }").unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ConflictingTypes(_, _))
+        );
+    }
+
+    #[test]
+    fn test_do_nothing_thrown() {
+        let mut ir = lowered_ir("do { let a = 1 } catch e { let b = 2 }").unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ThrowError(ThrowError::NothingThrown(_)))
+        );
+    }
+
+    #[test]
+    fn test_do_catch_untyped() {
+        for code in [
+            "do { @{ foo() } } catch e { let b = 2 }",
+            "external parse: (String) -> Number
+            func f() -> Number { do { return parse(\"x\") } catch e { let m: String = e; return 0 } }",
+            "external JSON: { parse: (String) -> Number }
+            do { let n = JSON.parse(\"x\") } catch e { let b = 2 }",
+        ] {
+            let mut ir = lowered_ir(code).unwrap();
+            assert_matches!(type_check(&mut ir).1, Ok(_), "{code}");
+        }
+
+        let mut ir =
+            lowered_ir("do { let f = () -> Number { return @{ 1 } } } catch e { let b = 2 }")
+                .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ThrowError(ThrowError::NothingThrown(_)))
+        );
+    }
+}
@@ -11,15 +11,39 @@ pub fn can_coerce_to<'a>(
     let self_t = &ctx[type_sig];
     let other_t = &ctx[other];
 
-    if let (TypeSignatureValue::Tuple(selves), TypeSignatureValue::Tuple(others)) =
-        (self_t, other_t)
-    {
-        selves
+    match (self_t, other_t) {
+        (TypeSignatureValue::Tuple(selves), TypeSignatureValue::Tuple(others)) => selves
             .iter()
             .zip(others.iter())
-            .all(|(slf, other)| can_coerce_to(slf, other, ctx))
-    } else {
-        type_sig == other
+            .all(|(slf, other)| can_coerce_to(slf, other, ctx)),
+        (TypeSignatureValue::Optional(slf), TypeSignatureValue::Optional(other)) => {
+            can_coerce_to(slf, other, ctx)
+        }
+        // a value can be used wherever an optional value is expected, but not the other way around
+        (_, TypeSignatureValue::Optional(other)) => can_coerce_to(type_sig, other, ctx),
+        // a record can be used wherever a record with a subset of its fields is expected
+        (TypeSignatureValue::Record(selves), TypeSignatureValue::Record(others)) => {
+            others.iter().all(|(name, other)| {
+                selves
+                    .iter()
+                    .find(|(slf_name, _)| slf_name == name)
+                    .is_some_and(|(_, slf)| can_coerce_to(slf, other, ctx))
+            })
+        }
+        // a function that does not throw can be used wherever a throwing function is expected
+        (
+            TypeSignatureValue::Function {
+                args: self_args,
+                return_type: self_return,
+                throws: None,
+            },
+            TypeSignatureValue::Function {
+                args: other_args,
+                return_type: other_return,
+                throws: Some(_),
+            },
+        ) => **self_args == **other_args && **self_return == **other_return,
+        _ => type_sig == other,
     }
 }
 
@@ -36,3 +60,191 @@ pub fn coerce<'a>(
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::assert_matches;
+
+    use crate::{
+        ir::test_utils::utils::{lowered_ir, type_check},
+        type_checker::TypeCheckerError,
+    };
+
+    #[test]
+    fn test_coerce_to_optional() {
+        let mut ir = lowered_ir(
+            "
+        struct Point { let x: Number? }
+        func parse(text: String) -> Number? {
+            if text == \"\" {
+                return nil
+            }
+            return 1
+        }
+        func offset(by: Number?) {}
+
+        var value: Number? = 1
+        value = nil
+        value = parse(\"2\")
+        offset(3)
+        let point = Point { x: 4 }
+        let pair: (Number?, String) = (5, \"five\")
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_optional_not_coerced_to_value() {
+        for code in [
+            "let value: Number? = 1; let number: Number = value",
+            "func offset(by: Number) {}; let value: Number? = 1; offset(value)",
+            "let value: Number? = 1; let sum = value + 1",
+            "let value: Number = nil",
+            "let value: Number? = \"one\"",
+        ] {
+            let mut ir = lowered_ir(code).unwrap();
+
+            assert_matches!(
+                type_check(&mut ir).1,
+                Err(TypeCheckerError::ConflictingTypes(_, _)),
+                "{code}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_declared_type_of_nested_optionals() {
+        let mut ir = lowered_ir(
+            "
+        let numbers: Array<Number?> = [1, nil]
+        var maybe = numbers
+        maybe = [nil, 2]
+        let nested: Array<Array<Number?>> = [[1], [nil]]
+        var pair: (Number?, String) = (5, \"five\")
+        pair = (nil, \"none\")
+        let record: { a: Number? } = { a: 1 }
+        let a: Number? = record.a
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+
+        for code in [
+            "let numbers: Array<Number?> = [1]; let first: Number = numbers[0]",
+            "let numbers = [1]; let maybe: Array<Number?> = numbers",
+            "let pair: (Number?, String) = (5, \"five\"); let first: Number = pair.0",
+            "let record: { a: Number? } = { a: 1 }; let a: Number = record.a",
+        ] {
+            let mut ir = lowered_ir(code).unwrap();
+
+            assert_matches!(
+                type_check(&mut ir).1,
+                Err(TypeCheckerError::ConflictingTypes(_, _)),
+                "{code}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_coerce_record_to_fewer_fields() {
+        let mut ir = lowered_ir(
+            "
+        type Named = { name: String }
+        func greet(person: Named) -> String {
+            return person.name
+        }
+
+        let bob = { name: \"Bob\", age: 42 }
+        let greeting = greet(bob)
+        let named: Named = bob
+        let people: Array<{ name: String, age: Number? }> = [bob]
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+
+        for code in [
+            "func greet(person: { name: String }) {}; greet({ age: 42 })",
+            "let bob = { name: \"Bob\" }; let person: { name: String, age: Number } = bob",
+            "let bob = { name: 42 }; let person: { name: String } = bob",
+        ] {
+            let mut ir = lowered_ir(code).unwrap();
+
+            assert_matches!(
+                type_check(&mut ir).1,
+                Err(TypeCheckerError::ConflictingTypes(_, _)),
+                "{code}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_coerce_function_to_throwing() {
+        let mut ir = lowered_ir(
+            "
+        func double(n: Number) -> Number { return n * 2 }
+        func apply(f: (Number) throws(String) -> Number) throws(String) -> Number {
+            return try f(1)
+        }
+
+        func run() throws(String) -> Number { return try apply(double) }
+        let f: (Number) throws(String) -> Number = double
+        let g: (Number) throws(String) -> Number = (n) { return n + 1 }
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+
+        for code in [
+            "func fail(n: Number) throws(String) -> Number { throw \"no\" }
+            let f: (Number) -> Number = fail",
+            "func double(n: Number) -> Number { return n * 2 }
+            let f: (String) throws(String) -> Number = double",
+            "func double(n: Number) -> Number { return n * 2 }
+            let f: (Number) throws(String) -> String = double",
+        ] {
+            let mut ir = lowered_ir(code).unwrap();
+
+            assert_matches!(
+                type_check(&mut ir).1,
+                Err(TypeCheckerError::ConflictingTypes(_, _)),
+                "{code}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_coalesce() {
+        let mut ir = lowered_ir(
+            "
+        let ages = [\"alice\": 31]
+        let age: Number = ages[\"bob\"] ?? 0
+        let fallback: Number? = nil
+        let maybe: Number? = ages[\"bob\"] ?? fallback
+        let isKnown: Boolean = ages[\"alice\"] != nil
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+
+        let mut ir = lowered_ir(
+            "
+        let ages = [\"alice\": 31]
+        let age = ages[\"bob\"] ?? \"unknown\"
+        ",
+        )
+        .unwrap();
+
+        assert_matches!(
+            type_check(&mut ir).1,
+            Err(TypeCheckerError::ConflictingTypes(_, _))
+        );
+    }
+}
@@ -10,11 +10,18 @@ use crate::{
         ir_walker::walk_ir,
         node::{
             assignment::Assignment,
+            dictionary::Dictionary,
             enumeration::{EnumInit, EnumValue},
             expression::Expr,
-            function::FunctionCall,
+            extension::Extension,
+            function::{Await, Function, FunctionCall},
+            generics::TypeParam,
+            loops::{Loop, LoopJump},
             member_access::UnresolvedMemberAccess,
-            structure::Struct,
+            operator::BinaryOperation,
+            pattern_match::{EnumPattern, LetElse, Match, MatchArm},
+            structure::{Struct, StructInit, StructInitValue},
+            traits::Trait,
             type_signature::{TypeEvalError, TypeSignature},
             NodeRef,
         },
@@ -28,16 +35,28 @@ use crate::{
 
 use self::{
     check_assignment::AssignmentError,
+    check_extension::ExtensionError,
+    check_init::InitError,
     check_struct::StructTypeError,
+    check_throw::ThrowError,
     type_inference::{TypeConstraint, TypeInferrer},
     type_resolver::TypeResolver,
     types_walker::EndTypeChecker,
 };
 
 pub mod check_assignment;
+pub mod check_async;
+pub mod check_dictionary;
 pub mod check_enum;
 pub mod check_expr_ident;
+pub mod check_extension;
+pub mod check_init;
+pub mod check_loop;
+pub mod check_match;
+pub mod check_operator;
+pub mod check_string_interpolation;
 pub mod check_struct;
+pub mod check_throw;
 pub mod coercion;
 pub mod type_inference;
 pub mod type_resolver;
@@ -56,6 +75,53 @@ pub enum TypeCheckerError<'a> {
     UnresolvableTypeConstraints(VecDeque<TypeConstraint<'a>>),
     UndeterminableTypes(Vec<UndeterminableType<'a>>),
     IdentNotExpression(NodeRef<'a, Expr<'a>>, SymbolValue<'a>),
+    InvalidOperandType(NodeRef<'a, BinaryOperation<'a>>, TypeSignature<'a>),
+    /// An operator of a trait applied to a value typed as the trait outside of the default
+    /// operators of the trait, where the type declaring the operator is not known
+    TraitOperatorCall(NodeRef<'a, BinaryOperation<'a>>, NodeRef<'a, Trait<'a>>),
+    /// A value matched against an enum pattern that is not an enum
+    MatchNonEnum(NodeRef<'a, Expr<'a>>, TypeSignature<'a>),
+    EnumPatternArgCountMismatch(NodeRef<'a, EnumPattern<'a>>, NodeRef<'a, EnumValue<'a>>),
+    NonExhaustiveMatch(NodeRef<'a, Match<'a>>, Vec<NodeRef<'a, EnumValue<'a>>>),
+    /// An arm that can never be reached, together with the earlier arm that matches
+    /// all of its values, or `None` if the values are covered by multiple arms
+    UnreachableMatchArm(NodeRef<'a, MatchArm<'a>>, Option<NodeRef<'a, MatchArm<'a>>>),
+    LetElseNotDiverging(NodeRef<'a, LetElse<'a>>),
+    ExtensionError(NodeRef<'a, Extension<'a>>, ExtensionError<'a>),
+    /// A type argument that does not conform to the trait bound of its type parameter
+    MissingConformance(TypeSignature<'a>, NodeRef<'a, TypeParam<'a>>),
+    /// A type argument that is not a structure, for a type parameter whose bound declares operators
+    OperatorBoundNonStruct(TypeSignature<'a>, NodeRef<'a, TypeParam<'a>>),
+    /// An `await` outside of an async function, together with the enclosing function if any
+    AwaitOutsideAsync(NodeRef<'a, Await<'a>>, Option<NodeRef<'a, Function<'a>>>),
+    ThrowError(ThrowError<'a>),
+    /// An interpolated value that can not be converted to a string
+    NonStringExpressible(NodeRef<'a, Expr<'a>>, TypeSignature<'a>),
+    /// A `StringExpressible` trait that does not declare `func toString() -> String`,
+    /// together with the interpolated value relying on it
+    InvalidExpressibleTrait(NodeRef<'a, Trait<'a>>, NodeRef<'a, Expr<'a>>),
+    /// A dictionary whose keys are not compared by value
+    InvalidDictionaryKey(NodeRef<'a, Dictionary<'a>>, TypeSignature<'a>),
+    /// A `break` or `continue` outside of a loop
+    LoopJumpOutsideLoop(NodeRef<'a, LoopJump<'a>>),
+    /// A `break` or `continue` whose label does not name an enclosing loop
+    UnknownLoopLabel(NodeRef<'a, LoopJump<'a>>),
+    /// A loop labelled like a loop enclosing it, given first
+    DuplicateLoopLabel(Loop<'a>, Loop<'a>),
+    /// A `return` inside of the arms of a match that is only part of the value of a statement,
+    /// together with the match
+    ReturnInMatchExpr(NodeRef<'a, Expr<'a>>, NodeRef<'a, Match<'a>>),
+    /// A `break` or `continue` leaving the arms of a match that is only part of the value of
+    /// a statement, together with the match
+    LoopJumpInMatchExpr(NodeRef<'a, LoopJump<'a>>, NodeRef<'a, Match<'a>>),
+    InitError(NodeRef<'a, Function<'a>>, InitError<'a>),
+    SelfInStaticMember(NodeRef<'a, Expr<'a>>),
+    /// A field given twice in a record literal or in the initialization of a struct,
+    /// together with its first occurrence
+    DuplicateField(
+        NodeRef<'a, StructInitValue<'a>>,
+        NodeRef<'a, StructInitValue<'a>>,
+    ),
 }
 
 #[derive(Debug)]
@@ -65,12 +131,15 @@ pub enum FunctionError<'a> {
         call: NodeRef<'a, FunctionCall<'a>>,
         func_type: TypeSignature<'a>,
     },
+    /// Argument labels on the call of anything but the init of a struct
+    UnexpectedArgLabels(NodeRef<'a, FunctionCall<'a>>),
 }
 
 #[derive(Debug, Clone)]
 pub enum ExpectedType {
     Enum,
     Struct,
+    Record,
 }
 
 impl Display for ExpectedType {
@@ -78,6 +147,7 @@ impl Display for ExpectedType {
         match self {
             ExpectedType::Enum => write!(f, "enum"),
             ExpectedType::Struct => write!(f, "struct"),
+            ExpectedType::Record => write!(f, "record"),
         }
     }
 }
@@ -95,6 +165,9 @@ pub struct TypeChecker<'a> {
     pub constraints: VecDeque<TypeConstraint<'a>>,
     pub previous_undeterminable_types: Vec<UndeterminableType<'a>>,
     pub immediate_undeterminable_types: Vec<UndeterminableType<'a>>,
+    /// Struct inits without a struct name whose type is unknown,
+    /// together with the record type of their values
+    pub untyped_struct_inits: Vec<(NodeRef<'a, StructInit<'a>>, TypeSignature<'a>)>,
     pub needs_rerun: bool,
 }
 
@@ -108,6 +181,7 @@ impl<'a> TypeChecker<'a> {
             constraints: VecDeque::new(),
             previous_undeterminable_types: Vec::new(),
             immediate_undeterminable_types: Vec::new(),
+            untyped_struct_inits: Vec::new(),
             needs_rerun: true,
         }
     }
@@ -117,6 +191,7 @@ impl<'a> TypeChecker<'a> {
             self.needs_rerun = false;
             self.substitutions.clear();
             self.constraints.clear();
+            self.untyped_struct_inits.clear();
 
             self.previous_undeterminable_types.clear();
             std::mem::swap(
@@ -130,6 +205,11 @@ impl<'a> TypeChecker<'a> {
             let mut type_resolver = TypeResolver::new(&la.ctx, &mut type_inferrer);
             walk_ir(&mut type_resolver, la)?;
 
+            // once nothing else can be inferred, the remaining struct inits are record literals
+            if !type_resolver.0.needs_rerun {
+                type_resolver.type_record_literals(&mut la.ctx);
+            }
+
             if !type_resolver.0.needs_rerun {
                 let mut type_checker = EndTypeChecker::new(&la.ctx, &mut type_resolver);
                 walk_ir(&mut type_checker, la)?;
@@ -153,6 +233,22 @@ impl<'a> TypeChecker<'a> {
 
     #[inline]
     fn add_constraint(&mut self, a: TypeSignature<'a>, b: TypeSignature<'a>) {
-        self.constraints.push_back(TypeConstraint(a, b))
+        self.constraints.push_back(TypeConstraint::Equal(a, b))
+    }
+
+    #[inline]
+    fn add_coercion_constraint(&mut self, from: TypeSignature<'a>, to: TypeSignature<'a>) {
+        self.constraints
+            .push_back(TypeConstraint::Coercible(from, to))
+    }
+
+    #[inline]
+    fn add_conformance_constraint(
+        &mut self,
+        type_sig: TypeSignature<'a>,
+        param: NodeRef<'a, TypeParam<'a>>,
+    ) {
+        self.constraints
+            .push_back(TypeConstraint::Conforms(type_sig, param))
     }
 }
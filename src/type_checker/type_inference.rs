@@ -3,45 +3,80 @@ use crate::{
         context::IrCtx,
         ir_walker::{IrWalker, ScopeValue},
         node::{
+            control_flow::{IfStmt, NarrowedBinding},
             expression::Expr,
-            function::Function,
-            statement::{Stmt, StmtBlock},
+            function::{Function, FunctionCall},
+            generics::{declared_type_param, type_param_names, TypeParam},
+            identifier::{Ident, IdentParent, Identifiable},
+            operator::{BinaryOperator, UnaryOperator},
+            pattern_match::{EnumPattern, LetElse, Match, MatchArm},
+            statement::Stmt,
             type_signature::{
-                BuiltinType, TypeEvalError, TypeSignature, TypeSignatureValue, Typed,
+                BuiltinType, TypeEvalError, TypeSignature, TypeSignatureContext,
+                TypeSignatureParent, TypeSignatureValue, Typed,
             },
             NodeRef,
         },
     },
-    symbols::symbol_resolver::SymbolResolutionError,
+    symbols::{symbol_resolver::SymbolResolutionError, symbol_table::SymbolValueItem},
 };
 
-use super::{coercion::coerce, FunctionError, TypeChecker, TypeCheckerError};
+use super::{
+    check_extension::conforms_to_bound,
+    check_throw::ThrowTarget,
+    coercion::{can_coerce_to, coerce},
+    FunctionError, TypeChecker, TypeCheckerError,
+};
 
 #[derive(Debug)]
-pub struct TypeConstraint<'a>(pub TypeSignature<'a>, pub TypeSignature<'a>);
+pub enum TypeConstraint<'a> {
+    /// Both types have to be the same
+    Equal(TypeSignature<'a>, TypeSignature<'a>),
+    /// The first type has to be coercible to the second, e.g. a value to an optional value
+    Coercible(TypeSignature<'a>, TypeSignature<'a>),
+    /// The type has to conform to the trait bound of the type parameter
+    Conforms(TypeSignature<'a>, NodeRef<'a, TypeParam<'a>>),
+}
+
+impl<'a> TypeConstraint<'a> {
+    pub fn type_sigs(&self) -> Vec<&TypeSignature<'a>> {
+        match self {
+            TypeConstraint::Equal(a, b) | TypeConstraint::Coercible(a, b) => vec![a, b],
+            TypeConstraint::Conforms(type_sig, _) => vec![type_sig],
+        }
+    }
+}
 
 #[derive(Debug)]
-pub struct TypeInferrer<'a, 'b>(pub &'b mut TypeChecker<'a>);
+pub struct TypeInferrer<'a, 'b>(
+    pub &'b mut TypeChecker<'a>,
+    /// Return types collected for each of the enclosing functions
+    Vec<Vec<TypeSignature<'a>>>,
+);
 
 impl<'a, 'b> TypeInferrer<'a, 'b> {
     pub fn new(ctx: &IrCtx<'a>, type_checker: &'b mut TypeChecker<'a>) -> Self {
         type_checker.symbols.reset(ctx);
-        Self(type_checker)
+        Self(type_checker, Vec::new())
     }
 }
 
 impl<'a> IrWalker<'a> for TypeInferrer<'a, '_> {
     type Error = TypeCheckerError<'a>;
-    type Scope = ();
+    type Scope = Option<ThrowTarget<'a>>;
 
     fn visit_scope_begin(
         &mut self,
         ctx: &mut IrCtx<'a>,
-        _parent: &mut Self::Scope,
+        parent: &mut Self::Scope,
         value: ScopeValue<'a>,
-    ) -> Result<(), TypeCheckerError<'a>> {
+    ) -> Result<Self::Scope, TypeCheckerError<'a>> {
+        if let ScopeValue::Func(func) = value {
+            self.1.push(vec![(*ctx[func].return_type).clone()]);
+        }
+
         value.visit_scope_begin(ctx, &mut self.0.symbols);
-        Ok(())
+        Ok(ThrowTarget::of_scope(&value, *parent))
     }
 
     fn visit_scope_end(
@@ -80,19 +115,76 @@ impl<'a> IrWalker<'a> for TypeInferrer<'a, '_> {
     fn visit_stmt(
         &mut self,
         ctx: &mut IrCtx<'a>,
-        _scope: &mut Self::Scope,
+        scope: &mut Self::Scope,
         stmt: NodeRef<'a, Stmt<'a>>,
     ) -> Result<(), Self::Error> {
         match ctx[stmt].clone() {
             Stmt::VariableDecl(var_decl) => {
-                let val_type = ctx[var_decl]
-                    .value
+                let var_type = ctx[var_decl].type_sig.cloned();
+                self.add_value_coercion(ctx, ctx[var_decl].value, var_type)?;
+            }
+            Stmt::Return(expr) => {
+                let expr_type = expr
+                    .eval_type(&mut self.0.symbols, ctx)
+                    .map_err(TypeCheckerError::TypeEval)?;
+
+                if let Some(return_types) = self.1.last_mut() {
+                    return_types.push(expr_type);
+                }
+            }
+            Stmt::Throw(expr) => {
+                if let Some(thrown_type) = scope.and_then(|target| target.thrown_type(ctx)) {
+                    let expr_type = expr
+                        .eval_type(&mut self.0.symbols, ctx)
+                        .map_err(TypeCheckerError::TypeEval)?;
+
+                    self.0.add_constraint(thrown_type, expr_type);
+                }
+            }
+            Stmt::IfBranch(ifb) => {
+                if let Some(pattern) = ctx[ifb].pattern {
+                    self.infer_enum_patterns(ctx, ctx[ifb].condition, vec![pattern])?;
+                }
+            }
+            Stmt::WhileLoop(wl) => {
+                let condition_type = ctx[wl]
+                    .condition
                     .clone()
                     .eval_type(&mut self.0.symbols, ctx)
                     .map_err(TypeCheckerError::TypeEval)?;
 
-                self.0
-                    .add_constraint((*ctx[var_decl].type_sig).clone(), val_type)
+                self.0.add_constraint(
+                    condition_type,
+                    ctx.get_builtin_type_sig(BuiltinType::Boolean),
+                );
+            }
+            Stmt::ForLoop(fl) => {
+                let sequence_type = ctx[fl]
+                    .sequence
+                    .clone()
+                    .eval_type(&mut self.0.symbols, ctx)
+                    .map_err(TypeCheckerError::TypeEval)?;
+
+                let type_ctx = TypeSignatureContext {
+                    parent: TypeSignatureParent::ForLoop(fl),
+                    type_span: None,
+                }
+                .alloc();
+
+                let expected_sequence = if ctx[fl].counts_range {
+                    ctx.get_type_sig(TypeSignatureValue::Builtin(BuiltinType::Range), type_ctx)
+                } else if let Some((key, value)) = sequence_type.dictionary_types(ctx) {
+                    // the entries of a map are iterated as pairs of their key and value
+                    let entry = ctx
+                        .get_type_sig(TypeSignatureValue::Tuple(vec![key, value].into()), type_ctx);
+                    self.0.add_constraint(ctx[fl].item_type.cloned(), entry);
+
+                    sequence_type.clone()
+                } else {
+                    ctx.get_array_type_sig(ctx[fl].item_type.cloned(), type_ctx)
+                };
+
+                self.0.add_constraint(sequence_type, expected_sequence);
             }
             Stmt::StructDecl(st) => {
                 for attr in ctx[st].attrs.clone() {
@@ -101,8 +193,22 @@ impl<'a> IrWalker<'a> for TypeInferrer<'a, '_> {
                             .eval_type(&mut self.0.symbols, ctx)
                             .map_err(TypeCheckerError::TypeEval)?;
 
+                        self.0.add_coercion_constraint(
+                            default_val_type,
+                            (*ctx[attr].type_sig).clone(),
+                        )
+                    }
+                }
+            }
+            Stmt::Extension(ext) => {
+                for attr in ctx[ext].static_attrs.clone() {
+                    if let Some(attr_val) = ctx[attr].default_value {
+                        let val_type = attr_val
+                            .eval_type(&mut self.0.symbols, ctx)
+                            .map_err(TypeCheckerError::TypeEval)?;
+
                         self.0
-                            .add_constraint((*ctx[attr].type_sig).clone(), default_val_type)
+                            .add_coercion_constraint(val_type, (*ctx[attr].type_sig).clone())
                     }
                 }
             }
@@ -115,22 +221,26 @@ impl<'a> IrWalker<'a> for TypeInferrer<'a, '_> {
     fn visit_expr(
         &mut self,
         ctx: &mut IrCtx<'a>,
-        _scope: &mut Self::Scope,
+        scope: &mut Self::Scope,
         expr: NodeRef<'a, Expr<'a>>,
     ) -> Result<(), Self::Error> {
         match ctx[expr].clone() {
             Expr::FunctionCall(call) => {
-                let func_type_sig = ctx[call]
-                    .func
-                    .clone()
-                    .eval_type(&mut self.0.symbols, ctx)
-                    .map_err(TypeCheckerError::TypeEval)?;
+                // labels select the init of a struct, which the symbol resolver already did
+                let labelled = ctx[call].arg_labels.iter().any(Option::is_some);
+                if labelled && call.called_init(ctx).is_none() {
+                    return Err(TypeCheckerError::FunctionError(
+                        FunctionError::UnexpectedArgLabels(call),
+                    ));
+                }
+
+                let func_type_sig = match ctx[call].instance.clone() {
+                    Some(instance) => instance,
+                    None => self.instantiate_call(ctx, call)?,
+                };
 
                 let args = match &ctx[&func_type_sig] {
-                    TypeSignatureValue::Function {
-                        args,
-                        return_type: _,
-                    } => args.clone(),
+                    TypeSignatureValue::Function { args, .. } => args.clone(),
                     _ => {
                         return Err(TypeCheckerError::TypeEval(TypeEvalError::CallNonFunction(
                             call,
@@ -151,14 +261,18 @@ impl<'a> IrWalker<'a> for TypeInferrer<'a, '_> {
                 }
 
                 for (arg, param) in (*args).clone().into_iter().zip(func_params.into_iter()) {
-                    let param_type = param
-                        .eval_type(&mut self.0.symbols, ctx)
-                        .map_err(TypeCheckerError::TypeEval)?;
-
-                    self.0.add_constraint(arg, param_type)
+                    self.add_value_coercion(ctx, param, arg)?;
                 }
             }
             Expr::Identifier(_, _) => {}
+            Expr::StructInit(st_init) if st_init.is_record(ctx) => {
+                let values_type = st_init
+                    .values_record_type(ctx, &mut self.0.symbols)
+                    .map_err(TypeCheckerError::TypeEval)?;
+
+                self.0
+                    .add_coercion_constraint(values_type, ctx[st_init].type_sig.cloned());
+            }
             Expr::StructInit(st_init) => {
                 let Some(st_name) = st_init.struct_name(ctx) else {
                     return Ok(())
@@ -173,12 +287,39 @@ impl<'a> IrWalker<'a> for TypeInferrer<'a, '_> {
                     )))?
                     .unwrap_struct(ctx);
 
+                let type_params = type_param_names(ctx, &ctx[st].type_params);
+                let (st_type, mut type_args) = ctx[st_init].type_sig.split_instance(ctx);
+
+                // the type arguments of a generic struct are inferred from the values
+                if type_args.is_empty() && !type_params.is_empty() {
+                    type_args = type_params
+                        .iter()
+                        .map(|_| ctx.make_type_var(TypeSignatureParent::StructInit(st_init)))
+                        .collect();
+                    self.add_bound_constraints(ctx, &type_params, &type_args);
+
+                    ctx[st_init].type_sig = ctx
+                        .get_type_sig(
+                            TypeSignatureValue::Instance {
+                                base: st_type,
+                                args: type_args.clone(),
+                            },
+                            ctx[st_init].type_sig.context.clone(),
+                        )
+                        .into();
+                }
+
                 for val in ctx[st_init].values.clone() {
                     let st_attr =
                         st.lookup_attr(*ctx[val].name, ctx)
                             .ok_or(TypeCheckerError::TypeEval(TypeEvalError::UnknownIdent(
                                 *ctx[val].name,
                             )))?;
+                    let attr_type =
+                        ctx[st_attr]
+                            .type_sig
+                            .cloned()
+                            .substitute(ctx, &type_params, &type_args);
 
                     let val_type = ctx[val]
                         .value
@@ -186,26 +327,22 @@ impl<'a> IrWalker<'a> for TypeInferrer<'a, '_> {
                         .eval_type(&mut self.0.symbols, ctx)
                         .map_err(TypeCheckerError::TypeEval)?;
 
-                    self.0
-                        .add_constraint((*ctx[st_attr].type_sig).clone(), val_type);
+                    self.0.add_coercion_constraint(val_type, attr_type);
                 }
             }
             Expr::StructAccess(_) => {}
             Expr::TupleAccess(_) => {}
             Expr::EscapeBlock(_) => {}
             Expr::Assignment(asg) => {
-                let lhs = ctx[asg]
-                    .lhs
-                    .clone()
-                    .eval_type(&mut self.0.symbols, ctx)
-                    .map_err(TypeCheckerError::TypeEval)?;
-                let rhs = ctx[asg]
-                    .rhs
-                    .clone()
-                    .eval_type(&mut self.0.symbols, ctx)
-                    .map_err(TypeCheckerError::TypeEval)?;
-
-                self.0.add_constraint(lhs, rhs);
+                // an entry of a dictionary is looked up as optional, but assigned a value
+                let lhs = match ctx[ctx[asg].lhs] {
+                    Expr::DictionaryIndex(dict_idx) => {
+                        dict_idx.value_type(&mut self.0.symbols, ctx)
+                    }
+                    _ => ctx[asg].lhs.clone().eval_type(&mut self.0.symbols, ctx),
+                }
+                .map_err(TypeCheckerError::TypeEval)?;
+                self.add_value_coercion(ctx, ctx[asg].rhs, lhs)?;
             }
             Expr::Tuple(_) => {}
             Expr::EnumInit(enm_init) => {
@@ -223,25 +360,298 @@ impl<'a> IrWalker<'a> for TypeInferrer<'a, '_> {
                     ))?
                     .1;
 
+                let type_params = type_param_names(ctx, &ctx[enm].type_params);
+                let type_args = ctx[enm_init].type_args.clone();
+
                 for (arg, item_type) in ctx[enm_init]
                     .items
                     .clone()
                     .into_iter()
-                    .zip((*ctx[enm_val].items).clone().into_iter())
+                    .zip((*ctx[enm_val].items).clone())
                 {
                     let arg_type = arg
                         .eval_type(&mut self.0.symbols, ctx)
                         .map_err(TypeCheckerError::TypeEval)?;
-                    self.0.add_constraint(arg_type, item_type);
+                    let item_type = item_type.substitute(ctx, &type_params, &type_args);
+                    self.0.add_coercion_constraint(arg_type, item_type);
                 }
             }
             Expr::UnresolvedMemberAccess(_) => {}
+            Expr::BinaryOperation(bin_op) if ctx[bin_op].operator == BinaryOperator::Coalesce => {
+                let (lhs, rhs) = (ctx[bin_op].lhs, ctx[bin_op].rhs);
+                let lhs_type = lhs
+                    .eval_type(&mut self.0.symbols, ctx)
+                    .map_err(TypeCheckerError::TypeEval)?;
+                let rhs_type = rhs
+                    .eval_type(&mut self.0.symbols, ctx)
+                    .map_err(TypeCheckerError::TypeEval)?;
+
+                // the optional value has to wrap the type of the fallback value
+                let optional = ctx.get_type_sig(
+                    TypeSignatureValue::Optional(rhs_type),
+                    TypeSignatureContext {
+                        parent: TypeSignatureParent::BinaryOperation(bin_op),
+                        type_span: None,
+                    }
+                    .alloc(),
+                );
+
+                self.0.add_coercion_constraint(lhs_type, optional);
+            }
+            Expr::BinaryOperation(bin_op) => {
+                for (i, binding) in ctx[bin_op].narrowed.clone().into_iter().enumerate() {
+                    self.add_narrowing_constraint(
+                        ctx,
+                        &binding,
+                        TypeSignatureParent::OperandNarrowedBinding(bin_op, i),
+                    )?;
+                }
+
+                let (lhs, rhs) = (ctx[bin_op].lhs, ctx[bin_op].rhs);
+                let lhs_type = lhs
+                    .eval_type(&mut self.0.symbols, ctx)
+                    .map_err(TypeCheckerError::TypeEval)?;
+                let rhs_type = bin_op
+                    .rhs_type(&mut self.0.symbols, ctx)
+                    .map_err(TypeCheckerError::TypeEval)?;
+
+                // an operator declared for the type of the left operand is called like
+                // a function, the right operand being its second argument
+                ctx[bin_op].overload = bin_op.lookup_overload(ctx, &self.0.symbols, &lhs_type);
+                if let Some(overload) = ctx[bin_op].overload {
+                    if let Some(expected_rhs) = overload.rhs_type(ctx, &lhs_type) {
+                        self.0.add_coercion_constraint(rhs_type, expected_rhs);
+                    }
+
+                    return Ok(());
+                }
+
+                let operand_type = match ctx[bin_op].operator {
+                    BinaryOperator::Coalesce => unreachable!("coalescing is inferred separately"),
+                    BinaryOperator::Add
+                    | BinaryOperator::Eq
+                    | BinaryOperator::NotEq
+                    | BinaryOperator::Lt
+                    | BinaryOperator::LtEq
+                    | BinaryOperator::Gt
+                    | BinaryOperator::GtEq => None,
+                    BinaryOperator::Sub
+                    | BinaryOperator::Mul
+                    | BinaryOperator::Div
+                    | BinaryOperator::Mod
+                    | BinaryOperator::HalfOpenRange
+                    | BinaryOperator::ClosedRange => Some(BuiltinType::Number),
+                    BinaryOperator::And | BinaryOperator::Or => Some(BuiltinType::Boolean),
+                };
+
+                let known_lhs_type = self.0.substitutions.get(&lhs_type).unwrap_or(&lhs_type);
+
+                match operand_type {
+                    // a user type that does not declare the operator is reported like for the
+                    // operators checked by the end type checker, instead of conflicting types
+                    Some(_) if is_user_type(ctx, known_lhs_type) => {
+                        return Err(TypeCheckerError::InvalidOperandType(
+                            bin_op,
+                            known_lhs_type.clone(),
+                        ));
+                    }
+                    Some(builtin) => {
+                        let expected_lhs = operand_type_sig(ctx, builtin, lhs);
+                        let expected_rhs = operand_type_sig(ctx, builtin, rhs);
+
+                        self.0.add_constraint(lhs_type, expected_lhs);
+                        self.0.add_constraint(rhs_type, expected_rhs);
+                    }
+                    // a value compared to an optional value is coerced to the optional type
+                    None if matches!(
+                        ctx[bin_op].operator,
+                        BinaryOperator::Eq | BinaryOperator::NotEq
+                    ) =>
+                    {
+                        let is_optional = |type_sig: &TypeSignature<'a>| {
+                            let type_sig = self.0.substitutions.get(type_sig).unwrap_or(type_sig);
+                            matches!(ctx[type_sig], TypeSignatureValue::Optional(_))
+                        };
+
+                        match (is_optional(&lhs_type), is_optional(&rhs_type)) {
+                            (true, false) => self.0.add_coercion_constraint(rhs_type, lhs_type),
+                            (false, true) => self.0.add_coercion_constraint(lhs_type, rhs_type),
+                            _ => self.0.add_constraint(lhs_type, rhs_type),
+                        }
+                    }
+                    // the operands only have to agree with each other,
+                    // which types are allowed is checked by the end type checker
+                    None => self.0.add_constraint(lhs_type, rhs_type),
+                }
+            }
+            Expr::UnaryOperation(unary_op) => {
+                let operand = ctx[unary_op].operand;
+                let operand_type = operand
+                    .eval_type(&mut self.0.symbols, ctx)
+                    .map_err(TypeCheckerError::TypeEval)?;
+
+                let builtin = match ctx[unary_op].operator {
+                    UnaryOperator::Negate => BuiltinType::Number,
+                    UnaryOperator::Not => BuiltinType::Boolean,
+                };
+
+                let expected = operand_type_sig(ctx, builtin, operand);
+                self.0.add_constraint(operand_type, expected);
+            }
+            Expr::Match(mtch) => {
+                self.infer_enum_patterns(ctx, ctx[mtch].scrutinee, mtch.patterns(ctx))?
+            }
+            Expr::Await(aw) => {
+                let expr = ctx[aw].expr;
+                let expr_type = expr
+                    .eval_type(&mut self.0.symbols, ctx)
+                    .map_err(TypeCheckerError::TypeEval)?;
+
+                let promise = ctx.get_promise_type_sig(
+                    ctx[aw].type_sig.cloned(),
+                    TypeSignatureContext {
+                        parent: TypeSignatureParent::Await(aw),
+                        type_span: None,
+                    }
+                    .alloc(),
+                );
+                self.0.add_constraint(expr_type, promise);
+            }
+            Expr::Try(tr) => {
+                let callee_thrown_type = match tr.call(ctx) {
+                    Some(call) => call
+                        .callee_type(&mut self.0.symbols, ctx)
+                        .map_err(TypeCheckerError::TypeEval)?
+                        .thrown_type(ctx),
+                    None => None,
+                };
+                let target_thrown_type = scope.and_then(|target| target.thrown_type(ctx));
+
+                if let (Some(callee_thrown_type), Some(target_thrown_type)) =
+                    (callee_thrown_type, target_thrown_type)
+                {
+                    self.0
+                        .add_constraint(target_thrown_type, callee_thrown_type);
+                }
+            }
+            Expr::Array(arr) => {
+                // the element type may already be the one of the array the literal is assigned to
+                let elem_type = ctx[arr].elem_type.cloned();
+
+                for item in ctx[arr].items.clone() {
+                    self.add_value_coercion(ctx, item, elem_type.clone())?;
+                }
+            }
+            Expr::Index(idx) => {
+                let (array, index) = (ctx[idx].array, ctx[idx].index);
+                let array_type = array
+                    .eval_type(&mut self.0.symbols, ctx)
+                    .map_err(TypeCheckerError::TypeEval)?;
+                let index_type = index
+                    .eval_type(&mut self.0.symbols, ctx)
+                    .map_err(TypeCheckerError::TypeEval)?;
+
+                let expected_array = ctx.get_array_type_sig(
+                    ctx[idx].elem_type.cloned(),
+                    TypeSignatureContext {
+                        parent: TypeSignatureParent::Index(idx),
+                        type_span: None,
+                    }
+                    .alloc(),
+                );
+                let expected_index = operand_type_sig(ctx, BuiltinType::Number, index);
+
+                self.0.add_constraint(array_type, expected_array);
+                self.0.add_constraint(index_type, expected_index);
+            }
+            Expr::Dictionary(dict) => {
+                let key_type = ctx[dict].key_type.cloned();
+                let value_type = ctx[dict].value_type.cloned();
+
+                for (key, value) in ctx[dict].entries.clone() {
+                    let entry_key_type = key
+                        .eval_type(&mut self.0.symbols, ctx)
+                        .map_err(TypeCheckerError::TypeEval)?;
+                    let entry_value_type = value
+                        .eval_type(&mut self.0.symbols, ctx)
+                        .map_err(TypeCheckerError::TypeEval)?;
+
+                    self.0.add_constraint(key_type.clone(), entry_key_type);
+                    self.0.add_constraint(value_type.clone(), entry_value_type);
+                }
+            }
+            Expr::DictionaryIndex(dict_idx) => {
+                let (dictionary, key) = (ctx[dict_idx].dictionary, ctx[dict_idx].key);
+                let dictionary_type = dictionary
+                    .eval_type(&mut self.0.symbols, ctx)
+                    .map_err(TypeCheckerError::TypeEval)?;
+                let key_type = key
+                    .eval_type(&mut self.0.symbols, ctx)
+                    .map_err(TypeCheckerError::TypeEval)?;
+
+                if let Some((expected_key, _)) = dictionary_type.dictionary_types(ctx) {
+                    self.0.add_constraint(expected_key, key_type);
+                }
+            }
             _ => {}
         }
 
         Ok(())
     }
 
+    fn visit_match_arm(
+        &mut self,
+        ctx: &mut IrCtx<'a>,
+        _scope: &mut Self::Scope,
+        mtch: NodeRef<'a, Match<'a>>,
+        arm: NodeRef<'a, MatchArm<'a>>,
+    ) -> Result<(), Self::Error> {
+        let arm_type = match ctx[arm].result {
+            Some(result) => result
+                .eval_type(&mut self.0.symbols, ctx)
+                .map_err(TypeCheckerError::TypeEval)?,
+            // an arm that always returns does not contribute to the type of the match
+            None if ctx[arm].body.diverges(ctx) => return Ok(()),
+            None => ctx.get_type_sig(
+                TypeSignatureValue::Builtin(BuiltinType::Void),
+                TypeSignatureContext {
+                    parent: TypeSignatureParent::Match(mtch),
+                    type_span: Some(ctx[arm].span.clone()),
+                }
+                .alloc(),
+            ),
+        };
+
+        self.0.add_constraint(ctx[mtch].type_sig.cloned(), arm_type);
+        Ok(())
+    }
+
+    fn visit_if_stmt(
+        &mut self,
+        ctx: &mut IrCtx<'a>,
+        _scope: &mut Self::Scope,
+        ifb: NodeRef<'a, IfStmt<'a>>,
+    ) -> Result<(), Self::Error> {
+        for (i, binding) in ctx[ifb].narrowed.clone().into_iter().enumerate() {
+            self.add_narrowing_constraint(
+                ctx,
+                &binding,
+                TypeSignatureParent::NarrowedBinding(ifb, i),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn visit_let_else(
+        &mut self,
+        ctx: &mut IrCtx<'a>,
+        _scope: &mut Self::Scope,
+        let_else: NodeRef<'a, LetElse<'a>>,
+    ) -> Result<(), Self::Error> {
+        self.infer_enum_patterns(ctx, ctx[let_else].value, vec![ctx[let_else].pattern])
+    }
+
     fn visit_func_decl(
         &mut self,
         ctx: &mut IrCtx<'a>,
@@ -253,10 +663,195 @@ impl<'a> IrWalker<'a> for TypeInferrer<'a, '_> {
     }
 }
 
+fn operand_type_sig<'a>(
+    ctx: &mut IrCtx<'a>,
+    builtin: BuiltinType,
+    operand: NodeRef<'a, Expr<'a>>,
+) -> TypeSignature<'a> {
+    ctx.get_type_sig(
+        TypeSignatureValue::Builtin(builtin),
+        TypeSignatureContext {
+            parent: TypeSignatureParent::Expr(operand),
+            type_span: None,
+        }
+        .alloc(),
+    )
+}
+
+/// Whether the type is declared in the source, and could declare operators
+fn is_user_type<'a>(ctx: &IrCtx<'a>, type_sig: &TypeSignature<'a>) -> bool {
+    matches!(
+        ctx[&type_sig.split_instance(ctx).0],
+        TypeSignatureValue::Struct { .. }
+            | TypeSignatureValue::Enum { .. }
+            | TypeSignatureValue::Trait { .. }
+            | TypeSignatureValue::TypeParameter { .. }
+    )
+}
+
 impl<'a> TypeInferrer<'a, '_> {
+    /// Constrains a value to be coercible to the type it is assigned to. The items of an array
+    /// literal are coerced one by one, as no other array shares them yet, so `[1]` can be
+    /// an `Array<Number?>` while a variable of type `Array<Number>` cannot
+    fn add_value_coercion(
+        &mut self,
+        ctx: &mut IrCtx<'a>,
+        value: NodeRef<'a, Expr<'a>>,
+        expected: TypeSignature<'a>,
+    ) -> Result<(), TypeCheckerError<'a>> {
+        if let (Expr::Array(arr), Some(expected_elem)) =
+            (ctx[value].clone(), expected.array_element_type(ctx))
+        {
+            arr.expect_type(ctx, &expected);
+
+            for item in ctx[arr].items.clone() {
+                self.add_value_coercion(ctx, item, expected_elem.clone())?;
+            }
+
+            return Ok(());
+        }
+
+        let value_type = value
+            .eval_type(&mut self.0.symbols, ctx)
+            .map_err(TypeCheckerError::TypeEval)?;
+
+        self.0.add_coercion_constraint(value_type, expected);
+        Ok(())
+    }
+
     fn resolve_constraints(&mut self, ctx: &mut IrCtx<'a>) -> Result<(), TypeCheckerError<'a>> {
         let mut unresolvable_count = 0;
-        while let Some(TypeConstraint(type_a, type_b)) = self.0.constraints.pop_front() {
+        while let Some(constraint) = self.0.constraints.pop_front() {
+            let (type_a, type_b) = match constraint {
+                TypeConstraint::Equal(type_a, type_b) => (type_a, type_b),
+                TypeConstraint::Coercible(from, to) => {
+                    let from = (*self.0.substitutions.get(&from).unwrap_or(&from)).clone();
+                    let to = (*self.0.substitutions.get(&to).unwrap_or(&to)).clone();
+
+                    if can_coerce_to(&from, &to, ctx) {
+                        unresolvable_count = 0;
+                        continue;
+                    }
+
+                    match (ctx[&from].clone(), ctx[&to].clone()) {
+                        (TypeSignatureValue::Optional(from), TypeSignatureValue::Optional(to)) => {
+                            unresolvable_count = 0;
+                            self.0.add_coercion_constraint(from, to);
+                            continue;
+                        }
+                        // the fields of the record that are not expected are left out
+                        (
+                            TypeSignatureValue::Record(from_fields),
+                            TypeSignatureValue::Record(to_fields),
+                        ) => {
+                            unresolvable_count = 0;
+                            for (name, to_field) in to_fields {
+                                let Some((_, from_field)) =
+                                    from_fields.iter().find(|(field, _)| *field == name)
+                                else {
+                                    return Err(TypeCheckerError::ConflictingTypes(from, to));
+                                };
+
+                                self.0.add_coercion_constraint(from_field.clone(), to_field);
+                            }
+                            continue;
+                        }
+                        // a function that does not throw is coerced to a throwing one
+                        // with the same arguments and return type
+                        (
+                            TypeSignatureValue::Function {
+                                args: from_args,
+                                return_type: from_return,
+                                throws: None,
+                            },
+                            TypeSignatureValue::Function {
+                                args: to_args,
+                                return_type: to_return,
+                                throws: Some(_),
+                            },
+                        ) => {
+                            unresolvable_count = 0;
+                            if from_args.len() != to_args.len() {
+                                return Err(TypeCheckerError::FunctionError(
+                                    FunctionError::ArgCountMismatch(from, to),
+                                ));
+                            }
+
+                            self.0
+                                .add_constraint((*from_return).clone(), (*to_return).clone());
+                            for (from_arg, to_arg) in
+                                (*from_args).clone().into_iter().zip((*to_args).clone())
+                            {
+                                self.0.add_constraint(from_arg, to_arg);
+                            }
+                            continue;
+                        }
+                        // whether the value is optional itself is only known once it is inferred,
+                        // otherwise it is assumed to be of the optional type
+                        (TypeSignatureValue::TypeVariable(_), TypeSignatureValue::Optional(_))
+                            if unresolvable_count < self.0.constraints.len() =>
+                        {
+                            unresolvable_count += 1;
+                            self.0.add_coercion_constraint(from, to);
+                            continue;
+                        }
+                        // a record literal is typed by its own fields, so a value of an unknown
+                        // type is not assumed to be of the expected record type
+                        (TypeSignatureValue::TypeVariable(_), TypeSignatureValue::Record(_)) => {
+                            self.0.add_coercion_constraint(from, to);
+                            if unresolvable_count < self.0.constraints.len() {
+                                unresolvable_count += 1;
+                                continue;
+                            } else {
+                                return Ok(());
+                            }
+                        }
+                        (TypeSignatureValue::TypeVariable(_), _) => (from, to),
+                        (_, TypeSignatureValue::Optional(to)) => {
+                            unresolvable_count = 0;
+                            self.0.add_coercion_constraint(from, to);
+                            continue;
+                        }
+                        _ => (from, to),
+                    }
+                }
+                TypeConstraint::Conforms(type_sig, param) => {
+                    let type_sig =
+                        (*self.0.substitutions.get(&type_sig).unwrap_or(&type_sig)).clone();
+
+                    // the conformance can only be checked once the type is known
+                    if let TypeSignatureValue::TypeVariable(_) = ctx[&type_sig] {
+                        if unresolvable_count < self.0.constraints.len() {
+                            unresolvable_count += 1;
+                            self.0.add_conformance_constraint(type_sig, param);
+                            continue;
+                        } else {
+                            self.0.add_conformance_constraint(type_sig, param);
+                            return Ok(());
+                        }
+                    }
+
+                    unresolvable_count = 0;
+                    if !conforms_to_bound(ctx, &self.0.symbols, &type_sig, param) {
+                        return Err(TypeCheckerError::MissingConformance(type_sig, param));
+                    }
+
+                    // the operators of the bound are called through the structure of the value
+                    let has_operators = param
+                        .bound_trait(ctx, &self.0.symbols)
+                        .is_some_and(|tr| !ctx[tr].operators.is_empty());
+                    if has_operators
+                        && matches!(
+                            ctx[&type_sig.split_instance(ctx).0],
+                            TypeSignatureValue::Enum { .. } | TypeSignatureValue::Trait { .. }
+                        )
+                    {
+                        return Err(TypeCheckerError::OperatorBoundNonStruct(type_sig, param));
+                    }
+                    continue;
+                }
+            };
+
             let type_a = (*self.0.substitutions.get(&type_a).unwrap_or(&type_a)).clone();
             let type_b = (*self.0.substitutions.get(&type_b).unwrap_or(&type_b)).clone();
 
@@ -298,10 +893,12 @@ impl<'a> TypeInferrer<'a, '_> {
                     TypeSignatureValue::Function {
                         args: args_a,
                         return_type: return_type_a,
+                        throws: throws_a,
                     },
                     TypeSignatureValue::Function {
                         args: args_b,
                         return_type: return_type_b,
+                        throws: throws_b,
                     },
                 ) => {
                     if args_a.len() != args_b.len() {
@@ -310,6 +907,14 @@ impl<'a> TypeInferrer<'a, '_> {
                         ));
                     }
 
+                    match (throws_a, throws_b) {
+                        (Some(throws_a), Some(throws_b)) => {
+                            self.0.add_constraint(throws_a, throws_b)
+                        }
+                        (None, None) => {}
+                        _ => return Err(TypeCheckerError::ConflictingTypes(type_a, type_b)),
+                    }
+
                     self.0
                         .add_constraint((*return_type_a).clone(), (*return_type_b).clone());
                     for (arg_a, arg_b) in (*args_a)
@@ -320,6 +925,58 @@ impl<'a> TypeInferrer<'a, '_> {
                         self.0.add_constraint(arg_a, arg_b);
                     }
                 }
+                (
+                    TypeSignatureValue::Instance {
+                        base: base_a,
+                        args: args_a,
+                    },
+                    TypeSignatureValue::Instance {
+                        base: base_b,
+                        args: args_b,
+                    },
+                ) => {
+                    unresolvable_count = 0;
+                    if base_a != base_b || args_a.len() != args_b.len() {
+                        return Err(TypeCheckerError::ConflictingTypes(type_a, type_b));
+                    }
+
+                    for (arg_a, arg_b) in args_a.into_iter().zip(args_b) {
+                        self.0.add_constraint(arg_a, arg_b);
+                    }
+                }
+                (
+                    TypeSignatureValue::Dictionary {
+                        key: key_a,
+                        value: value_a,
+                    },
+                    TypeSignatureValue::Dictionary {
+                        key: key_b,
+                        value: value_b,
+                    },
+                ) => {
+                    unresolvable_count = 0;
+                    self.0.add_constraint(key_a, key_b);
+                    self.0.add_constraint(value_a, value_b);
+                }
+                (TypeSignatureValue::Optional(value_a), TypeSignatureValue::Optional(value_b)) => {
+                    unresolvable_count = 0;
+                    self.0.add_constraint(value_a, value_b);
+                }
+                (TypeSignatureValue::Record(fields_a), TypeSignatureValue::Record(fields_b)) => {
+                    unresolvable_count = 0;
+                    let names_a = fields_a.iter().map(|(name, _)| name);
+                    if !names_a.eq(fields_b.iter().map(|(name, _)| name)) {
+                        return Err(TypeCheckerError::ConflictingTypes(type_a, type_b));
+                    }
+
+                    for ((_, field_a), (_, field_b)) in fields_a.into_iter().zip(fields_b) {
+                        self.0.add_constraint(field_a, field_b);
+                    }
+                }
+                // a value is only coerced to an optional value where a coercion is allowed
+                (TypeSignatureValue::Optional(_), _) | (_, TypeSignatureValue::Optional(_)) => {
+                    return Err(TypeCheckerError::ConflictingTypes(type_a, type_b));
+                }
                 _ => {
                     unresolvable_count = 0;
                     if coerce(&type_a, &type_b, ctx).is_none() {
@@ -337,52 +994,177 @@ impl<'a> TypeInferrer<'a, '_> {
         Ok(())
     }
 
-    fn infer_function_body(
+    /// The type of the called function, where the type parameters of a generic function
+    /// are replaced by new type variables to be inferred from the call
+    fn instantiate_call(
         &mut self,
         ctx: &mut IrCtx<'a>,
-        func: NodeRef<'a, Function<'a>>,
-    ) -> Result<(), TypeCheckerError<'a>> {
-        fn collect_return_types<'a>(
-            inferrer: &mut TypeInferrer<'a, '_>,
-            ctx: &mut IrCtx<'a>,
-            stmt_block: NodeRef<'a, StmtBlock<'a>>,
-            acc: &mut Vec<TypeSignature<'a>>,
-        ) -> Result<(), TypeCheckerError<'a>> {
-            for stmt in ctx[stmt_block].0.clone() {
-                match ctx[stmt] {
-                    Stmt::Return(expr) => {
-                        let expr_type = expr
-                            .eval_type(&mut inferrer.0.symbols, ctx)
-                            .map_err(TypeCheckerError::TypeEval)?;
-                        acc.push(expr_type)
-                    }
-                    Stmt::IfBranch(ifb) => {
-                        collect_return_types(inferrer, ctx, ctx[ifb].body, acc)?;
-                        if let Some(else_block) = ctx[ifb].else_body {
-                            collect_return_types(inferrer, ctx, else_block, acc)?;
-                        }
-                    }
-                    _ => {}
-                };
-            }
-
-            Ok(())
+        call: NodeRef<'a, FunctionCall<'a>>,
+    ) -> Result<TypeSignature<'a>, TypeCheckerError<'a>> {
+        let func_type_sig = call
+            .func_type(&mut self.0.symbols, ctx)
+            .map_err(TypeCheckerError::TypeEval)?;
+
+        // type parameters of an enclosing declaration stay the same inside of it, except for
+        // those of a struct, which every static call like `Pair(first: 1, second: 2)` gives anew
+        let static_call = matches!(ctx[ctx[call].func], Expr::StaticAccess(_));
+        let type_params = func_type_sig
+            .type_parameters(ctx)
+            .into_iter()
+            .filter(|param| {
+                static_call
+                    || !self.0.symbols.lookup(ctx, *param).is_some_and(|sym| {
+                        matches!(ctx[sym], SymbolValueItem::TypeParam(p) if *ctx[p].name == *param)
+                    })
+            })
+            .collect::<Vec<_>>();
+
+        if type_params.is_empty() {
+            return Ok(func_type_sig);
         }
 
-        let mut return_types = Vec::new();
-        return_types.push((*ctx[func].return_type).clone());
-        collect_return_types(self, ctx, ctx[func].body, &mut return_types)?;
+        let type_args = type_params
+            .iter()
+            .map(|_| ctx.make_type_var(TypeSignatureParent::FunctionCall(call)))
+            .collect::<Vec<_>>();
+
+        self.add_bound_constraints(ctx, &type_params, &type_args);
+
+        let instance = func_type_sig.substitute(ctx, &type_params, &type_args);
+        ctx[call].instance = Some(instance.clone());
 
-        if return_types.len() == 1 {
+        Ok(instance)
+    }
+
+    /// Requires the type arguments to conform to the trait bounds of their type parameters
+    fn add_bound_constraints(
+        &mut self,
+        ctx: &IrCtx<'a>,
+        type_params: &[Ident<'a>],
+        type_args: &[TypeSignature<'a>],
+    ) {
+        for (param, arg) in type_params.iter().zip(type_args) {
+            match declared_type_param(*param) {
+                Some(param) if ctx[param].bound.is_some() => {
+                    self.0.add_conformance_constraint(arg.clone(), param)
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn infer_function_body(
+        &mut self,
+        ctx: &mut IrCtx<'a>,
+        func: NodeRef<'a, Function<'a>>,
+    ) -> Result<(), TypeCheckerError<'a>> {
+        // the return statements of the body have been collected while walking it
+        let return_types = self
+            .1
+            .pop()
+            .expect("function scope should collect return types");
+
+        // a body that only throws never returns, so it does not have to return void, unless
+        // it is a declared function without a return type, which returns void when called.
+        // A lambda takes its return type from where it is passed instead
+        let is_lambda = ctx[func].name.value(ctx).is_none();
+        let declared = !matches!(ctx[&return_types[0]], TypeSignatureValue::TypeVariable(_));
+        let diverges = (is_lambda || declared) && ctx[func].body.diverges(ctx);
+        if return_types.len() == 1 && !diverges {
             self.0.add_constraint(
                 return_types[0].clone(),
                 ctx.get_builtin_type_sig(BuiltinType::Void),
             )
         }
 
-        for i in 1..return_types.len() {
+        // the first type is the declared return type, which all returned values coerce to
+        for returned_type in return_types.iter().skip(1) {
             self.0
-                .add_constraint(return_types[i - 1].clone(), return_types[i].clone());
+                .add_coercion_constraint(returned_type.clone(), return_types[0].clone());
+        }
+
+        Ok(())
+    }
+
+    /// Constrains the value of a narrowed binding to be the optional of its type
+    fn add_narrowing_constraint(
+        &mut self,
+        ctx: &mut IrCtx<'a>,
+        binding: &NarrowedBinding<'a>,
+        parent: TypeSignatureParent<'a>,
+    ) -> Result<(), TypeCheckerError<'a>> {
+        let value_type = binding
+            .value
+            .eval_type(&mut self.0.symbols, ctx)
+            .map_err(TypeCheckerError::TypeEval)?;
+
+        let optional_type = ctx.get_type_sig(
+            TypeSignatureValue::Optional(binding.type_sig.cloned()),
+            TypeSignatureContext {
+                parent,
+                type_span: None,
+            }
+            .alloc(),
+        );
+
+        self.0.add_constraint(optional_type, value_type);
+        Ok(())
+    }
+
+    fn infer_enum_patterns(
+        &mut self,
+        ctx: &mut IrCtx<'a>,
+        scrutinee: NodeRef<'a, Expr<'a>>,
+        patterns: Vec<NodeRef<'a, EnumPattern<'a>>>,
+    ) -> Result<(), TypeCheckerError<'a>> {
+        let scrutinee_type = scrutinee
+            .eval_type(&mut self.0.symbols, ctx)
+            .map_err(TypeCheckerError::TypeEval)?;
+
+        // the patterns are resolved by the type resolver once the type is known,
+        // other types are reported by the end type checker
+        let (enm_type, type_args) = scrutinee_type.split_instance(ctx);
+        let TypeSignatureValue::Enum { name: enm_name } = ctx[&enm_type] else {
+            return Ok(());
+        };
+
+        let enm = self
+            .0
+            .symbols
+            .lookup(ctx, enm_name)
+            .ok_or(TypeCheckerError::TypeEval(TypeEvalError::UnknownIdent(
+                enm_name,
+            )))?;
+
+        let enm_name = ctx[enm].name(ctx);
+        let enm = enm.unwrap_enum(ctx);
+        let type_params = type_param_names(ctx, &ctx[enm].type_params);
+
+        for pattern in patterns {
+            let (_, enm_val) = enm.lookup_value(ctx, *ctx[pattern].enum_value).ok_or(
+                TypeCheckerError::SymbolResolutionError(SymbolResolutionError::UnknownEnumValue {
+                    enm,
+                    enum_value: *ctx[pattern].enum_value,
+                }),
+            )?;
+
+            ctx[pattern].enum_name = Ident {
+                id: enm_name.id,
+                parent: IdentParent::EnumPatternEnumName(pattern).into(),
+            }
+            .into();
+            let enum_value_id = ctx[enm_val].name.id;
+            ctx[pattern].enum_value.id = enum_value_id;
+
+            for (binding, item_type) in ctx[pattern]
+                .bindings
+                .clone()
+                .into_iter()
+                .zip((*ctx[enm_val].items).clone())
+            {
+                let item_type = item_type.substitute(ctx, &type_params, &type_args);
+                self.0.add_constraint(binding.type_sig.cloned(), item_type);
+            }
         }
 
         Ok(())
@@ -391,7 +1173,7 @@ impl<'a> TypeInferrer<'a, '_> {
 
 #[cfg(test)]
 mod tests {
-    use std::assert_matches::assert_matches;
+    use std::assert_matches;
 
     use crate::ir::{
         node::type_signature::BuiltinType,
@@ -616,4 +1398,57 @@ mod tests {
             ir.ctx.get_builtin_type_sig(BuiltinType::Boolean),
         );
     }
+
+    #[test]
+    fn test_arithmetic_operator_types() {
+        let mut ir = lowered_ir("let x: Boolean = 1 + 2 * 3").unwrap();
+        assert_type_mismatch(
+            type_check(&mut ir),
+            ir.ctx.get_builtin_type_sig(BuiltinType::Boolean),
+            ir.ctx.get_builtin_type_sig(BuiltinType::Number),
+        );
+    }
+
+    #[test]
+    fn test_arithmetic_operator_operands() {
+        let mut ir = lowered_ir("let x = 1 - \"2\"").unwrap();
+        assert_type_mismatch(
+            type_check(&mut ir),
+            ir.ctx.get_builtin_type_sig(BuiltinType::String),
+            ir.ctx.get_builtin_type_sig(BuiltinType::Number),
+        );
+    }
+
+    #[test]
+    fn test_add_mismatched_operands() {
+        let mut ir = lowered_ir("let x = \"a\" + 1").unwrap();
+        assert_type_mismatch(
+            type_check(&mut ir),
+            ir.ctx.get_builtin_type_sig(BuiltinType::String),
+            ir.ctx.get_builtin_type_sig(BuiltinType::Number),
+        );
+    }
+
+    #[test]
+    fn test_comparison_operator_type() {
+        let mut ir = lowered_ir("let x: Boolean = 1 < 2 && \"a\" != \"b\"").unwrap();
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
+
+    #[test]
+    fn test_unary_operator_operand() {
+        let mut ir = lowered_ir("let x = -true").unwrap();
+        assert_type_mismatch(
+            type_check(&mut ir),
+            ir.ctx.get_builtin_type_sig(BuiltinType::Boolean),
+            ir.ctx.get_builtin_type_sig(BuiltinType::Number),
+        );
+    }
+
+    #[test]
+    fn test_operator_infer_lambda_args() {
+        let mut ir =
+            lowered_ir("let f = (a, b) { return a * b }; let x: Number = f(1, 2)").unwrap();
+        assert_matches!(type_check(&mut ir).1, Ok(_));
+    }
 }
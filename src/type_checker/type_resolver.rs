@@ -8,11 +8,14 @@ use crate::{
             expression::Expr,
             identifier::{Ident, IdentParent, Identifiable},
             member_access::UnresolvedMemberAccess,
-            type_signature::{TypeEvalError, TypeSignature, TypeSignatureValue},
+            pattern_match::{EnumPattern, LetElse},
+            statement::Stmt,
+            type_signature::{TypeEvalError, TypeSignature, TypeSignatureValue, Typed},
             IrAlloc, NodeRef,
         },
     },
-    symbols::symbol_resolver::SymbolResolutionError,
+    parser::Span,
+    symbols::symbol_resolver::{resolve_ident, SymbolResolutionError},
 };
 
 use super::{
@@ -69,8 +72,14 @@ impl<'a> IrWalker<'a> for TypeResolver<'a, '_> {
         let new_type = self.0.substitutions.get(&type_sig).cloned();
 
         if let Some(t) = &new_type {
-            // struct_init uses type_sig to resolve the struct definition which can be used to infer the attributes
-            if let TypeSignatureValue::Struct { name: _ } = ctx[t] {
+            // struct_init uses type_sig to resolve the struct definition which can be used to infer the attributes,
+            // the type arguments of an instance may contain type variables resolved in the next run
+            if let TypeSignatureValue::Struct { name: _ }
+            | TypeSignatureValue::Instance { .. }
+            | TypeSignatureValue::Dictionary { .. }
+            | TypeSignatureValue::Optional(_)
+            | TypeSignatureValue::Record(_) = ctx[t]
+            {
                 self.0.needs_rerun = true;
             }
         }
@@ -85,10 +94,51 @@ impl<'a> IrWalker<'a> for TypeResolver<'a, '_> {
         expr: NodeRef<'a, Expr<'a>>,
     ) -> Result<(), Self::Error> {
         match ctx[expr] {
-            Expr::UnresolvedMemberAccess(mem_acc) => self.resolve_member_access(ctx, mem_acc, expr),
+            Expr::UnresolvedMemberAccess(mem_acc) => match ctx[mem_acc].object {
+                Some(obj) => self.resolve_object_access(ctx, mem_acc, obj, expr),
+                None => self.resolve_member_access(ctx, mem_acc, expr),
+            },
+            Expr::StructInit(st_init) => {
+                if let TypeSignatureValue::TypeVariable(_) = ctx[&*ctx[st_init].type_sig] {
+                    let values_type = st_init
+                        .values_record_type(ctx, &mut self.0.symbols)
+                        .map_err(TypeCheckerError::TypeEval)?;
+
+                    self.0.untyped_struct_inits.push((st_init, values_type));
+                }
+
+                Ok(())
+            }
+            Expr::Match(mtch) => {
+                self.resolve_enum_patterns(ctx, ctx[mtch].scrutinee, mtch.patterns(ctx))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn visit_stmt(
+        &mut self,
+        ctx: &mut IrCtx<'a>,
+        _scope: &mut Self::Scope,
+        stmt: NodeRef<'a, Stmt<'a>>,
+    ) -> Result<(), Self::Error> {
+        match ctx[stmt] {
+            Stmt::IfBranch(ifb) => match ctx[ifb].pattern {
+                Some(pattern) => self.resolve_enum_patterns(ctx, ctx[ifb].condition, vec![pattern]),
+                None => Ok(()),
+            },
             _ => Ok(()),
         }
     }
+
+    fn visit_let_else(
+        &mut self,
+        ctx: &mut IrCtx<'a>,
+        _scope: &mut Self::Scope,
+        let_else: NodeRef<'a, LetElse<'a>>,
+    ) -> Result<(), Self::Error> {
+        self.resolve_enum_patterns(ctx, ctx[let_else].value, vec![ctx[let_else].pattern])
+    }
 }
 
 impl<'a, 'b> TypeResolver<'a, 'b> {
@@ -117,7 +167,8 @@ impl<'a, 'b> TypeResolver<'a, 'b> {
             .visit_type_sig(ctx, &mut (), (*ctx[mem_acc].type_sig).clone())?
             .into();
 
-        let enm_init = match &ctx[&*ctx[mem_acc].type_sig] {
+        let (enm_type, type_args) = ctx[mem_acc].type_sig.split_instance(ctx);
+        let enm_init = match &ctx[&enm_type] {
             TypeSignatureValue::Enum { name } => {
                 let items = match ctx[mem_acc].items.clone() {
                     Some((items, span)) => (Some(items), Some(span)),
@@ -129,29 +180,14 @@ impl<'a, 'b> TypeResolver<'a, 'b> {
                     enum_value: *ctx[mem_acc].member_name,
                     items: items.0.unwrap_or_default(),
                     items_span: items.1,
+                    type_args: Vec::new(),
                     span: ctx[mem_acc].span.clone(),
                 }
                 .allocate(ctx)
             }
             TypeSignatureValue::TypeVariable(_) => {
                 let span = (*ctx[mem_acc].type_sig).get_span(ctx).unwrap();
-
-                if !self
-                    .0
-                    .previous_undeterminable_types
-                    .iter()
-                    .find(|x| x.span == span)
-                    .is_some()
-                {
-                    self.0.needs_rerun = true;
-                }
-
-                self.0
-                    .immediate_undeterminable_types
-                    .push(UndeterminableType {
-                        span,
-                        expected: ExpectedType::Enum,
-                    });
+                self.undeterminable_type(span, ExpectedType::Enum);
 
                 return Ok(());
             }
@@ -184,6 +220,7 @@ impl<'a, 'b> TypeResolver<'a, 'b> {
             }),
         )?;
         ctx[enm_init].enum_value = *ctx[enm_val].name;
+        enm_init.instantiate(ctx, enm, type_args);
 
         let sym_id =
             self.0
@@ -202,11 +239,127 @@ impl<'a, 'b> TypeResolver<'a, 'b> {
         self.0.needs_rerun = true;
         Ok(())
     }
+
+    /// Resolves the access of a member of an object whose type was not known during symbol
+    /// resolution, which is the case for record literals and for the values of types declared
+    /// further down
+    fn resolve_object_access(
+        &mut self,
+        ctx: &mut IrCtx<'a>,
+        mem_acc: NodeRef<'a, UnresolvedMemberAccess<'a>>,
+        obj: NodeRef<'a, Expr<'a>>,
+        expr: NodeRef<'a, Expr<'a>>,
+    ) -> Result<(), TypeCheckerError<'a>> {
+        let obj_type = obj
+            .eval_type(&mut self.0.symbols, ctx)
+            .map_err(TypeCheckerError::TypeEval)?;
+        let obj_type = self.visit_type_sig(ctx, &mut (), obj_type)?;
+
+        let record_type = match &ctx[&obj_type] {
+            TypeSignatureValue::Optional(value) if ctx[mem_acc].optional => value.clone(),
+            _ => obj_type.clone(),
+        };
+
+        match &ctx[&record_type] {
+            TypeSignatureValue::Record(_) => {
+                ctx[expr] = mem_acc.resolve_record_access(ctx, obj);
+
+                // the type of the field can now be inferred
+                self.0.needs_rerun = true;
+            }
+            TypeSignatureValue::Struct { name: _ }
+            | TypeSignatureValue::TypeParameter { name: _ }
+            | TypeSignatureValue::Instance { .. } => {
+                ctx[expr] = mem_acc.resolve_struct_access(ctx, obj);
+
+                let st_acc = match ctx[expr] {
+                    Expr::FunctionCall(call) => ctx[call].func,
+                    _ => expr,
+                };
+                if let Expr::StructAccess(st_acc) = ctx[st_acc] {
+                    resolve_ident(&mut self.0.symbols, ctx, ctx[st_acc].attr_name)
+                        .map_err(TypeCheckerError::SymbolResolutionError)?;
+                }
+
+                // the type of the member can now be inferred
+                self.0.needs_rerun = true;
+            }
+            TypeSignatureValue::TypeVariable(_) => {
+                let span = obj.get_span(ctx).unwrap();
+                self.undeterminable_type(span, ExpectedType::Record);
+            }
+            _ => {
+                return Err(TypeCheckerError::SymbolResolutionError(
+                    SymbolResolutionError::InvalidMemberAccessType { mem_acc, obj_type },
+                ))
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Types the struct inits without a struct name whose type could not be inferred as records
+    pub fn type_record_literals(&mut self, ctx: &mut IrCtx<'a>) {
+        for (st_init, values_type) in std::mem::take(&mut self.0.untyped_struct_inits) {
+            ctx[st_init].type_sig = values_type.into();
+            self.0.needs_rerun = true;
+        }
+    }
+
+    fn resolve_enum_patterns(
+        &mut self,
+        ctx: &mut IrCtx<'a>,
+        scrutinee: NodeRef<'a, Expr<'a>>,
+        patterns: Vec<NodeRef<'a, EnumPattern<'a>>>,
+    ) -> Result<(), TypeCheckerError<'a>> {
+        // a match with only a wildcard arm does not need the type of the matched value
+        if patterns.is_empty() {
+            return Ok(());
+        }
+
+        let scrutinee_type = scrutinee
+            .eval_type(&mut self.0.symbols, ctx)
+            .map_err(TypeCheckerError::TypeEval)?;
+        let scrutinee_type = self.visit_type_sig(ctx, &mut (), scrutinee_type)?;
+
+        match &ctx[&scrutinee_type.split_instance(ctx).0] {
+            TypeSignatureValue::TypeVariable(_) => {
+                let span = scrutinee.get_span(ctx).unwrap();
+                self.undeterminable_type(span, ExpectedType::Enum);
+            }
+            // the patterns are resolved by the type inferrer once the enum is known
+            TypeSignatureValue::Enum { name: _ }
+                if patterns
+                    .iter()
+                    .any(|pattern| ctx[*pattern].enum_name.is_empty()) =>
+            {
+                self.0.needs_rerun = true;
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn undeterminable_type(&mut self, span: Span<'a>, expected: ExpectedType) {
+        if !self
+            .0
+            .previous_undeterminable_types
+            .iter()
+            .any(|x| x.span == span)
+        {
+            self.0.needs_rerun = true;
+        }
+
+        self.0
+            .immediate_undeterminable_types
+            .push(UndeterminableType { span, expected });
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::assert_matches::assert_matches;
+    use std::assert_matches;
 
     use crate::ir::test_utils::utils::{lowered_ir, type_check};
 
@@ -2,20 +2,82 @@ use crate::{
     ir::{
         context::IrCtx,
         ir_walker::{IrWalker, ScopeValue},
-        node::{expression::Expr, NodeRef},
+        node::{
+            error_handling::DoCatch,
+            expression::Expr,
+            extension::Extension,
+            function::Function,
+            loops::{Loop, LoopJump, LoopJumpKind},
+            pattern_match::{LetElse, Match, MatchArm},
+            statement::Stmt,
+            NodeRef,
+        },
     },
     symbols::symbol_table::symbol_table_zipper::SymbolTableZipper,
 };
 
 use super::{
-    check_assignment::check_assignment, check_enum::check_enum_init,
-    check_expr_ident::check_expr_ident, check_struct::check_struct_init,
-    type_resolver::TypeResolver, TypeCheckerError,
+    check_assignment::check_assignment,
+    check_async::check_await,
+    check_dictionary::check_dictionary,
+    check_enum::check_enum_init,
+    check_expr_ident::check_expr_ident,
+    check_extension::check_extension,
+    check_init::check_init,
+    check_loop::{check_loop_jump, check_loop_label, enclosing_loops_of_scope},
+    check_match::{check_if_let, check_let_else, check_match, direct_match},
+    check_operator::check_binary_operation,
+    check_string_interpolation::check_string_interpolation,
+    check_struct::check_struct_init,
+    check_throw::{
+        check_call, check_do_catch, check_handled, check_try, throws_untyped, ThrowTarget,
+    },
+    type_resolver::TypeResolver,
+    TypeCheckerError,
 };
 
+#[derive(Debug, Default, Clone)]
+pub struct EndTypeCheckerScope<'a> {
+    /// The function whose body directly contains the scope
+    pub func: Option<NodeRef<'a, Function<'a>>>,
+    pub throw_target: Option<ThrowTarget<'a>>,
+    /// The loops of the function body enclosing the scope, innermost last
+    pub enclosing_loops: Vec<Loop<'a>>,
+    /// The match arms of the function body enclosing the scope, together with the number
+    /// of loops enclosing each of them
+    pub enclosing_arms: Vec<(NodeRef<'a, MatchArm<'a>>, usize)>,
+    /// The extension whose members are declared directly inside of the scope
+    pub extension: Option<NodeRef<'a, Extension<'a>>>,
+    /// The extension declaring the function whose body directly contains the scope
+    pub member_of: Option<NodeRef<'a, Extension<'a>>>,
+    /// Whether the scope is inside of a static member of an extension, where there is no `self`
+    pub in_static: bool,
+    /// The value of the first `return` inside of the scope, not counting nested functions
+    pub first_return: Option<NodeRef<'a, Expr<'a>>>,
+}
+
+impl<'a> EndTypeCheckerScope<'a> {
+    pub fn init(&self, ctx: &IrCtx<'a>) -> Option<NodeRef<'a, Function<'a>>> {
+        let func = self.func?;
+        ctx[self.member_of?].inits.contains(&func).then_some(func)
+    }
+}
+
 #[derive(Debug)]
 pub struct EndTypeChecker<'a, 'b> {
     pub symbols: &'b mut SymbolTableZipper<'a>,
+    /// The matches with a `return` inside of their arms, together with the first returned value,
+    /// which are only allowed to be the whole value of the statement visited next
+    returning_matches: Vec<(NodeRef<'a, Match<'a>>, NodeRef<'a, Expr<'a>>)>,
+    /// The arms visited so far of the matches that are being visited
+    returning_arms: Vec<(NodeRef<'a, Match<'a>>, NodeRef<'a, Expr<'a>>)>,
+    /// The matches with a `break` or `continue` leaving their arms, which like returning
+    /// matches have to be the whole value of the statement visited next
+    jumping_matches: Vec<(NodeRef<'a, Match<'a>>, NodeRef<'a, LoopJump<'a>>)>,
+    /// The arms left by a `break` or `continue` whose match has not been visited yet
+    jumping_arms: Vec<(NodeRef<'a, MatchArm<'a>>, NodeRef<'a, LoopJump<'a>>)>,
+    /// The do bodies running escape blocks or external functions, which may throw any error
+    untyped_throwing: Vec<NodeRef<'a, DoCatch<'a>>>,
 }
 
 impl<'a, 'b> EndTypeChecker<'a, 'b> {
@@ -23,34 +85,98 @@ impl<'a, 'b> EndTypeChecker<'a, 'b> {
         type_resolver.0.symbols.reset(ctx);
         EndTypeChecker {
             symbols: &mut type_resolver.0.symbols,
+            returning_matches: Vec::new(),
+            returning_arms: Vec::new(),
+            jumping_matches: Vec::new(),
+            jumping_arms: Vec::new(),
+            untyped_throwing: Vec::new(),
         }
     }
 }
 
 impl<'a> IrWalker<'a> for EndTypeChecker<'a, '_> {
     type Error = TypeCheckerError<'a>;
+    type Scope = EndTypeCheckerScope<'a>;
 
     fn visit_scope_begin(
         &mut self,
         ctx: &mut IrCtx<'a>,
-        _parent: &mut Self::Scope,
+        parent: &mut Self::Scope,
         value: ScopeValue<'a>,
-    ) -> Result<(), TypeCheckerError<'a>> {
-        value.visit_scope_begin(ctx, &mut self.symbols);
-        Ok(())
+    ) -> Result<Self::Scope, TypeCheckerError<'a>> {
+        value.visit_scope_begin(ctx, self.symbols);
+        check_loop_label(ctx, &value, &parent.enclosing_loops)?;
+
+        let func = match value {
+            ScopeValue::Func(func) => Some(func),
+            ScopeValue::Struct(_)
+            | ScopeValue::Enum(_)
+            | ScopeValue::Trait(_)
+            | ScopeValue::Extension(_) => None,
+            ScopeValue::StructInit(_)
+            | ScopeValue::IfBranch(_, _)
+            | ScopeValue::MatchArm(_)
+            | ScopeValue::LetElse(_)
+            | ScopeValue::DoCatch(_, _)
+            | ScopeValue::WhileLoop(_)
+            | ScopeValue::ForLoop(_)
+            | ScopeValue::RightOperand(_) => parent.func,
+        };
+
+        let (member_of, in_static) = match value {
+            ScopeValue::Func(func) => match parent.extension {
+                Some(ext) => (Some(ext), ctx[ext].static_methods.contains(&func)),
+                None => (None, parent.in_static),
+            },
+            // the values of the static attributes are declared directly inside of the extension
+            ScopeValue::Extension(_) => (None, true),
+            ScopeValue::Struct(_) | ScopeValue::Enum(_) | ScopeValue::Trait(_) => (None, false),
+            _ => (parent.member_of, parent.in_static),
+        };
+
+        Ok(EndTypeCheckerScope {
+            func,
+            throw_target: ThrowTarget::of_scope(&value, parent.throw_target),
+            enclosing_loops: enclosing_loops_of_scope(&value, &parent.enclosing_loops),
+            enclosing_arms: match value {
+                ScopeValue::MatchArm(arm) => [
+                    parent.enclosing_arms.as_slice(),
+                    &[(arm, parent.enclosing_loops.len())],
+                ]
+                .concat(),
+                ScopeValue::Func(_)
+                | ScopeValue::Struct(_)
+                | ScopeValue::Enum(_)
+                | ScopeValue::Trait(_)
+                | ScopeValue::Extension(_) => Vec::new(),
+                _ => parent.enclosing_arms.clone(),
+            },
+            extension: match value {
+                ScopeValue::Extension(ext) => Some(ext),
+                _ => None,
+            },
+            member_of,
+            in_static,
+            first_return: None,
+        })
     }
 
     fn visit_scope_end(
         &mut self,
         ctx: &mut IrCtx<'a>,
-        _parent: &mut Self::Scope,
-        _child: Self::Scope,
-        _value: ScopeValue<'a>,
+        parent: &mut Self::Scope,
+        child: Self::Scope,
+        value: ScopeValue<'a>,
     ) -> Result<(), TypeCheckerError<'a>> {
         self.symbols
             .exit_scope(ctx)
             .expect("scope should not be global scope");
 
+        // a `return` leaves the enclosing function
+        if child.func.is_some() && !matches!(value, ScopeValue::Func(_)) {
+            parent.first_return = parent.first_return.or(child.first_return);
+        }
+
         Ok(())
     }
 
@@ -66,22 +192,150 @@ impl<'a> IrWalker<'a> for EndTypeChecker<'a, '_> {
     fn visit_expr(
         &mut self,
         ctx: &mut IrCtx<'a>,
-        _scope: &mut (),
+        scope: &mut Self::Scope,
         expr: NodeRef<'a, Expr<'a>>,
     ) -> Result<(), TypeCheckerError<'a>> {
+        if let Some(ThrowTarget::DoCatch(dc)) = scope.throw_target {
+            if throws_untyped(ctx, self.symbols, expr) {
+                self.untyped_throwing.push(dc);
+            }
+        }
+
         match ctx[expr].clone() {
-            Expr::Assignment(asg) => check_assignment(ctx, &mut self.symbols, asg),
-            Expr::StructInit(st_init) => check_struct_init(ctx, &mut self.symbols, st_init),
-            Expr::EnumInit(enm_init) => check_enum_init(ctx, &mut self.symbols, enm_init),
-            Expr::Identifier(ident, _) => check_expr_ident(ctx, &mut self.symbols, expr, *ident),
+            Expr::Assignment(asg) => {
+                let in_init = scope.init(ctx).is_some();
+                check_assignment(ctx, self.symbols, asg, in_init)
+            }
+            Expr::StructInit(st_init) => check_struct_init(ctx, self.symbols, st_init),
+            Expr::EnumInit(enm_init) => check_enum_init(ctx, self.symbols, enm_init),
+            Expr::Identifier(ident, _) => {
+                check_expr_ident(ctx, self.symbols, expr, *ident, scope.in_static)
+            }
+            Expr::BinaryOperation(bin_op) => {
+                check_binary_operation(ctx, self.symbols, bin_op, scope.func)
+            }
+            Expr::Match(mtch) => {
+                if let Some(&(_, value)) = self.returning_arms.iter().find(|(m, _)| *m == mtch) {
+                    self.returning_arms.retain(|(m, _)| *m != mtch);
+                    self.returning_matches.push((mtch, value));
+                }
+
+                let arms = &ctx[mtch].arms;
+                let (left, pending) = std::mem::take(&mut self.jumping_arms)
+                    .into_iter()
+                    .partition::<Vec<_>, _>(|(arm, _)| arms.contains(arm));
+                self.jumping_arms = pending;
+                self.jumping_matches
+                    .extend(left.into_iter().map(|(_, jump)| (mtch, jump)));
+
+                check_match(ctx, self.symbols, mtch)
+            }
+            Expr::Await(aw) => check_await(ctx, aw, scope.func),
+            Expr::FunctionCall(call) => check_call(ctx, self.symbols, call),
+            Expr::StringInterpolation(interp) => {
+                check_string_interpolation(ctx, self.symbols, interp)
+            }
+            Expr::Dictionary(dict) => check_dictionary(ctx, self.symbols, dict),
+            Expr::Try(tr) => check_try(ctx, self.symbols, expr, tr, scope.throw_target, scope.func),
             _ => Ok(()),
         }
     }
+
+    fn visit_stmt(
+        &mut self,
+        ctx: &mut IrCtx<'a>,
+        scope: &mut Self::Scope,
+        stmt: NodeRef<'a, Stmt<'a>>,
+    ) -> Result<(), Self::Error> {
+        // a match that is not the whole value of the statement is generated inside of
+        // a closure, from which a `return` in its arms would not leave the function
+        let direct = direct_match(ctx, stmt);
+        for (mtch, value) in std::mem::take(&mut self.returning_matches) {
+            if direct != Some(mtch) {
+                return Err(TypeCheckerError::ReturnInMatchExpr(value, mtch));
+            }
+        }
+        for (mtch, jump) in std::mem::take(&mut self.jumping_matches) {
+            if direct != Some(mtch) {
+                return Err(TypeCheckerError::LoopJumpInMatchExpr(jump, mtch));
+            }
+        }
+
+        if let Stmt::Return(value) = ctx[stmt] {
+            scope.first_return = scope.first_return.or(Some(value));
+        }
+
+        match ctx[stmt] {
+            Stmt::IfBranch(ifb) => check_if_let(ctx, self.symbols, ifb),
+            Stmt::Extension(ext) => check_extension(ctx, self.symbols, ext),
+            Stmt::Throw(expr) => check_handled(ctx, expr, scope.throw_target, scope.func),
+            Stmt::DoCatch(dc) => check_do_catch(ctx, dc, self.untyped_throwing.contains(&dc)),
+            Stmt::LoopJump(jump) => {
+                let target = check_loop_jump(ctx, jump, &scope.enclosing_loops)?;
+
+                // the arms of the matches inside of the loop are the cases of a `switch`,
+                // which a `break` without a label would only leave
+                let left_arms = scope
+                    .enclosing_arms
+                    .iter()
+                    .filter(|(_, loops)| target < *loops)
+                    .map(|(arm, _)| (*arm, jump))
+                    .collect::<Vec<_>>();
+
+                if !left_arms.is_empty()
+                    && ctx[jump].kind == LoopJumpKind::Break
+                    && ctx[jump].label.is_none()
+                {
+                    scope.enclosing_loops[target].set_broken_from_match(ctx);
+                }
+
+                self.jumping_arms.extend(left_arms);
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn visit_match_arm(
+        &mut self,
+        _ctx: &mut IrCtx<'a>,
+        scope: &mut Self::Scope,
+        mtch: NodeRef<'a, Match<'a>>,
+        _arm: NodeRef<'a, MatchArm<'a>>,
+    ) -> Result<(), Self::Error> {
+        // the match is only known to be returning once all of its arms are visited
+        if let Some(value) = scope.first_return {
+            self.returning_arms.push((mtch, value));
+        }
+
+        Ok(())
+    }
+
+    fn visit_func_decl(
+        &mut self,
+        ctx: &mut IrCtx<'a>,
+        scope: &mut Self::Scope,
+        func: NodeRef<'a, Function<'a>>,
+    ) -> Result<(), Self::Error> {
+        match (scope.member_of, scope.init(ctx)) {
+            (Some(ext), Some(_)) => check_init(ctx, self.symbols, ext, func),
+            _ => Ok(()),
+        }
+    }
+
+    fn visit_let_else(
+        &mut self,
+        ctx: &mut IrCtx<'a>,
+        _scope: &mut Self::Scope,
+        let_else: NodeRef<'a, LetElse<'a>>,
+    ) -> Result<(), Self::Error> {
+        check_let_else(ctx, self.symbols, let_else)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::assert_matches::assert_matches;
+    use std::assert_matches;
 
     use crate::{
         ir::{
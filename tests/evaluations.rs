@@ -0,0 +1,176 @@
+// runs the transpiled code with node, for behaviour that is only observable at runtime
+use std::process::Command;
+
+use taro::ir::test_utils::utils::final_codegen;
+
+/// Evaluates `result` after the transpiled `input` in strict mode,
+/// or returns `None` if node is not installed
+fn evaluate(input: &str, result: &str) -> Option<String> {
+    let code = final_codegen(input).unwrap();
+    let script = format!("\"use strict\";\n{code}\nprocess.stdout.write(String({result}));");
+
+    let output = Command::new("node").arg("-e").arg(script).output().ok()?;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    Some(String::from_utf8(output.stdout).unwrap())
+}
+
+#[test]
+fn evaluate_self_in_nested_function() {
+    let input = "
+    struct Car { var model: String }
+    extend Car {
+        init(name: String) {
+            func prefixed() -> String { return self.model + name }
+            self.model = \"Volvo \"
+            self.model = prefixed()
+        }
+        func describe() -> String {
+            func inner() -> String { return self.model }
+            return inner()
+        }
+    }
+    trait Named {
+        func name() -> String
+        func greet() -> String {
+            func inner() -> String { return \"hi \" + self.name() }
+            return inner()
+        }
+    }
+    extend Car: Named { func name() -> String { return self.model } }
+    let car = Car(name: \"V70\")
+    ";
+
+    if let Some(output) = evaluate(input, "car.describe() + \", \" + car.greet()") {
+        assert_eq!(output, "Volvo V70, hi Volvo V70");
+    }
+}
+
+#[test]
+fn evaluate_static_members_named_like_function_properties() {
+    let input = "
+    struct P { let x: Number = 1 }
+    extend P {
+        init() {}
+        static let name = \"pt\"
+        static let length = 7
+        static func call() -> Number { return 3 }
+    }
+    let p = P()
+    let name = P.name
+    let length = P.length
+    let called = P.call()
+    ";
+
+    if let Some(output) = evaluate(input, "[name, length, called, p.x].join()") {
+        assert_eq!(output, "pt,7,3,1");
+    }
+}
+
+#[test]
+fn evaluate_loop_jumps_in_match_arms() {
+    let input = "
+    enum Step { skip; stop; take(Number) }
+    func step(i: Number) -> Step {
+        if i == 1 { return Step.skip }
+        if i == 4 { return Step.stop }
+        return Step.take(i)
+    }
+    var total = 0
+    for i in 0..<10 {
+        match step(i) {
+            .skip => { continue },
+            .stop => { break },
+            .take(n) => { total = total + n }
+        }
+    }
+    ";
+
+    if let Some(output) = evaluate(input, "total") {
+        assert_eq!(output, "5");
+    }
+}
+
+#[test]
+fn evaluate_dictionary_entries() {
+    let input = "
+    let ages = [\"alice\": 31, \"bob\": 27]
+    ages[\"carol\"] = 40
+    var total = 0
+    var names = \"\"
+    for entry in ages {
+        names = names + entry.0
+        total = total + entry.1
+    }
+    let assigned = ages[\"dave\"] = 9
+    ";
+
+    if let Some(output) = evaluate(input, "[names, total, assigned, ages.get(\"dave\")].join()") {
+        assert_eq!(output, "alicebobcarol,98,9,9");
+    }
+}
+
+#[test]
+fn evaluate_match_assigned_to_shadowed_name() {
+    let input = "
+    enum IpAddress { v4(Number, Number, Number, Number); v6(Number) }
+    enum Letter { a; b }
+    let ip = IpAddress.v6(7)
+    let s = match ip { .v4(a, b, c, d) => a + b + c + d, .v6(s) => s + 1 }
+    let letter = Letter.a
+    let t = match letter {
+        .a => {
+            let t = 2
+            t + 1
+        },
+        .b => 0
+    }
+    ";
+
+    if let Some(output) = evaluate(input, "[s, t].join()") {
+        assert_eq!(output, "8,3");
+    }
+}
+
+#[test]
+fn evaluate_names_like_generated_temporaries() {
+    let input = "
+    enum E { v4(Number); v6 }
+    func a() -> E { return E.v4(3) }
+    func f() -> Number {
+        let .v4(x) = a() else { return 0 }
+        let $x = 1
+        return x + $x
+    }
+    let $match = 10
+    let m = match a() { .v4(n) => n + $match, .v6 => $match }
+    let $value = \"k\"
+    let d = [\"k\": 1]
+    let assigned = d[$value] = 5
+    struct Car { var model: String }
+    extend Car {
+        func describe() -> String {
+            let $self = \"own \"
+            func inner() -> String { return $self + self.model }
+            return inner()
+        }
+    }
+    let car = Car { model: \"V70\" }
+    var total = 0
+    loop$1: for i in 0..<3 {
+        for j in 0..<3 {
+            match a() { .v4(n) => { break }, .v6 => { continue } }
+        }
+        total = total + i
+    }
+    ";
+
+    let result = "[f(), m, assigned, d.get(\"k\"), car.describe(), total].join()";
+    if let Some(output) = evaluate(input, result) {
+        assert_eq!(output, "4,13,5,5,own V70,3");
+    }
+}